@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use leptos::prelude::*;
 use sequoia_shared::TerritoryChange;
@@ -128,6 +129,8 @@ pub(crate) struct HeatEntriesByTerritory(pub RwSignal<HashMap<String, u64>>);
 pub(crate) struct HeatMaxTakeCount(pub RwSignal<u64>);
 #[derive(Clone, Copy)]
 pub(crate) struct HeatWindowLabel(pub RwSignal<String>);
+#[derive(Clone, Copy)]
+pub(crate) struct InferredHqs(pub RwSignal<Arc<HashMap<String, f32>>>);
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub(crate) enum MapMode {
     Live,
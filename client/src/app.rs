@@ -7,6 +7,7 @@ use wasm_bindgen::JsCast;
 use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::sync::Arc;
 
 pub(crate) const DEFAULT_SIDEBAR_WIDTH: f64 = 420.0;
 pub(crate) const SIDEBAR_WIDTH_MIN: f64 = 320.0;
@@ -88,6 +89,7 @@ thread_local! {
     static RESIZE_BINDING: RefCell<Option<ResizeBinding>> = const { RefCell::new(None) };
 }
 
+use sequoia_shared::guild_hq::{GuildHqEstimate, GuildHqSource};
 use sequoia_shared::history::{
     HistoryGuildSrEntry, HistoryHeat, HistoryHeatMeta, HistoryHeatSource,
 };
@@ -226,11 +228,15 @@ pub(crate) struct HeatFallbackApplied(pub RwSignal<bool>);
 pub(crate) struct HeatWindowLabel(pub RwSignal<String>);
 #[derive(Clone, Copy)]
 pub(crate) struct HeatMetaState(pub RwSignal<Option<HistoryHeatMeta>>);
+/// Inferred HQ confidence per territory name, from `/api/guilds/hq`.
+#[derive(Clone, Copy)]
+pub(crate) struct InferredHqs(pub RwSignal<Arc<HashMap<String, f32>>>);
 
 pub(crate) const MOBILE_BREAKPOINT: f64 = 768.0;
 const GUILD_ONLINE_POLL_INTERVAL_SECS: u64 = 120;
 const GUILD_ONLINE_BOOTSTRAP_RETRY_SECS: u64 = 3;
 const GUILD_ONLINE_ERROR_RETRY_SECS: u64 = 15;
+const GUILD_HQ_POLL_INTERVAL_SECS: u64 = 60;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum MapMode {
@@ -883,6 +889,7 @@ pub fn MapPage() -> impl IntoView {
     let heat_fallback_applied: RwSignal<bool> = RwSignal::new(false);
     let heat_window_label: RwSignal<String> = RwSignal::new(String::new());
     let heat_meta: RwSignal<Option<HistoryHeatMeta>> = RwSignal::new(None);
+    let inferred_hqs: RwSignal<Arc<HashMap<String, f32>>> = RwSignal::new(Arc::new(HashMap::new()));
     let heat_refresh_nonce: RwSignal<u64> = RwSignal::new(0);
 
     // History mode signals
@@ -1005,6 +1012,7 @@ pub fn MapPage() -> impl IntoView {
     provide_context(HeatFallbackApplied(heat_fallback_applied));
     provide_context(HeatWindowLabel(heat_window_label));
     provide_context(HeatMetaState(heat_meta));
+    provide_context(InferredHqs(inferred_hqs));
 
     Effect::new(move || {
         if reset_settings_trigger.get() == 0 {
@@ -1500,6 +1508,32 @@ pub fn MapPage() -> impl IntoView {
         }
     });
 
+    // Poll inferred guild HQs while in live mode; history frames only show observed HQs.
+    Effect::new(move || {
+        if map_mode.get() != MapMode::Live && !inferred_hqs.with_untracked(|hqs| hqs.is_empty()) {
+            inferred_hqs.set(Arc::new(HashMap::new()));
+        }
+    });
+    wasm_bindgen_futures::spawn_local(async move {
+        loop {
+            if map_mode.get_untracked() == MapMode::Live
+                && let Ok(resp) = gloo_net::http::Request::get("/api/guilds/hq").send().await
+                && resp.ok()
+                && let Ok(estimates) = resp.json::<Vec<GuildHqEstimate>>().await
+                && map_mode.get_untracked() == MapMode::Live
+            {
+                let by_territory = estimates
+                    .into_iter()
+                    .filter(|estimate| estimate.source == GuildHqSource::Inferred)
+                    .map(|estimate| (estimate.territory, estimate.confidence as f32))
+                    .collect();
+                inferred_hqs.set(Arc::new(by_territory));
+            }
+            gloo_timers::future::sleep(std::time::Duration::from_secs(GUILD_HQ_POLL_INTERVAL_SECS))
+                .await;
+        }
+    });
+
     // Persist settings to localStorage on any change
     Effect::new(move || {
         let settings = SettingsV2 {
@@ -2482,6 +2516,7 @@ struct TooltipInfo {
     resources_from_live: bool,
     defense_tier: Option<String>,
     is_headquarters: bool,
+    inferred_hq_confidence: Option<f32>,
     live_production_rates: Option<Resources>,
     live_storage_capacity: Option<Resources>,
     takes_in_window: Option<u64>,
@@ -2536,6 +2571,7 @@ fn Tooltip() -> impl IntoView {
     let HistoryTimestamp(history_timestamp) = expect_context();
     let HeatModeEnabled(heat_mode_enabled) = expect_context();
     let HeatEntriesByTerritory(heat_entries_by_territory) = expect_context();
+    let InferredHqs(inferred_hqs) = expect_context();
 
     let tooltip_info = Memo::new(move |_| {
         let reference_secs = if mode.get() == MapMode::History {
@@ -2565,6 +2601,11 @@ fn Tooltip() -> impl IntoView {
         let is_headquarters = runtime
             .and_then(|runtime| runtime.headquarters)
             .unwrap_or(false);
+        let inferred_hq_confidence = if runtime.and_then(|runtime| runtime.headquarters).is_none() {
+            inferred_hqs.with(|hqs| hqs.get(&name).copied())
+        } else {
+            None
+        };
         let takes_in_window = if heat_mode_enabled.get() {
             Some(
                 heat_entries_by_territory
@@ -2602,6 +2643,7 @@ fn Tooltip() -> impl IntoView {
             resources_from_live,
             defense_tier,
             is_headquarters,
+            inferred_hq_confidence,
             live_production_rates,
             live_storage_capacity,
             takes_in_window,
@@ -2741,6 +2783,11 @@ fn Tooltip() -> impl IntoView {
                                     "[HQ]"
                                 </span>
                             })}
+                            {info.inferred_hq_confidence.map(|confidence| view! {
+                                <span style="font-family: 'JetBrains Mono', monospace; font-size: 0.62rem; letter-spacing: 0.08em; text-transform: uppercase; color: rgba(245,197,66,0.7); flex-shrink: 0;">
+                                    {format!("[HQ? {:.0}%]", confidence * 100.0)}
+                                </span>
+                            })}
                         </div>
                         // Guild name + prefix
                         <div style="display: flex; align-items: baseline; gap: 4px; margin-left: 22px; margin-bottom: 8px;">
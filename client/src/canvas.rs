@@ -1,5 +1,5 @@
 use std::cell::{Cell, RefCell};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -11,12 +11,12 @@ use crate::app::{
     AbbreviateNames, BoldConnections, ConnectionOpacityScale, ConnectionThicknessScale,
     ConnectionZoomFadeEnd, ConnectionZoomFadeStart, CurrentMode, DefenseHighlight,
     DetailReturnGuild, FillAlphaBoost, HeatEntriesByTerritory, HeatMaxTakeCount, HeatModeEnabled,
-    HeatWindowLabel, HistoryTimestamp, Hovered, IsMobile, LabelScaleDynamic, LabelScaleIcons,
-    LabelScaleMaster, LabelScaleStatic, LabelScaleStaticName, MapMode, NameColorSetting,
-    PeekTerritory, ReadableFont, ResourceHighlight, Selected, ShowClaimLabels, ShowCompoundMapTime,
-    ShowCountdown, ShowFarZoomTerritoryTags, ShowGranularMapTime, ShowMinimap, ShowNames,
-    ShowResourceIcons, ShowSettings, ShowTerritoryOrnaments, SidebarOpen, SidebarTransient,
-    SuppressCooldownVisuals, TagColorSetting, ThickCooldownBorders,
+    HeatWindowLabel, HistoryTimestamp, Hovered, InferredHqs, IsMobile, LabelScaleDynamic,
+    LabelScaleIcons, LabelScaleMaster, LabelScaleStatic, LabelScaleStaticName, MapMode,
+    NameColorSetting, PeekTerritory, ReadableFont, ResourceHighlight, Selected, ShowClaimLabels,
+    ShowCompoundMapTime, ShowCountdown, ShowFarZoomTerritoryTags, ShowGranularMapTime, ShowMinimap,
+    ShowNames, ShowResourceIcons, ShowSettings, ShowTerritoryOrnaments, SidebarOpen,
+    SidebarTransient, SuppressCooldownVisuals, TagColorSetting, ThickCooldownBorders,
 };
 use crate::gpu::{GpuRenderer, RenderFrameInput};
 use crate::icons::{self, ResourceAtlas};
@@ -111,6 +111,8 @@ pub struct ClaimCanvasController {
     pub tool: RwSignal<ClaimTool>,
     pub handle_hit: Arc<dyn Fn(String, bool) + Send + Sync>,
     pub handle_box_select: Arc<dyn Fn(Vec<String>, bool) + Send + Sync>,
+    /// Territories to outline as changed, e.g. by the Compare tab diff.
+    pub changed_territories: RwSignal<Arc<HashSet<String>>>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -401,6 +403,7 @@ pub fn MapCanvas() -> impl IntoView {
     let HeatModeEnabled(heat_mode_enabled) = expect_context();
     let HeatEntriesByTerritory(heat_entries_by_territory) = expect_context();
     let HeatMaxTakeCount(heat_max_take_count) = expect_context();
+    let InferredHqs(inferred_hqs) = expect_context();
     let HeatWindowLabel(heat_window_label) = expect_context();
     let LabelScaleMaster(label_scale_master) = expect_context();
     let LabelScaleStatic(label_scale_static_tag) = expect_context();
@@ -411,6 +414,9 @@ pub fn MapCanvas() -> impl IntoView {
     let SidebarTransient(sidebar_transient) = expect_context();
     let ShowSettings(show_settings) = expect_context();
     let claim_canvas = use_context::<ClaimCanvasController>();
+    let claim_changed_territories = claim_canvas
+        .as_ref()
+        .map(|controller| controller.changed_territories);
 
     let canvas_ref = NodeRef::<leptos::html::Canvas>::new();
     let icon_atlas_requested = Rc::new(Cell::new(false));
@@ -522,6 +528,10 @@ pub fn MapCanvas() -> impl IntoView {
             renderer.connection_zoom_fade_end = connection_zoom_fade_end.get_untracked() as f32;
             renderer.suppress_cooldown_visuals = suppress_cooldown_visuals.get_untracked();
             renderer.fill_alpha_boost = fill_alpha_boost.get_untracked() as f32;
+            renderer.inferred_hqs = inferred_hqs.get_untracked();
+            renderer.changed_territories = claim_changed_territories
+                .map(|changed| changed.get_untracked())
+                .unwrap_or_default();
             let new_readable = readable_font.get_untracked();
             if renderer.use_readable_font != new_readable {
                 renderer.use_readable_font = new_readable;
@@ -725,6 +735,10 @@ pub fn MapCanvas() -> impl IntoView {
             heat_mode_enabled.track();
             heat_entries_by_territory.track();
             heat_max_take_count.track();
+            inferred_hqs.track();
+            if let Some(changed) = claim_changed_territories {
+                changed.track();
+            }
             if let Some(renderer) = gpu.borrow_mut().as_mut() {
                 renderer.mark_dirty(InvalidationReason::Geometry);
                 renderer.mark_dirty(InvalidationReason::StaticLabel);
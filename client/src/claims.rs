@@ -1,4 +1,4 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::sync::Arc;
 
 use chrono::Utc;
@@ -11,24 +11,27 @@ use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::{JsFuture, spawn_local};
 
 use sequoia_shared::{
-    ClaimDocumentBase, ClaimDocumentV1, ClaimMacro, ClaimOwner, ClaimTerritoryStateOverride,
-    ClaimValidationError, ClaimViewState, ClaimsBootstrapGeometry, ClaimsTerritoryGeometry,
-    GuildRef, LiveState, Resources, Territory, TerritoryMap, compact_claim_overrides,
-    compute_claim_metrics, validate_claim_document,
+    ClaimAnnotation, ClaimDiff, ClaimDocumentBase, ClaimDocumentV2, ClaimMacro, ClaimOwner,
+    ClaimTerritoryStateOverride, ClaimValidationError, ClaimViewState, ClaimsBootstrapGeometry,
+    ClaimsTerritoryGeometry, GuildRef, LiveState, Resources, Territory, TerritoryMap,
+    compact_claim_overrides, compute_claim_metrics, diff_claim_documents, validate_claim_document,
 };
 
+use sequoia_shared::economy::{TowerUpgrades, guild_economy};
+use sequoia_shared::resource_routing::{DEFAULT_FOREIGN_TAX_RATE, route_guild_resources};
+
 use crate::app::{
     AbbreviateNames, BoldConnections, ConnectionOpacityScale, ConnectionThicknessScale,
     ConnectionZoomFadeEnd, ConnectionZoomFadeStart, CurrentMode, DetailReturnGuild, FillAlphaBoost,
     HeatEntriesByTerritory, HeatMaxTakeCount, HeatModeEnabled, HeatWindowLabel,
     HistoryBufferModeActive, HistoryBufferSizeMax, HistoryBufferedUpdates, HistoryFetchNonce,
-    HistoryTimestamp, Hovered, IsMobile, LabelScaleDynamic, LabelScaleIcons, LabelScaleMaster,
-    LabelScaleStatic, LabelScaleStaticName, LastLiveSeq, LiveResyncInFlight, MapMode, NameColor,
-    NameColorSetting, NeedsLiveResync, PeekTerritory, ReadableFont, ResourceHighlight, Selected,
-    ShowClaimLabels, ShowCompoundMapTime, ShowCountdown, ShowFarZoomTerritoryTags,
-    ShowGranularMapTime, ShowMinimap, ShowNames, ShowSettings, ShowTerritoryOrnaments, SidebarOpen,
-    SidebarTransient, SseSeqGapDetectedCount, SuppressCooldownVisuals, TagColorSetting,
-    ThickCooldownBorders, canvas_dimensions,
+    HistoryTimestamp, Hovered, InferredHqs, IsMobile, LabelScaleDynamic, LabelScaleIcons,
+    LabelScaleMaster, LabelScaleStatic, LabelScaleStaticName, LastLiveSeq, LiveResyncInFlight,
+    MapMode, NameColor, NameColorSetting, NeedsLiveResync, PeekTerritory, ReadableFont,
+    ResourceHighlight, Selected, ShowClaimLabels, ShowCompoundMapTime, ShowCountdown,
+    ShowFarZoomTerritoryTags, ShowGranularMapTime, ShowMinimap, ShowNames, ShowSettings,
+    ShowTerritoryOrnaments, SidebarOpen, SidebarTransient, SseSeqGapDetectedCount,
+    SuppressCooldownVisuals, TagColorSetting, ThickCooldownBorders, canvas_dimensions,
 };
 use crate::canvas::{ClaimCanvasController, ClaimTool, MapCanvas};
 use crate::history;
@@ -40,6 +43,8 @@ use crate::viewport::Viewport;
 const DRAFT_STORAGE_KEY: &str = "sequoia_claim_draft_v1";
 const PRESET_STORAGE_KEY: &str = "sequoia_claim_presets_v1";
 const MACRO_LIBRARY_STORAGE_KEY: &str = "sequoia_claim_macros_v1";
+const EDIT_TOKEN_STORAGE_KEY: &str = "sequoia_claim_edit_token_v1";
+const CLAIM_EDIT_TOKEN_HEADER: &str = "x-claim-edit-token";
 const IMPORT_HANDOFF_STORAGE_KEY: &str = "sequoia_claim_import_handoff_v1";
 const LIVE_BOOTSTRAP_STORAGE_KEY: &str = "sequoia_claim_live_bootstrap_v1";
 const GEOMETRY_BOOTSTRAP_STORAGE_KEY: &str = "sequoia_claim_geometry_bootstrap_v1";
@@ -52,6 +57,7 @@ const BOOTSTRAP_STORAGE_VERSION: u8 = 1;
 const LIVE_SYNC_PENDING_MESSAGE: &str = "Live ownership is still syncing. The board is usable now and will reconcile in the background.";
 
 const NEUTRAL_GUILD_UUID: &str = "__neutral__";
+const CLAIM_DIFF_LIST_LIMIT: usize = 100;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum ClaimTab {
@@ -76,7 +82,7 @@ impl ClaimTab {
 
 #[derive(Clone)]
 struct ClaimUndoState {
-    document: ClaimDocumentV1,
+    document: ClaimDocumentV2,
     follow_live: bool,
     selection: Vec<String>,
     active_owner: ClaimOwner,
@@ -84,7 +90,7 @@ struct ClaimUndoState {
 
 #[derive(Clone)]
 struct ClaimWorkingSession {
-    document: ClaimDocumentV1,
+    document: ClaimDocumentV2,
     follow_live: bool,
     dirty: bool,
     selection: Vec<String>,
@@ -96,7 +102,7 @@ struct ClaimWorkingSession {
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 struct StoredClaimDraft {
-    document: ClaimDocumentV1,
+    document: ClaimDocumentV2,
     follow_live: bool,
     #[serde(default = "stored_claim_draft_dirty_default")]
     dirty: bool,
@@ -111,12 +117,12 @@ struct StoredClaimDraft {
 struct StoredClaimPreset {
     id: String,
     name: String,
-    document: ClaimDocumentV1,
+    document: ClaimDocumentV2,
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 struct StartupImportHandoff {
-    document: ClaimDocumentV1,
+    document: ClaimDocumentV2,
     #[serde(default)]
     follow_live: bool,
     #[serde(default)]
@@ -147,7 +153,7 @@ struct StagedGeometryBootstrap {
 struct ClaimsEditorInit {
     geometry: ClaimsBootstrapGeometry,
     live_state: Option<LiveState>,
-    document: ClaimDocumentV1,
+    document: ClaimDocumentV2,
     follow_live: bool,
     dirty: bool,
     selection: Vec<String>,
@@ -180,8 +186,28 @@ impl ClaimsBootPayload {
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 struct SavedClaimResponse {
     id: String,
+    #[serde(default)]
     created_at: String,
     url: String,
+    #[serde(default)]
+    edit_token: Option<String>,
+}
+
+#[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+struct OwnedClaimLayout {
+    id: String,
+    url: String,
+    created_at: String,
+    #[serde(default)]
+    updated_at: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    revision: i32,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct OwnedClaimLayoutsResponse {
+    layouts: Vec<OwnedClaimLayout>,
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
@@ -189,7 +215,7 @@ struct SavedClaimDocumentResponse {
     id: String,
     created_at: String,
     title: Option<String>,
-    document: ClaimDocumentV1,
+    document: ClaimDocumentV2,
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
@@ -241,7 +267,7 @@ fn parse_claims_route(path: &str) -> ClaimsRoute {
     ClaimsRoute::Root
 }
 
-fn current_hash_document() -> Result<Option<ClaimDocumentV1>, String> {
+fn current_hash_document() -> Result<Option<ClaimDocumentV2>, String> {
     let hash = web_sys::window()
         .and_then(|window| window.location().hash().ok())
         .unwrap_or_default();
@@ -307,7 +333,7 @@ fn current_live_owner_map(live_territories: &ClientTerritoryMap) -> HashMap<Stri
         .collect()
 }
 
-fn document_base_owner(document: &ClaimDocumentV1, territory: &str) -> ClaimOwner {
+fn document_base_owner(document: &ClaimDocumentV2, territory: &str) -> ClaimOwner {
     match &document.base {
         ClaimDocumentBase::Blank => ClaimOwner::Neutral,
         ClaimDocumentBase::FrozenLiveSnapshot { owners, .. } => owners
@@ -440,6 +466,119 @@ fn set_effective_resources(
     true
 }
 
+/// Make `territory` the HQ of its current owner, or clear it if it already is.
+fn toggle_session_hq(
+    session: &mut ClaimWorkingSession,
+    territory: &str,
+    live_owners: &HashMap<String, ClaimOwner>,
+) -> bool {
+    let owner = effective_owner_for_session(session, live_owners, territory);
+    let Some(key) = owner.identity_key() else {
+        return false;
+    };
+    if session.document.hqs.get(&key).map(String::as_str) == Some(territory) {
+        session.document.hqs.remove(&key);
+    } else {
+        session.document.hqs.insert(key, territory.to_string());
+    }
+    true
+}
+
+fn set_session_tower_level(
+    session: &mut ClaimWorkingSession,
+    territory: &str,
+    field: &'static str,
+    value: u8,
+) -> bool {
+    let mut levels = session
+        .document
+        .tower_levels
+        .get(territory)
+        .copied()
+        .unwrap_or_default();
+    let (slot, max) = match field {
+        "damage" => (&mut levels.damage, 11),
+        "attack" => (&mut levels.attack, 11),
+        "health" => (&mut levels.health, 11),
+        "defense" => (&mut levels.defense, 11),
+        "aura" => (&mut levels.aura, 3),
+        "volley" => (&mut levels.volley, 3),
+        _ => return false,
+    };
+    let value = value.min(max);
+    if *slot == value {
+        return false;
+    }
+    *slot = value;
+    if levels.is_empty() {
+        session.document.tower_levels.remove(territory);
+    } else {
+        session
+            .document
+            .tower_levels
+            .insert(territory.to_string(), levels);
+    }
+    true
+}
+
+/// Replace the note on `territory`; an empty text removes it.
+fn set_session_note(session: &mut ClaimWorkingSession, territory: &str, text: &str) -> bool {
+    let text = text.trim();
+    let current = session
+        .document
+        .annotations
+        .iter()
+        .find_map(|annotation| match annotation {
+            ClaimAnnotation::Note { territory: t, text } if t == territory => Some(text.as_str()),
+            _ => None,
+        });
+    if current.unwrap_or_default() == text {
+        return false;
+    }
+    session.document.annotations.retain(|annotation| {
+        !matches!(annotation, ClaimAnnotation::Note { territory: t, .. } if t == territory)
+    });
+    if !text.is_empty() {
+        session.document.annotations.push(ClaimAnnotation::Note {
+            territory: territory.to_string(),
+            text: text.to_string(),
+        });
+    }
+    true
+}
+
+fn add_session_arrow(session: &mut ClaimWorkingSession, from: &str, to: &str) -> bool {
+    let exists = session.document.annotations.iter().any(|annotation| {
+        matches!(annotation, ClaimAnnotation::Arrow { from: f, to: t, .. } if f == from && t == to)
+    });
+    if from == to || exists {
+        return false;
+    }
+    session.document.annotations.push(ClaimAnnotation::Arrow {
+        from: from.to_string(),
+        to: to.to_string(),
+        label: None,
+    });
+    true
+}
+
+/// Apply one undoable edit to the working session.
+fn apply_session_edit(
+    session: RwSignal<Option<ClaimWorkingSession>>,
+    active_owner: RwSignal<ClaimOwner>,
+    edit: impl FnOnce(&mut ClaimWorkingSession) -> bool,
+) {
+    session.update(|session_state| {
+        let Some(session_state) = session_state.as_mut() else {
+            return;
+        };
+        push_undo_state(session_state, &active_owner.get_untracked());
+        if !edit(session_state) {
+            let _ = session_state.undo_stack.pop();
+        }
+    });
+}
+
 fn selection_focus(selection: &[String], preferred: Option<&str>) -> Option<String> {
     preferred
         .filter(|territory| selection.iter().any(|name| name == territory))
@@ -603,7 +742,7 @@ fn canonical_document_for_session(
     live_territories: &ClientTerritoryMap,
     live_seq: u64,
     view: ClaimViewState,
-) -> ClaimDocumentV1 {
+) -> ClaimDocumentV2 {
     let live_owners = current_live_owner_map(live_territories);
     let mut document = session.document.clone();
     document.view = view;
@@ -642,9 +781,58 @@ fn canonical_document_for_session(
                 .as_ref()
                 .is_some_and(|resources| *resources != base_resources)
         });
+    document.retain_owned_hqs();
+    document
+        .tower_levels
+        .retain(|territory, levels| territory_map.contains_key(territory) && !levels.is_empty());
+    document.annotations.retain(|annotation| {
+        annotation
+            .territories()
+            .iter()
+            .all(|territory| territory_map.contains_key(*territory))
+    });
     document
 }
 
+/// Diff the session's layout against `target` (or current live ownership when `None`), with the
+/// target as the `before` side like the server's layout diff.
+fn session_claim_diff(
+    session: &ClaimWorkingSession,
+    target: Option<&ClaimDocumentV2>,
+    live_territories: &ClientTerritoryMap,
+    live_seq: u64,
+) -> ClaimDiff {
+    let current = canonical_document_for_session(
+        session,
+        live_territories,
+        live_seq,
+        session.document.view.clone(),
+    );
+    let before = target.cloned().unwrap_or_else(|| {
+        ClaimDocumentV2::frozen_live(None, live_seq, current_live_owner_map(live_territories))
+    });
+    diff_claim_documents(
+        &before,
+        &current,
+        &territory_map_from_client(live_territories),
+    )
+}
+
+/// Saved claim id from a bare id or a pasted `/claims/s/{id}` URL.
+fn saved_claim_id_from_input(input: &str) -> Option<String> {
+    let input = input.trim();
+    let id = match input.find("/claims/s/") {
+        Some(index) => &input[index + "/claims/s/".len()..],
+        None => input,
+    };
+    let id = id
+        .split(['#', '?'])
+        .next()
+        .unwrap_or_default()
+        .trim_matches('/');
+    (!id.is_empty() && !id.contains('/')).then(|| id.to_string())
+}
+
 fn default_view_from(viewport: &Viewport, active_owner: &ClaimOwner) -> ClaimViewState {
     ClaimViewState {
         offset_x: viewport.offset_x,
@@ -678,7 +866,7 @@ fn next_macro_id(name: &str) -> String {
 }
 
 #[cfg(test)]
-fn encode_claim_fragment(document: &ClaimDocumentV1) -> Result<String, String> {
+fn encode_claim_fragment(document: &ClaimDocumentV2) -> Result<String, String> {
     let bytes = serde_json::to_vec(document).map_err(|error| error.to_string())?;
     if bytes.len() > MAX_SHARE_FRAGMENT_BYTES {
         return Err("claim document is too large for URL sharing".to_string());
@@ -686,7 +874,7 @@ fn encode_claim_fragment(document: &ClaimDocumentV1) -> Result<String, String> {
     Ok(base64_url_encode(&bytes))
 }
 
-fn decode_claim_fragment(encoded: &str) -> Result<ClaimDocumentV1, String> {
+fn decode_claim_fragment(encoded: &str) -> Result<ClaimDocumentV2, String> {
     let bytes = base64_url_decode(encoded)?;
     serde_json::from_slice(&bytes).map_err(|error| error.to_string())
 }
@@ -779,6 +967,10 @@ fn read_macro_library() -> Vec<ClaimMacro> {
     gloo_storage::LocalStorage::get(MACRO_LIBRARY_STORAGE_KEY).unwrap_or_default()
 }
 
+fn read_claim_edit_token() -> Option<String> {
+    gloo_storage::LocalStorage::get(EDIT_TOKEN_STORAGE_KEY).ok()
+}
+
 fn take_startup_import_handoff() -> Option<StartupImportHandoff> {
     let handoff = gloo_storage::SessionStorage::get(IMPORT_HANDOFF_STORAGE_KEY).ok();
     gloo_storage::SessionStorage::delete(IMPORT_HANDOFF_STORAGE_KEY);
@@ -894,7 +1086,7 @@ fn client_map_from_geometry(geometry: &ClaimsBootstrapGeometry) -> ClientTerrito
 }
 
 fn validate_document_against_geometry(
-    document: &ClaimDocumentV1,
+    document: &ClaimDocumentV2,
     geometry: &ClaimsBootstrapGeometry,
 ) -> Result<(), ClaimValidationError> {
     validate_claim_document(document, geometry.territories.keys().map(String::as_str))
@@ -903,7 +1095,7 @@ fn validate_document_against_geometry(
 fn editor_init(
     geometry: ClaimsBootstrapGeometry,
     live_state: Option<LiveState>,
-    document: ClaimDocumentV1,
+    document: ClaimDocumentV2,
     follow_live: bool,
     dirty: bool,
     selection: Vec<String>,
@@ -927,7 +1119,7 @@ fn editor_init(
 fn hash_import_boot_payload(
     geometry: ClaimsBootstrapGeometry,
     live_state: Option<LiveState>,
-    document: ClaimDocumentV1,
+    document: ClaimDocumentV2,
 ) -> ClaimsBootPayload {
     let active_owner = document
         .view
@@ -964,7 +1156,7 @@ fn apply_live_state(
 }
 
 fn validate_document_against_live(
-    document: &ClaimDocumentV1,
+    document: &ClaimDocumentV2,
     live_territories: &ClientTerritoryMap,
 ) -> Result<(), ClaimValidationError> {
     let territory_names: Vec<&str> = live_territories.keys().map(String::as_str).collect();
@@ -979,7 +1171,7 @@ fn apply_document_to_session(
     tab: RwSignal<ClaimTab>,
     status_message: RwSignal<Option<String>>,
     error_message: RwSignal<Option<String>>,
-    document: ClaimDocumentV1,
+    document: ClaimDocumentV2,
     source_snapshot_id: Option<String>,
     source_snapshot_url: Option<String>,
     follow_live: bool,
@@ -1045,7 +1237,7 @@ fn boot_route_copy(route: &ClaimsRoute) -> &'static str {
     }
 }
 
-fn document_active_owner(document: &ClaimDocumentV1) -> ClaimOwner {
+fn document_active_owner(document: &ClaimDocumentV2) -> ClaimOwner {
     document
         .view
         .active_owner
@@ -1080,14 +1272,28 @@ async fn fetch_saved_claim_document(
         .map_err(|error| format!("parse error: {error}"))
 }
 
-async fn create_saved_claim(document: ClaimDocumentV1) -> Result<SavedClaimResponse, String> {
+/// Save `document` as a new layout, or in place when `owned_id` is one of our layouts.
+///
+/// The browser keeps one edit token for every layout it saves, issued by the first save.
+async fn save_claim_layout(
+    document: ClaimDocumentV2,
+    owned_id: Option<String>,
+) -> Result<SavedClaimResponse, String> {
     let request_body = serde_json::json!({
         "title": document.title.clone(),
         "document": document,
     });
     let request_body = serde_json::to_string(&request_body)
         .map_err(|error| format!("serialize error: {error}"))?;
-    let request = gloo_net::http::Request::post("/api/claims")
+    let edit_token = read_claim_edit_token();
+    let mut request = match owned_id.as_deref() {
+        Some(id) => gloo_net::http::Request::put(&format!("/api/claims/{id}")),
+        None => gloo_net::http::Request::post("/api/claims"),
+    };
+    if let Some(token) = edit_token.as_deref() {
+        request = request.header(CLAIM_EDIT_TOKEN_HEADER, token);
+    }
+    let request = request
         .header("Content-Type", "application/json")
         .body(request_body)
         .map_err(|_| "Failed to build save request".to_string())?;
@@ -1103,12 +1309,55 @@ async fn create_saved_claim(document: ClaimDocumentV1) -> Result<SavedClaimRespo
         ));
     }
 
-    response
+    let payload = response
         .json::<SavedClaimResponse>()
         .await
+        .map_err(|error| format!("parse error: {error}"))?;
+    if let Some(token) = payload.edit_token.as_deref() {
+        let _ = gloo_storage::LocalStorage::set(EDIT_TOKEN_STORAGE_KEY, token);
+    }
+    Ok(payload)
+}
+
+async fn fetch_owned_claim_layouts(token: &str) -> Result<Vec<OwnedClaimLayout>, String> {
+    let response = gloo_net::http::Request::get("/api/claims")
+        .header(CLAIM_EDIT_TOKEN_HEADER, token)
+        .send()
+        .await
+        .map_err(|error| format!("fetch error: {error}"))?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    response
+        .json::<OwnedClaimLayoutsResponse>()
+        .await
+        .map(|payload| payload.layouts)
         .map_err(|error| format!("parse error: {error}"))
 }
 
+async fn delete_owned_claim_layout(id: &str, token: &str) -> Result<(), String> {
+    let response = gloo_net::http::Request::delete(&format!("/api/claims/{id}"))
+        .header(CLAIM_EDIT_TOKEN_HEADER, token)
+        .send()
+        .await
+        .map_err(|error| format!("fetch error: {error}"))?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+/// The session's saved layout, when this browser holds its edit token.
+fn owned_source_snapshot_id(
+    session: &ClaimWorkingSession,
+    owned_layouts: &[OwnedClaimLayout],
+) -> Option<String> {
+    session
+        .source_snapshot_id
+        .clone()
+        .filter(|id| owned_layouts.iter().any(|layout| layout.id == *id))
+}
+
 fn absolute_claim_url(url: &str) -> String {
     if url.starts_with("http://") || url.starts_with("https://") {
         return url.to_string();
@@ -1164,8 +1413,8 @@ fn invalidate_reusable_share_url_for_live_update(
 }
 
 fn documents_match_for_saved_snapshot(
-    current_document: &ClaimDocumentV1,
-    saved_document: &ClaimDocumentV1,
+    current_document: &ClaimDocumentV2,
+    saved_document: &ClaimDocumentV2,
 ) -> bool {
     let mut current_document = current_document.clone();
     let mut saved_document = saved_document.clone();
@@ -1188,7 +1437,7 @@ fn apply_saved_snapshot_if_current(
     active_owner: RwSignal<ClaimOwner>,
     snapshot_id: String,
     snapshot_url: String,
-    saved_document: &ClaimDocumentV1,
+    saved_document: &ClaimDocumentV2,
 ) -> bool {
     let mut applied = false;
     session.update(|state| {
@@ -1239,7 +1488,7 @@ async fn resolve_boot_payload(
         ClaimsRoute::NewBlank => Ok(ClaimsBootPayload::Blank(editor_init(
             geometry,
             read_staged_live_bootstrap(),
-            ClaimDocumentV1::blank(),
+            ClaimDocumentV2::blank(),
             false,
             false,
             Vec::new(),
@@ -1254,7 +1503,7 @@ async fn resolve_boot_payload(
                 None => history::fetch_live_state().await?,
             };
             stage_live_bootstrap(&live_state);
-            let document = ClaimDocumentV1::frozen_live(
+            let document = ClaimDocumentV2::frozen_live(
                 None,
                 live_state.seq,
                 live_owner_map_from_state(&live_state),
@@ -1490,8 +1739,16 @@ fn ClaimsEditor(boot: ClaimsBootPayload) -> impl IntoView {
     let error_message: RwSignal<Option<String>> = RwSignal::new(None);
     let status_message: RwSignal<Option<String>> = RwSignal::new(None);
     let claims_persistence_available: RwSignal<bool> = RwSignal::new(false);
+    let owned_layouts: RwSignal<Vec<OwnedClaimLayout>> = RwSignal::new(Vec::new());
+    let owned_layouts_nonce: RwSignal<u64> = RwSignal::new(0);
     let preset_name_input: RwSignal<String> = RwSignal::new(String::new());
     let macro_name_input: RwSignal<String> = RwSignal::new(String::new());
+    let tower_plan_level: RwSignal<u32> = RwSignal::new(0);
+    let diff_target_input: RwSignal<String> = RwSignal::new(String::new());
+    let diff_target: RwSignal<Option<SavedClaimDocumentResponse>> = RwSignal::new(None);
+    let diff_target_loading: RwSignal<bool> = RwSignal::new(false);
+    let changed_territories: RwSignal<Arc<HashSet<String>>> =
+        RwSignal::new(Arc::new(HashSet::new()));
     let guild_query: RwSignal<String> = RwSignal::new(String::new());
     let guild_results: RwSignal<Vec<GuildCatalogEntry>> = RwSignal::new(Vec::new());
     let guild_search_nonce: RwSignal<u64> = RwSignal::new(0);
@@ -1579,6 +1836,7 @@ fn ClaimsEditor(boot: ClaimsBootPayload) -> impl IntoView {
     provide_context(HeatEntriesByTerritory(RwSignal::new(HashMap::new())));
     provide_context(HeatMaxTakeCount(RwSignal::new(0)));
     provide_context(HeatWindowLabel(RwSignal::new(String::new())));
+    provide_context(InferredHqs(RwSignal::new(Arc::new(HashMap::new()))));
     provide_context(LabelScaleMaster(RwSignal::new(1.0)));
     provide_context(LabelScaleStatic(RwSignal::new(1.0)));
     provide_context(LabelScaleStaticName(RwSignal::new(1.0)));
@@ -1694,6 +1952,7 @@ fn ClaimsEditor(boot: ClaimsBootPayload) -> impl IntoView {
         tool,
         handle_hit: apply_hit,
         handle_box_select: apply_box_select,
+        changed_territories,
     });
 
     let metrics = Memo::new(move |_| {
@@ -1712,6 +1971,31 @@ fn ClaimsEditor(boot: ClaimsBootPayload) -> impl IntoView {
         Some(compute_claim_metrics(&document, &territory_map))
     });
 
+    let claim_diff = Memo::new(move |_| {
+        if tab.get() != ClaimTab::Compare {
+            return None;
+        }
+        let session = session.get()?;
+        let target = diff_target.get();
+        Some(session_claim_diff(
+            &session,
+            target.as_ref().map(|target| &target.document),
+            &live_territories.get(),
+            live_seq.get(),
+        ))
+    });
+
+    Effect::new(move || {
+        let changed = claim_diff.with(|diff| {
+            diff.as_ref()
+                .map(ClaimDiff::changed_territories)
+                .unwrap_or_default()
+        });
+        if changed_territories.with_untracked(|current| **current != changed) {
+            changed_territories.set(Arc::new(changed));
+        }
+    });
+
     let active_metrics = Memo::new(move |_| {
         let metrics = metrics.get()?;
         let active = owner_identity(&active_owner.get());
@@ -1721,6 +2005,75 @@ fn ClaimsEditor(boot: ClaimsBootPayload) -> impl IntoView {
             .find(|entry| owner_identity(&entry.owner) == active)
     });
 
+    let active_routing = Memo::new(move |_| {
+        if tab.get() != ClaimTab::Summary {
+            return None;
+        }
+        let owner = active_owner.get();
+        let guild = owner.as_guild()?;
+        let territory_map = territory_map_from_client(&effective_territories.get());
+        // A planned HQ wins over inference while the guild still holds it.
+        let planned_hq = session.with(|state| {
+            state
+                .as_ref()
+                .and_then(|state| state.document.hq_for(&owner))
+                .filter(|hq| {
+                    territory_map
+                        .get(*hq)
+                        .is_some_and(|territory| territory.guild.name == guild.name)
+                })
+                .map(ToOwned::to_owned)
+        });
+        route_guild_resources(
+            &territory_map,
+            &guild.name,
+            planned_hq.as_deref(),
+            DEFAULT_FOREIGN_TAX_RATE,
+        )
+    });
+
+    // Upkeep of planned tower levels (or a maxed HQ plus uniform towers elsewhere), against the
+    // layout's production. Territories taken in the layout start over at the lowest treasury level.
+    let active_economy = Memo::new(move |_| {
+        let routing = active_routing.get()?;
+        let level = tower_plan_level.get();
+        let now = Utc::now();
+        let live_map = live_territories.get();
+        let mut territory_map = territory_map_from_client(&effective_territories.get());
+        for (name, territory) in territory_map.iter_mut() {
+            let retaken = live_map
+                .get(name)
+                .is_none_or(|live| live.territory.guild.name != territory.guild.name);
+            if retaken {
+                territory.acquired = now;
+                territory.runtime = None;
+            }
+        }
+        let planned = session.with(|state| {
+            state
+                .as_ref()
+                .map(|state| state.document.tower_levels.clone())
+                .unwrap_or_default()
+        });
+        guild_economy(
+            &territory_map,
+            &routing.guild_name,
+            now.timestamp(),
+            |territory| {
+                if let Some(levels) = planned.get(territory) {
+                    levels.upgrades()
+                } else if territory == routing.hq {
+                    TowerUpgrades::MAX
+                } else {
+                    TowerUpgrades::uniform(level)
+                }
+            },
+        )
+    });
+
+    // Separate from the numbers so the level input is not rebuilt while typing.
+    let has_active_economy = Memo::new(move |_| active_economy.with(Option::is_some));
+
     let selected_territory_details = Memo::new(move |_| {
         let territory_name = selected.get()?;
         let session_state = session.get()?;
@@ -1753,6 +2106,48 @@ fn ClaimsEditor(boot: ClaimsBootPayload) -> impl IntoView {
         ))
     });
 
+    let selected_territory_plan = Memo::new(move |_| {
+        let territory_name = selected.get()?;
+        let session_state = session.get()?;
+        let live_owners = current_live_owner_map(&live_territories.get());
+        let owner = effective_owner_for_session(&session_state, &live_owners, &territory_name);
+        let document = &session_state.document;
+        let is_hq = document.hq_for(&owner) == Some(territory_name.as_str());
+        let levels = document
+            .tower_levels
+            .get(&territory_name)
+            .copied()
+            .unwrap_or_default();
+        let mut note = String::new();
+        let mut arrows = Vec::new();
+        for (index, annotation) in document.annotations.iter().enumerate() {
+            match annotation {
+                ClaimAnnotation::Note { territory, text } if *territory == territory_name => {
+                    note = text.clone();
+                }
+                ClaimAnnotation::Arrow { from, to, .. }
+                    if *from == territory_name || *to == territory_name =>
+                {
+                    arrows.push((index, format!("{from} \u{2192} {to}")));
+                }
+                _ => {}
+            }
+        }
+        let arrow_target = match session_state.selection.as_slice() {
+            [from, to] => Some((from.clone(), to.clone())),
+            _ => None,
+        };
+        Some((
+            territory_name,
+            owner.as_guild().is_some(),
+            is_hq,
+            levels,
+            note,
+            arrows,
+            arrow_target,
+        ))
+    });
+
     // Auto-switch to Territory tab only when a new territory is selected in View mode,
     // not on every tab change (which would lock users out of other tabs).
     {
@@ -2014,6 +2409,22 @@ fn ClaimsEditor(boot: ClaimsBootPayload) -> impl IntoView {
         });
     });
 
+    let persistence_ready = Memo::new(move |_| claims_persistence_available.get());
+    Effect::new(move || {
+        owned_layouts_nonce.track();
+        if !persistence_ready.get() {
+            return;
+        }
+        let Some(token) = read_claim_edit_token() else {
+            return;
+        };
+        spawn_local(async move {
+            if let Ok(layouts) = fetch_owned_claim_layouts(&token).await {
+                owned_layouts.set(layouts);
+            }
+        });
+    });
+
     let apply_active_to_selection = move |_| {
         let current_active_owner = active_owner.get_untracked();
         let live_owners = current_live_owner_map(&live_territories.get_untracked());
@@ -2133,7 +2544,7 @@ fn ClaimsEditor(boot: ClaimsBootPayload) -> impl IntoView {
                         error_message.set(Some("Import file was not valid text".to_string()));
                         return;
                     };
-                    match serde_json::from_str::<ClaimDocumentV1>(&text) {
+                    match serde_json::from_str::<ClaimDocumentV2>(&text) {
                         Ok(document) => {
                             if let Err(error) = validate_document_against_live(
                                 &document,
@@ -2550,6 +2961,101 @@ fn ClaimsEditor(boot: ClaimsBootPayload) -> impl IntoView {
                                             "Click any territory to inspect its owner, resources, and connections."
                                         </div>
                                     }.into_any())}
+                                    {move || selected_territory_plan.get().map(|(territory_name, owned, is_hq, levels, note, arrows, arrow_target)| {
+                                        let hq_territory = territory_name.clone();
+                                        let note_territory = territory_name.clone();
+                                        let tower_fields: [(&'static str, &'static str, u8, u8); 6] = [
+                                            ("Damage", "damage", levels.damage, 11),
+                                            ("Attack", "attack", levels.attack, 11),
+                                            ("Health", "health", levels.health, 11),
+                                            ("Defense", "defense", levels.defense, 11),
+                                            ("Aura", "aura", levels.aura, 3),
+                                            ("Volley", "volley", levels.volley, 3),
+                                        ];
+                                        view! {
+                                            <div class="card">
+                                                <div class="section-label" style="font-size: 0.74rem;">"War Plan"</div>
+                                                {owned.then(|| view! {
+                                                    <button class="btn"
+                                                        on:click=move |_| {
+                                                            let live_owners = current_live_owner_map(&live_territories.get_untracked());
+                                                            let territory = hq_territory.clone();
+                                                            apply_session_edit(session, active_owner, |state| {
+                                                                toggle_session_hq(state, &territory, &live_owners)
+                                                            });
+                                                        }
+                                                    >
+                                                        {if is_hq { "Clear HQ" } else { "Mark As HQ" }}
+                                                    </button>
+                                                })}
+                                                <div style="display: grid; grid-template-columns: 1fr 1fr 1fr; gap: 8px;">
+                                                    {tower_fields.into_iter().map(|(label, field, value, max)| {
+                                                        let territory = territory_name.clone();
+                                                        view! {
+                                                            <label style="display: grid; gap: 4px;">
+                                                                <span style="color: #8d97b3; font-size: 0.7rem;">{label}</span>
+                                                                <input class="input" type="number" min="0" max=max.to_string()
+                                                                    prop:value=value.to_string()
+                                                                    on:input=move |event| {
+                                                                        let parsed = event_target_value(&event).trim().parse::<u8>().unwrap_or(0);
+                                                                        let territory = territory.clone();
+                                                                        apply_session_edit(session, active_owner, |state| {
+                                                                            set_session_tower_level(state, &territory, field, parsed)
+                                                                        });
+                                                                    }
+                                                                />
+                                                            </label>
+                                                        }
+                                                    }).collect_view()}
+                                                </div>
+                                                <label style="display: grid; gap: 4px;">
+                                                    <span style="color: #8d97b3; font-size: 0.7rem;">"Note"</span>
+                                                    <textarea class="input" rows="3" maxlength="500"
+                                                        prop:value=note
+                                                        on:change=move |event| {
+                                                            let text = event_target_value(&event);
+                                                            let territory = note_territory.clone();
+                                                            apply_session_edit(session, active_owner, |state| {
+                                                                set_session_note(state, &territory, &text)
+                                                            });
+                                                        }
+                                                    />
+                                                </label>
+                                                {arrows.into_iter().map(|(index, label)| view! {
+                                                    <div style="display: flex; align-items: center; justify-content: space-between; gap: 8px;">
+                                                        <span>{label}</span>
+                                                        <button class="btn btn-sm"
+                                                            on:click=move |_| {
+                                                                apply_session_edit(session, active_owner, |state| {
+                                                                    if index >= state.document.annotations.len() {
+                                                                        return false;
+                                                                    }
+                                                                    state.document.annotations.remove(index);
+                                                                    true
+                                                                });
+                                                            }
+                                                        >
+                                                            "Remove"
+                                                        </button>
+                                                    </div>
+                                                }).collect_view()}
+                                                {arrow_target.map(|(from, to)| {
+                                                    let label = format!("Arrow {from} \u{2192} {to}");
+                                                    view! {
+                                                        <button class="btn"
+                                                            on:click=move |_| {
+                                                                apply_session_edit(session, active_owner, |state| {
+                                                                    add_session_arrow(state, &from, &to)
+                                                                });
+                                                            }
+                                                        >
+                                                            {label}
+                                                        </button>
+                                                    }
+                                                })}
+                                            </div>
+                                        }.into_any()
+                                    })}
                                 </div>
                             }
                             .into_any()
@@ -2579,6 +3085,58 @@ fn ClaimsEditor(boot: ClaimsBootPayload) -> impl IntoView {
                                             </div>
                                         }.into_any()
                                     }).unwrap_or_else(|| view! { <div style="color: #9a9590;">"Choose a guild or paint territories to see the summary."</div> }.into_any())}
+                                    {move || active_routing.get().map(|routing| {
+                                        let lost = routing.lost();
+                                        let taxed = routing.routes.iter().filter(|route| route.foreign_hops > 0).count();
+                                        view! {
+                                            <div class="section-label">"Resource Routing"</div>
+                                            <div class="card" style="gap: 6px;">
+                                                <div>{format!("HQ: {}", routing.hq)}</div>
+                                                <div>{format!("Routed: {}/{} • Longest: {} hops • Taxed: {}", routing.routes.len(), routing.routes.len() + routing.unreachable.len(), routing.longest_route, taxed)}</div>
+                                                <div>{format!("Lost/hr: Em {} • Ore {} • Crops {} • Fish {} • Wood {}", lost.emeralds, lost.ore, lost.crops, lost.fish, lost.wood)}</div>
+                                                {(!routing.unreachable.is_empty()).then(|| view! {
+                                                    <div style="color: #e05252;">{format!("Cut off: {}", routing.unreachable.join(", "))}</div>
+                                                })}
+                                                {routing.bottlenecks.iter().take(5).map(|b| view! {
+                                                    <div style="color: #e0a052;">{format!("Storage full: {} • {} {}/{}", b.territory, b.resource.label(), b.transit_per_transfer, b.capacity)}</div>
+                                                }).collect_view()}
+                                            </div>
+                                        }.into_any()
+                                    })}
+                                    {move || has_active_economy.get().then(|| view! {
+                                        <div class="section-label">"Tower Upkeep"</div>
+                                        <label style="display: flex; align-items: center; gap: 8px;">
+                                            "Default tower level"
+                                            <input class="input" type="number" min="0" max="11" style="width: 64px;"
+                                                prop:value=move || tower_plan_level.get().to_string()
+                                                on:input=move |event| {
+                                                    if let Ok(level) = event_target_value(&event).parse::<u32>() {
+                                                        tower_plan_level.set(level.min(11));
+                                                    }
+                                                }
+                                            />
+                                        </label>
+                                    })}
+                                    {move || active_economy.get().map(|economy| {
+                                        let balance = economy.balance();
+                                        let deficits = economy.deficits();
+                                        view! {
+                                            <div class="card" style="gap: 6px;">
+                                                <div>{format!("Upkeep/hr: Ore {} • Crops {} • Fish {} • Wood {}", economy.upkeep.ore, economy.upkeep.crops, economy.upkeep.fish, economy.upkeep.wood)}</div>
+                                                <div>{format!("Balance/hr: Em {} • Ore {} • Crops {} • Fish {} • Wood {}", balance.emeralds, balance.ore, balance.crops, balance.fish, balance.wood)}</div>
+                                                {if deficits.is_empty() {
+                                                    view! { <div style="color: #50c878;">"Affordable with this tower plan."</div> }.into_any()
+                                                } else {
+                                                    let shortfall = deficits
+                                                        .iter()
+                                                        .map(|(resource, amount)| format!("{} -{amount}", resource.label()))
+                                                        .collect::<Vec<_>>()
+                                                        .join(" • ");
+                                                    view! { <div style="color: #e05252;">{format!("Can't afford upgrades: {shortfall}")}</div> }.into_any()
+                                                }}
+                                            </div>
+                                        }.into_any()
+                                    })}
                                 </div>
                             }
                             .into_any()
@@ -2586,6 +3144,107 @@ fn ClaimsEditor(boot: ClaimsBootPayload) -> impl IntoView {
                         ClaimTab::Compare => {
                             view! {
                                 <div style="display: flex; flex-direction: column; gap: 8px;">
+                                    <div class="section-label">"Diff"</div>
+                                    <div style="display: flex; gap: 6px;">
+                                        <input class="input" style="flex: 1;"
+                                            prop:value=move || diff_target_input.get()
+                                            placeholder="Saved claim id or URL (blank = live map)"
+                                            on:input=move |event| diff_target_input.set(event_target_value(&event))
+                                        />
+                                        <button class="btn btn-sm"
+                                            disabled=move || diff_target_loading.get()
+                                            on:click=move |_| {
+                                                let input = diff_target_input.get_untracked();
+                                                if input.trim().is_empty() {
+                                                    diff_target.set(None);
+                                                    return;
+                                                }
+                                                let Some(id) = saved_claim_id_from_input(&input) else {
+                                                    error_message.set(Some("Not a saved claim id or URL".to_string()));
+                                                    return;
+                                                };
+                                                diff_target_loading.set(true);
+                                                spawn_local(async move {
+                                                    let result = fetch_saved_claim_document(&id).await;
+                                                    diff_target_loading.set(false);
+                                                    match result {
+                                                        Ok(payload) => diff_target.set(Some(payload)),
+                                                        Err(error) => error_message.set(Some(format!("Failed to load claim {id}: {error}"))),
+                                                    }
+                                                });
+                                            }
+                                        >
+                                            {move || if diff_target_loading.get() { "Loading..." } else { "Compare" }}
+                                        </button>
+                                    </div>
+                                    {move || claim_diff.get().map(|diff| {
+                                        let against = diff_target
+                                            .get()
+                                            .map(|target| format!("Against saved claim {}", target.title.unwrap_or(target.id)))
+                                            .unwrap_or_else(|| "Against the live map".to_string());
+                                        let changed: Vec<String> = {
+                                            let mut changed: Vec<String> = diff.changed_territories().into_iter().collect();
+                                            changed.sort();
+                                            changed
+                                        };
+                                        let hidden_owner_changes = diff.owner_changes.len().saturating_sub(CLAIM_DIFF_LIST_LIMIT);
+                                        let nothing_changed = changed.is_empty();
+                                        view! {
+                                            <div class="card-inset">
+                                                {format!(
+                                                    "{against} • {} owner changes • {} resource overrides",
+                                                    diff.owner_changes.len(),
+                                                    diff.resource_changes.len()
+                                                )}
+                                            </div>
+                                            <button class="btn btn-sm"
+                                                disabled=nothing_changed
+                                                on:click=move |_| {
+                                                    let preferred = selected.get_untracked();
+                                                    session.update(|state| {
+                                                        if let Some(state) = state.as_mut() {
+                                                            state.selection = changed.clone();
+                                                        }
+                                                    });
+                                                    selected.set(selection_focus(&changed, preferred.as_deref()));
+                                                }
+                                            >
+                                                "Select Changed Territories"
+                                            </button>
+                                            {diff.guilds.into_iter().map(|entry| {
+                                                let doubles = |metrics: &Option<sequoia_shared::ClaimGuildMetrics>| {
+                                                    metrics.as_ref().map_or(0, |metrics| metrics.resources.any_double)
+                                                };
+                                                view! {
+                                                    <div class="card-inset">
+                                                        {format!(
+                                                            "{} • {:+} terr • doubles {} → {}",
+                                                            entry.owner.display_name(),
+                                                            entry.territory_delta,
+                                                            doubles(&entry.before),
+                                                            doubles(&entry.after)
+                                                        )}
+                                                    </div>
+                                                }
+                                            }).collect_view()}
+                                            {diff.owner_changes.into_iter().take(CLAIM_DIFF_LIST_LIMIT).map(|change| {
+                                                view! {
+                                                    <div style="color: #8d97b3; font-size: 0.7rem;">
+                                                        {format!(
+                                                            "{}: {} → {}",
+                                                            change.territory,
+                                                            change.before.display_name(),
+                                                            change.after.display_name()
+                                                        )}
+                                                    </div>
+                                                }
+                                            }).collect_view()}
+                                            {(hidden_owner_changes > 0).then(|| view! {
+                                                <div style="color: #9a9590;">{format!("...and {hidden_owner_changes} more")}</div>
+                                            })}
+                                        }.into_any()
+                                    })}
+                                    <div class="section-label">"Metrics"</div>
                                     {move || metrics.get().map(|metrics| {
                                         view! {
                                             <div class="card-inset">
@@ -2760,12 +3419,18 @@ fn ClaimsEditor(boot: ClaimsBootPayload) -> impl IntoView {
                                                 default_view_from(&viewport.get_untracked(), &active_owner.get_untracked()),
                                             );
                                             let saved_document = document.clone();
+                                            // Our own saved plans are updated in place so their URL stays stable.
+                                            let owned_id = owned_layouts.with_untracked(|layouts| {
+                                                owned_source_snapshot_id(&session_state, layouts)
+                                            });
+                                            let updated_in_place = owned_id.is_some();
                                             snapshot_save_in_flight.set(true);
                                             spawn_local(async move {
-                                                let result = create_saved_claim(document).await;
+                                                let result = save_claim_layout(document, owned_id).await;
                                                 snapshot_save_in_flight.set(false);
                                                 match result {
                                                     Ok(payload) => {
+                                                        owned_layouts_nonce.update(|nonce| *nonce += 1);
                                                         let share_url = absolute_claim_url(&payload.url);
                                                         if !apply_saved_snapshot_if_current(
                                                             session,
@@ -2783,7 +3448,11 @@ fn ClaimsEditor(boot: ClaimsBootPayload) -> impl IntoView {
                                                             return;
                                                         }
                                                         copy_url_to_clipboard(&share_url);
-                                                        status_message.set(Some("Copied short share URL".to_string()));
+                                                        status_message.set(Some(if updated_in_place {
+                                                            "Updated saved plan and copied its share URL".to_string()
+                                                        } else {
+                                                            "Copied short share URL".to_string()
+                                                        }));
                                                     }
                                                     Err(error) => error_message.set(Some(error)),
                                                 }
@@ -2814,10 +3483,11 @@ fn ClaimsEditor(boot: ClaimsBootPayload) -> impl IntoView {
                                             let saved_document = document.clone();
                                             snapshot_save_in_flight.set(true);
                                             spawn_local(async move {
-                                                let result = create_saved_claim(document).await;
+                                                let result = save_claim_layout(document, None).await;
                                                 snapshot_save_in_flight.set(false);
                                                 match result {
                                                     Ok(payload) => {
+                                                        owned_layouts_nonce.update(|nonce| *nonce += 1);
                                                         let share_url = absolute_claim_url(&payload.url);
                                                         if !apply_saved_snapshot_if_current(
                                                             session,
@@ -2849,6 +3519,61 @@ fn ClaimsEditor(boot: ClaimsBootPayload) -> impl IntoView {
                                     >
                                         "Save Immutable Snapshot"
                                     </button>
+                                    {move || {
+                                        let layouts = owned_layouts.get();
+                                        (!layouts.is_empty()).then(|| view! {
+                                            <div style="display: flex; flex-direction: column; gap: 8px;">
+                                                <div class="section-label">"My Saved Plans"</div>
+                                                {layouts.into_iter().map(|layout| {
+                                                    let label = format!(
+                                                        "{} • rev {}",
+                                                        layout.title.clone().unwrap_or_else(|| layout.id.clone()),
+                                                        layout.revision
+                                                    );
+                                                    let id = layout.id.clone();
+                                                    view! {
+                                                        <div class="card" style="flex-direction: row; align-items: center; padding: 8px;">
+                                                            <a style="flex: 1; color: inherit;" href=layout.url.clone()>{label}</a>
+                                                            <button class="btn btn-sm"
+                                                                on:click=move |_| {
+                                                                    let confirmed = web_sys::window()
+                                                                        .and_then(|window| window.confirm_with_message("Delete this saved plan and its history?").ok())
+                                                                        .unwrap_or(false);
+                                                                    let Some(token) = read_claim_edit_token() else {
+                                                                        return;
+                                                                    };
+                                                                    if !confirmed {
+                                                                        return;
+                                                                    }
+                                                                    let id = id.clone();
+                                                                    spawn_local(async move {
+                                                                        match delete_owned_claim_layout(&id, &token).await {
+                                                                            Ok(()) => {
+                                                                                owned_layouts.update(|layouts| layouts.retain(|layout| layout.id != id));
+                                                                                session.update(|state| {
+                                                                                    if let Some(state) = state.as_mut()
+                                                                                        && state.source_snapshot_id.as_deref() == Some(id.as_str())
+                                                                                    {
+                                                                                        state.source_snapshot_id = None;
+                                                                                        state.source_snapshot_url = None;
+                                                                                        state.dirty = true;
+                                                                                    }
+                                                                                });
+                                                                                status_message.set(Some(format!("Deleted saved plan {id}")));
+                                                                            }
+                                                                            Err(error) => error_message.set(Some(format!("Failed to delete {id}: {error}"))),
+                                                                        }
+                                                                    });
+                                                                }
+                                                            >
+                                                                "Delete"
+                                                            </button>
+                                                        </div>
+                                                    }
+                                                }).collect_view()}
+                                            </div>
+                                        })
+                                    }}
                                     <button class="btn"
                                         on:click=move |_| {
                                             let Some(session_state) = session.get_untracked() else {
@@ -2983,9 +3708,52 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn saved_claim_id_from_input_accepts_ids_and_share_urls() {
+        assert_eq!(
+            saved_claim_id_from_input(" abc123 ").as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(
+            saved_claim_id_from_input("https://example.test/claims/s/abc123/#c=xyz").as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(saved_claim_id_from_input("/claims/s/"), None);
+        assert_eq!(saved_claim_id_from_input("claims/new/live"), None);
+    }
+
+    #[test]
+    fn only_owned_source_snapshots_are_updated_in_place() {
+        let mut session = ClaimWorkingSession {
+            document: ClaimDocumentV2::blank(),
+            follow_live: false,
+            dirty: true,
+            selection: Vec::new(),
+            source_snapshot_id: Some("clm1".to_string()),
+            source_snapshot_url: None,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+        };
+        let owned = vec![OwnedClaimLayout {
+            id: "clm1".to_string(),
+            url: "/claims/s/clm1".to_string(),
+            created_at: String::new(),
+            updated_at: None,
+            title: None,
+            revision: 3,
+        }];
+        assert_eq!(
+            owned_source_snapshot_id(&session, &owned).as_deref(),
+            Some("clm1")
+        );
+        assert_eq!(owned_source_snapshot_id(&session, &[]), None);
+        session.source_snapshot_id = Some("clm2".to_string());
+        assert_eq!(owned_source_snapshot_id(&session, &owned), None);
+    }
+
     #[test]
     fn claim_fragment_round_trip_preserves_document() {
-        let mut document = ClaimDocumentV1::blank();
+        let mut document = ClaimDocumentV2::blank();
         document.title = Some("Test".to_string());
         document.overrides.insert(
             "Ragni".to_string(),
@@ -3019,7 +3787,7 @@ mod tests {
     #[test]
     fn reusable_share_url_prefers_stored_url_and_requires_clean_saved_session() {
         let session = ClaimWorkingSession {
-            document: ClaimDocumentV1::blank(),
+            document: ClaimDocumentV2::blank(),
             follow_live: false,
             dirty: false,
             selection: Vec::new(),
@@ -3052,7 +3820,7 @@ mod tests {
     #[test]
     fn live_updates_invalidate_reusable_share_urls_for_live_sessions() {
         let mut session = ClaimWorkingSession {
-            document: ClaimDocumentV1::blank(),
+            document: ClaimDocumentV2::blank(),
             follow_live: true,
             dirty: false,
             selection: Vec::new(),
@@ -3072,7 +3840,7 @@ mod tests {
     #[test]
     fn live_updates_do_not_invalidate_reusable_share_urls_for_frozen_sessions() {
         let mut session = ClaimWorkingSession {
-            document: ClaimDocumentV1::blank(),
+            document: ClaimDocumentV2::blank(),
             follow_live: false,
             dirty: false,
             selection: Vec::new(),
@@ -3095,8 +3863,8 @@ mod tests {
     #[test]
     fn documents_match_for_saved_snapshot_ignores_captured_at() {
         let owners = HashMap::from([("Ragni".to_string(), neutral_owner())]);
-        let current_document = ClaimDocumentV1::frozen_live(None, 42, owners.clone());
-        let mut saved_document = ClaimDocumentV1::frozen_live(None, 42, owners);
+        let current_document = ClaimDocumentV2::frozen_live(None, 42, owners.clone());
+        let mut saved_document = ClaimDocumentV2::frozen_live(None, 42, owners);
         if let ClaimDocumentBase::FrozenLiveSnapshot { captured_at, .. } = &mut saved_document.base
         {
             *captured_at = "2000-01-01T00:00:00Z".to_string();
@@ -3109,7 +3877,7 @@ mod tests {
 
     #[test]
     fn documents_match_for_saved_snapshot_ignores_view() {
-        let mut current_document = ClaimDocumentV1::blank();
+        let mut current_document = ClaimDocumentV2::blank();
         current_document.view = ClaimViewState {
             offset_x: 120.0,
             offset_y: -45.0,
@@ -3137,7 +3905,7 @@ mod tests {
     #[test]
     fn stored_claim_draft_defaults_dirty_for_legacy_data() {
         let draft = serde_json::from_value::<StoredClaimDraft>(serde_json::json!({
-            "document": ClaimDocumentV1::blank(),
+            "document": ClaimDocumentV2::blank(),
             "follow_live": false,
             "selection": [],
             "source_snapshot_id": "snapshot-123",
@@ -3186,7 +3954,7 @@ mod tests {
                 },
             )]),
         };
-        let mut document = ClaimDocumentV1::blank();
+        let mut document = ClaimDocumentV2::blank();
         document.title = Some("Shared".to_string());
 
         let payload = hash_import_boot_payload(geometry.clone(), None, document.clone());
@@ -3214,7 +3982,7 @@ mod tests {
                 },
             )]),
         };
-        let mut document = ClaimDocumentV1::blank();
+        let mut document = ClaimDocumentV2::blank();
         document
             .overrides
             .insert("Ragni".to_string(), ClaimOwner::Neutral);
@@ -3228,4 +3996,57 @@ mod tests {
             Err(ClaimValidationError::UnknownTerritory(name)) if name == "Detlas"
         ));
     }
+
+    #[test]
+    fn war_plan_edits_track_hq_towers_and_annotations() {
+        let alpha = ClaimOwner::from_guild(GuildRef {
+            uuid: "g1".to_string(),
+            name: "Alpha".to_string(),
+            prefix: "ALP".to_string(),
+            color: None,
+        });
+        let mut session = ClaimWorkingSession {
+            document: ClaimDocumentV2::blank(),
+            follow_live: false,
+            dirty: false,
+            selection: Vec::new(),
+            source_snapshot_id: None,
+            source_snapshot_url: None,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+        };
+        session
+            .document
+            .overrides
+            .insert("Detlas".to_string(), alpha.clone());
+        let live_owners = HashMap::new();
+
+        assert!(!toggle_session_hq(&mut session, "Ragni", &live_owners));
+        assert!(toggle_session_hq(&mut session, "Detlas", &live_owners));
+        assert_eq!(session.document.hq_for(&alpha), Some("Detlas"));
+        assert!(toggle_session_hq(&mut session, "Detlas", &live_owners));
+        assert!(session.document.hqs.is_empty());
+
+        assert!(set_session_tower_level(&mut session, "Detlas", "aura", 9));
+        assert_eq!(
+            session.document.tower_levels.get("Detlas"),
+            Some(&sequoia_shared::ClaimTowerLevels {
+                aura: 3,
+                ..sequoia_shared::ClaimTowerLevels::default()
+            })
+        );
+        assert!(!set_session_tower_level(&mut session, "Detlas", "aura", 3));
+        assert!(set_session_tower_level(&mut session, "Detlas", "aura", 0));
+        assert!(session.document.tower_levels.is_empty());
+
+        assert!(set_session_note(&mut session, "Detlas", " hold "));
+        assert!(!set_session_note(&mut session, "Detlas", "hold"));
+        assert!(set_session_note(&mut session, "Detlas", "retreat"));
+        assert!(add_session_arrow(&mut session, "Ragni", "Detlas"));
+        assert!(!add_session_arrow(&mut session, "Ragni", "Detlas"));
+        assert!(!add_session_arrow(&mut session, "Ragni", "Ragni"));
+        assert_eq!(session.document.annotations.len(), 2);
+        assert!(set_session_note(&mut session, "Detlas", ""));
+        assert_eq!(session.document.annotations.len(), 1);
+    }
 }
@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write as _;
+use std::sync::Arc;
 
 use wasm_bindgen::JsCast;
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
@@ -31,7 +32,7 @@ use crate::overlay_sizing::{
     compute_territory_ornament_tint, static_name_bottom_bound,
 };
 use crate::renderer::{FrameMetrics, InvalidationReason, RenderCapabilities, SceneSnapshot};
-use crate::territory::{ClientTerritoryMap, is_sequoia_guild, is_unclaimed_guild};
+use crate::territory::{ClientTerritory, ClientTerritoryMap, is_sequoia_guild, is_unclaimed_guild};
 use crate::tiles::{LoadedTile, TileQuality};
 use crate::time_format::write_hms;
 use crate::viewport::Viewport;
@@ -235,6 +236,7 @@ const HQ_CROWN_NORMAL_SIDE_PADDING_PX: f32 = 4.0;
 const HQ_CROWN_NORMAL_LABEL_GAP_PX: f32 = 3.0;
 const HQ_CROWN_NORMAL_MAX_HEIGHT_FRACTION: f32 = 0.24;
 const HQ_CROWN_NORMAL_MIN_RENDERED_PX: f32 = 12.0;
+const INFERRED_HQ_CROWN_MIN_ALPHA: f32 = 0.35;
 const ORNAMENT_MIN_RENDERED_PX: f32 = 1.0;
 const ORNAMENT_UV_PADDING_PX: u32 = 2;
 const SEQUOIA_ORNAMENT_FALLBACK_GOLD: [u8; 3] = [245, 197, 66];
@@ -266,6 +268,29 @@ fn resource_icons_visible_for_territory(
         && resource_icons_drawable(resources)
 }
 
+/// Crown opacity for a territory, or `None` when it is not drawn as an HQ.
+///
+/// Ingest runtime flags are authoritative; otherwise the server's inferred HQ is drawn faded
+/// by its confidence.
+fn hq_crown_alpha(
+    inferred_hqs: &HashMap<String, f32>,
+    name: &str,
+    ct: &ClientTerritory,
+) -> Option<f32> {
+    match ct
+        .territory
+        .runtime
+        .as_ref()
+        .and_then(|runtime| runtime.headquarters)
+    {
+        Some(true) => Some(1.0),
+        Some(false) => None,
+        None => inferred_hqs.get(name).map(|confidence| {
+            (INFERRED_HQ_CROWN_MIN_ALPHA + confidence * 0.5).clamp(INFERRED_HQ_CROWN_MIN_ALPHA, 0.9)
+        }),
+    }
+}
+
 #[inline]
 fn hq_crown_expanded_at_zoom(px_per_world: f32) -> bool {
     px_per_world <= HQ_CROWN_EXPANDED_MAX_SCALE
@@ -1050,6 +1075,10 @@ pub struct GpuRenderer {
     pub label_scale_static_name: f32,
     pub label_scale_dynamic: f32,
     pub label_scale_icons: f32,
+    /// Inferred HQ confidence per territory name, for guilds without ingest HQ data.
+    pub inferred_hqs: Arc<HashMap<String, f32>>,
+    /// Territories outlined as changed by the claims diff view.
+    pub changed_territories: Arc<HashSet<String>>,
 }
 
 impl GpuRenderer {
@@ -1771,6 +1800,8 @@ impl GpuRenderer {
             label_scale_static_name: 1.0,
             label_scale_dynamic: 1.0,
             label_scale_icons: 1.0,
+            inferred_hqs: Arc::new(HashMap::new()),
+            changed_territories: Arc::new(HashSet::new()),
         };
 
         if !renderer.ensure_text_renderer() {
@@ -2893,6 +2924,7 @@ impl GpuRenderer {
 
                 let is_hovered = hovered.as_deref() == Some(name.as_str());
                 let is_selected = selected.as_deref() == Some(name.as_str());
+                let is_changed = self.changed_territories.contains(name);
 
                 let resource_data = if self.defense_highlight {
                     defense_tier_overlay_data(
@@ -2931,8 +2963,10 @@ impl GpuRenderer {
                     .as_ref()
                     .and_then(|runtime| runtime.headquarters)
                     .unwrap_or(false);
-                let flags =
-                    (is_hovered as u32) + (is_selected as u32) * 2 + (is_headquarters as u32) * 4;
+                let flags = (is_hovered as u32)
+                    + (is_selected as u32) * 2
+                    + (is_headquarters as u32) * 4
+                    + (is_changed as u32) * 8;
 
                 let acquired_rel_secs = if self.suppress_cooldown_visuals {
                     -1_000_000.0_f32
@@ -3196,12 +3230,7 @@ impl GpuRenderer {
                     let hh = loc.height() as f32;
                     let sw = ww * scale;
                     let sh = hh * scale;
-                    let is_hq = ct
-                        .territory
-                        .runtime
-                        .as_ref()
-                        .and_then(|runtime| runtime.headquarters)
-                        .unwrap_or(false);
+                    let is_hq = hq_crown_alpha(&self.inferred_hqs, name, ct).is_some();
                     if is_hq && hq_crown_expanded_at_zoom(scale) {
                         continue;
                     }
@@ -3478,18 +3507,13 @@ impl GpuRenderer {
             let glyphs = &text_renderer.glyphs;
             let kerning = &text_renderer.kerning;
             let line_height = text_renderer.line_height;
-            for (_name, ct) in territories {
+            for (name, ct) in territories {
                 let loc = &ct.territory.location;
                 let ww = loc.width() as f32;
                 let hh = loc.height() as f32;
                 let sw = ww * scale;
                 let sh = hh * scale;
-                let is_hq = ct
-                    .territory
-                    .runtime
-                    .as_ref()
-                    .and_then(|runtime| runtime.headquarters)
-                    .unwrap_or(false);
+                let is_hq = hq_crown_alpha(&self.inferred_hqs, name, ct).is_some();
                 if is_hq && hq_crown_expanded_at_zoom(scale) {
                     continue;
                 }
@@ -3770,18 +3794,14 @@ impl GpuRenderer {
         }
 
         let scale = vp.scale as f32;
-        for (_name, ct) in territories {
+        for (name, ct) in territories {
             let loc = &ct.territory.location;
             let ww = loc.width() as f32;
             let hh = loc.height() as f32;
             let sw = ww * scale;
             let sh = hh * scale;
-            let is_hq = ct
-                .territory
-                .runtime
-                .as_ref()
-                .and_then(|runtime| runtime.headquarters)
-                .unwrap_or(false);
+            let hq_alpha = hq_crown_alpha(&self.inferred_hqs, name, ct);
+            let is_hq = hq_alpha.is_some();
             let px_per_world = scale.max(0.0001);
             let cx = loc.midpoint_x() as f32;
             let cy = loc.midpoint_y() as f32;
@@ -3897,7 +3917,7 @@ impl GpuRenderer {
                         crown_size_world,
                     ],
                     uv_rect: crown_uv,
-                    tint: [1.0, 1.0, 1.0, 1.0],
+                    tint: [1.0, 1.0, 1.0, hq_alpha.unwrap_or(1.0)],
                 });
             }
 
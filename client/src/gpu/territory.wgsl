@@ -223,6 +223,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let is_hovered = (u32(flags) & 1u) != 0u;
     let is_selected = (u32(flags) & 2u) != 0u;
     let is_headquarters = (u32(flags) & 4u) != 0u;
+    let is_changed = (u32(flags) & 8u) != 0u;
 
     // GPU-side color animation
     var base_color = in.color.rgb;
@@ -278,6 +279,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     if is_headquarters {
         b_alpha = max(b_alpha, 0.88);
     }
+    if is_changed {
+        b_alpha = max(b_alpha, 0.95);
+    }
 
     // Fill zone — compute color and alpha with all effects
     var fill_color = compute_resource_fill(in.resource_data, in.uv, in.size_px, base_color);
@@ -370,6 +374,10 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     if is_headquarters {
         border_color = mix(base_color, vec3<f32>(0.973, 0.831, 0.275), 0.8);
     }
+    if is_changed {
+        // Claims diff: cyan outline, distinct from the gold HQ border
+        border_color = vec3<f32>(0.353, 0.863, 0.949);
+    }
     var color = mix(fill_color, border_color, border_t);
     var alpha = mix(f_alpha, b_alpha, border_t) * outer_aa;
     if cooldown_strip_mix > 0.0 {
@@ -66,6 +66,8 @@ mod gpu {
         pub label_scale_static_name: f32,
         pub label_scale_dynamic: f32,
         pub label_scale_icons: f32,
+        pub inferred_hqs: std::sync::Arc<std::collections::HashMap<String, f32>>,
+        pub changed_territories: std::sync::Arc<std::collections::HashSet<String>>,
         capabilities: RenderCapabilities,
         metrics: FrameMetrics,
     }
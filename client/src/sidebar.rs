@@ -2,7 +2,10 @@ use leptos::prelude::*;
 use std::collections::HashMap;
 use wasm_bindgen::JsCast;
 
-use sequoia_shared::history::HistoryHeatMeta;
+use sequoia_shared::history::{HistoryHeatMeta, HistoryRivalries, TerritoryOwnershipHistory};
+use sequoia_shared::resource_routing::{
+    DEFAULT_FOREIGN_TAX_RATE, GuildResourceRouting, route_guild_resources,
+};
 use sequoia_shared::{
     DataProvenance, Resources, TreasuryLevel, passive_sr_per_5s, passive_sr_per_hour,
 };
@@ -145,6 +148,21 @@ fn format_age_seconds(age_secs: i64) -> String {
     format!("{hours}h {mins}m")
 }
 
+/// Compact span length for ownership intervals, e.g. `3d 4h`, `5h 12m`, `42m`.
+fn format_hold_duration(duration_secs: i64) -> String {
+    let secs = duration_secs.max(0);
+    let days = secs / 86_400;
+    let hours = (secs % 86_400) / 3600;
+    let mins = (secs % 3600) / 60;
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {mins}m")
+    } else {
+        format!("{mins}m")
+    }
+}
+
 fn visibility_label(provenance: &DataProvenance) -> &'static str {
     match provenance.visibility {
         sequoia_shared::VisibilityClass::Public => "Public",
@@ -240,7 +258,11 @@ pub fn Sidebar() -> impl IntoView {
                     } else {
                         let query = search_query.get();
                         if query.is_empty() {
-                            view! { <LeaderboardPanel /> }.into_any()
+                            view! {
+                                <LeaderboardPanel />
+                                <RivalryPanel />
+                            }
+                            .into_any()
                         } else {
                             view! { <SearchResults /> }.into_any()
                         }
@@ -1599,6 +1621,97 @@ fn LeaderboardPanel() -> impl IntoView {
     }
 }
 
+/// Number of guild pairs listed in the top rivalries section.
+const TOP_RIVALRY_PAIRS: usize = 8;
+
+#[component]
+fn RivalryPanel() -> impl IntoView {
+    let SelectedGuild(selected_guild) = expect_context();
+    let HistoryAvailable(history_available) = expect_context();
+    let HeatSelectedSeasonId(heat_selected_season_id) = expect_context();
+
+    let rivalries: RwSignal<Option<HistoryRivalries>> = RwSignal::new(None);
+    let rivalries_request_nonce: RwSignal<u64> = RwSignal::new(0);
+
+    // Refetch when history becomes available or the selected heat season changes
+    Effect::new(move || {
+        let request_nonce = rivalries_request_nonce.get_untracked().wrapping_add(1);
+        rivalries_request_nonce.set(request_nonce);
+
+        if !history_available.get() {
+            rivalries.set(None);
+            return;
+        }
+        let url = match heat_selected_season_id.get() {
+            Some(season_id) => {
+                format!("/api/history/rivalries?source=season&season_id={season_id}")
+            }
+            None => "/api/history/rivalries?source=season".to_string(),
+        };
+        wasm_bindgen_futures::spawn_local(async move {
+            let response = match gloo_net::http::Request::get(&url).send().await {
+                Ok(resp) if resp.ok() => resp.json::<HistoryRivalries>().await.ok(),
+                _ => None,
+            };
+            if rivalries_request_nonce.get_untracked() != request_nonce {
+                return;
+            }
+            rivalries.set(response);
+        });
+    });
+
+    let top_pairs = Memo::new(move |_| {
+        rivalries
+            .get()
+            .map(|rivalries| rivalries.top_pairs(TOP_RIVALRY_PAIRS))
+            .unwrap_or_default()
+    });
+
+    view! {
+        <Show when=move || !top_pairs.get().is_empty()>
+            <div style="padding: 10px 14px 8px; border-top: 1px solid #282c3e;">
+                <div style="font-family: 'Silkscreen', monospace; font-size: 0.986rem; text-transform: uppercase; letter-spacing: 0.12em; color: #5a5860; margin-bottom: 8px;">
+                    <span style="color: #f5c542; margin-right: 5px; font-size: 0.812rem;">{"\u{25C6}"}</span>"Top Rivalries"
+                </div>
+                <div style="display: flex; flex-direction: column; gap: 4px;">
+                    {move || top_pairs.get().into_iter().map(|pair| {
+                        let (ar, ag, ab) = sequoia_shared::colors::guild_color(&pair.guild_a_name);
+                        let (br, bg, bb) = sequoia_shared::colors::guild_color(&pair.guild_b_name);
+                        let territories_title = pair.territories.join(", ");
+                        let guild_a = pair.guild_a_name.clone();
+                        let guild_b = pair.guild_b_name.clone();
+                        view! {
+                            <div
+                                style="display: flex; justify-content: space-between; align-items: center; gap: 8px; padding: 4px 0; border-bottom: 1px solid rgba(40,44,62,0.4);"
+                                title=territories_title
+                            >
+                                <span style="display: flex; align-items: center; gap: 6px; min-width: 0; font-family: 'JetBrains Mono', monospace; font-size: 0.835rem;">
+                                    <span
+                                        style={format!("cursor: pointer; color: {};", rgba_css(ar, ag, ab, 1.0))}
+                                        on:click=move |_| selected_guild.set(Some(guild_a.clone()))
+                                    >
+                                        {format!("[{}]", pair.guild_a_prefix)}
+                                    </span>
+                                    <span style="color: #5a5860;">"vs"</span>
+                                    <span
+                                        style={format!("cursor: pointer; color: {};", rgba_css(br, bg, bb, 1.0))}
+                                        on:click=move |_| selected_guild.set(Some(guild_b.clone()))
+                                    >
+                                        {format!("[{}]", pair.guild_b_prefix)}
+                                    </span>
+                                </span>
+                                <span style="font-family: 'JetBrains Mono', monospace; font-size: 0.812rem; color: #e2e0d8; font-variant-numeric: tabular-nums; flex-shrink: 0;">
+                                    {format!("{} \u{2013} {}", pair.a_took_from_b, pair.b_took_from_a)}
+                                </span>
+                            </div>
+                        }
+                    }).collect::<Vec<_>>()}
+                </div>
+            </div>
+        </Show>
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct OnlineMemberRow {
     username: String,
@@ -1743,6 +1856,17 @@ fn GuildPanel() -> impl IntoView {
         terrs
     });
 
+    // Resource routes from every owned territory to the (observed or inferred) HQ
+    let resource_routing = Memo::new(move |_| {
+        let name = selected_guild.get()?;
+        let map = territories.get();
+        let territory_map = map
+            .iter()
+            .map(|(tn, ct)| (tn.clone(), ct.territory.clone()))
+            .collect();
+        route_guild_resources(&territory_map, &name, None, DEFAULT_FOREIGN_TAX_RATE)
+    });
+
     view! {
         <div class="panel-reveal" style="border-bottom: 1px solid #282c3e; position: relative;">
             // Close button
@@ -1966,6 +2090,11 @@ fn GuildPanel() -> impl IntoView {
                     })
                 }}
 
+                // Resource routing section
+                {move || {
+                    resource_routing.get().map(|routing| view! { <ResourceRoutingSummary routing=routing /> })
+                }}
+
                 // Territories section
                 {move || {
                     let terrs = guild_territories.get();
@@ -2036,6 +2165,97 @@ fn GuildPanel() -> impl IntoView {
     }
 }
 
+#[component]
+fn ResourceRoutingSummary(routing: GuildResourceRouting) -> impl IntoView {
+    let reachable = routing.routes.len();
+    let owned = reachable + routing.unreachable.len();
+    let foreign_routes = routing
+        .routes
+        .iter()
+        .filter(|route| route.foreign_hops > 0)
+        .count();
+    let lost = build_resource_items(&routing.lost());
+    let longest: Vec<(String, u32, u32)> = routing
+        .routes
+        .iter()
+        .filter(|route| route.hops > 0)
+        .take(3)
+        .map(|route| (route.territory.clone(), route.hops, route.foreign_hops))
+        .collect();
+    let bottlenecks: Vec<String> = routing
+        .bottlenecks
+        .iter()
+        .take(5)
+        .map(|b| {
+            format!(
+                "{} \u{00b7} {} {}/{}",
+                b.territory,
+                b.resource.label(),
+                b.transit_per_transfer,
+                b.capacity
+            )
+        })
+        .collect();
+    let row = "display: flex; justify-content: space-between; font-family: 'JetBrains Mono', monospace; font-size: 0.87rem;";
+
+    view! {
+        <div style="margin-bottom: 14px; border-top: 1px solid #282c3e; padding-top: 12px;">
+            <div style="font-family: 'Silkscreen', monospace; font-size: 0.87rem; text-transform: uppercase; letter-spacing: 0.14em; color: #5a5860; margin-bottom: 8px;">
+                <span style="color: #6ec6ff; margin-right: 6px; font-size: 0.696rem;">{"\u{25C6}"}</span>"Resource Routing"
+            </div>
+            <div style="display: flex; flex-direction: column; gap: 4px;">
+                <div style=row>
+                    <span style="color: #5a5860;">"HQ"</span>
+                    <span style="color: #f5c542;">{routing.hq.clone()}</span>
+                </div>
+                <div style=row>
+                    <span style="color: #5a5860;">"Routed"</span>
+                    <span style="color: #e2e0d8;">{format!("{reachable}/{owned}")}</span>
+                </div>
+                <div style=row>
+                    <span style="color: #5a5860;">"Longest route"</span>
+                    <span style="color: #e2e0d8;">{format!("{} hops", routing.longest_route)}</span>
+                </div>
+                <div style=row>
+                    <span style="color: #5a5860;">"Taxed routes"</span>
+                    <span style="color: #e2e0d8;">{foreign_routes}</span>
+                </div>
+                {(!lost.is_empty()).then(|| {
+                    let text = lost
+                        .into_iter()
+                        .map(|(label, value, _)| format!("{label} {value}"))
+                        .collect::<Vec<_>>()
+                        .join(" \u{00b7} ");
+                    view! {
+                        <div style=row>
+                            <span style="color: #5a5860;">"Lost / hr"</span>
+                            <span style="color: #e05252; text-align: right;">{text}</span>
+                        </div>
+                    }
+                })}
+                {longest.into_iter().map(|(territory, hops, foreign_hops)| {
+                    let detail = if foreign_hops > 0 {
+                        format!("{hops} hops ({foreign_hops} taxed)")
+                    } else {
+                        format!("{hops} hops")
+                    };
+                    view! {
+                        <div style=row>
+                            <span style="color: #9a9590; overflow: hidden; text-overflow: ellipsis; white-space: nowrap;">{territory}</span>
+                            <span style="color: #9a9590;">{detail}</span>
+                        </div>
+                    }
+                }).collect::<Vec<_>>()}
+                {bottlenecks.into_iter().map(|text| view! {
+                    <div style="font-family: 'JetBrains Mono', monospace; font-size: 0.812rem; color: #e0a052;">
+                        "Storage full: " {text}
+                    </div>
+                }).collect::<Vec<_>>()}
+            </div>
+        </div>
+    }
+}
+
 #[component]
 fn DetailPanel() -> impl IntoView {
     let Selected(selected) = expect_context();
@@ -2563,6 +2783,7 @@ fn DetailPanel() -> impl IntoView {
                                     }
                                 })}
                                 <TowerCalculator />
+                                <TerritoryOwnershipPanel />
                             </div>
                         }
                     })
@@ -2571,6 +2792,110 @@ fn DetailPanel() -> impl IntoView {
     }
 }
 
+/// Ownership intervals for the selected territory over the server's default window.
+#[component]
+fn TerritoryOwnershipPanel() -> impl IntoView {
+    let Selected(selected) = expect_context();
+    let HistoryAvailable(history_available) = expect_context();
+
+    let ownership: RwSignal<Option<TerritoryOwnershipHistory>> = RwSignal::new(None);
+    let ownership_loading: RwSignal<bool> = RwSignal::new(false);
+    let ownership_request_nonce: RwSignal<u64> = RwSignal::new(0);
+
+    // Fetch the ownership timeline whenever the selected territory changes
+    Effect::new(move || {
+        let request_nonce = ownership_request_nonce.get_untracked().wrapping_add(1);
+        ownership_request_nonce.set(request_nonce);
+
+        let Some(name) = selected.get().filter(|_| history_available.get()) else {
+            ownership.set(None);
+            ownership_loading.set(false);
+            return;
+        };
+        ownership_loading.set(true);
+        ownership.set(None);
+        wasm_bindgen_futures::spawn_local(async move {
+            let url = format!(
+                "/api/history/territory/{}",
+                js_sys::encode_uri_component(&name)
+                    .as_string()
+                    .unwrap_or_default()
+            );
+            let history = match gloo_net::http::Request::get(&url).send().await {
+                Ok(resp) if resp.ok() => resp.json::<TerritoryOwnershipHistory>().await.ok(),
+                _ => None,
+            };
+
+            if ownership_request_nonce.get_untracked() != request_nonce
+                || selected.get_untracked().as_deref() != Some(name.as_str())
+            {
+                return;
+            }
+
+            ownership.set(history);
+            ownership_loading.set(false);
+        });
+    });
+
+    view! {
+        <Show when=move || history_available.get()>
+            <div style="padding: 10px 0 4px;">
+                <div style="font-family: 'Silkscreen', monospace; font-size: 0.986rem; text-transform: uppercase; letter-spacing: 0.12em; color: #5a5860; margin-bottom: 8px;">
+                    <span style="color: #f5c542; margin-right: 5px; font-size: 0.812rem;">{"\u{25C6}"}</span>"Ownership History"
+                </div>
+                {move || {
+                    if ownership_loading.get() {
+                        return view! {
+                            <span class="status-pulse" style="font-family: 'JetBrains Mono', monospace; font-size: 0.835rem; color: #3a3f5c;">"Loading ownership..."</span>
+                        }.into_any();
+                    }
+                    let intervals = ownership
+                        .get()
+                        .map(|history| history.intervals)
+                        .unwrap_or_default();
+                    if intervals.is_empty() {
+                        return view! {
+                            <span style="font-family: 'JetBrains Mono', monospace; font-size: 0.835rem; color: #5a5860;">"No recorded changes"</span>
+                        }.into_any();
+                    }
+                    view! {
+                        <div style="display: flex; flex-direction: column; gap: 4px;">
+                            {intervals.into_iter().rev().map(|interval| {
+                                let (r, g, b) = interval
+                                    .guild_color
+                                    .unwrap_or_else(|| sequoia_shared::colors::guild_color(&interval.guild_name));
+                                let since = chrono::DateTime::parse_from_rfc3339(&interval.start)
+                                    .map(|dt| dt.format("%b %d %H:%M").to_string())
+                                    .unwrap_or_else(|_| interval.start.clone());
+                                let held = format_hold_duration(interval.duration_secs);
+                                let held_label = if interval.end.is_none() {
+                                    format!("{held} \u{2022} now")
+                                } else {
+                                    held
+                                };
+                                view! {
+                                    <div style="display: flex; justify-content: space-between; align-items: center; gap: 8px; padding: 4px 0; border-bottom: 1px solid rgba(40,44,62,0.4);">
+                                        <span style="display: flex; align-items: center; gap: 6px; min-width: 0;">
+                                            <span style={format!("width: 8px; height: 8px; border-radius: 2px; flex-shrink: 0; background: {};", rgba_css(r, g, b, 1.0))} />
+                                            <span style="font-family: 'JetBrains Mono', monospace; font-size: 0.835rem; color: #e2e0d8; white-space: nowrap; overflow: hidden; text-overflow: ellipsis;" title=interval.guild_name.clone()>
+                                                {format!("[{}] {}", interval.guild_prefix, interval.guild_name)}
+                                            </span>
+                                        </span>
+                                        <span style="display: flex; flex-direction: column; align-items: flex-end; flex-shrink: 0;">
+                                            <span style="font-family: 'JetBrains Mono', monospace; font-size: 0.812rem; color: #e2e0d8; font-variant-numeric: tabular-nums;">{held_label}</span>
+                                            <span style="font-family: 'JetBrains Mono', monospace; font-size: 0.719rem; color: #7c829e;">{since}</span>
+                                        </span>
+                                    </div>
+                                }
+                            }).collect::<Vec<_>>()}
+                        </div>
+                    }.into_any()
+                }}
+            </div>
+        </Show>
+    }
+}
+
 #[component]
 fn StatsBar() -> impl IntoView {
     let territories: RwSignal<ClientTerritoryMap> = expect_context();
@@ -34,6 +34,7 @@ struct SseConnection {
     snapshot_handler: Closure<dyn Fn(MessageEvent)>,
     update_handler: Closure<dyn Fn(MessageEvent)>,
     runtime_update_handler: Closure<dyn Fn(MessageEvent)>,
+    resumed_handler: Closure<dyn Fn(MessageEvent)>,
 }
 
 struct ReconnectWatchdog {
@@ -66,6 +67,12 @@ impl SseConnection {
                 self.runtime_update_handler.as_ref().unchecked_ref(),
             )
             .ok();
+        self.es
+            .remove_event_listener_with_callback(
+                "resumed",
+                self.resumed_handler.as_ref().unchecked_ref(),
+            )
+            .ok();
         self.es.close();
     }
 }
@@ -515,6 +522,14 @@ pub fn connect(territories: RwSignal<ClientTerritoryMap>, connection: RwSignal<C
     )
     .ok();
 
+    // On "resumed" event: the server filled the reconnect gap from its replay buffer
+    // (EventSource sends Last-Event-ID automatically), so no snapshot will follow.
+    let resumed_handler = Closure::<dyn Fn(MessageEvent)>::new(move |_e: MessageEvent| {
+        mark_post_reconnect_event_received();
+    });
+    es.add_event_listener_with_callback("resumed", resumed_handler.as_ref().unchecked_ref())
+        .ok();
+
     // On error
     let conn = connection;
     let on_error = Closure::<dyn Fn()>::new(move || {
@@ -537,6 +552,7 @@ pub fn connect(territories: RwSignal<ClientTerritoryMap>, connection: RwSignal<C
             snapshot_handler,
             update_handler,
             runtime_update_handler,
+            resumed_handler,
         });
     });
 }
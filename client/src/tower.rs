@@ -3,6 +3,9 @@ use std::collections::HashMap;
 use leptos::prelude::*;
 use wasm_bindgen::JsCast;
 
+use sequoia_shared::Resources;
+use sequoia_shared::economy::{TowerUpgrades, guild_economy};
+use sequoia_shared::resource_routing::RoutedResource;
 use sequoia_shared::tower::{
     self, ATTACK_RATES, AURA_LABELS, DAMAGES, DEFENSES, HEALTHS, VOLLEY_LABELS,
 };
@@ -43,6 +46,33 @@ impl TowerState {
     }
 }
 
+impl TowerState {
+    /// Current calculator levels as an upkeep configuration.
+    pub fn upgrades(&self) -> TowerUpgrades {
+        TowerUpgrades {
+            damage: self.damage_lvl.get(),
+            attack: self.attack_lvl.get(),
+            health: self.health_lvl.get(),
+            defense: self.defense_lvl.get(),
+            aura: self.aura_lvl.get(),
+            volley: self.volley_lvl.get(),
+        }
+    }
+}
+
+/// "Ore 1k · Crops 300" for the non-zero entries, or `None` when all are zero.
+fn format_resource_amounts(resources: &Resources) -> Option<String> {
+    let parts: Vec<String> = RoutedResource::ALL
+        .into_iter()
+        .filter_map(|resource| {
+            let amount = resource.amount(resources);
+            (amount != 0)
+                .then(|| format!("{} {}", resource.label(), tower::format_stat(amount as f64)))
+        })
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(" \u{00b7} "))
+}
+
 #[derive(Clone, Copy)]
 struct TowerLevelSnapshot {
     damage_lvl: u32,
@@ -59,6 +89,8 @@ struct TowerLevelSnapshot {
 pub fn TowerCalculator() -> impl IntoView {
     let Selected(selected) = expect_context();
     let territories: RwSignal<ClientTerritoryMap> = expect_context();
+    let tick: RwSignal<i64> = expect_context();
+    let tower_state: TowerState = expect_context();
 
     let TowerState {
         damage_lvl,
@@ -71,7 +103,7 @@ pub fn TowerCalculator() -> impl IntoView {
         connections,
         externals,
         max_preset_snapshot,
-    } = expect_context();
+    } = tower_state;
 
     // Computed results
     let dps = Memo::new(move |_| {
@@ -126,6 +158,32 @@ pub fn TowerCalculator() -> impl IntoView {
 
     let defense_pct = Memo::new(move |_| DEFENSES[defense_lvl.get().min(11) as usize]);
 
+    let hourly_cost = Memo::new(move |_| tower_state.upgrades().hourly_cost());
+
+    // Owning guild's hourly balance if the selected territory ran this configuration
+    let guild_balance = Memo::new(move |_| {
+        let name = selected.get()?;
+        let upgrades = tower_state.upgrades();
+        let map = territories.get();
+        let guild_name = map.get(&name)?.territory.guild.name.clone();
+        let territory_map = map
+            .iter()
+            .map(|(tn, ct)| (tn.clone(), ct.territory.clone()))
+            .collect();
+        guild_economy(
+            &territory_map,
+            &guild_name,
+            tick.get_untracked(),
+            |territory| {
+                if territory == name {
+                    upgrades
+                } else {
+                    TowerUpgrades::default()
+                }
+            },
+        )
+    });
+
     let is_max_preset_active = Memo::new(move |_| max_preset_snapshot.get().is_some());
     let on_max_toggle = move |_| {
         if let Some(snapshot) = max_preset_snapshot.get_untracked() {
@@ -377,6 +435,35 @@ pub fn TowerCalculator() -> impl IntoView {
                         {move || format!("{} ({})", defense_rating.get().label(), defense_index.get())}
                     </span>
                 </div>
+                <div style="display: flex; justify-content: space-between; align-items: center; gap: 8px;">
+                    <span style="font-family: 'Silkscreen', monospace; font-size: 0.6rem; color: #9f9a95; text-transform: uppercase; letter-spacing: 0.1em; white-space: nowrap;">"Upkeep/hr"</span>
+                    <span style="font-family: 'JetBrains Mono', monospace; font-size: 0.72rem; color: #e2e0d8; text-align: right;">
+                        {move || format_resource_amounts(&hourly_cost.get()).unwrap_or_else(|| "Free".to_string())}
+                    </span>
+                </div>
+                {move || guild_balance.get().map(|economy| {
+                    let shortfall: Vec<String> = economy
+                        .deficits()
+                        .into_iter()
+                        .map(|(resource, amount)| format!("{} -{}", resource.label(), tower::format_stat(amount as f64)))
+                        .collect();
+                    let (color, text) = if shortfall.is_empty() {
+                        ("#50c878", "Affordable".to_string())
+                    } else {
+                        ("#e05252", shortfall.join(" \u{00b7} "))
+                    };
+                    view! {
+                        <div
+                            style="display: flex; justify-content: space-between; align-items: center; gap: 8px;"
+                            title="Owning guild's treasury-buffed hourly production minus this tower's upkeep"
+                        >
+                            <span style="font-family: 'Silkscreen', monospace; font-size: 0.6rem; color: #9f9a95; text-transform: uppercase; letter-spacing: 0.1em; white-space: nowrap;">"Guild/hr"</span>
+                            <span style=format!("font-family: 'JetBrains Mono', monospace; font-size: 0.72rem; color: {color}; text-align: right;")>
+                                {text}
+                            </span>
+                        </div>
+                    }
+                })}
             </div>
         </div>
     }
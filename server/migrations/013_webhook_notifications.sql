@@ -0,0 +1,28 @@
+CREATE TABLE webhook_rules (
+    id         BIGSERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    name       TEXT NOT NULL,
+    url        TEXT NOT NULL,
+    secret     TEXT,
+    trigger    JSONB NOT NULL,
+    enabled    BOOLEAN NOT NULL DEFAULT TRUE
+);
+
+CREATE TABLE webhook_outbox (
+    id              BIGSERIAL PRIMARY KEY,
+    rule_id         BIGINT NOT NULL REFERENCES webhook_rules (id) ON DELETE CASCADE,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT now(),
+    payload         JSONB NOT NULL,
+    status          TEXT NOT NULL DEFAULT 'pending',
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_error      TEXT,
+    delivered_at    TIMESTAMPTZ
+);
+
+CREATE INDEX idx_webhook_outbox_pending_due
+    ON webhook_outbox (next_attempt_at)
+    WHERE status = 'pending';
+
+CREATE INDEX idx_webhook_outbox_rule_created
+    ON webhook_outbox (rule_id, created_at DESC);
@@ -0,0 +1,13 @@
+CREATE TABLE guild_hq_moves (
+    id             BIGSERIAL PRIMARY KEY,
+    recorded_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+    guild_uuid     TEXT NOT NULL,
+    guild_name     TEXT NOT NULL,
+    from_territory TEXT,
+    to_territory   TEXT NOT NULL,
+    confidence     DOUBLE PRECISION NOT NULL,
+    source         TEXT NOT NULL
+);
+
+CREATE INDEX idx_guild_hq_moves_guild_recorded
+    ON guild_hq_moves (LOWER(guild_name), recorded_at DESC);
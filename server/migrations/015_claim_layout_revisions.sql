@@ -0,0 +1,21 @@
+ALTER TABLE claim_layouts
+    ADD COLUMN edit_token_hash TEXT,
+    ADD COLUMN revision        INTEGER NOT NULL DEFAULT 1,
+    ADD COLUMN updated_at      TIMESTAMPTZ;
+
+CREATE INDEX idx_claim_layouts_edit_token_hash
+    ON claim_layouts (edit_token_hash, created_at DESC)
+    WHERE edit_token_hash IS NOT NULL;
+
+CREATE TABLE claim_layout_revisions (
+    layout_id        TEXT NOT NULL REFERENCES claim_layouts (id) ON DELETE CASCADE,
+    revision         INTEGER NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT now(),
+    title            TEXT,
+    document_version INTEGER NOT NULL,
+    document         JSONB NOT NULL,
+    PRIMARY KEY (layout_id, revision)
+);
+
+INSERT INTO claim_layout_revisions (layout_id, revision, created_at, title, document_version, document)
+SELECT id, 1, created_at, title, document_version, document FROM claim_layouts;
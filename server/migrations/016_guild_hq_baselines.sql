@@ -0,0 +1,11 @@
+-- Last known HQ per guild, including first sightings that never produced a move row.
+CREATE TABLE guild_hq_baselines (
+    guild_key   TEXT PRIMARY KEY,
+    territory   TEXT NOT NULL,
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+INSERT INTO guild_hq_baselines (guild_key, territory, updated_at)
+SELECT DISTINCT ON (LOWER(guild_name)) LOWER(guild_name), to_territory, recorded_at
+FROM guild_hq_moves
+ORDER BY LOWER(guild_name), recorded_at DESC, id DESC;
@@ -27,6 +27,8 @@ const SHORT_STATIC_CACHE_CONTROL: &str = "public, max-age=86400";
 struct HtmlResponseOptions {
     canonical: Option<String>,
     robots: Option<&'static str>,
+    /// Overrides the default OpenGraph image; also applied without a canonical URL.
+    og_image: Option<String>,
 }
 
 pub(crate) fn build_app(state: AppState) -> Router {
@@ -58,6 +60,10 @@ pub(crate) fn build_app(state: AppState) -> Router {
             "/api/guilds/online",
             axum::routing::get(routes::api::get_guilds_online),
         )
+        .route(
+            "/api/guilds/hq",
+            axum::routing::get(routes::api::get_guild_hqs),
+        )
         .route(
             "/api/guilds/catalog",
             axum::routing::get(routes::claims::get_guild_catalog),
@@ -82,6 +88,10 @@ pub(crate) fn build_app(state: AppState) -> Router {
             "/api/season/race",
             axum::routing::get(routes::api::get_season_race),
         )
+        .route(
+            "/api/season/race/scenario",
+            axum::routing::post(routes::api::post_season_race_scenario),
+        )
         .route(
             "/api/map/intel",
             axum::routing::get(routes::api::get_map_intel),
@@ -92,15 +102,38 @@ pub(crate) fn build_app(state: AppState) -> Router {
         )
         .route(
             "/api/claims",
-            axum::routing::post(routes::claims::create_claim_layout),
+            axum::routing::post(routes::claims::create_claim_layout)
+                .get(routes::claims::list_owned_claim_layouts),
         )
         .route(
             "/api/claims/{id}",
-            axum::routing::get(routes::claims::get_claim_layout),
+            axum::routing::get(routes::claims::get_claim_layout)
+                .put(routes::claims::update_claim_layout)
+                .delete(routes::claims::delete_claim_layout),
+        )
+        .route(
+            "/api/claims/{id}/preview.png",
+            axum::routing::get(routes::claims::get_claim_preview),
+        )
+        .route(
+            "/api/claims/{id}/revisions",
+            axum::routing::get(routes::claims::list_claim_revisions),
+        )
+        .route(
+            "/api/claims/{id}/revisions/{revision}",
+            axum::routing::get(routes::claims::get_claim_revision),
+        )
+        .route(
+            "/api/claims/{a}/diff/{b}",
+            axum::routing::get(routes::claims::diff_claim_layouts),
         )
         .route(
             "/api/wars/live",
-            axum::routing::get(routes::ingest::get_live_wars),
+            axum::routing::get(routes::wars::get_live_wars),
+        )
+        .route(
+            "/api/wars/history",
+            axum::routing::get(routes::wars::get_war_history),
         )
         .route(
             "/api/events",
@@ -110,20 +143,53 @@ pub(crate) fn build_app(state: AppState) -> Router {
             "/api/internal/ingest/territory",
             axum::routing::post(routes::ingest::ingest_territory),
         )
+        .route(
+            "/api/internal/ingest/war",
+            axum::routing::post(routes::ingest::ingest_war),
+        )
         .route(
             "/api/internal/ingest/heartbeat",
             axum::routing::post(routes::ingest::heartbeat),
         )
+        .route(
+            "/api/internal/archive/import",
+            axum::routing::post(routes::archive::import_archive),
+        )
+        .route(
+            "/api/internal/season/backtest",
+            axum::routing::post(routes::api::post_season_backtest),
+        )
+        .route(
+            "/api/internal/webhooks",
+            axum::routing::get(routes::webhooks::list_webhooks)
+                .post(routes::webhooks::create_webhook),
+        )
+        .route(
+            "/api/internal/webhooks/{id}",
+            axum::routing::delete(routes::webhooks::delete_webhook),
+        )
         .route("/api/health", axum::routing::get(routes::api::health))
         .route("/api/metrics", axum::routing::get(routes::api::metrics))
         .route(
             "/api/history/at",
             axum::routing::get(routes::history::history_at),
         )
+        .route(
+            "/api/history/at.png",
+            axum::routing::get(routes::history::history_at_png),
+        )
+        .route(
+            "/api/history/timelapse",
+            axum::routing::get(routes::history::history_timelapse),
+        )
         .route(
             "/api/history/events",
             axum::routing::get(routes::history::history_events),
         )
+        .route(
+            "/api/history/export",
+            axum::routing::get(routes::history::history_export),
+        )
         .route(
             "/api/history/sr-samples",
             axum::routing::get(routes::history::history_sr_samples),
@@ -140,6 +206,18 @@ pub(crate) fn build_app(state: AppState) -> Router {
             "/api/history/heat",
             axum::routing::get(routes::history::history_heat),
         )
+        .route(
+            "/api/history/rivalries",
+            axum::routing::get(routes::history::history_rivalries),
+        )
+        .route(
+            "/api/history/territory/{name}",
+            axum::routing::get(routes::history::history_territory),
+        )
+        .route(
+            "/api/history/guild/{name}",
+            axum::routing::get(routes::history::history_guild),
+        )
         .route("/api", axum::routing::any(api_not_found))
         .route("/api/{*path}", axum::routing::any(api_not_found))
         .route("/claims", axum::routing::get(serve_claims_route))
@@ -244,6 +322,7 @@ async fn serve_map_root() -> impl IntoResponse {
         HtmlResponseOptions {
             canonical: Some(map_root_url()),
             robots: None,
+            og_image: None,
         },
     )
     .await
@@ -255,6 +334,7 @@ async fn serve_map_history() -> impl IntoResponse {
         HtmlResponseOptions {
             canonical: Some(map_root_url()),
             robots: Some("noindex, follow"),
+            og_image: None,
         },
     )
     .await
@@ -272,11 +352,33 @@ async fn serve_claims_route(request: Request) -> impl IntoResponse {
         HtmlResponseOptions {
             canonical: None,
             robots: Some("noindex, follow"),
+            og_image: Some(claims_og_image_url(&path)),
         },
     )
     .await
 }
 
+/// Saved layouts link their rendered preview; every other claims page uses the default image.
+fn claims_og_image_url(path: &str) -> String {
+    let base_url = crate::config::map_public_base_url();
+    let saved_id = path
+        .strip_prefix("/claims/s/")
+        .map(|id| id.trim_end_matches('/'))
+        .filter(|id| {
+            !id.is_empty()
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        });
+    match saved_id {
+        Some(id) => format!("{base_url}/api/claims/{id}/preview.png"),
+        None => format!(
+            "{base_url}{}",
+            versioned_asset_path(DEFAULT_OG_IMAGE_PATH, public_asset_version().as_deref())
+        ),
+    }
+}
+
 async fn serve_html_file(path: &str, options: HtmlResponseOptions) -> Response {
     match tokio::fs::read(path).await {
         Ok(body) => {
@@ -308,6 +410,9 @@ fn apply_html_body_substitutions(body: Vec<u8>, options: &HtmlResponseOptions) -
 
     html = html.replace(ASSET_VERSION_TOKEN, asset_version.as_deref().unwrap_or(""));
 
+    if let Some(og_image) = options.og_image.as_deref() {
+        html = html.replace(OG_IMAGE_URL_TOKEN, og_image);
+    }
     if let Some(canonical) = options.canonical.as_deref() {
         html = html.replace(CANONICAL_URL_TOKEN, canonical);
         html = html.replace(
@@ -593,6 +698,7 @@ mod tests {
             &HtmlResponseOptions {
                 canonical: None,
                 robots: None,
+                og_image: None,
             },
         );
         unsafe {
@@ -778,6 +884,35 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn saved_claims_route_links_rendered_preview_image() {
+        let app = build_app(AppState::new(None));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/claims/s/abc123")
+                    .body(Body::empty())
+                    .expect("build saved claims request"),
+            )
+            .await
+            .expect("saved claims request should succeed");
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        let body = String::from_utf8(body.to_vec()).expect("utf8 body");
+        assert!(body.contains(
+            "property=\"og:image\" content=\"https://map.example.com/api/claims/abc123/preview.png\""
+        ));
+        assert!(!body.contains("__SEQUOIA_OG_IMAGE_URL__"));
+        assert!(
+            claims_og_image_url("/claims/s/../x")
+                .starts_with("https://map.example.com/tiles/main-3-2.webp")
+        );
+    }
+
     #[tokio::test]
     async fn robots_and_sitemap_routes_expose_canonical_root() {
         let app = build_app(AppState::new(None));
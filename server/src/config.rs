@@ -1,4 +1,5 @@
 use std::env::VarError;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
@@ -32,18 +33,33 @@ pub const DEFAULT_SEASON_RATING_CONTENDER_COUNT: usize = 10;
 pub const MAX_GUILD_CACHE_ENTRIES: usize = 64;
 pub const SSE_KEEPALIVE_SECS: u64 = 15;
 pub const DEFAULT_BROADCAST_BUFFER: usize = 256;
+pub const DEFAULT_SSE_REPLAY_BUFFER: usize = 1024;
+pub const DEFAULT_SSE_REPLAY_MAX_PERSISTED_EVENTS: i64 = 2_000;
 pub const DEFAULT_DB_MAX_CONNECTIONS: u32 = 10;
 pub const DEFAULT_UPSTREAM_HTTP_TIMEOUT_SECS: u64 = 10;
 pub const DEFAULT_UPSTREAM_CONNECT_TIMEOUT_SECS: u64 = 3;
 pub const DEFAULT_MAP_DOMAIN: &str = "map.example.com";
 pub const SERVER_PORT: u16 = 3000;
 pub const DEFAULT_CANONICAL_OVERRIDE_TTL_SECS: u64 = 180;
+pub const LIVE_WAR_STALE_SECS: i64 = 1800; // drop wars without an end event after 30 minutes
 pub const DEFAULT_API_BODY_LIMIT_BYTES: usize = 2 * 1024 * 1024;
 pub const DEFAULT_MAX_INGEST_UPDATES_PER_REQUEST: usize = 1024;
 pub const DEFAULT_MAX_HISTORY_REPLAY_EVENTS: i64 = 20_000;
 pub const DEFAULT_MAX_HISTORY_SR_SAMPLE_ROWS: i64 = 20_000;
+pub const DEFAULT_MAX_HISTORY_EXPORT_ROWS: i64 = 250_000;
 pub const DEFAULT_SEASON_RACE_TOP_GUILDS: usize = 10;
 pub const DEFAULT_SEASON_RACE_LOOKBACK_HOURS: i64 = 24;
+pub const SEASON_RACE_SCENARIO_MAX_OVERRIDES: usize = 32;
+pub const SEASON_RACE_SCENARIO_MAX_SCALAR: f64 = 100.0;
+pub const SEASON_RACE_SIM_RUNS: u32 = 1_000;
+pub const SEASON_RACE_SIM_STEP_HOURS: i64 = 6;
+pub const SEASON_RACE_SIM_HISTORY_HOURS: i64 = 14 * 24;
+pub const SEASON_RACE_SIM_DEFAULT_TOP_N: u32 = 3;
+pub const SEASON_RACE_SIM_DEFAULT_RATE_CV: f64 = 0.35;
+pub const SEASON_RACE_SIM_DEFAULT_TERRITORY_SD: f64 = 2.0;
+pub const SEASON_RACE_SIM_SCALAR_STEP_JITTER: f64 = 0.05; // share of the season window
+pub const SEASON_BACKTEST_DEFAULT_PROGRESS: [f64; 4] = [0.25, 0.5, 0.75, 0.9];
+pub const SEASON_BACKTEST_MAX_CHECKPOINTS: usize = 64;
 pub const DEFAULT_SEASON_RAID_PLAYERS_PER_COMPLETION: f64 = 4.0;
 pub const DEFAULT_SEASON_RAID_SR_PER_COMPLETION: f64 = 380.0;
 pub const MIN_INTERNAL_INGEST_TOKEN_LEN: usize = 24;
@@ -54,6 +70,29 @@ pub const SNAPSHOT_INTERVAL_SECS: u64 = 21600; // every 6 hours
 pub const DEFAULT_TERRITORY_HISTORY_RETENTION_DAYS: i64 = 365;
 pub const DEFAULT_SEASON_HISTORY_RETENTION_DAYS: i64 = 365;
 pub const RETENTION_CHECK_SECS: u64 = 86400; // daily
+pub const TIMELAPSE_DEFAULT_FPS: u16 = 8;
+pub const TIMELAPSE_MAX_FPS: u16 = 30;
+pub const TIMELAPSE_DEFAULT_FRAMES: u32 = 60;
+pub const TIMELAPSE_MAX_FRAMES: u32 = 120;
+pub const TIMELAPSE_END_HOLD_SECS: u16 = 2;
+pub const TIMELAPSE_MAX_CONCURRENT_RENDERS: usize = 2;
+pub const TIMELAPSE_CACHE_MAX_ENTRIES: usize = 32;
+
+// Webhook notifications
+pub const WEBHOOK_RULE_REFRESH_SECS: u64 = 30;
+pub const WEBHOOK_DISPATCH_INTERVAL_SECS: u64 = 5;
+pub const WEBHOOK_DISPATCH_BATCH: i64 = 25;
+pub const WEBHOOK_MAX_ATTEMPTS: i32 = 8;
+pub const WEBHOOK_OUTBOX_RETENTION_DAYS: i64 = 14;
+
+// Guild HQ inference
+pub const GUILD_HQ_INFERENCE_SECS: u64 = 60;
+pub const GUILD_HQ_MOVE_MIN_CONFIDENCE: f64 = 0.6;
+pub const GUILD_HQ_MOVES_LIMIT: i64 = 20;
+
+// Claim layouts
+pub const CLAIM_LAYOUT_MAX_REVISIONS: i32 = 50; // older revisions are pruned on update
+pub const CLAIM_OWNED_LAYOUTS_LIMIT: i64 = 100;
 
 const INTERNAL_INGEST_TOKEN_REJECTED_VALUES: &[&str] = &[
     "changeme",
@@ -107,6 +146,22 @@ pub fn sse_broadcast_buffer() -> usize {
         .unwrap_or(DEFAULT_BROADCAST_BUFFER)
 }
 
+pub fn sse_replay_buffer() -> usize {
+    std::env::var("SSE_REPLAY_BUFFER")
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_SSE_REPLAY_BUFFER)
+}
+
+pub fn sse_replay_max_persisted_events() -> i64 {
+    std::env::var("SSE_REPLAY_MAX_PERSISTED_EVENTS")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_SSE_REPLAY_MAX_PERSISTED_EVENTS)
+}
+
 pub fn upstream_http_timeout() -> Duration {
     std::env::var("UPSTREAM_HTTP_TIMEOUT_SECS")
         .ok()
@@ -176,6 +231,16 @@ pub fn season_history_retention_days() -> i64 {
         .unwrap_or(DEFAULT_SEASON_HISTORY_RETENTION_DAYS)
 }
 
+/// Directory for compressed NDJSON archives written before retention cleanup deletes rows.
+/// Archival is disabled when unset.
+pub fn history_archive_dir() -> Option<PathBuf> {
+    std::env::var("HISTORY_ARCHIVE_DIR")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
 pub fn season_rating_contender_count() -> usize {
     std::env::var("SEASON_RATING_CONTENDER_COUNT")
         .ok()
@@ -250,6 +315,14 @@ pub fn max_history_replay_events() -> i64 {
         .unwrap_or(DEFAULT_MAX_HISTORY_REPLAY_EVENTS)
 }
 
+pub fn max_history_export_rows() -> i64 {
+    std::env::var("MAX_HISTORY_EXPORT_ROWS")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_MAX_HISTORY_EXPORT_ROWS)
+}
+
 pub fn max_history_sr_sample_rows() -> i64 {
     std::env::var("MAX_HISTORY_SR_SAMPLE_ROWS")
         .ok()
@@ -7,7 +7,9 @@ mod services;
 mod state;
 
 extern crate self as sqlx;
-pub use crate::db_sqlx::{PgPool, Postgres, QueryBuilder, postgres, query, query_as, query_scalar};
+pub use crate::db_sqlx::{
+    PgPool, Postgres, QueryBuilder, Transaction, postgres, query, query_as, query_scalar,
+};
 
 use sqlx::postgres::PgPoolOptions;
 use std::sync::atomic::Ordering;
@@ -61,6 +63,7 @@ async fn main() {
             Ok(Some(seq)) if seq > 0 => {
                 state.next_seq.store(seq as u64, Ordering::Relaxed);
                 state.next_seq_reserved.store(seq as u64, Ordering::Relaxed);
+                state.event_replay.lock().await.reset(seq as u64);
                 tracing::info!("Initialized stream sequence counter from DB at {seq}");
             }
             Ok(_) => {
@@ -73,16 +76,24 @@ async fn main() {
     }
 
     services::season_scalar_estimator::warm_cache(&state).await;
+    match routes::wars::warm_live_wars(&state).await {
+        Ok(count) if count > 0 => tracing::info!("Restored {count} live wars from DB"),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to restore live wars: {e}"),
+    }
 
     // Spawn background services
     tokio::spawn(services::territory_poller::run(state.clone()));
     tokio::spawn(services::guild_evictor::run(state.clone()));
     tokio::spawn(services::extra_data_loader::run(state.clone()));
     tokio::spawn(services::guild_color_loader::run(state.clone()));
+    tokio::spawn(services::guild_hq_tracker::run(state.clone()));
     tokio::spawn(services::season_scalar_estimator::run(state.clone()));
 
     tokio::spawn(services::snapshot_service::run(state.clone()));
     tokio::spawn(services::retention_cleaner::run(state.clone()));
+    tokio::spawn(services::notifier::run(state.clone()));
+    tokio::spawn(services::notifier::run_dispatcher(state.clone()));
 
     let app = app::build_app(state);
 
@@ -9,16 +9,22 @@ use axum::response::{IntoResponse, Response};
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use futures::stream::{self, StreamExt};
+use sequoia_shared::guild_hq::GuildHqEstimate;
 use tracing::warn;
 
 use super::http_util::{if_none_match_matches, json_bytes_response, not_modified_response};
+use super::ingest::ensure_internal_ingest_auth;
 
 use crate::config::{
     GUILD_CACHE_TTL_SECS, MAX_GUILD_CACHE_ENTRIES, WYNNCRAFT_GUILD_URL,
     guilds_online_cache_ttl_secs, guilds_online_max_concurrency,
 };
+use crate::services::guild_hq_tracker::load_guild_hq_moves;
+use crate::services::season_backtest::{self, SeasonBacktestRequest};
 use crate::services::season_data::{self, SeasonDataError};
 use crate::services::season_race::{self, SeasonRaceError};
+use crate::services::season_race_scenario::{self, SeasonRaceScenarioRequest};
+use crate::services::season_race_simulation;
 use crate::services::wynncraft_api;
 use crate::state::{AppState, CachedGuild, ObservabilitySnapshot};
 
@@ -28,11 +34,12 @@ type LatestObservedRatingRow = (String, DateTime<Utc>, i32);
 
 pub async fn health(State(state): State<AppState>) -> Json<serde_json::Value> {
     let territory_count = state.live_snapshot.read().await.territories.len();
+    let live_war_count = state.live_wars.read().await.len();
     let observability = state.observability.snapshot();
     Json(serde_json::json!({
         "status": "ok",
         "territories": territory_count,
-        "live_wars": 0,
+        "live_wars": live_war_count,
         "guild_cache_size": state.guild_cache.len(),
         "history_available": state.db.is_some(),
         "claims_persistence_available": state.db.is_some(),
@@ -111,6 +118,8 @@ pub async fn get_season_scalar_current(State(state): State<AppState>) -> impl In
 pub struct SeasonRaceQuery {
     #[serde(default)]
     pub season_id: Option<i32>,
+    #[serde(default)]
+    pub top_n: Option<u32>,
 }
 
 #[derive(serde::Deserialize)]
@@ -164,13 +173,19 @@ pub async fn get_season_race(
     State(state): State<AppState>,
     Query(query): Query<SeasonRaceQuery>,
 ) -> Result<impl IntoResponse, StatusCode> {
-    let response = season_race::build_race_response(&state, query.season_id)
+    let mut response = season_race::build_race_response(&state, query.season_id)
         .await
-        .map_err(|error| match error {
-            SeasonRaceError::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
-            SeasonRaceError::BadRequest => StatusCode::BAD_REQUEST,
-            SeasonRaceError::Internal => StatusCode::INTERNAL_SERVER_ERROR,
-        })?;
+        .map_err(map_season_race_status)?;
+    // Bands are an optional extra; a failed simulation still serves the projection.
+    if let Err(error) =
+        season_race_simulation::attach_intervals(&state, &mut response, query.top_n).await
+    {
+        warn!(
+            ?error,
+            season_id = response.season_id,
+            "failed to simulate season race intervals"
+        );
+    }
 
     let mut headers = HeaderMap::new();
     headers.insert(
@@ -180,6 +195,39 @@ pub async fn get_season_race(
     Ok((headers, Json(response)))
 }
 
+/// `POST /api/season/race/scenario` — Re-project the season race under what-if overrides.
+pub async fn post_season_race_scenario(
+    State(state): State<AppState>,
+    Json(request): Json<SeasonRaceScenarioRequest>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let response = season_race_scenario::build_scenario_response(&state, request)
+        .await
+        .map_err(map_season_race_status)?;
+    Ok(Json(response))
+}
+
+/// `POST /api/internal/season/backtest` — Replay completed seasons and score the scalar
+/// forecast and race projection against their final outcome.
+pub async fn post_season_backtest(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<SeasonBacktestRequest>,
+) -> Result<impl IntoResponse, StatusCode> {
+    ensure_internal_ingest_auth(&state, &headers)?;
+    let response = season_backtest::run_backtest(&state, request)
+        .await
+        .map_err(map_season_race_status)?;
+    Ok(Json(response))
+}
+
+fn map_season_race_status(error: SeasonRaceError) -> StatusCode {
+    match error {
+        SeasonRaceError::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        SeasonRaceError::BadRequest => StatusCode::BAD_REQUEST,
+        SeasonRaceError::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
 pub async fn get_map_intel(State(state): State<AppState>) -> Result<impl IntoResponse, StatusCode> {
     let response = wynncraft_api::cached_map_intel_summary(&state)
         .await
@@ -446,11 +494,22 @@ pub async fn get_guild(
             .signed_duration_since(cached.fetched_at)
             .num_seconds();
         if age < GUILD_CACHE_TTL_SECS {
-            return Ok(json_bytes_response(
-                Bytes::from(cached.data.clone()),
-                "public, max-age=300",
-                None,
-            ));
+            let (body, data, fetched_at) =
+                (cached.body.clone(), cached.data.clone(), cached.fetched_at);
+            drop(cached);
+            let body = match body {
+                Some(body) => body,
+                None => {
+                    let body = with_guild_hq(&state, &name, data).await;
+                    if let Some(mut entry) = state.guild_cache.get_mut(&name)
+                        && entry.fetched_at == fetched_at
+                    {
+                        entry.body = Some(body.clone());
+                    }
+                    body
+                }
+            };
+            return Ok(json_bytes_response(body, "public, max-age=300", None));
         }
     }
 
@@ -469,13 +528,65 @@ pub async fn get_guild(
 
     let data = resp.text().await.map_err(|_| StatusCode::BAD_GATEWAY)?;
 
-    cache_guild_payload(&state, name, data.clone());
+    // Attach the HQ once per fetch; cache hits serve these bytes as-is.
+    let body = with_guild_hq(&state, &name, data.clone()).await;
+    cache_guild_payload(&state, name, data, Some(body.clone()));
+
+    Ok(json_bytes_response(body, "public, max-age=300", None))
+}
 
-    Ok(json_bytes_response(
-        Bytes::from(data),
-        "public, max-age=300",
-        None,
-    ))
+/// Add the guild's HQ estimate (`sequoia_hq`) and recorded HQ moves (`sequoia_hq_moves`) to
+/// an upstream guild payload. Non-object payloads pass through unchanged.
+async fn with_guild_hq(state: &AppState, name: &str, data: String) -> Bytes {
+    let Ok(serde_json::Value::Object(mut object)) = serde_json::from_str(&data) else {
+        return Bytes::from(data);
+    };
+    let guild_name = object
+        .get("name")
+        .and_then(|value| value.as_str())
+        .unwrap_or(name)
+        .to_string();
+    let hq = state
+        .guild_hqs
+        .read()
+        .await
+        .get(&guild_name.to_ascii_lowercase())
+        .cloned();
+    let moves = match state.db.as_ref() {
+        Some(pool) => load_guild_hq_moves(pool, &guild_name)
+            .await
+            .unwrap_or_else(|error| {
+                warn!(error = %error, "failed to load guild HQ moves");
+                Vec::new()
+            }),
+        None => Vec::new(),
+    };
+    object.insert(
+        "sequoia_hq".to_string(),
+        serde_json::to_value(hq).unwrap_or_default(),
+    );
+    object.insert(
+        "sequoia_hq_moves".to_string(),
+        serde_json::to_value(moves).unwrap_or_default(),
+    );
+    match serde_json::to_vec(&object) {
+        Ok(body) => Bytes::from(body),
+        Err(_) => Bytes::from(data),
+    }
+}
+
+/// `GET /api/guilds/hq` — Current HQ estimate for every guild holding territory.
+pub async fn get_guild_hqs(State(state): State<AppState>) -> impl IntoResponse {
+    let mut estimates: Vec<GuildHqEstimate> =
+        state.guild_hqs.read().await.values().cloned().collect();
+    estimates.sort_by(|a, b| a.guild_name.cmp(&b.guild_name));
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("public, max-age=30"),
+    );
+    (headers, Json(estimates))
 }
 
 const MAX_GUILDS_ONLINE_BATCH: usize = 25;
@@ -582,7 +693,7 @@ pub async fn get_guilds_online(
 
                 let entry = parse_guild_online_entry(&data);
                 let entry_missing = entry.is_none();
-                cache_guild_payload(&state, name.clone(), data);
+                cache_guild_payload(&state, name.clone(), data, None);
                 (name, entry, entry_missing)
             }
         }))
@@ -773,7 +884,7 @@ fn guild_details_url(guild_name: &str) -> Result<reqwest::Url, StatusCode> {
     Ok(url)
 }
 
-fn cache_guild_payload(state: &AppState, name: String, data: String) {
+fn cache_guild_payload(state: &AppState, name: String, data: String, body: Option<Bytes>) {
     if !state.guild_cache.contains_key(&name) {
         while state.guild_cache.len() >= MAX_GUILD_CACHE_ENTRIES {
             if !evict_oldest_guild_entry(state) {
@@ -786,6 +897,7 @@ fn cache_guild_payload(state: &AppState, name: String, data: String) {
         name,
         CachedGuild {
             data,
+            body,
             fetched_at: Utc::now(),
         },
     );
@@ -0,0 +1,51 @@
+use axum::Json;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use super::ingest::ensure_internal_ingest_auth;
+
+use crate::config::history_archive_dir;
+use crate::services::history_archive::{import_archive_file, resolve_archive_path};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct ArchiveImportRequest {
+    /// Archive file relative to `HISTORY_ARCHIVE_DIR`, e.g. `territory_events/2025-01-02.ndjson.gz`.
+    pub path: String,
+}
+
+/// `POST /api/internal/archive/import` — Load one retention archive back into its table.
+pub async fn import_archive(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<ArchiveImportRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_internal_ingest_auth(&state, &headers)?;
+    let pool = state.db.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let archive_dir = history_archive_dir().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let (path, table) =
+        resolve_archive_path(&archive_dir, &request.path).ok_or(StatusCode::BAD_REQUEST)?;
+    if !path.is_file() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let (read, inserted) = import_archive_file(pool, path, table).await.map_err(|e| {
+        warn!("archive import of {} failed: {e}", request.path);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    info!(
+        "Imported {inserted}/{read} rows into {} from archive {}",
+        table.table_name(),
+        request.path
+    );
+
+    Ok(Json(serde_json::json!({
+        "ok": true,
+        "table": table.table_name(),
+        "read": read,
+        "inserted": inserted,
+        "skipped": read.saturating_sub(inserted),
+    })))
+}
@@ -4,26 +4,52 @@ use std::sync::atomic::Ordering as AtomicOrdering;
 
 use axum::Json;
 use axum::extract::{Path, Query, State};
-use axum::http::{HeaderMap, StatusCode};
-use axum::response::Response;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
 use bytes::Bytes;
 
 use super::http_util::{if_none_match_matches, json_bytes_response, not_modified_response};
 use chrono::Utc;
+use sequoia_shared::colors::guild_color;
 use sequoia_shared::{
-    CLAIM_DOCUMENT_VERSION_V1, ClaimDocumentV1, ClaimValidationError, ClaimsTerritoryGeometry,
+    CLAIM_DOCUMENT_VERSION_V2, ClaimDiff, ClaimDocumentV2, ClaimOwner, ClaimValidationError,
+    ClaimsTerritoryGeometry, diff_claim_documents, materialize_claim_owners,
     validate_claim_document,
 };
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
+use crate::config::{CLAIM_LAYOUT_MAX_REVISIONS, CLAIM_OWNED_LAYOUTS_LIMIT};
+use crate::services::map_preview::{PreviewTerritory, render_map_preview};
 use crate::state::{
-    AppState, CachedGuildCatalog, CachedGuildCatalogEntry, StoredClaimLayout,
+    AppState, CachedGuildCatalog, CachedGuildCatalogEntry, LiveSnapshot, StoredClaimLayout,
     build_guild_color_lookup, lookup_guild_color,
 };
 
+type StoredClaimLayoutRow = (
+    chrono::DateTime<Utc>,
+    Option<chrono::DateTime<Utc>>,
+    Option<String>,
+    i32,
+    serde_json::Value,
+);
+type OwnedClaimLayoutRow = (
+    String,
+    chrono::DateTime<Utc>,
+    Option<chrono::DateTime<Utc>>,
+    Option<String>,
+    i32,
+);
+
 const GUILD_CATALOG_TTL_SECS: i64 = 3600;
 const DEFAULT_GUILD_CATALOG_LIMIT: usize = 24;
 const MAX_GUILD_CATALOG_LIMIT: usize = 100;
+/// `b` path segment of the diff route that compares against the live map.
+const LIVE_CLAIM_DIFF_TARGET: &str = "live";
+// Layouts can be edited in place, so previews are only cached briefly.
+const CLAIM_PREVIEW_CACHE_CONTROL: &str = "public, max-age=300";
+const CLAIM_EDIT_TOKEN_HEADER: &str = "x-claim-edit-token";
+const CLAIM_EDIT_TOKEN_BYTES: usize = 32;
 const CLAIMS_GEOMETRY_ETAG_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
 const CLAIMS_GEOMETRY_ETAG_PRIME: u64 = 0x100000001b3;
 
@@ -54,7 +80,7 @@ pub struct GuildCatalogResponse {
 pub struct CreateClaimRequest {
     #[serde(default)]
     pub title: Option<String>,
-    pub document: ClaimDocumentV1,
+    pub document: ClaimDocumentV2,
 }
 
 #[derive(Debug, Serialize)]
@@ -62,6 +88,49 @@ pub struct CreateClaimResponse {
     pub id: String,
     pub created_at: String,
     pub url: String,
+    pub revision: i32,
+    /// Secret needed to update or delete the layout; only returned when newly issued.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edit_token: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpdateClaimResponse {
+    pub id: String,
+    pub url: String,
+    pub revision: i32,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OwnedClaimLayoutSummary {
+    pub id: String,
+    pub url: String,
+    pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub revision: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OwnedClaimLayoutsResponse {
+    pub layouts: Vec<OwnedClaimLayoutSummary>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClaimRevisionSummary {
+    pub revision: i32,
+    pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClaimRevisionsResponse {
+    pub id: String,
+    pub revisions: Vec<ClaimRevisionSummary>,
 }
 
 pub async fn get_guild_catalog(
@@ -132,47 +201,47 @@ pub async fn get_claims_bootstrap_geometry(
 
 pub async fn create_claim_layout(
     State(state): State<AppState>,
-    Json(mut payload): Json<CreateClaimRequest>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateClaimRequest>,
 ) -> Result<(StatusCode, Json<CreateClaimResponse>), StatusCode> {
     let Some(pool) = state.db.as_ref() else {
         return Err(StatusCode::SERVICE_UNAVAILABLE);
     };
 
-    let territory_names: Vec<String> = {
-        let live_snapshot = state.live_snapshot.read().await;
-        live_snapshot.territories.keys().cloned().collect()
+    // Reusing a caller's token groups their layouts for listing; otherwise a new one is issued.
+    let (edit_token_hash, issued_token) = match provided_edit_token(&headers)? {
+        Some(token) => (hash_edit_token(token), None),
+        None => {
+            let token = generate_edit_token().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            (hash_edit_token(&token), Some(token))
+        }
     };
-
-    let title = payload
-        .title
-        .take()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty());
-    payload.document.title = title.clone();
-
-    validate_claim_document(
-        &payload.document,
-        territory_names.iter().map(String::as_str),
-    )
-    .map_err(validation_status)?;
-
-    let document_json =
-        serde_json::to_value(&payload.document).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let (title, document_json) = prepare_claim_document(&state, payload).await?;
     let id = next_claim_id(&state);
     let created_at = Utc::now();
 
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     sqlx::query(
-        "INSERT INTO claim_layouts (id, created_at, title, document_version, document) \
-         VALUES ($1, $2, $3, $4, $5)",
+        "INSERT INTO claim_layouts \
+         (id, created_at, title, document_version, document, edit_token_hash, revision) \
+         VALUES ($1, $2, $3, $4, $5, $6, 1)",
     )
     .bind(&id)
     .bind(created_at)
     .bind(title.clone())
-    .bind(i32::from(CLAIM_DOCUMENT_VERSION_V1))
-    .bind(document_json)
-    .execute(pool)
+    .bind(i32::from(CLAIM_DOCUMENT_VERSION_V2))
+    .bind(&document_json)
+    .bind(&edit_token_hash)
+    .execute(&mut *tx)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    insert_claim_revision(&mut tx, &id, 1, created_at, title, &document_json).await?;
+    tx.commit()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     Ok((
         StatusCode::CREATED,
@@ -180,10 +249,198 @@ pub async fn create_claim_layout(
             id: id.clone(),
             created_at: created_at.to_rfc3339(),
             url: format!("/claims/s/{id}"),
+            revision: 1,
+            edit_token: issued_token,
         }),
     ))
 }
 
+/// `PUT /api/claims/{id}` — Replace a layout's document in place, keeping its ID and URL.
+pub async fn update_claim_layout(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateClaimRequest>,
+) -> Result<Json<UpdateClaimResponse>, StatusCode> {
+    let Some(pool) = state.db.as_ref() else {
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    };
+    let token = provided_edit_token(&headers)?.ok_or(StatusCode::UNAUTHORIZED)?;
+    let (title, document_json) = prepare_claim_document(&state, payload).await?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let revision = lock_owned_claim_layout(&mut tx, &id, token).await? + 1;
+    let updated_at = Utc::now();
+    sqlx::query(
+        "UPDATE claim_layouts \
+         SET title = $2, document_version = $3, document = $4, revision = $5, updated_at = $6 \
+         WHERE id = $1",
+    )
+    .bind(&id)
+    .bind(title.clone())
+    .bind(i32::from(CLAIM_DOCUMENT_VERSION_V2))
+    .bind(&document_json)
+    .bind(revision)
+    .bind(updated_at)
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    insert_claim_revision(&mut tx, &id, revision, updated_at, title, &document_json).await?;
+    sqlx::query("DELETE FROM claim_layout_revisions WHERE layout_id = $1 AND revision <= $2")
+        .bind(&id)
+        .bind(revision - CLAIM_LAYOUT_MAX_REVISIONS)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    tx.commit()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(UpdateClaimResponse {
+        url: format!("/claims/s/{id}"),
+        id,
+        revision,
+        updated_at: updated_at.to_rfc3339(),
+    }))
+}
+
+/// `DELETE /api/claims/{id}` — Delete a layout and its revision history.
+pub async fn delete_claim_layout(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<StatusCode, StatusCode> {
+    let Some(pool) = state.db.as_ref() else {
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    };
+    let token = provided_edit_token(&headers)?.ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    lock_owned_claim_layout(&mut tx, &id, token).await?;
+    sqlx::query("DELETE FROM claim_layouts WHERE id = $1")
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    tx.commit()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /api/claims` — Layouts created with the caller's edit token, newest first.
+pub async fn list_owned_claim_layouts(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<OwnedClaimLayoutsResponse>, StatusCode> {
+    let Some(pool) = state.db.as_ref() else {
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    };
+    let token = provided_edit_token(&headers)?.ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let rows: Vec<OwnedClaimLayoutRow> = sqlx::query_as(
+        "SELECT id, created_at, updated_at, title, revision FROM claim_layouts \
+         WHERE edit_token_hash = $1 \
+         ORDER BY created_at DESC, id DESC \
+         LIMIT $2",
+    )
+    .bind(hash_edit_token(token))
+    .bind(CLAIM_OWNED_LAYOUTS_LIMIT)
+    .fetch_all(pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let layouts = rows
+        .into_iter()
+        .map(
+            |(id, created_at, updated_at, title, revision)| OwnedClaimLayoutSummary {
+                url: format!("/claims/s/{id}"),
+                id,
+                created_at: created_at.to_rfc3339(),
+                updated_at: updated_at.map(|value| value.to_rfc3339()),
+                title,
+                revision,
+            },
+        )
+        .collect();
+    Ok(Json(OwnedClaimLayoutsResponse { layouts }))
+}
+
+/// `GET /api/claims/{id}/revisions` — Revision history of a layout, newest first.
+pub async fn list_claim_revisions(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ClaimRevisionsResponse>, StatusCode> {
+    let Some(pool) = state.db.as_ref() else {
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    };
+
+    let rows: Vec<(i32, chrono::DateTime<Utc>, Option<String>)> = sqlx::query_as(
+        "SELECT revision, created_at, title FROM claim_layout_revisions \
+         WHERE layout_id = $1 \
+         ORDER BY revision DESC",
+    )
+    .bind(&id)
+    .fetch_all(pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if rows.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(Json(ClaimRevisionsResponse {
+        id,
+        revisions: rows
+            .into_iter()
+            .map(|(revision, created_at, title)| ClaimRevisionSummary {
+                revision,
+                created_at: created_at.to_rfc3339(),
+                title,
+            })
+            .collect(),
+    }))
+}
+
+/// `GET /api/claims/{id}/revisions/{revision}` — One past revision of a layout.
+pub async fn get_claim_revision(
+    State(state): State<AppState>,
+    Path((id, revision)): Path<(String, i32)>,
+) -> Result<Json<StoredClaimLayout>, StatusCode> {
+    let Some(pool) = state.db.as_ref() else {
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    };
+
+    let row: Option<(chrono::DateTime<Utc>, Option<String>, serde_json::Value)> = sqlx::query_as(
+        "SELECT created_at, title, document FROM claim_layout_revisions \
+             WHERE layout_id = $1 AND revision = $2",
+    )
+    .bind(&id)
+    .bind(revision)
+    .fetch_optional(pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let Some((created_at, title, document_json)) = row else {
+        return Err(StatusCode::NOT_FOUND);
+    };
+    let document: ClaimDocumentV2 =
+        serde_json::from_value(document_json).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(StoredClaimLayout {
+        id,
+        created_at,
+        updated_at: None,
+        title,
+        revision,
+        document,
+    }))
+}
+
 pub async fn get_claim_layout(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -192,28 +449,275 @@ pub async fn get_claim_layout(
         return Err(StatusCode::SERVICE_UNAVAILABLE);
     };
 
-    let row: Option<(chrono::DateTime<Utc>, Option<String>, serde_json::Value)> =
-        sqlx::query_as("SELECT created_at, title, document FROM claim_layouts WHERE id = $1")
-            .bind(&id)
-            .fetch_optional(pool)
-            .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let Some(layout) = load_claim_layout(pool, id).await? else {
+        return Err(StatusCode::NOT_FOUND);
+    };
+    Ok(Json(layout))
+}
+
+/// `GET /api/claims/{id}/preview.png` — Saved layout rendered as an OpenGraph preview image.
+pub async fn get_claim_preview(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let Some(pool) = state.db.as_ref() else {
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    };
 
-    let Some((created_at, title, document_json)) = row else {
+    let Some(layout) = load_claim_layout(pool, id).await? else {
         return Err(StatusCode::NOT_FOUND);
     };
+    let fallback_colors = state.guild_colors.read().await.clone();
+    let fallback_colors_normalized = build_guild_color_lookup(&fallback_colors);
+    let territories: Vec<PreviewTerritory> = {
+        let live_snapshot = state.live_snapshot.read().await;
+        let owners = materialize_claim_owners(&layout.document, &live_snapshot.territories);
+        live_snapshot
+            .territories
+            .iter()
+            .map(|(name, territory)| PreviewTerritory {
+                location: territory.location.clone(),
+                owner: owners
+                    .get(name)
+                    .and_then(ClaimOwner::as_guild)
+                    .map(|guild| {
+                        let color = guild
+                            .color
+                            .or_else(|| {
+                                lookup_guild_color(
+                                    &fallback_colors,
+                                    &fallback_colors_normalized,
+                                    &guild.name,
+                                )
+                            })
+                            .unwrap_or_else(|| guild_color(&guild.name));
+                        (guild.prefix.clone(), color)
+                    }),
+            })
+            .collect()
+    };
+    let caption = layout
+        .title
+        .filter(|title| !title.trim().is_empty())
+        .unwrap_or_else(|| "Untitled claim plan".to_string());
+    let png = tokio::task::spawn_blocking(move || render_map_preview(&territories, &caption))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/png"),
+            (header::CACHE_CONTROL, CLAIM_PREVIEW_CACHE_CONTROL),
+        ],
+        png,
+    ))
+}
+
+/// Diff layout `a` against `b`, another saved layout or the live map when `b` is `live`.
+///
+/// `b` is the `before` side and `a` the plan, matching the claims editor's session diff.
+pub async fn diff_claim_layouts(
+    State(state): State<AppState>,
+    Path((a, b)): Path<(String, String)>,
+) -> Result<Json<ClaimDiff>, StatusCode> {
+    let Some(pool) = state.db.as_ref() else {
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    };
+
+    let plan = load_claim_layout(pool, a)
+        .await?
+        .ok_or(StatusCode::NOT_FOUND)?
+        .document;
+    let reference = if b == LIVE_CLAIM_DIFF_TARGET {
+        None
+    } else {
+        Some(
+            load_claim_layout(pool, b)
+                .await?
+                .ok_or(StatusCode::NOT_FOUND)?
+                .document,
+        )
+    };
+
+    let live_snapshot = state.live_snapshot.read().await;
+    Ok(Json(claim_layout_diff(
+        &plan,
+        reference.as_ref(),
+        &live_snapshot,
+    )))
+}
+
+/// `plan` relative to `reference`, or to current live ownership when there is none.
+fn claim_layout_diff(
+    plan: &ClaimDocumentV2,
+    reference: Option<&ClaimDocumentV2>,
+    live_snapshot: &LiveSnapshot,
+) -> ClaimDiff {
+    match reference {
+        Some(reference) => diff_claim_documents(reference, plan, &live_snapshot.territories),
+        None => diff_claim_documents(
+            &live_claim_document(live_snapshot),
+            plan,
+            &live_snapshot.territories,
+        ),
+    }
+}
+
+async fn load_claim_layout(
+    pool: &sqlx::PgPool,
+    id: String,
+) -> Result<Option<StoredClaimLayout>, StatusCode> {
+    let row: Option<StoredClaimLayoutRow> = sqlx::query_as(
+        "SELECT created_at, updated_at, title, revision, document FROM claim_layouts \
+         WHERE id = $1",
+    )
+    .bind(&id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let Some((created_at, updated_at, title, revision, document_json)) = row else {
+        return Ok(None);
+    };
 
-    let document: ClaimDocumentV1 =
+    let document: ClaimDocumentV2 =
         serde_json::from_value(document_json).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    Ok(Json(StoredClaimLayout {
+    Ok(Some(StoredClaimLayout {
         id,
         created_at,
+        updated_at,
         title,
+        revision,
         document,
     }))
 }
 
+/// Normalize the title into the document and validate it against the live territory set.
+async fn prepare_claim_document(
+    state: &AppState,
+    mut payload: CreateClaimRequest,
+) -> Result<(Option<String>, serde_json::Value), StatusCode> {
+    let territory_names: Vec<String> = {
+        let live_snapshot = state.live_snapshot.read().await;
+        live_snapshot.territories.keys().cloned().collect()
+    };
+
+    let title = payload
+        .title
+        .take()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    payload.document.title = title.clone();
+
+    validate_claim_document(
+        &payload.document,
+        territory_names.iter().map(String::as_str),
+    )
+    .map_err(validation_status)?;
+
+    let document_json =
+        serde_json::to_value(&payload.document).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok((title, document_json))
+}
+
+async fn insert_claim_revision(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: &str,
+    revision: i32,
+    created_at: chrono::DateTime<Utc>,
+    title: Option<String>,
+    document_json: &serde_json::Value,
+) -> Result<(), StatusCode> {
+    sqlx::query(
+        "INSERT INTO claim_layout_revisions \
+         (layout_id, revision, created_at, title, document_version, document) \
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(id)
+    .bind(revision)
+    .bind(created_at)
+    .bind(title)
+    .bind(i32::from(CLAIM_DOCUMENT_VERSION_V2))
+    .bind(document_json)
+    .execute(&mut **tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(())
+}
+
+/// Lock a layout row for writing and return its current revision if `token` owns it.
+///
+/// Layouts saved before edit tokens existed have no owner and stay immutable.
+async fn lock_owned_claim_layout(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: &str,
+    token: &str,
+) -> Result<i32, StatusCode> {
+    let row: Option<(Option<String>, i32)> = sqlx::query_as(
+        "SELECT edit_token_hash, revision FROM claim_layouts WHERE id = $1 FOR UPDATE",
+    )
+    .bind(id)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let Some((owner_hash, revision)) = row else {
+        return Err(StatusCode::NOT_FOUND);
+    };
+    if owner_hash.as_deref() != Some(hash_edit_token(token).as_str()) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(revision)
+}
+
+/// Edit token from the request headers; a present but malformed token is rejected.
+fn provided_edit_token(headers: &HeaderMap) -> Result<Option<&str>, StatusCode> {
+    let Some(value) = headers.get(CLAIM_EDIT_TOKEN_HEADER) else {
+        return Ok(None);
+    };
+    let token = value.to_str().map_err(|_| StatusCode::UNAUTHORIZED)?.trim();
+    if !is_valid_edit_token(token) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    Ok(Some(token))
+}
+
+fn is_valid_edit_token(token: &str) -> bool {
+    token.len() == CLAIM_EDIT_TOKEN_BYTES * 2
+        && token
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte))
+}
+
+fn generate_edit_token() -> Result<String, getrandom::Error> {
+    let mut bytes = [0u8; CLAIM_EDIT_TOKEN_BYTES];
+    getrandom::getrandom(&mut bytes)?;
+    Ok(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+/// Only the SHA-256 of an edit token is stored.
+fn hash_edit_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Current live ownership as an unedited frozen-live document.
+fn live_claim_document(live_snapshot: &LiveSnapshot) -> ClaimDocumentV2 {
+    let owners = live_snapshot
+        .territories
+        .iter()
+        .map(|(name, territory)| {
+            (
+                name.clone(),
+                ClaimOwner::from_guild(territory.guild.clone()),
+            )
+        })
+        .collect();
+    ClaimDocumentV2::frozen_live(None, live_snapshot.seq, owners)
+}
+
 async fn load_guild_catalog(state: &AppState) -> Result<CachedGuildCatalog, StatusCode> {
     {
         let cache = state.guild_catalog_cache.read().await;
@@ -328,6 +832,10 @@ fn validation_status(error: ClaimValidationError) -> StatusCode {
         | ClaimValidationError::UnknownTerritory(_)
         | ClaimValidationError::DuplicateMacroId(_)
         | ClaimValidationError::EmptyMacroName(_)
+        | ClaimValidationError::HqNotOwned(_)
+        | ClaimValidationError::InvalidTowerLevels(_)
+        | ClaimValidationError::TooManyAnnotations(_)
+        | ClaimValidationError::InvalidAnnotation(_)
         | ClaimValidationError::DocumentTooLarge(_) => StatusCode::BAD_REQUEST,
     }
 }
@@ -446,6 +954,82 @@ mod tests {
         assert_eq!(first, second);
     }
 
+    #[test]
+    fn create_request_accepts_v1_documents_as_v2() {
+        let request: CreateClaimRequest = serde_json::from_value(serde_json::json!({
+            "title": "Old share",
+            "document": {
+                "version": 1,
+                "base": { "kind": "blank" },
+                "overrides": { "Alpha": { "kind": "neutral" } }
+            }
+        }))
+        .expect("v1 request");
+        assert_eq!(request.document.version, CLAIM_DOCUMENT_VERSION_V2);
+        assert_eq!(request.document.overrides.len(), 1);
+        assert!(request.document.hqs.is_empty());
+        assert!(validate_claim_document(&request.document, ["Alpha"]).is_ok());
+    }
+
+    #[test]
+    fn layout_diff_takes_live_or_reference_as_before_side() {
+        let mut live_snapshot = LiveSnapshot::default();
+        live_snapshot
+            .territories
+            .insert("Alpha".to_string(), test_territory([0, 0], [1, 1]));
+        live_snapshot
+            .territories
+            .insert("Bravo".to_string(), test_territory([2, 2], [4, 4]));
+        let live = live_claim_document(&live_snapshot);
+
+        let mut saved = live.clone();
+        saved
+            .overrides
+            .insert("Bravo".to_string(), ClaimOwner::Neutral);
+        // Live is the before side: the saved plan drops Bravo from the guild.
+        let diff = claim_layout_diff(&saved, None, &live_snapshot);
+        assert_eq!(diff.owner_changes.len(), 1);
+        assert_eq!(diff.owner_changes[0].territory, "Bravo");
+        assert_eq!(diff.owner_changes[0].before.display_name(), "Guild");
+        assert_eq!(diff.owner_changes[0].after, ClaimOwner::Neutral);
+        assert_eq!(diff.guilds[0].territory_delta, -1);
+
+        // A saved reference takes the same side as live.
+        let diff = claim_layout_diff(&saved, Some(&live), &live_snapshot);
+        assert_eq!(diff.guilds[0].territory_delta, -1);
+        let diff = claim_layout_diff(&live, Some(&saved), &live_snapshot);
+        assert_eq!(diff.guilds[0].territory_delta, 1);
+    }
+
+    #[test]
+    fn generated_edit_tokens_are_valid_and_stored_hashed() {
+        let token = generate_edit_token().expect("edit token");
+        assert!(is_valid_edit_token(&token));
+        assert_ne!(token, generate_edit_token().expect("second edit token"));
+        let hash = hash_edit_token(&token);
+        assert_eq!(hash.len(), 64);
+        assert_ne!(hash, token);
+        assert_eq!(hash, hash_edit_token(&token));
+    }
+
+    #[test]
+    fn provided_edit_token_rejects_malformed_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(provided_edit_token(&headers), Ok(None));
+
+        headers.insert(CLAIM_EDIT_TOKEN_HEADER, "short".parse().unwrap());
+        assert_eq!(provided_edit_token(&headers), Err(StatusCode::UNAUTHORIZED));
+
+        let token = "ab".repeat(CLAIM_EDIT_TOKEN_BYTES);
+        headers.insert(CLAIM_EDIT_TOKEN_HEADER, token.parse().unwrap());
+        assert_eq!(provided_edit_token(&headers), Ok(Some(token.as_str())));
+        headers.insert(
+            CLAIM_EDIT_TOKEN_HEADER,
+            token.to_ascii_uppercase().parse().unwrap(),
+        );
+        assert_eq!(provided_edit_token(&headers), Err(StatusCode::UNAUTHORIZED));
+    }
+
     fn test_territory(start: [i32; 2], end: [i32; 2]) -> Territory {
         Territory {
             guild: GuildRef {
@@ -1,21 +1,35 @@
+use std::borrow::Cow;
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use axum::Json;
-use axum::extract::{Query, State};
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
 use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
 use axum::response::IntoResponse;
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
-use sequoia_shared::SeasonScalarSample;
+use sequoia_shared::colors::guild_color;
 use sequoia_shared::history::{
-    HistoryBounds, HistoryEvent, HistoryEvents, HistoryGuildSrEntry, HistoryHeat, HistoryHeatEntry,
-    HistoryHeatMeta, HistoryHeatSeasonWindow, HistoryHeatSource, HistorySnapshot, HistorySrSamples,
-    HistorySrSnapshot, OwnershipRecord,
+    GuildHistory, GuildLedgerDirection, GuildLedgerEntry, GuildTerritoryCountPoint, HistoryBounds,
+    HistoryBucket, HistoryEvent, HistoryEvents, HistoryGuildSrEntry, HistoryHeat, HistoryHeatEntry,
+    HistoryHeatMeta, HistoryHeatSeasonWindow, HistoryHeatSource, HistoryRivalries,
+    HistoryRivalryEntry, HistorySnapshot, HistorySrSamples, HistorySrSnapshot, OwnershipRecord,
+    TerritoryOwnershipHistory, TerritoryOwnershipInterval,
 };
+use sequoia_shared::{Region, SeasonScalarSample};
 use serde::Deserialize;
+use tracing::warn;
 
-use crate::config::territory_history_retention_days;
-use crate::state::{AppState, build_guild_color_lookup, lookup_guild_color};
+use crate::config::{
+    TIMELAPSE_CACHE_MAX_ENTRIES, TIMELAPSE_DEFAULT_FPS, TIMELAPSE_DEFAULT_FRAMES,
+    TIMELAPSE_END_HOLD_SECS, TIMELAPSE_MAX_FPS, TIMELAPSE_MAX_FRAMES,
+    territory_history_retention_days,
+};
+use crate::services::map_preview::{PreviewTerritory, TimelapseEncoder, render_map_preview};
+use crate::state::{
+    AppState, CachedTimelapse, TimelapseKey, build_guild_color_lookup, lookup_guild_color,
+};
 
 type HistoryEventRow = (
     i64,
@@ -40,6 +54,17 @@ type HistoryBoundsRow = (
     i64,
     Option<i64>,
 );
+type TimelapseEventRow = (
+    DateTime<Utc>,
+    String,
+    String,
+    String,
+    String,
+    Option<i16>,
+    Option<i16>,
+    Option<i16>,
+    DateTime<Utc>,
+);
 type SeasonScalarRow = (DateTime<Utc>, i32, f64, f64, f64, i32);
 const AUTHORITATIVE_SCALAR_CONFIDENCE_MIN: f64 = 0.99;
 const AUTHORITATIVE_SCALAR_SAMPLE_COUNT_MIN: i32 = 1;
@@ -56,8 +81,47 @@ type SeasonObservationRow = (
 );
 type SeasonWindowRow = (i32, DateTime<Utc>, DateTime<Utc>);
 type HeatCountRow = (String, i64);
+type RivalryRow = (
+    String,
+    String,
+    String,
+    Option<String>,
+    i64,
+    Vec<String>,
+    i64,
+    i64,
+);
+type TerritoryOwnerRow = (
+    DateTime<Utc>,
+    DateTime<Utc>,
+    String,
+    String,
+    String,
+    Option<i16>,
+    Option<i16>,
+    Option<i16>,
+);
 
 const HEAT_SEASON_FALLBACK_DAYS: i64 = 60;
+const DEFAULT_RIVALRY_LIMIT: i64 = 100;
+const MAX_RIVALRY_LIMIT: i64 = 500;
+const TERRITORY_HISTORY_DEFAULT_DAYS: i64 = 30;
+const MAX_TERRITORY_NAME_LEN: usize = 96;
+const GUILD_HISTORY_DEFAULT_DAYS: i64 = 7;
+const GUILD_HISTORY_HOURLY_MAX_DAYS: i64 = 14;
+const MAX_GUILD_HISTORY_BUCKETS: i64 = 2000;
+const MAX_GUILD_NAME_LEN: usize = 64;
+const EXPORT_MAX_WINDOW_DAYS: i64 = 31;
+const EXPORT_PAGE_SIZE: i64 = 1_000;
+type GuildChangeRow = (
+    i64,
+    DateTime<Utc>,
+    String,
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+);
 
 #[derive(Debug, Clone)]
 struct SeasonObservation {
@@ -277,6 +341,16 @@ pub struct AtQuery {
     t: String,
 }
 
+#[derive(Deserialize)]
+pub struct TimelapseQuery {
+    from: String,
+    to: String,
+    #[serde(default)]
+    fps: Option<u16>,
+    #[serde(default)]
+    frames: Option<u32>,
+}
+
 #[derive(Deserialize)]
 pub struct EventsQuery {
     from: String,
@@ -287,6 +361,29 @@ pub struct EventsQuery {
     limit: i64,
 }
 
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    Ndjson,
+}
+
+#[derive(Deserialize)]
+pub struct ExportQuery {
+    from: String,
+    to: String,
+    #[serde(default = "default_export_format")]
+    format: ExportFormat,
+    #[serde(default)]
+    guild: Option<String>,
+    #[serde(default)]
+    territory: Option<String>,
+}
+
+fn default_export_format() -> ExportFormat {
+    ExportFormat::Ndjson
+}
+
 #[derive(Deserialize)]
 pub struct SrSamplesQuery {
     from: String,
@@ -309,6 +406,328 @@ pub struct HeatQuery {
     at: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct RivalryQuery {
+    #[serde(default = "default_rivalry_source")]
+    source: HeatQuerySource,
+    #[serde(default)]
+    season_id: Option<i32>,
+    #[serde(default)]
+    at: Option<String>,
+    #[serde(default)]
+    from: Option<String>,
+    #[serde(default)]
+    to: Option<String>,
+    #[serde(default = "default_rivalry_limit")]
+    limit: i64,
+}
+
+fn default_rivalry_source() -> HeatQuerySource {
+    HeatQuerySource::Season
+}
+
+fn default_rivalry_limit() -> i64 {
+    DEFAULT_RIVALRY_LIMIT
+}
+
+/// Aggregation window resolved from a heat-style `source`/`season_id`/`at` selection.
+struct HeatWindow {
+    source: HistoryHeatSource,
+    season_id: Option<i32>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    fallback_applied: bool,
+}
+
+#[derive(Deserialize)]
+pub struct TerritoryHistoryQuery {
+    #[serde(default)]
+    from: Option<String>,
+    #[serde(default)]
+    to: Option<String>,
+}
+
+/// Start of an ownership span, before its end is known.
+#[derive(Debug, Clone)]
+struct OwnershipSpanStart {
+    guild_uuid: String,
+    guild_name: String,
+    guild_prefix: String,
+    guild_color: Option<(u8, u8, u8)>,
+    start: DateTime<Utc>,
+}
+
+impl OwnershipSpanStart {
+    fn same_owner(&self, other: &Self) -> bool {
+        if self.guild_uuid.is_empty() || other.guild_uuid.is_empty() {
+            self.guild_name == other.guild_name
+        } else {
+            self.guild_uuid == other.guild_uuid
+        }
+    }
+
+    fn into_interval(
+        self,
+        end: Option<DateTime<Utc>>,
+        window_end: DateTime<Utc>,
+    ) -> TerritoryOwnershipInterval {
+        let until = end.unwrap_or(window_end);
+        TerritoryOwnershipInterval {
+            guild_uuid: self.guild_uuid,
+            guild_name: self.guild_name,
+            guild_prefix: self.guild_prefix,
+            guild_color: self.guild_color,
+            start: self.start.to_rfc3339(),
+            end: end.map(|value| value.to_rfc3339()),
+            duration_secs: (until - self.start).num_seconds().max(0),
+        }
+    }
+}
+
+/// Collapse an initial owner plus ordered ownership changes into contiguous intervals.
+fn build_ownership_intervals(
+    initial: Option<OwnershipSpanStart>,
+    changes: Vec<OwnershipSpanStart>,
+    window_end: DateTime<Utc>,
+) -> Vec<TerritoryOwnershipInterval> {
+    let mut intervals = Vec::new();
+    let mut current = initial;
+    for mut change in changes {
+        match current.as_mut() {
+            Some(active) if active.same_owner(&change) => {
+                // Re-reported owner (e.g. acquired timestamp correction): keep the span open.
+                if active.guild_color.is_none() {
+                    active.guild_color = change.guild_color;
+                }
+            }
+            Some(active) => {
+                // Upstream acquired timestamps can trail our recorded order; never go backwards.
+                change.start = change.start.max(active.start);
+                let previous = current.replace(change.clone());
+                if let Some(previous) = previous {
+                    intervals.push(previous.into_interval(Some(change.start), window_end));
+                }
+            }
+            None => current = Some(change),
+        }
+    }
+    if let Some(active) = current {
+        intervals.push(active.into_interval(None, window_end));
+    }
+    intervals
+}
+
+fn territory_owner_row_to_span(
+    row: TerritoryOwnerRow,
+    fallback_colors: &HashMap<String, (u8, u8, u8)>,
+    fallback_colors_normalized: &HashMap<String, (u8, u8, u8)>,
+) -> OwnershipSpanStart {
+    let (_recorded_at, acquired_at, guild_uuid, guild_name, guild_prefix, r, g, b) = row;
+    let guild_color = with_fallback_color(
+        parse_rgb_triplet(r, g, b),
+        &guild_name,
+        fallback_colors,
+        fallback_colors_normalized,
+    );
+    OwnershipSpanStart {
+        guild_uuid,
+        guild_name,
+        guild_prefix,
+        guild_color,
+        start: acquired_at,
+    }
+}
+
+#[derive(Deserialize)]
+pub struct GuildHistoryQuery {
+    #[serde(default)]
+    from: Option<String>,
+    #[serde(default)]
+    to: Option<String>,
+    #[serde(default)]
+    bucket: Option<HistoryBucket>,
+}
+
+#[derive(Debug, Clone)]
+struct GuildOwnershipChange {
+    stream_seq: i64,
+    recorded_at: DateTime<Utc>,
+    territory: String,
+    guild_name: String,
+    guild_prefix: String,
+    prev_guild_name: Option<String>,
+    prev_guild_prefix: Option<String>,
+}
+
+impl From<GuildChangeRow> for GuildOwnershipChange {
+    fn from(value: GuildChangeRow) -> Self {
+        let (
+            stream_seq,
+            recorded_at,
+            territory,
+            guild_name,
+            guild_prefix,
+            prev_guild_name,
+            prev_guild_prefix,
+        ) = value;
+        Self {
+            stream_seq,
+            recorded_at,
+            territory,
+            guild_name,
+            guild_prefix,
+            prev_guild_name,
+            prev_guild_prefix,
+        }
+    }
+}
+
+/// Walk ownership changes touching `guild_name`, producing the gains/losses ledger and the
+/// guild's territory count after each change that altered its holdings.
+fn build_guild_ledger(
+    guild_name: &str,
+    held: &mut HashSet<String>,
+    changes: Vec<GuildOwnershipChange>,
+) -> (Vec<GuildLedgerEntry>, Vec<(DateTime<Utc>, u32)>) {
+    let mut ledger = Vec::new();
+    let mut counts = Vec::new();
+    for change in changes {
+        let gained = change.guild_name == guild_name
+            && change.prev_guild_name.as_deref() != Some(guild_name);
+        let lost = change.prev_guild_name.as_deref() == Some(guild_name)
+            && change.guild_name != guild_name;
+        let (direction, counterpart_name, counterpart_prefix, holdings_changed) = if gained {
+            (
+                GuildLedgerDirection::Gained,
+                change.prev_guild_name,
+                change.prev_guild_prefix,
+                held.insert(change.territory.clone()),
+            )
+        } else if lost {
+            (
+                GuildLedgerDirection::Lost,
+                Some(change.guild_name),
+                Some(change.guild_prefix),
+                held.remove(&change.territory),
+            )
+        } else {
+            continue;
+        };
+        if holdings_changed {
+            counts.push((
+                change.recorded_at,
+                u32::try_from(held.len()).unwrap_or(u32::MAX),
+            ));
+        }
+        ledger.push(GuildLedgerEntry {
+            stream_seq: u64::try_from(change.stream_seq).unwrap_or_default(),
+            timestamp: change.recorded_at.to_rfc3339(),
+            territory: change.territory,
+            direction,
+            counterpart_name,
+            counterpart_prefix,
+        });
+    }
+    (ledger, counts)
+}
+
+fn floor_to_bucket(at: DateTime<Utc>, bucket: HistoryBucket) -> DateTime<Utc> {
+    let step = bucket.seconds();
+    let secs = at.timestamp();
+    DateTime::from_timestamp(secs - secs.rem_euclid(step), 0).unwrap_or(at)
+}
+
+/// Sample territory counts into fixed buckets covering `[from, to]`.
+fn build_guild_count_series(
+    start_count: u32,
+    counts: &[(DateTime<Utc>, u32)],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    bucket: HistoryBucket,
+) -> Vec<GuildTerritoryCountPoint> {
+    let step = chrono::Duration::seconds(bucket.seconds());
+    let mut series = Vec::new();
+    let mut current = start_count;
+    let mut pending = counts.iter().peekable();
+    let mut bucket_start = floor_to_bucket(from, bucket);
+    while bucket_start <= to {
+        let bucket_end = bucket_start + step;
+        let mut min_count = current;
+        let mut max_count = current;
+        while let Some((_, count)) = pending.next_if(|(at, _)| *at < bucket_end) {
+            current = *count;
+            min_count = min_count.min(current);
+            max_count = max_count.max(current);
+        }
+        series.push(GuildTerritoryCountPoint {
+            timestamp: bucket_start.to_rfc3339(),
+            territory_count: current,
+            min_count,
+            max_count,
+        });
+        bucket_start = bucket_end;
+    }
+    series
+}
+
+/// Territories held by `guild_name` at `at`, from the latest snapshot plus later events.
+async fn load_guild_holdings_at(
+    pool: &sqlx::PgPool,
+    guild_name: &str,
+    at: DateTime<Utc>,
+) -> Result<(HashSet<String>, Option<String>), StatusCode> {
+    let snapshot_row = sqlx::query_as::<_, (DateTime<Utc>, serde_json::Value)>(
+        "SELECT created_at, ownership FROM territory_snapshots \
+         WHERE created_at <= $1 ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind(at)
+    .fetch_optional(pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut owners: HashMap<String, (String, String)> = HashMap::new();
+    let replay_after =
+        match snapshot_row {
+            Some((created_at, ownership_json)) => {
+                let ownership: HashMap<String, OwnershipRecord> =
+                    serde_json::from_value(ownership_json)
+                        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                owners.extend(ownership.into_iter().map(|(territory, record)| {
+                    (territory, (record.guild_name, record.guild_prefix))
+                }));
+                created_at
+            }
+            None => DateTime::<Utc>::UNIX_EPOCH,
+        };
+
+    // One row per territory, so this stays bounded by the map size.
+    let latest_rows: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT DISTINCT ON (territory) territory, guild_name, guild_prefix \
+         FROM territory_events \
+         WHERE recorded_at > $1 AND recorded_at <= $2 \
+         ORDER BY territory, stream_seq DESC",
+    )
+    .bind(replay_after)
+    .bind(at)
+    .fetch_all(pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    for (territory, name, prefix) in latest_rows {
+        owners.insert(territory, (name, prefix));
+    }
+
+    let mut prefix = None;
+    let held = owners
+        .into_iter()
+        .filter(|(_, (name, _))| name == guild_name)
+        .map(|(territory, (_, guild_prefix))| {
+            prefix.get_or_insert(guild_prefix);
+            territory
+        })
+        .collect();
+    Ok((held, prefix))
+}
+
 #[derive(Debug, Clone)]
 struct SeasonWindow {
     season_id: i32,
@@ -373,50 +792,15 @@ async fn load_owner_change_earliest(
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
 }
 
-/// `GET /api/history/at?t={rfc3339}` — Reconstruct ownership at a point in time.
-pub async fn history_at(
-    State(state): State<AppState>,
-    Query(query): Query<AtQuery>,
-) -> Result<impl IntoResponse, StatusCode> {
-    let pool = state.db.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
-    let max_replay_events = state.max_history_replay_events;
-
-    let target: DateTime<Utc> = query
-        .t
-        .parse::<DateTime<Utc>>()
-        .map_err(|_| StatusCode::BAD_REQUEST)?;
-    let (fallback_colors, fallback_colors_normalized) =
-        merged_fallback_colors(&state, pool).await?;
-
-    let season_scalar_fut = async {
-        sqlx::query_as::<_, SeasonScalarRow>(
-            "SELECT sampled_at, season_id, scalar_weighted, scalar_raw, confidence, sample_count \
-             FROM season_scalar_samples \
-             WHERE sampled_at <= $1 \
-             ORDER BY (confidence >= $2 AND sample_count >= $3) DESC, sampled_at DESC \
-             LIMIT 1",
-        )
-        .bind(target)
-        .bind(AUTHORITATIVE_SCALAR_CONFIDENCE_MIN)
-        .bind(AUTHORITATIVE_SCALAR_SAMPLE_COUNT_MIN)
-        .fetch_optional(pool)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
-        .map(|row| {
-            row.map(
-                |(sampled_at, season_id, scalar_weighted, scalar_raw, confidence, sample_count)| {
-                    SeasonScalarSample {
-                        sampled_at: sampled_at.to_rfc3339(),
-                        season_id,
-                        scalar_weighted,
-                        scalar_raw,
-                        confidence,
-                        sample_count: u32::try_from(sample_count.max(0)).unwrap_or(u32::MAX),
-                    }
-                },
-            )
-        })
-    };
+/// Ownership at `target`: the latest snapshot at or before it plus replayed events, with
+/// missing guild colors filled from the live and persisted color caches.
+async fn reconstruct_ownership_at(
+    state: &AppState,
+    pool: &sqlx::PgPool,
+    target: DateTime<Utc>,
+    fallback_colors: &HashMap<String, (u8, u8, u8)>,
+    fallback_colors_normalized: &HashMap<String, (u8, u8, u8)>,
+) -> Result<HashMap<String, OwnershipRecord>, StatusCode> {
     let snapshot_fut = async {
         sqlx::query_as::<_, (i64, DateTime<Utc>, serde_json::Value)>(
             "SELECT id, created_at, ownership FROM territory_snapshots \
@@ -455,15 +839,14 @@ pub async fn history_at(
         )
         .bind(target)
         .bind(target)
-        .bind(max_replay_events.saturating_add(1))
+        .bind(state.max_history_replay_events.saturating_add(1))
         .fetch_all(pool)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
     };
-    let (season_scalar, snapshot_row, event_rows) =
-        tokio::try_join!(season_scalar_fut, snapshot_fut, events_fut)?;
+    let (snapshot_row, event_rows) = tokio::try_join!(snapshot_fut, events_fut)?;
 
-    if event_rows.len() as i64 > max_replay_events {
+    if event_rows.len() as i64 > state.max_history_replay_events {
         return Err(StatusCode::PAYLOAD_TOO_LARGE);
     }
 
@@ -491,8 +874,8 @@ pub async fn history_at(
         let guild_color = with_fallback_color(
             parse_rgb_triplet(guild_color_r, guild_color_g, guild_color_b),
             &guild_name,
-            &fallback_colors,
-            &fallback_colors_normalized,
+            fallback_colors,
+            fallback_colors_normalized,
         );
         ownership.insert(
             territory,
@@ -510,42 +893,619 @@ pub async fn history_at(
         record.guild_color = with_fallback_color(
             record.guild_color,
             &record.guild_name,
-            &fallback_colors,
-            &fallback_colors_normalized,
+            fallback_colors,
+            fallback_colors_normalized,
         );
     }
 
-    let mut guild_names: Vec<String> = ownership
-        .values()
-        .map(|record| record.guild_name.clone())
-        .collect();
-    guild_names.sort();
-    guild_names.dedup();
-    let season_leaderboard = season_leaderboard_at(pool, &guild_names, target).await?;
+    Ok(ownership)
+}
 
-    let snapshot = HistorySnapshot {
-        timestamp: target.to_rfc3339(),
-        ownership,
-        season_scalar,
-        season_leaderboard,
-    };
+/// Cache older timestamps aggressively, recent ones briefly.
+/// Windows ending over an hour ago are settled and cache for a day; recent ones for a minute.
+fn history_cache_max_age_secs(target: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+    if (now - target).num_seconds() > 3600 {
+        86400
+    } else {
+        60
+    }
+}
 
-    // Cache older timestamps aggressively, recent ones briefly
-    let age_secs = (Utc::now() - target).num_seconds();
-    let max_age = if age_secs > 3600 { 86400 } else { 60 };
+fn history_at_cache_headers(target: DateTime<Utc>) -> Result<HeaderMap, StatusCode> {
+    let max_age = history_cache_max_age_secs(target, Utc::now());
 
     let mut headers = HeaderMap::new();
     let cache_control = HeaderValue::from_str(&format!("public, max-age={max_age}"))
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     headers.insert(header::CACHE_CONTROL, cache_control);
-
-    Ok((headers, Json(snapshot)))
+    Ok(headers)
 }
 
-/// `GET /api/history/events?from={t}&to={t}&limit={n}&after_seq={seq}` — Paginated event list.
-pub async fn history_events(
+/// `GET /api/history/at?t={rfc3339}` — Reconstruct ownership at a point in time.
+pub async fn history_at(
     State(state): State<AppState>,
-    Query(query): Query<EventsQuery>,
+    Query(query): Query<AtQuery>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let pool = state.db.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let target: DateTime<Utc> = query
+        .t
+        .parse::<DateTime<Utc>>()
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    let season_scalar_fut = async {
+        sqlx::query_as::<_, SeasonScalarRow>(
+            "SELECT sampled_at, season_id, scalar_weighted, scalar_raw, confidence, sample_count \
+             FROM season_scalar_samples \
+             WHERE sampled_at <= $1 \
+             ORDER BY (confidence >= $2 AND sample_count >= $3) DESC, sampled_at DESC \
+             LIMIT 1",
+        )
+        .bind(target)
+        .bind(AUTHORITATIVE_SCALAR_CONFIDENCE_MIN)
+        .bind(AUTHORITATIVE_SCALAR_SAMPLE_COUNT_MIN)
+        .fetch_optional(pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+        .map(|row| {
+            row.map(
+                |(sampled_at, season_id, scalar_weighted, scalar_raw, confidence, sample_count)| {
+                    SeasonScalarSample {
+                        sampled_at: sampled_at.to_rfc3339(),
+                        season_id,
+                        scalar_weighted,
+                        scalar_raw,
+                        confidence,
+                        sample_count: u32::try_from(sample_count.max(0)).unwrap_or(u32::MAX),
+                    }
+                },
+            )
+        })
+    };
+    let (fallback_colors, fallback_colors_normalized) =
+        merged_fallback_colors(&state, pool).await?;
+    let (season_scalar, ownership) = tokio::try_join!(
+        season_scalar_fut,
+        reconstruct_ownership_at(
+            &state,
+            pool,
+            target,
+            &fallback_colors,
+            &fallback_colors_normalized
+        )
+    )?;
+
+    let mut guild_names: Vec<String> = ownership
+        .values()
+        .map(|record| record.guild_name.clone())
+        .collect();
+    guild_names.sort();
+    guild_names.dedup();
+    let season_leaderboard = season_leaderboard_at(pool, &guild_names, target).await?;
+
+    let snapshot = HistorySnapshot {
+        timestamp: target.to_rfc3339(),
+        ownership,
+        season_scalar,
+        season_leaderboard,
+    };
+
+    Ok((history_at_cache_headers(target)?, Json(snapshot)))
+}
+
+/// `GET /api/history/at.png?t={rfc3339}` — Ownership at a point in time as a preview image.
+pub async fn history_at_png(
+    State(state): State<AppState>,
+    Query(query): Query<AtQuery>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let pool = state.db.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let target: DateTime<Utc> = query
+        .t
+        .parse::<DateTime<Utc>>()
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    let (fallback_colors, fallback_colors_normalized) =
+        merged_fallback_colors(&state, pool).await?;
+    let ownership = reconstruct_ownership_at(
+        &state,
+        pool,
+        target,
+        &fallback_colors,
+        &fallback_colors_normalized,
+    )
+    .await?;
+
+    let geometry = live_territory_geometry(&state).await;
+    let territories = preview_territories(&geometry, &ownership);
+    let caption = format!("Territory map at {}", target.format("%Y-%m-%d %H:%M UTC"));
+    let png = tokio::task::spawn_blocking(move || render_map_preview(&territories, &caption))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut headers = history_at_cache_headers(target)?;
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("image/png"));
+    Ok((headers, png))
+}
+
+/// `GET /api/history/timelapse?from=&to=&fps=&frames=` — Animated PNG of ownership over a
+/// window, stepped through evenly spaced frames.
+///
+/// Encoded output is cached per clamped request, and at most `TIMELAPSE_MAX_CONCURRENT_RENDERS`
+/// renders run at once; further cache misses get `429`.
+pub async fn history_timelapse(
+    State(state): State<AppState>,
+    Query(query): Query<TimelapseQuery>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let pool = state.db.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let (from, to) = parse_time_window(&query.from, &query.to)?;
+    if from >= to {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let fps = query
+        .fps
+        .unwrap_or(TIMELAPSE_DEFAULT_FPS)
+        .clamp(1, TIMELAPSE_MAX_FPS);
+    let frames = query
+        .frames
+        .unwrap_or(TIMELAPSE_DEFAULT_FRAMES)
+        .clamp(2, TIMELAPSE_MAX_FRAMES);
+    let key: TimelapseKey = (from, to, frames, fps);
+    if let Some(png) = cached_timelapse(&state, &key, Utc::now()).await {
+        return timelapse_response(to, png);
+    }
+    let permit = state
+        .timelapse_permits
+        .clone()
+        .try_acquire_owned()
+        .map_err(|_| StatusCode::TOO_MANY_REQUESTS)?;
+
+    let (fallback_colors, fallback_colors_normalized) =
+        merged_fallback_colors(&state, pool).await?;
+    let initial = reconstruct_ownership_at(
+        &state,
+        pool,
+        from,
+        &fallback_colors,
+        &fallback_colors_normalized,
+    )
+    .await?;
+    let event_rows = load_timelapse_events(
+        pool,
+        from,
+        to,
+        state.max_history_replay_events.saturating_add(1),
+    )
+    .await?;
+    if event_rows.len() as i64 > state.max_history_replay_events {
+        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    let events: Vec<(DateTime<Utc>, String, OwnershipRecord)> = event_rows
+        .into_iter()
+        .map(
+            |(
+                recorded_at,
+                territory,
+                guild_uuid,
+                guild_name,
+                guild_prefix,
+                guild_color_r,
+                guild_color_g,
+                guild_color_b,
+                acquired_at,
+            )| {
+                let guild_color = with_fallback_color(
+                    parse_rgb_triplet(guild_color_r, guild_color_g, guild_color_b),
+                    &guild_name,
+                    &fallback_colors,
+                    &fallback_colors_normalized,
+                );
+                let record = OwnershipRecord {
+                    guild_uuid,
+                    guild_name,
+                    guild_prefix,
+                    guild_color,
+                    acquired_at: acquired_at.to_rfc3339(),
+                };
+                (recorded_at, territory, record)
+            },
+        )
+        .collect();
+    let geometry = live_territory_geometry(&state).await;
+    let frame_times = timelapse_frame_times(from, to, frames);
+    let png = tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        Bytes::from(render_timelapse(
+            &geometry,
+            initial,
+            events,
+            &frame_times,
+            fps,
+        ))
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    store_timelapse(&state, key, png.clone(), Utc::now()).await;
+    timelapse_response(to, png)
+}
+
+fn timelapse_response(to: DateTime<Utc>, png: Bytes) -> Result<(HeaderMap, Bytes), StatusCode> {
+    let mut headers = history_at_cache_headers(to)?;
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("image/apng"));
+    Ok((headers, png))
+}
+
+async fn cached_timelapse(
+    state: &AppState,
+    key: &TimelapseKey,
+    now: DateTime<Utc>,
+) -> Option<Bytes> {
+    let cache = state.timelapse_cache.read().await;
+    cache
+        .get(key)
+        .filter(|entry| entry.expires_at > now)
+        .map(|entry| entry.png.clone())
+}
+
+/// Keep the encoded PNG for as long as clients may cache it, evicting the entry closest to
+/// expiry once the cache is full.
+async fn store_timelapse(state: &AppState, key: TimelapseKey, png: Bytes, now: DateTime<Utc>) {
+    let expires_at = now + chrono::Duration::seconds(history_cache_max_age_secs(key.1, now));
+    let mut cache = state.timelapse_cache.write().await;
+    cache.retain(|_, entry| entry.expires_at > now);
+    if cache.len() >= TIMELAPSE_CACHE_MAX_ENTRIES
+        && let Some(oldest) = cache
+            .iter()
+            .min_by_key(|(_, entry)| entry.expires_at)
+            .map(|(key, _)| *key)
+    {
+        cache.remove(&oldest);
+    }
+    cache.insert(key, CachedTimelapse { png, expires_at });
+}
+
+/// Territory rectangles of the live map, which history frames are drawn onto.
+async fn live_territory_geometry(state: &AppState) -> Vec<(String, Region)> {
+    let snapshot = state.live_snapshot.read().await;
+    let mut geometry: Vec<(String, Region)> = snapshot
+        .territories
+        .iter()
+        .map(|(name, territory)| (name.clone(), territory.location.clone()))
+        .collect();
+    geometry.sort_by(|a, b| a.0.cmp(&b.0));
+    geometry
+}
+
+fn preview_territories(
+    geometry: &[(String, Region)],
+    ownership: &HashMap<String, OwnershipRecord>,
+) -> Vec<PreviewTerritory> {
+    geometry
+        .iter()
+        .map(|(name, location)| PreviewTerritory {
+            location: location.clone(),
+            owner: ownership
+                .get(name)
+                .filter(|record| !record.guild_name.is_empty())
+                .map(|record| {
+                    (
+                        record.guild_prefix.clone(),
+                        record
+                            .guild_color
+                            .unwrap_or_else(|| guild_color(&record.guild_name)),
+                    )
+                }),
+        })
+        .collect()
+}
+
+/// `frames` evenly spaced timestamps from `from` to `to`, both inclusive.
+fn timelapse_frame_times(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    frames: u32,
+) -> Vec<DateTime<Utc>> {
+    let span_ms = (to - from).num_milliseconds();
+    let steps = i64::from(frames.max(2) - 1);
+    (0..=steps)
+        .map(|step| from + chrono::Duration::milliseconds(span_ms * step / steps))
+        .collect()
+}
+
+/// Replay `events` (in stream order) over `ownership`, emitting one frame per timestamp.
+/// Ownership events in `(from, to]`, ordered by `recorded_at` since frames are cut by time;
+/// `stream_seq` breaks ties and is not monotonic in `recorded_at` across writers.
+async fn load_timelapse_events(
+    pool: &sqlx::PgPool,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<TimelapseEventRow>, StatusCode> {
+    sqlx::query_as(
+        "SELECT recorded_at, territory, guild_uuid, guild_name, guild_prefix, \
+                guild_color_r, guild_color_g, guild_color_b, acquired_at \
+         FROM territory_events \
+         WHERE recorded_at > $1 AND recorded_at <= $2 \
+         ORDER BY recorded_at ASC, stream_seq ASC \
+         LIMIT $3",
+    )
+    .bind(from)
+    .bind(to)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Step `events` (ordered by recorded time) into `ownership` and encode one frame per time.
+fn render_timelapse(
+    geometry: &[(String, Region)],
+    mut ownership: HashMap<String, OwnershipRecord>,
+    events: Vec<(DateTime<Utc>, String, OwnershipRecord)>,
+    frame_times: &[DateTime<Utc>],
+    fps: u16,
+) -> Vec<u8> {
+    let mut encoder = TimelapseEncoder::new(fps);
+    let mut pending = events.into_iter().peekable();
+    for at in frame_times {
+        while let Some((_, territory, record)) =
+            pending.next_if(|(recorded_at, _, _)| recorded_at <= at)
+        {
+            ownership.insert(territory, record);
+        }
+        encoder.push_frame(
+            &preview_territories(geometry, &ownership),
+            &at.format("%Y-%m-%d %H:%M UTC").to_string(),
+        );
+    }
+    encoder.hold_last_frame(TIMELAPSE_END_HOLD_SECS.saturating_mul(fps));
+    encoder.finish()
+}
+
+fn history_event_from_row(
+    row: HistoryEventRow,
+    fallback_colors: &HashMap<String, (u8, u8, u8)>,
+    fallback_colors_normalized: &HashMap<String, (u8, u8, u8)>,
+) -> Option<HistoryEvent> {
+    let (
+        stream_seq,
+        recorded_at,
+        acquired_at,
+        territory,
+        guild_uuid,
+        guild_name,
+        guild_prefix,
+        guild_color_r,
+        guild_color_g,
+        guild_color_b,
+        prev_guild_name,
+        prev_guild_prefix,
+        prev_guild_color_r,
+        prev_guild_color_g,
+        prev_guild_color_b,
+    ) = row;
+    let guild_color = with_fallback_color(
+        parse_rgb_triplet(guild_color_r, guild_color_g, guild_color_b),
+        &guild_name,
+        fallback_colors,
+        fallback_colors_normalized,
+    );
+    let prev_guild_color = prev_guild_name.as_deref().and_then(|name| {
+        with_fallback_color(
+            parse_rgb_triplet(prev_guild_color_r, prev_guild_color_g, prev_guild_color_b),
+            name,
+            fallback_colors,
+            fallback_colors_normalized,
+        )
+    });
+
+    Some(HistoryEvent {
+        stream_seq: u64::try_from(stream_seq).ok()?,
+        timestamp: recorded_at.to_rfc3339(),
+        acquired_at: Some(acquired_at.to_rfc3339()),
+        territory,
+        guild_uuid,
+        guild_name,
+        guild_prefix,
+        guild_color,
+        prev_guild_name,
+        prev_guild_prefix,
+        prev_guild_color,
+    })
+}
+
+const EXPORT_CSV_HEADER: &str = "stream_seq,timestamp,acquired_at,territory,guild_uuid,guild_name,\
+                                 guild_prefix,guild_color,prev_guild_name,prev_guild_prefix,\
+                                 prev_guild_color\n";
+
+fn csv_field(value: &str) -> Cow<'_, str> {
+    // Spreadsheets evaluate cells starting with these as formulas; a leading quote keeps them text.
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        Cow::Owned(format!("'{value}"))
+    } else {
+        Cow::Borrowed(value)
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        value
+    }
+}
+
+fn csv_color(color: Option<(u8, u8, u8)>) -> String {
+    color
+        .map(|(r, g, b)| format!("#{r:02x}{g:02x}{b:02x}"))
+        .unwrap_or_default()
+}
+
+fn history_event_csv_line(event: &HistoryEvent) -> String {
+    format!(
+        "{},{},{},{},{},{},{},{},{},{},{}\n",
+        event.stream_seq,
+        event.timestamp,
+        event.acquired_at.as_deref().unwrap_or_default(),
+        csv_field(&event.territory),
+        csv_field(&event.guild_uuid),
+        csv_field(&event.guild_name),
+        csv_field(&event.guild_prefix),
+        csv_color(event.guild_color),
+        csv_field(event.prev_guild_name.as_deref().unwrap_or_default()),
+        csv_field(event.prev_guild_prefix.as_deref().unwrap_or_default()),
+        csv_color(event.prev_guild_color),
+    )
+}
+
+fn encode_export_event(format: ExportFormat, event: &HistoryEvent) -> Option<Bytes> {
+    match format {
+        ExportFormat::Csv => Some(Bytes::from(history_event_csv_line(event))),
+        ExportFormat::Ndjson => {
+            let mut line = serde_json::to_vec(event).ok()?;
+            line.push(b'\n');
+            Some(Bytes::from(line))
+        }
+    }
+}
+
+/// `GET /api/history/export?from={t}&to={t}&format=csv|ndjson&guild=&territory=` — Stream every
+/// matching event from a window of at most `EXPORT_MAX_WINDOW_DAYS`, fetched in short pages so no
+/// pool connection is held while the client reads. Windows matching more than
+/// `max_history_export_rows` events are rejected with `413` rather than cut short.
+pub async fn history_export(
+    State(state): State<AppState>,
+    Query(query): Query<ExportQuery>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let pool = state
+        .db
+        .as_ref()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?
+        .clone();
+    let (from, to) = parse_time_window(&query.from, &query.to)?;
+    if from > to || to - from > chrono::Duration::days(EXPORT_MAX_WINDOW_DAYS) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let guild = optional_filter(query.guild.as_deref(), MAX_GUILD_NAME_LEN)?;
+    let territory = optional_filter(query.territory.as_deref(), MAX_TERRITORY_NAME_LEN)?;
+    let format = query.format;
+    let max_rows = state.max_history_export_rows;
+    let (matching,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM ( \
+             SELECT 1 FROM territory_events \
+             WHERE recorded_at > $1 AND recorded_at <= $2 \
+               AND ($3::text IS NULL OR guild_name = $3 OR prev_guild_name = $3) \
+               AND ($4::text IS NULL OR territory = $4) \
+             LIMIT $5 \
+         ) AS capped",
+    )
+    .bind(from)
+    .bind(to)
+    .bind(guild.as_deref())
+    .bind(territory.as_deref())
+    .bind(max_rows.saturating_add(1))
+    .fetch_one(&pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if matching > max_rows {
+        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+    let (fallback_colors, fallback_colors_normalized) =
+        merged_fallback_colors(&state, &pool).await?;
+
+    let body_stream = async_stream::stream! {
+        if format == ExportFormat::Csv {
+            yield Ok::<Bytes, std::io::Error>(Bytes::from_static(EXPORT_CSV_HEADER.as_bytes()));
+        }
+        // Page with short keyset queries so a slow reader never pins a pool connection.
+        // The count above bounds the work; rows recorded since are capped the same way.
+        let mut after_seq = 0_i64;
+        let mut sent = 0_i64;
+        'pages: while sent < max_rows {
+            let page = sqlx::query_as::<_, HistoryEventRow>(
+                "SELECT stream_seq, recorded_at, acquired_at, territory, guild_uuid, guild_name, \
+                        guild_prefix, guild_color_r, guild_color_g, guild_color_b, \
+                        prev_guild_name, prev_guild_prefix, \
+                        prev_guild_color_r, prev_guild_color_g, prev_guild_color_b \
+                 FROM territory_events \
+                 WHERE recorded_at > $1 AND recorded_at <= $2 \
+                   AND ($3::text IS NULL OR guild_name = $3 OR prev_guild_name = $3) \
+                   AND ($4::text IS NULL OR territory = $4) \
+                   AND stream_seq > $5 \
+                 ORDER BY stream_seq ASC \
+                 LIMIT $6",
+            )
+            .bind(from)
+            .bind(to)
+            .bind(guild.as_deref())
+            .bind(territory.as_deref())
+            .bind(after_seq)
+            .bind(EXPORT_PAGE_SIZE.min(max_rows - sent))
+            .fetch_all(&pool)
+            .await;
+
+            let rows = match page {
+                Ok(rows) => rows,
+                Err(e) => {
+                    warn!("history export aborted: {e}");
+                    yield Err(std::io::Error::other(e.to_string()));
+                    break;
+                }
+            };
+            let exhausted = (rows.len() as i64) < EXPORT_PAGE_SIZE;
+            for row in rows {
+                after_seq = row.0;
+                sent += 1;
+                let encoded = history_event_from_row(
+                    row,
+                    &fallback_colors,
+                    &fallback_colors_normalized,
+                )
+                .and_then(|event| encode_export_event(format, &event));
+                if let Some(bytes) = encoded {
+                    yield Ok(bytes);
+                }
+            }
+            if exhausted {
+                break 'pages;
+            }
+        }
+    };
+
+    let (content_type, extension) = match format {
+        ExportFormat::Csv => ("text/csv; charset=utf-8", "csv"),
+        ExportFormat::Ndjson => ("application/x-ndjson", "ndjson"),
+    };
+    let disposition = format!(
+        "attachment; filename=\"territory-events-{}-{}.{extension}\"",
+        from.format("%Y%m%dT%H%M%SZ"),
+        to.format("%Y%m%dT%H%M%SZ")
+    );
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&disposition).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+    );
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("public, max-age=60"),
+    );
+
+    Ok((headers, Body::from_stream(body_stream)))
+}
+
+fn optional_filter(raw: Option<&str>, max_len: usize) -> Result<Option<String>, StatusCode> {
+    match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) if value.len() > max_len => Err(StatusCode::BAD_REQUEST),
+        Some(value) => Ok(Some(value.to_string())),
+        None => Ok(None),
+    }
+}
+
+/// `GET /api/history/events?from={t}&to={t}&limit={n}&after_seq={seq}` — Paginated event list.
+pub async fn history_events(
+    State(state): State<AppState>,
+    Query(query): Query<EventsQuery>,
 ) -> Result<impl IntoResponse, StatusCode> {
     let pool = state.db.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
 
@@ -596,54 +1556,12 @@ pub async fn history_events(
     };
 
     let has_more = rows.len() as i64 > limit;
-    let mut events = Vec::with_capacity(limit as usize);
-    for (
-        stream_seq,
-        recorded_at,
-        acquired_at,
-        territory,
-        guild_uuid,
-        guild_name,
-        guild_prefix,
-        guild_color_r,
-        guild_color_g,
-        guild_color_b,
-        prev_guild_name,
-        prev_guild_prefix,
-        prev_guild_color_r,
-        prev_guild_color_g,
-        prev_guild_color_b,
-    ) in rows.into_iter().take(limit as usize)
-    {
-        let guild_color = with_fallback_color(
-            parse_rgb_triplet(guild_color_r, guild_color_g, guild_color_b),
-            &guild_name,
-            &fallback_colors,
-            &fallback_colors_normalized,
-        );
-        let prev_guild_color = prev_guild_name.as_deref().and_then(|name| {
-            with_fallback_color(
-                parse_rgb_triplet(prev_guild_color_r, prev_guild_color_g, prev_guild_color_b),
-                name,
-                &fallback_colors,
-                &fallback_colors_normalized,
-            )
-        });
-
-        events.push(HistoryEvent {
-            stream_seq: u64::try_from(stream_seq).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
-            timestamp: recorded_at.to_rfc3339(),
-            acquired_at: Some(acquired_at.to_rfc3339()),
-            territory,
-            guild_uuid,
-            guild_name,
-            guild_prefix,
-            guild_color,
-            prev_guild_name,
-            prev_guild_prefix,
-            prev_guild_color,
-        });
-    }
+    let events = rows
+        .into_iter()
+        .take(limit as usize)
+        .map(|row| history_event_from_row(row, &fallback_colors, &fallback_colors_normalized))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
 
     let mut headers = HeaderMap::new();
     headers.insert(
@@ -754,131 +1672,506 @@ pub async fn history_heat_meta(
     };
 
     let mut headers = HeaderMap::new();
-    headers.insert(
-        header::CACHE_CONTROL,
-        HeaderValue::from_static("public, max-age=60"),
-    );
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("public, max-age=60"),
+    );
+
+    Ok((headers, Json(meta)))
+}
+
+/// Resolve the season / all-time aggregation window shared by heat and rivalry views.
+async fn resolve_heat_window(
+    pool: &sqlx::PgPool,
+    source: HeatQuerySource,
+    season_id: Option<i32>,
+    at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Result<HeatWindow, StatusCode> {
+    let season_windows = load_season_windows(pool).await?;
+    let latest_season_id = season_windows.first().map(|window| window.season_id);
+
+    let window = match source {
+        HeatQuerySource::AllTime => {
+            let upper = at.map(|value| value.min(now)).unwrap_or(now);
+            let earliest = load_owner_change_earliest(pool).await?;
+            HeatWindow {
+                source: HistoryHeatSource::AllTime,
+                season_id: None,
+                from: earliest.map(|value| value.min(upper)).unwrap_or(upper),
+                to: upper,
+                fallback_applied: false, // fallback is season-source only
+            }
+        }
+        HeatQuerySource::Season => {
+            if let Some(latest_id) = latest_season_id {
+                let selected_id = season_id.unwrap_or(latest_id);
+                let selected = season_windows
+                    .iter()
+                    .find(|window| window.season_id == selected_id)
+                    .ok_or(StatusCode::BAD_REQUEST)?;
+                let upper = if selected.season_id == latest_id {
+                    now
+                } else {
+                    selected.end
+                };
+                let to = at.map(|value| value.min(upper)).unwrap_or(upper);
+                HeatWindow {
+                    source: HistoryHeatSource::Season,
+                    season_id: Some(selected.season_id),
+                    from: selected.start.min(to),
+                    to,
+                    fallback_applied: false,
+                }
+            } else {
+                let upper = at.map(|value| value.min(now)).unwrap_or(now);
+                let fallback_start = now - chrono::Duration::days(HEAT_SEASON_FALLBACK_DAYS);
+                HeatWindow {
+                    source: HistoryHeatSource::Season,
+                    season_id: None,
+                    from: fallback_start.min(upper),
+                    to: upper,
+                    fallback_applied: true,
+                }
+            }
+        }
+    };
+    Ok(window)
+}
+
+/// `GET /api/history/heat` — Territory takeover counts for the selected heat-map window.
+pub async fn history_heat(
+    State(state): State<AppState>,
+    Query(query): Query<HeatQuery>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let pool = state.db.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let now = Utc::now();
+    let at = parse_optional_timestamp(query.at.as_deref())?;
+    let HeatWindow {
+        source,
+        season_id,
+        from,
+        to,
+        fallback_applied,
+    } = resolve_heat_window(pool, query.source, query.season_id, at, now).await?;
+
+    let mut entries = if to >= from {
+        let rows: Vec<HeatCountRow> = sqlx::query_as(
+            "SELECT territory, COUNT(*)::BIGINT AS take_count \
+             FROM territory_events \
+             WHERE prev_guild_uuid IS NOT NULL \
+               AND recorded_at >= $1 \
+               AND recorded_at <= $2 \
+             GROUP BY territory",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        rows.into_iter()
+            .filter_map(|(territory, take_count)| {
+                let take_count = u64::try_from(take_count).ok()?;
+                Some(HistoryHeatEntry {
+                    territory,
+                    take_count,
+                })
+            })
+            .collect::<Vec<_>>()
+    } else {
+        Vec::new()
+    };
+    entries.sort_by(|a, b| {
+        b.take_count
+            .cmp(&a.take_count)
+            .then_with(|| a.territory.cmp(&b.territory))
+    });
+    let max_take_count = entries
+        .iter()
+        .map(|entry| entry.take_count)
+        .max()
+        .unwrap_or(0);
+
+    let response = HistoryHeat {
+        source,
+        season_id,
+        from: from.to_rfc3339(),
+        to: to.to_rfc3339(),
+        fallback_applied,
+        max_take_count,
+        entries,
+    };
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CACHE_CONTROL,
+        if at.is_some() {
+            HeaderValue::from_static("public, max-age=10")
+        } else {
+            HeaderValue::from_static("public, max-age=30")
+        },
+    );
+
+    Ok((headers, Json(response)))
+}
+
+/// `GET /api/history/rivalries` — Who took territories from whom over a heat-style season
+/// window, or an explicit `from`/`to` range.
+pub async fn history_rivalries(
+    State(state): State<AppState>,
+    Query(query): Query<RivalryQuery>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let explicit_from = parse_optional_timestamp(query.from.as_deref())?;
+    let explicit_to = parse_optional_timestamp(query.to.as_deref())?;
+    let at = parse_optional_timestamp(query.at.as_deref())?;
+    let limit = query.limit.clamp(1, MAX_RIVALRY_LIMIT);
+    let pool = state.db.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let now = Utc::now();
+
+    let window = match (explicit_from, explicit_to) {
+        (Some(from), Some(to)) => {
+            if from > to {
+                return Err(StatusCode::BAD_REQUEST);
+            }
+            HeatWindow {
+                source: HistoryHeatSource::Season,
+                season_id: None,
+                from,
+                to: to.min(now),
+                fallback_applied: false,
+            }
+        }
+        (None, None) => resolve_heat_window(pool, query.source, query.season_id, at, now).await?,
+        _ => return Err(StatusCode::BAD_REQUEST),
+    };
+    let explicit_window = explicit_from.is_some();
+
+    let rows: Vec<RivalryRow> = if window.to >= window.from {
+        sqlx::query_as(
+            "SELECT guild_name, MAX(guild_prefix), prev_guild_name, MAX(prev_guild_prefix), \
+                    COUNT(*)::BIGINT AS take_count, \
+                    ARRAY_AGG(DISTINCT territory ORDER BY territory)::TEXT[], \
+                    SUM(COUNT(*)) OVER ()::BIGINT, \
+                    COUNT(*) OVER ()::BIGINT \
+             FROM territory_events \
+             WHERE prev_guild_uuid IS NOT NULL \
+               AND prev_guild_name IS NOT NULL \
+               AND guild_name <> prev_guild_name \
+               AND recorded_at >= $1 \
+               AND recorded_at <= $2 \
+             GROUP BY guild_name, prev_guild_name \
+             ORDER BY take_count DESC, guild_name ASC, prev_guild_name ASC \
+             LIMIT $3",
+        )
+        .bind(window.from)
+        .bind(window.to)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    } else {
+        Vec::new()
+    };
+
+    let (total_takes, edge_count) = rows
+        .first()
+        .map(|row| (u64::try_from(row.6).unwrap_or(0), row.7))
+        .unwrap_or((0, 0));
+    let has_more = edge_count > rows.len() as i64;
+    let entries = rows
+        .into_iter()
+        .map(
+            |(
+                attacker_name,
+                attacker_prefix,
+                defender_name,
+                defender_prefix,
+                take_count,
+                territories,
+                _,
+                _,
+            )| HistoryRivalryEntry {
+                attacker_name,
+                attacker_prefix,
+                defender_name,
+                defender_prefix: defender_prefix.unwrap_or_default(),
+                take_count: u64::try_from(take_count).unwrap_or(0),
+                territories,
+            },
+        )
+        .collect();
+
+    let response = HistoryRivalries {
+        source: (!explicit_window).then_some(window.source),
+        season_id: window.season_id,
+        from: window.from.to_rfc3339(),
+        to: window.to.to_rfc3339(),
+        fallback_applied: window.fallback_applied,
+        total_takes,
+        has_more,
+        entries,
+    };
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CACHE_CONTROL,
+        if at.is_some() || explicit_window {
+            HeaderValue::from_static("public, max-age=10")
+        } else {
+            HeaderValue::from_static("public, max-age=30")
+        },
+    );
+
+    Ok((headers, Json(response)))
+}
+
+/// `GET /api/history/territory/{name}?from={t}&to={t}` — Ownership intervals for one territory.
+pub async fn history_territory(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Query(query): Query<TerritoryHistoryQuery>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let territory = name.trim().to_string();
+    if territory.is_empty() || territory.len() > MAX_TERRITORY_NAME_LEN {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let now = Utc::now();
+    let to = parse_optional_timestamp(query.to.as_deref())?.unwrap_or(now);
+    let from = parse_optional_timestamp(query.from.as_deref())?
+        .unwrap_or_else(|| to - chrono::Duration::days(TERRITORY_HISTORY_DEFAULT_DAYS));
+    if from > to {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let pool = state.db.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let max_replay_events = state.max_history_replay_events;
+    let (fallback_colors, fallback_colors_normalized) =
+        merged_fallback_colors(&state, pool).await?;
+
+    let snapshot_fut = async {
+        sqlx::query_as::<_, (DateTime<Utc>, Option<serde_json::Value>)>(
+            "SELECT created_at, ownership -> $2 FROM territory_snapshots \
+             WHERE created_at <= $1 ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(from)
+        .bind(&territory)
+        .fetch_optional(pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+    };
+    let prior_event_fut = async {
+        sqlx::query_as::<_, TerritoryOwnerRow>(
+            "SELECT recorded_at, acquired_at, guild_uuid, guild_name, guild_prefix, \
+                    guild_color_r, guild_color_g, guild_color_b \
+             FROM territory_events \
+             WHERE territory = $1 AND recorded_at <= $2 \
+             ORDER BY stream_seq DESC \
+             LIMIT 1",
+        )
+        .bind(&territory)
+        .bind(from)
+        .fetch_optional(pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+    };
+    let events_fut = async {
+        sqlx::query_as::<_, TerritoryOwnerRow>(
+            "SELECT recorded_at, acquired_at, guild_uuid, guild_name, guild_prefix, \
+                    guild_color_r, guild_color_g, guild_color_b \
+             FROM territory_events \
+             WHERE territory = $1 AND recorded_at > $2 AND recorded_at <= $3 \
+             ORDER BY stream_seq ASC \
+             LIMIT $4",
+        )
+        .bind(&territory)
+        .bind(from)
+        .bind(to)
+        .bind(max_replay_events.saturating_add(1))
+        .fetch_all(pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+    };
+    let (snapshot_row, prior_event, event_rows) =
+        tokio::try_join!(snapshot_fut, prior_event_fut, events_fut)?;
+
+    if event_rows.len() as i64 > max_replay_events {
+        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    // Owner at `from`: whichever of the last snapshot and the last event is more recent.
+    let snapshot_is_newer = match (&snapshot_row, &prior_event) {
+        (Some((created_at, _)), Some(event)) => *created_at > event.0,
+        (Some(_), None) => true,
+        _ => false,
+    };
+    let initial = if snapshot_is_newer {
+        snapshot_row
+            .and_then(|(_, record)| record)
+            .and_then(|record| serde_json::from_value::<OwnershipRecord>(record).ok())
+            .and_then(|record| {
+                let start = record.acquired_at.parse::<DateTime<Utc>>().ok()?;
+                let guild_color = with_fallback_color(
+                    record.guild_color,
+                    &record.guild_name,
+                    &fallback_colors,
+                    &fallback_colors_normalized,
+                );
+                Some(OwnershipSpanStart {
+                    guild_uuid: record.guild_uuid,
+                    guild_name: record.guild_name,
+                    guild_prefix: record.guild_prefix,
+                    guild_color,
+                    start,
+                })
+            })
+    } else {
+        prior_event.map(|row| {
+            territory_owner_row_to_span(row, &fallback_colors, &fallback_colors_normalized)
+        })
+    };
+    let changes = event_rows
+        .into_iter()
+        .map(|row| territory_owner_row_to_span(row, &fallback_colors, &fallback_colors_normalized))
+        .collect();
+
+    let intervals = build_ownership_intervals(initial, changes, to.min(now));
+    if intervals.is_empty()
+        && !state
+            .live_snapshot
+            .read()
+            .await
+            .territories
+            .contains_key(&territory)
+    {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let age_secs = (now - to).num_seconds();
+    let max_age = if age_secs > 3600 { 86400 } else { 60 };
+    let mut headers = HeaderMap::new();
+    let cache_control = HeaderValue::from_str(&format!("public, max-age={max_age}"))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    headers.insert(header::CACHE_CONTROL, cache_control);
 
-    Ok((headers, Json(meta)))
+    Ok((
+        headers,
+        Json(TerritoryOwnershipHistory {
+            territory,
+            from: from.to_rfc3339(),
+            to: to.to_rfc3339(),
+            intervals,
+        }),
+    ))
 }
 
-/// `GET /api/history/heat` — Territory takeover counts for the selected heat-map window.
-pub async fn history_heat(
+/// `GET /api/history/guild/{name}?from={t}&to={t}&bucket=hour|day` — Territory count series
+/// and gains/losses ledger for one guild.
+pub async fn history_guild(
     State(state): State<AppState>,
-    Query(query): Query<HeatQuery>,
+    Path(name): Path<String>,
+    Query(query): Query<GuildHistoryQuery>,
 ) -> Result<impl IntoResponse, StatusCode> {
-    let pool = state.db.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let guild_name = name.trim().to_string();
+    if guild_name.is_empty() || guild_name.len() > MAX_GUILD_NAME_LEN {
+        return Err(StatusCode::BAD_REQUEST);
+    }
     let now = Utc::now();
-    let at = parse_optional_timestamp(query.at.as_deref())?;
-    let season_windows = load_season_windows(pool).await?;
-    let latest_season_id = season_windows.first().map(|window| window.season_id);
-
-    let (source, season_id, from, to, fallback_applied) = match query.source {
-        HeatQuerySource::AllTime => {
-            let upper = at.map(|value| value.min(now)).unwrap_or(now);
-            let earliest = load_owner_change_earliest(pool).await?;
-            let from = earliest.map(|value| value.min(upper)).unwrap_or(upper);
-            (
-                HistoryHeatSource::AllTime,
-                None,
-                from,
-                upper,
-                false, // fallback is season-source only
-            )
-        }
-        HeatQuerySource::Season => {
-            if let Some(latest_id) = latest_season_id {
-                let selected_id = query.season_id.unwrap_or(latest_id);
-                let selected = season_windows
-                    .iter()
-                    .find(|window| window.season_id == selected_id)
-                    .ok_or(StatusCode::BAD_REQUEST)?;
-                let upper = if selected.season_id == latest_id {
-                    now
-                } else {
-                    selected.end
-                };
-                let to = at.map(|value| value.min(upper)).unwrap_or(upper);
-                (
-                    HistoryHeatSource::Season,
-                    Some(selected.season_id),
-                    selected.start.min(to),
-                    to,
-                    false,
-                )
-            } else {
-                let upper = at.map(|value| value.min(now)).unwrap_or(now);
-                let fallback_start = now - chrono::Duration::days(HEAT_SEASON_FALLBACK_DAYS);
-                (
-                    HistoryHeatSource::Season,
-                    None,
-                    fallback_start.min(upper),
-                    upper,
-                    true,
-                )
-            }
-        }
-    };
+    let to = parse_optional_timestamp(query.to.as_deref())?
+        .unwrap_or(now)
+        .min(now);
+    let from = parse_optional_timestamp(query.from.as_deref())?
+        .unwrap_or_else(|| to - chrono::Duration::days(GUILD_HISTORY_DEFAULT_DAYS));
+    if from > to {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let bucket = query.bucket.unwrap_or(
+        if to - from <= chrono::Duration::days(GUILD_HISTORY_HOURLY_MAX_DAYS) {
+            HistoryBucket::Hour
+        } else {
+            HistoryBucket::Day
+        },
+    );
+    let bucket_count = (floor_to_bucket(to, bucket) - floor_to_bucket(from, bucket)).num_seconds()
+        / bucket.seconds()
+        + 1;
+    if bucket_count > MAX_GUILD_HISTORY_BUCKETS {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let pool = state.db.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let max_replay_events = state.max_history_replay_events;
 
-    let mut entries = if to >= from {
-        let rows: Vec<HeatCountRow> = sqlx::query_as(
-            "SELECT territory, COUNT(*)::BIGINT AS take_count \
+    let holdings_fut = load_guild_holdings_at(pool, &guild_name, from);
+    let changes_fut = async {
+        sqlx::query_as::<_, GuildChangeRow>(
+            "SELECT stream_seq, recorded_at, territory, guild_name, guild_prefix, \
+                    prev_guild_name, prev_guild_prefix \
              FROM territory_events \
-             WHERE prev_guild_uuid IS NOT NULL \
-               AND recorded_at >= $1 \
-               AND recorded_at <= $2 \
-             GROUP BY territory",
+             WHERE recorded_at > $1 AND recorded_at <= $2 \
+               AND (guild_name = $3 OR prev_guild_name = $3) \
+             ORDER BY stream_seq ASC \
+             LIMIT $4",
         )
         .bind(from)
         .bind(to)
+        .bind(&guild_name)
+        .bind(max_replay_events.saturating_add(1))
         .fetch_all(pool)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-        rows.into_iter()
-            .filter_map(|(territory, take_count)| {
-                let take_count = u64::try_from(take_count).ok()?;
-                Some(HistoryHeatEntry {
-                    territory,
-                    take_count,
-                })
-            })
-            .collect::<Vec<_>>()
-    } else {
-        Vec::new()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
     };
-    entries.sort_by(|a, b| {
-        b.take_count
-            .cmp(&a.take_count)
-            .then_with(|| a.territory.cmp(&b.territory))
-    });
-    let max_take_count = entries
-        .iter()
-        .map(|entry| entry.take_count)
-        .max()
-        .unwrap_or(0);
+    let ((mut held, mut guild_prefix), change_rows) = tokio::try_join!(holdings_fut, changes_fut)?;
+    if change_rows.len() as i64 > max_replay_events {
+        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+    }
 
-    let response = HistoryHeat {
-        source,
-        season_id,
-        from: from.to_rfc3339(),
-        to: to.to_rfc3339(),
-        fallback_applied,
-        max_take_count,
-        entries,
-    };
+    let start_count = u32::try_from(held.len()).unwrap_or(u32::MAX);
+    let changes: Vec<GuildOwnershipChange> = change_rows
+        .into_iter()
+        .map(GuildOwnershipChange::from)
+        .collect();
+    if guild_prefix.is_none() {
+        guild_prefix = changes
+            .iter()
+            .find(|change| change.guild_name == guild_name)
+            .map(|change| change.guild_prefix.clone());
+    }
+    let (ledger, counts) = build_guild_ledger(&guild_name, &mut held, changes);
+
+    if start_count == 0 && ledger.is_empty() {
+        let snapshot = state.live_snapshot.read().await;
+        let live_prefix = snapshot
+            .territories
+            .values()
+            .find(|territory| territory.guild.name == guild_name)
+            .map(|territory| territory.guild.prefix.clone());
+        if live_prefix.is_none() {
+            return Err(StatusCode::NOT_FOUND);
+        }
+        guild_prefix = guild_prefix.or(live_prefix);
+    }
+
+    let series = build_guild_count_series(start_count, &counts, from, to, bucket);
 
+    let age_secs = (now - to).num_seconds();
+    let max_age = if age_secs > 3600 { 86400 } else { 60 };
     let mut headers = HeaderMap::new();
-    headers.insert(
-        header::CACHE_CONTROL,
-        if at.is_some() {
-            HeaderValue::from_static("public, max-age=10")
-        } else {
-            HeaderValue::from_static("public, max-age=30")
-        },
-    );
+    let cache_control = HeaderValue::from_str(&format!("public, max-age={max_age}"))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    headers.insert(header::CACHE_CONTROL, cache_control);
 
-    Ok((headers, Json(response)))
+    Ok((
+        headers,
+        Json(GuildHistory {
+            guild_name,
+            guild_prefix,
+            from: from.to_rfc3339(),
+            to: to.to_rfc3339(),
+            bucket,
+            start_count,
+            series,
+            ledger,
+        }),
+    ))
 }
 
 #[cfg(test)]
@@ -888,12 +2181,25 @@ mod tests {
     use chrono::{DateTime, Utc};
     use reqwest::StatusCode;
     use sequoia_shared::history::{
-        HistoryEvents, HistoryHeat, HistoryHeatMeta, HistoryHeatSource, HistorySnapshot,
-        HistorySrSamples,
+        HistoryEvent, HistoryEvents, HistoryHeat, HistoryHeatMeta, HistoryHeatSource,
+        HistorySnapshot, HistorySrSamples,
     };
     use sqlx::postgres::PgPoolOptions;
 
-    use super::HEAT_SEASON_FALLBACK_DAYS;
+    use std::collections::HashSet;
+
+    use sequoia_shared::history::{GuildLedgerDirection, HistoryBucket};
+
+    use std::collections::HashMap;
+
+    use sequoia_shared::Region;
+    use sequoia_shared::history::OwnershipRecord;
+
+    use super::{
+        EXPORT_CSV_HEADER, GuildOwnershipChange, HEAT_SEASON_FALLBACK_DAYS, OwnershipSpanStart,
+        build_guild_count_series, build_guild_ledger, build_ownership_intervals, csv_field,
+        history_event_csv_line, load_timelapse_events, render_timelapse, timelapse_frame_times,
+    };
     use crate::config::territory_history_retention_days;
     use crate::state::AppState;
 
@@ -971,10 +2277,273 @@ mod tests {
             .status();
         assert_eq!(heat_invalid_at_status, StatusCode::BAD_REQUEST);
 
+        let territory_inverted_window_status = client
+            .get(format!(
+                "{base_url}/api/history/territory/Detlas?from=2026-02-02T00:00:00Z&to=2026-02-01T00:00:00Z"
+            ))
+            .send()
+            .await
+            .expect("history territory inverted window request")
+            .status();
+        assert_eq!(territory_inverted_window_status, StatusCode::BAD_REQUEST);
+
+        let rivalries_half_window_status = client
+            .get(format!(
+                "{base_url}/api/history/rivalries?from=2026-02-01T00:00:00Z"
+            ))
+            .send()
+            .await
+            .expect("history rivalries half window request")
+            .status();
+        assert_eq!(rivalries_half_window_status, StatusCode::BAD_REQUEST);
+
+        let rivalries_inverted_window_status = client
+            .get(format!(
+                "{base_url}/api/history/rivalries?from=2026-02-02T00:00:00Z&to=2026-02-01T00:00:00Z"
+            ))
+            .send()
+            .await
+            .expect("history rivalries inverted window request")
+            .status();
+        assert_eq!(rivalries_inverted_window_status, StatusCode::BAD_REQUEST);
+
+        let export_invalid_format_status = client
+            .get(format!(
+                "{base_url}/api/history/export?from=2026-02-01T00:00:00Z&to=2026-02-02T00:00:00Z&format=xlsx"
+            ))
+            .send()
+            .await
+            .expect("history export invalid format request")
+            .status();
+        assert_eq!(export_invalid_format_status, StatusCode::BAD_REQUEST);
+
+        let guild_invalid_bucket_status = client
+            .get(format!(
+                "{base_url}/api/history/guild/Sequoia?bucket=minute"
+            ))
+            .send()
+            .await
+            .expect("history guild invalid bucket request")
+            .status();
+        assert_eq!(guild_invalid_bucket_status, StatusCode::BAD_REQUEST);
+
+        let guild_too_many_buckets_status = client
+            .get(format!(
+                "{base_url}/api/history/guild/Sequoia?from=2025-01-01T00:00:00Z&to=2026-01-01T00:00:00Z&bucket=hour"
+            ))
+            .send()
+            .await
+            .expect("history guild oversized window request")
+            .status();
+        assert_eq!(guild_too_many_buckets_status, StatusCode::BAD_REQUEST);
+
         server_handle.abort();
         let _ = server_handle.await;
     }
 
+    fn span(guild: &str, start: &str) -> OwnershipSpanStart {
+        OwnershipSpanStart {
+            guild_uuid: format!("{guild}-uuid"),
+            guild_name: guild.to_string(),
+            guild_prefix: guild[..3].to_string(),
+            guild_color: None,
+            start: start.parse::<DateTime<Utc>>().expect("valid timestamp"),
+        }
+    }
+
+    #[test]
+    fn ownership_intervals_merge_repeat_owners_and_close_on_change() {
+        let window_end = "2026-01-10T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let intervals = build_ownership_intervals(
+            Some(span("Aequitas", "2025-12-25T00:00:00Z")),
+            vec![
+                span("Aequitas", "2026-01-01T00:00:00Z"),
+                span("Paladins", "2026-01-02T00:00:00Z"),
+                // Acquired timestamp older than the previous change is clamped forward.
+                span("Sequoia", "2026-01-01T12:00:00Z"),
+            ],
+            window_end,
+        );
+
+        assert_eq!(intervals.len(), 3);
+        assert_eq!(intervals[0].guild_name, "Aequitas");
+        assert_eq!(
+            intervals[0].end.as_deref(),
+            Some("2026-01-02T00:00:00+00:00")
+        );
+        assert_eq!(intervals[0].duration_secs, 8 * 86_400);
+        assert_eq!(intervals[1].guild_name, "Paladins");
+        assert_eq!(intervals[1].duration_secs, 0);
+        assert_eq!(intervals[2].guild_name, "Sequoia");
+        assert_eq!(intervals[2].start, "2026-01-02T00:00:00+00:00");
+        assert_eq!(intervals[2].end, None);
+        assert_eq!(intervals[2].duration_secs, 8 * 86_400);
+    }
+
+    #[test]
+    fn ownership_intervals_without_initial_owner_start_at_first_change() {
+        let window_end = "2026-01-03T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let intervals = build_ownership_intervals(
+            None,
+            vec![span("Paladins", "2026-01-02T00:00:00Z")],
+            window_end,
+        );
+        assert_eq!(intervals.len(), 1);
+        assert_eq!(intervals[0].duration_secs, 86_400);
+        assert!(build_ownership_intervals(None, Vec::new(), window_end).is_empty());
+    }
+
+    #[test]
+    fn export_csv_lines_quote_fields_and_match_header_columns() {
+        let event = HistoryEvent {
+            stream_seq: 42,
+            timestamp: "2026-01-01T00:00:00+00:00".to_string(),
+            acquired_at: Some("2025-12-31T23:59:58+00:00".to_string()),
+            territory: "Corkus City, South".to_string(),
+            guild_uuid: "uuid-1".to_string(),
+            guild_name: "The \"Quoted\" Guild".to_string(),
+            guild_prefix: "TQG".to_string(),
+            guild_color: Some((255, 16, 0)),
+            prev_guild_name: None,
+            prev_guild_prefix: None,
+            prev_guild_color: None,
+        };
+
+        let line = history_event_csv_line(&event);
+        assert_eq!(
+            line,
+            "42,2026-01-01T00:00:00+00:00,2025-12-31T23:59:58+00:00,\"Corkus City, South\",uuid-1,\
+             \"The \"\"Quoted\"\" Guild\",TQG,#ff1000,,,\n"
+        );
+        assert_eq!(EXPORT_CSV_HEADER.matches(',').count(), 10);
+    }
+
+    #[test]
+    fn csv_field_neutralizes_formula_cells() {
+        assert_eq!(csv_field("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+        assert_eq!(csv_field("+1"), "'+1");
+        assert_eq!(csv_field("-2"), "'-2");
+        assert_eq!(csv_field("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(csv_field("Sequoia"), "Sequoia");
+    }
+
+    fn change(
+        seq: i64,
+        at: &str,
+        territory: &str,
+        guild: &str,
+        prev: Option<&str>,
+    ) -> GuildOwnershipChange {
+        GuildOwnershipChange {
+            stream_seq: seq,
+            recorded_at: at.parse::<DateTime<Utc>>().expect("valid timestamp"),
+            territory: territory.to_string(),
+            guild_name: guild.to_string(),
+            guild_prefix: guild[..3].to_string(),
+            prev_guild_name: prev.map(str::to_string),
+            prev_guild_prefix: prev.map(|name| name[..3].to_string()),
+        }
+    }
+
+    #[test]
+    fn guild_ledger_and_series_track_gains_and_losses() {
+        let mut held: HashSet<String> = ["Detlas".to_string()].into_iter().collect();
+        let (ledger, counts) = build_guild_ledger(
+            "Sequoia",
+            &mut held,
+            vec![
+                change(
+                    1,
+                    "2026-01-01T00:10:00Z",
+                    "Ragni",
+                    "Sequoia",
+                    Some("Paladins"),
+                ),
+                change(
+                    2,
+                    "2026-01-01T00:40:00Z",
+                    "Detlas",
+                    "Aequitas",
+                    Some("Sequoia"),
+                ),
+                // Re-report of an owner we already hold is not a gain.
+                change(
+                    3,
+                    "2026-01-01T01:20:00Z",
+                    "Ragni",
+                    "Sequoia",
+                    Some("Sequoia"),
+                ),
+                change(4, "2026-01-01T02:05:00Z", "Almuj", "Sequoia", None),
+            ],
+        );
+
+        assert_eq!(ledger.len(), 3);
+        assert_eq!(ledger[0].direction, GuildLedgerDirection::Gained);
+        assert_eq!(ledger[0].counterpart_name.as_deref(), Some("Paladins"));
+        assert_eq!(ledger[1].direction, GuildLedgerDirection::Lost);
+        assert_eq!(ledger[1].counterpart_name.as_deref(), Some("Aequitas"));
+        assert_eq!(ledger[2].stream_seq, 4);
+        assert_eq!(ledger[2].counterpart_name, None);
+
+        let from = "2026-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let to = "2026-01-01T02:30:00Z".parse::<DateTime<Utc>>().unwrap();
+        let series = build_guild_count_series(1, &counts, from, to, HistoryBucket::Hour);
+        assert_eq!(series.len(), 3);
+        assert_eq!(
+            (
+                series[0].territory_count,
+                series[0].min_count,
+                series[0].max_count
+            ),
+            (1, 1, 2)
+        );
+        assert_eq!(series[1].territory_count, 1);
+        assert_eq!(series[2].territory_count, 2);
+        assert_eq!(series[2].timestamp, "2026-01-01T02:00:00+00:00");
+    }
+
+    #[test]
+    fn timelapse_replays_events_between_evenly_spaced_frames() {
+        let from = "2026-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let to = "2026-01-01T01:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let frame_times = timelapse_frame_times(from, to, 5);
+        assert_eq!(frame_times.len(), 5);
+        assert_eq!(frame_times[0], from);
+        assert_eq!(frame_times[2].to_rfc3339(), "2026-01-01T00:30:00+00:00");
+        assert_eq!(frame_times[4], to);
+
+        let record = |guild: &str| OwnershipRecord {
+            guild_uuid: format!("{guild}-uuid"),
+            guild_name: guild.to_string(),
+            guild_prefix: guild[..3].to_string(),
+            guild_color: Some((200, 40, 40)),
+            acquired_at: from.to_rfc3339(),
+        };
+        let geometry = vec![(
+            "Ragni".to_string(),
+            Region {
+                start: [0, 0],
+                end: [100, 100],
+            },
+        )];
+        let ownership = HashMap::from([("Ragni".to_string(), record("Sequoia"))]);
+        let events = vec![(
+            "2026-01-01T00:20:00Z".parse::<DateTime<Utc>>().unwrap(),
+            "Ragni".to_string(),
+            record("Aequitas"),
+        )];
+        let png = render_timelapse(&geometry, ownership, events, &frame_times, 4);
+
+        let actl = png
+            .windows(4)
+            .position(|window| window == b"acTL")
+            .expect("animation control chunk");
+        let frames = u32::from_be_bytes(png[actl + 4..actl + 8].try_into().unwrap());
+        // Every frame caption differs, so none are merged.
+        assert_eq!(frames, 5);
+    }
+
     #[tokio::test]
     async fn history_events_paginates_with_after_seq() {
         let Ok(database_url) = std::env::var("DATABASE_URL") else {
@@ -1319,6 +2888,41 @@ mod tests {
         assert_eq!(season_leaderboard[0].season_rank, 1);
         assert_eq!(season_leaderboard[0].season_rating, 1200);
 
+        let export_url = |base_url: &str| {
+            let mut url = reqwest::Url::parse(&format!("{base_url}/api/history/export"))
+                .expect("history export url");
+            url.query_pairs_mut()
+                .append_pair("from", &from)
+                .append_pair("to", &to)
+                .append_pair("format", "csv");
+            url
+        };
+        let export = client
+            .get(export_url(&base_url))
+            .send()
+            .await
+            .expect("export request")
+            .error_for_status()
+            .expect("export status")
+            .text()
+            .await
+            .expect("export body");
+        assert_eq!(export.lines().count(), 3);
+
+        // A window matching more rows than the cap is refused instead of silently truncated.
+        let mut capped_state = AppState::new(Some(pool.clone()));
+        capped_state.max_history_export_rows = 1;
+        let (capped_addr, capped_handle) = spawn_test_server(capped_state).await;
+        let capped_status = client
+            .get(export_url(&format!("http://{capped_addr}")))
+            .send()
+            .await
+            .expect("capped export request")
+            .status();
+        assert_eq!(capped_status, StatusCode::PAYLOAD_TOO_LARGE);
+        capped_handle.abort();
+        let _ = capped_handle.await;
+
         sqlx::query("SELECT pg_advisory_unlock($1)")
             .bind(REAL_DB_TEST_LOCK)
             .execute(&mut *lock_conn)
@@ -1329,6 +2933,62 @@ mod tests {
         let _ = server_handle.await;
     }
 
+    #[tokio::test]
+    async fn timelapse_events_follow_recorded_time_not_stream_seq() {
+        let Ok(database_url) = std::env::var("DATABASE_URL") else {
+            eprintln!("Skipping real-Postgres timelapse ordering test: DATABASE_URL is not set");
+            return;
+        };
+
+        let pool = PgPoolOptions::new()
+            .max_connections(2)
+            .connect(&database_url)
+            .await
+            .expect("connect real postgres");
+        let mut lock_conn = pool.acquire().await.expect("acquire lock connection");
+        sqlx::query("SELECT pg_advisory_lock($1)")
+            .bind(REAL_DB_TEST_LOCK)
+            .execute(&mut *lock_conn)
+            .await
+            .expect("acquire history test db lock");
+        crate::db_migrations::run(&pool)
+            .await
+            .expect("run migrations");
+        sqlx::query("TRUNCATE TABLE territory_events RESTART IDENTITY")
+            .execute(&pool)
+            .await
+            .expect("truncate territory events");
+
+        // Poller and ingest reserve seq blocks independently, so a later seq can be recorded first.
+        let now = Utc::now();
+        for (seq, minutes_ago, guild) in [(1_i64, 1, "Later"), (2_i64, 2, "Earlier")] {
+            let recorded_at = now - chrono::TimeDelta::minutes(minutes_ago);
+            sqlx::query(
+                "INSERT INTO territory_events \
+                 (stream_seq, recorded_at, acquired_at, territory, guild_uuid, guild_name, guild_prefix) \
+                 VALUES ($1, $2, $2, 'Alpha', $3, $3, 'GLD')",
+            )
+            .bind(seq)
+            .bind(recorded_at)
+            .bind(guild)
+            .execute(&pool)
+            .await
+            .expect("insert territory event");
+        }
+
+        let rows = load_timelapse_events(&pool, now - chrono::TimeDelta::hours(1), now, 10)
+            .await
+            .expect("load timelapse events");
+        let guilds: Vec<&str> = rows.iter().map(|row| row.3.as_str()).collect();
+        assert_eq!(guilds, vec!["Earlier", "Later"]);
+
+        sqlx::query("SELECT pg_advisory_unlock($1)")
+            .bind(REAL_DB_TEST_LOCK)
+            .execute(&mut *lock_conn)
+            .await
+            .expect("release history test db lock");
+    }
+
     #[tokio::test]
     async fn history_at_includes_latest_scalar_sample_at_or_before_timestamp() {
         let Ok(database_url) = std::env::var("DATABASE_URL") else {
@@ -4,17 +4,19 @@ use std::sync::atomic::Ordering;
 
 use axum::Json;
 use axum::extract::State;
-use axum::http::{HeaderMap, HeaderValue, StatusCode};
-use axum::response::{IntoResponse, Response};
+use axum::http::{HeaderMap, StatusCode};
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use sequoia_shared::{
-    BASE_HOURLY_SR, CanonicalTerritoryBatch, CanonicalTerritoryUpdate, DataProvenance,
-    SeasonScalarCurrent, SeasonScalarSample, TerritoryChange, TerritoryEvent, TerritoryMap,
-    TerritoryRuntimeChange, TerritoryRuntimeData, weighted_units,
+    BASE_HOURLY_SR, CanonicalTerritoryBatch, CanonicalTerritoryUpdate, CanonicalWarBatch,
+    CanonicalWarReport, DataProvenance, SeasonScalarCurrent, SeasonScalarSample, TerritoryChange,
+    TerritoryEvent, TerritoryMap, TerritoryRuntimeChange, TerritoryRuntimeData, VisibilityClass,
+    WarEvent, weighted_units,
 };
 use tracing::{info, warn};
 
+use super::wars::{apply_live_war_event, prune_stale_live_wars, war_event_kind_label};
+
 use crate::state::{
     AppState, IngestTerritoryOverride, PreSerializedEvent, build_guild_color_lookup,
     lookup_guild_color, normalize_guild_color_key,
@@ -293,7 +295,7 @@ pub async fn ingest_territory(
     }
 
     for event in outgoing {
-        let _ = state.event_tx.send(event);
+        state.publish_event(event).await;
     }
 
     if rejected > 0 {
@@ -327,19 +329,92 @@ pub async fn heartbeat(
     })))
 }
 
-pub async fn get_live_wars() -> Response {
-    let mut response = Json(Vec::<serde_json::Value>::new()).into_response();
-    response
-        .headers_mut()
-        .insert("deprecation", HeaderValue::from_static("true"));
-    response.headers_mut().insert(
-        "warning",
-        HeaderValue::from_static("299 - \"deprecated endpoint; always returns empty array\""),
-    );
-    response
+pub async fn ingest_war(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(batch): Json<CanonicalWarBatch>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    ensure_internal_ingest_auth(&state, &headers)?;
+    if batch.events.len() > state.max_ingest_updates_per_request {
+        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    let now = Utc::now();
+    let mut rejected = 0_u64;
+    let mut accepted: Vec<CanonicalWarReport> = Vec::with_capacity(batch.events.len());
+    {
+        let snapshot = state.live_snapshot.read().await;
+        for mut report in batch.events {
+            if !snapshot.territories.contains_key(&report.event.territory) {
+                rejected += 1;
+                continue;
+            }
+            let observed_at = parse_optional_rfc3339(Some(report.event.observed_at.as_str()))
+                .or_else(|| {
+                    parse_optional_rfc3339(Some(report.event.provenance.observed_at.as_str()))
+                })
+                .map(|value| sanitize_override_observed_at(value, now))
+                .unwrap_or(now);
+            report.event.observed_at = observed_at.to_rfc3339();
+            report.event.provenance.confidence = report.event.provenance.confidence.clamp(0.0, 1.0);
+            if report.event.id.trim().is_empty() {
+                report.event.id = report.idempotency_key.clone().unwrap_or_else(|| {
+                    format!(
+                        "{}:{}:{}",
+                        report.event.territory,
+                        war_event_kind_label(report.event.kind),
+                        observed_at.timestamp_millis()
+                    )
+                });
+            }
+            accepted.push(report);
+        }
+    }
+
+    // Duplicate deliveries (same idempotency key) must not re-toggle the live board.
+    let fresh = match state.db.as_ref() {
+        Some(pool) => match persist_canonical_war_events(pool, &accepted).await {
+            Ok(fresh) => fresh,
+            Err(e) => {
+                warn!("failed to persist canonical war events: {e}");
+                accepted.into_iter().map(|report| report.event).collect()
+            }
+        },
+        None => accepted.into_iter().map(|report| report.event).collect(),
+    };
+
+    let mut transitions: Vec<WarEvent> = Vec::new();
+    {
+        let mut live = state.live_wars.write().await;
+        prune_stale_live_wars(&mut live, now);
+        for event in &fresh {
+            if apply_live_war_event(&mut live, event) {
+                transitions.push(event.clone());
+            }
+        }
+    }
+
+    let broadcast = transitions.len();
+    if !transitions.is_empty() {
+        let json = serialize_event(TerritoryEvent::War {
+            events: transitions,
+            timestamp: now.to_rfc3339(),
+        })?;
+        state.publish_event(PreSerializedEvent::War { json }).await;
+    }
+
+    Ok(Json(serde_json::json!({
+        "ok": true,
+        "applied": fresh.len(),
+        "rejected": rejected,
+        "broadcast": broadcast,
+    })))
 }
 
-fn ensure_internal_ingest_auth(state: &AppState, headers: &HeaderMap) -> Result<(), StatusCode> {
+pub(crate) fn ensure_internal_ingest_auth(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<(), StatusCode> {
     let Some(expected) = state.internal_ingest_token.as_deref() else {
         return Err(StatusCode::SERVICE_UNAVAILABLE);
     };
@@ -537,10 +612,7 @@ async fn persist_canonical_territory_updates(
             .filter(|value| !value.trim().is_empty())
             .unwrap_or_else(|| "unknown".to_string());
         let visibility = provenance
-            .map(|value| match value.visibility {
-                sequoia_shared::VisibilityClass::Public => "public",
-                sequoia_shared::VisibilityClass::GuildOptIn => "guild_opt_in",
-            })
+            .map(|value| visibility_label(value.visibility))
             .unwrap_or("public");
         let reporter_count = provenance
             .map(|value| i32::from(value.reporter_count))
@@ -571,6 +643,54 @@ async fn persist_canonical_territory_updates(
     Ok(())
 }
 
+/// Insert war events, returning only those not already stored under their idempotency key.
+async fn persist_canonical_war_events(
+    pool: &sqlx::PgPool,
+    reports: &[CanonicalWarReport],
+) -> Result<Vec<WarEvent>, String> {
+    let mut fresh = Vec::with_capacity(reports.len());
+    for report in reports {
+        let event = &report.event;
+        let payload = serde_json::to_value(event)
+            .map_err(|e| format!("serialize canonical war payload: {e}"))?;
+        let observed_at =
+            parse_optional_rfc3339(Some(event.observed_at.as_str())).unwrap_or_else(Utc::now);
+        let source = Some(event.provenance.source.clone())
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let result = sqlx::query(
+            "INSERT INTO canonical_war_events \
+             (territory, kind, observed_at, confidence, visibility, source, reporter_count, idempotency_key, payload) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9::jsonb) \
+             ON CONFLICT (idempotency_key) WHERE idempotency_key IS NOT NULL DO NOTHING",
+        )
+        .bind(&event.territory)
+        .bind(war_event_kind_label(event.kind))
+        .bind(observed_at)
+        .bind(f64::from(event.provenance.confidence))
+        .bind(visibility_label(event.provenance.visibility))
+        .bind(source)
+        .bind(i32::from(event.provenance.reporter_count))
+        .bind(report.idempotency_key.as_deref())
+        .bind(payload)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("insert canonical_war_events row: {e}"))?;
+        if result.rows_affected() > 0 {
+            fresh.push(event.clone());
+        }
+    }
+    Ok(fresh)
+}
+
+fn visibility_label(visibility: VisibilityClass) -> &'static str {
+    match visibility {
+        VisibilityClass::Public => "public",
+        VisibilityClass::GuildOptIn => "guild_opt_in",
+    }
+}
+
 async fn persist_authoritative_scalar_sample(
     state: &AppState,
     updates: &[CanonicalTerritoryUpdate],
@@ -732,13 +852,15 @@ mod tests {
     use axum::http::{HeaderMap, HeaderValue, StatusCode};
     use chrono::{DateTime, Duration, Utc};
     use sequoia_shared::{
-        CanonicalTerritoryBatch, CanonicalTerritoryUpdate, DataProvenance, GuildRef, LiveState,
-        Region, SeasonScalarSample, Territory, TerritoryRuntimeData, VisibilityClass,
+        CanonicalTerritoryBatch, CanonicalTerritoryUpdate, CanonicalWarBatch, CanonicalWarReport,
+        DataProvenance, GuildRef, LiveState, Region, SeasonScalarSample, Territory, TerritoryEvent,
+        TerritoryRuntimeData, VisibilityClass, WarEvent, WarEventKind,
     };
 
     use crate::routes::ingest::{
-        constant_time_eq, ingest_territory, is_duplicate_scalar_sample, runtime_has_claim_fields,
-        sanitize_override_observed_at, scalar_sample_quality, should_replace_ingest_override,
+        constant_time_eq, ingest_territory, ingest_war, is_duplicate_scalar_sample,
+        runtime_has_claim_fields, sanitize_override_observed_at, scalar_sample_quality,
+        should_replace_ingest_override,
     };
     use crate::state::{AppState, IngestTerritoryOverride, PreSerializedEvent};
 
@@ -912,6 +1034,97 @@ mod tests {
         assert!(matches!(result, Err(StatusCode::UNAUTHORIZED)));
     }
 
+    #[tokio::test]
+    async fn war_ingest_tracks_live_wars_and_broadcasts_unsequenced_transitions() {
+        let mut state = AppState::new(None);
+        state.internal_ingest_token = Some("test-token".to_string());
+        state.live_snapshot.write().await.territories.insert(
+            "Detlas".to_string(),
+            Territory {
+                guild: GuildRef {
+                    uuid: "uuid".to_string(),
+                    name: "Aequitas".to_string(),
+                    prefix: "Aeq".to_string(),
+                    color: None,
+                },
+                acquired: Utc::now(),
+                location: Region {
+                    start: [0, 0],
+                    end: [1, 1],
+                },
+                resources: Default::default(),
+                connections: Vec::new(),
+                runtime: None,
+            },
+        );
+        let mut rx = state.event_tx.subscribe();
+        let seq_before = state.next_seq.load(std::sync::atomic::Ordering::Relaxed);
+
+        let report = |kind: WarEventKind, territory: &str| CanonicalWarReport {
+            event: WarEvent {
+                id: String::new(),
+                kind,
+                territory: territory.to_string(),
+                guild: None,
+                tower_state: None,
+                observed_at: Utc::now().to_rfc3339(),
+                provenance: DataProvenance::default(),
+            },
+            idempotency_key: None,
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-internal-ingest-token",
+            HeaderValue::from_static("test-token"),
+        );
+
+        let batch = CanonicalWarBatch {
+            generated_at: Utc::now().to_rfc3339(),
+            events: vec![
+                report(WarEventKind::Queued, "Detlas"),
+                report(WarEventKind::Queued, "Nowhere"),
+            ],
+        };
+        let response = ingest_war(State(state.clone()), headers.clone(), Json(batch))
+            .await
+            .expect("war ingest should accept valid internal token");
+        assert_eq!(response.0["applied"], 1);
+        assert_eq!(response.0["rejected"], 1);
+        assert_eq!(response.0["broadcast"], 1);
+
+        let live = state.live_wars.read().await.get("Detlas").cloned();
+        let live = live.expect("queued war should be live");
+        assert_eq!(live.kind, WarEventKind::Queued);
+        assert!(!live.id.is_empty());
+
+        match rx.try_recv().expect("war event should be broadcast") {
+            PreSerializedEvent::War { json } => {
+                let parsed: TerritoryEvent =
+                    serde_json::from_slice(json.as_ref()).expect("war payload");
+                let TerritoryEvent::War { events, .. } = parsed else {
+                    panic!("expected war event payload");
+                };
+                assert_eq!(events.len(), 1);
+                assert_eq!(events[0].territory, "Detlas");
+            }
+            other => panic!("expected war event, got {other:?}"),
+        }
+        assert_eq!(
+            state.next_seq.load(std::sync::atomic::Ordering::Relaxed),
+            seq_before
+        );
+
+        let batch = CanonicalWarBatch {
+            generated_at: Utc::now().to_rfc3339(),
+            events: vec![report(WarEventKind::Captured, "Detlas")],
+        };
+        let response = ingest_war(State(state.clone()), headers, Json(batch))
+            .await
+            .expect("capture should be accepted");
+        assert_eq!(response.0["broadcast"], 1);
+        assert!(state.live_wars.read().await.is_empty());
+    }
+
     #[tokio::test]
     async fn ingest_rejects_batches_over_configured_max() {
         let mut state = AppState::new(None);
@@ -990,6 +1203,7 @@ mod tests {
                             menu_captured_territories: Some(64),
                             menu_sr_per_hour: Some(30301),
                             menu_observed_at: Some(observed_at),
+                            corroborated_fields: None,
                         }),
                         ..TerritoryRuntimeData::default()
                     }),
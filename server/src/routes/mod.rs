@@ -1,6 +1,9 @@
 pub mod api;
+pub mod archive;
 pub mod claims;
 pub mod history;
 pub mod http_util;
 pub mod ingest;
 pub mod sse;
+pub mod wars;
+pub mod webhooks;
@@ -1,91 +1,138 @@
 use std::convert::Infallible;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use axum::extract::State;
+use axum::http::HeaderMap;
 use axum::response::Sse;
 use axum::response::sse::{Event, KeepAlive};
 use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use futures::stream::Stream;
+use sequoia_shared::{GuildRef, TerritoryChange, TerritoryEvent, TerritoryMap};
 use tokio_stream::StreamExt;
 use tokio_stream::wrappers::BroadcastStream;
-use tracing::warn;
+use tracing::{debug, warn};
 
-use crate::config::SSE_KEEPALIVE_SECS;
+use crate::config::{SSE_KEEPALIVE_SECS, sse_replay_max_persisted_events};
 use crate::state::{AppState, PreSerializedEvent};
 
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+type PersistedUpdateRow = (
+    i64,
+    DateTime<Utc>,
+    DateTime<Utc>,
+    String,
+    String,
+    String,
+    String,
+    Option<i16>,
+    Option<i16>,
+    Option<i16>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<i16>,
+    Option<i16>,
+    Option<i16>,
+);
+
 pub async fn territory_events(
     State(state): State<AppState>,
+    headers: HeaderMap,
 ) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let resume_from = parse_last_event_id(&headers);
+
     let stream = async_stream::stream! {
-        // Send pre-serialized snapshot (Arc clone = O(1) refcount bump, not 200KB String copy)
-        let (seq, data) = {
-            let snapshot = state.live_snapshot.read().await;
-            (snapshot.seq, snapshot.snapshot_json.clone())
+        // Subscribe before reading replay state so nothing published in between is lost;
+        // live events already covered by the replay or snapshot are filtered by `replayed_through`.
+        let rx = state.event_tx.subscribe();
+        let mut stream = BroadcastStream::new(rx);
+        let mut last_sent_seq = 0_u64;
+        // The poller and ingest reserve seq blocks independently, so live events can arrive out
+        // of order. Only seqs at or below this replay high-water mark are known duplicates.
+        let mut replayed_through = 0_u64;
+
+        let initial = match resume_from {
+            Some(last_seq) => resume_events(&state, last_seq).await,
+            None => None,
         };
-        if !data.is_empty() {
-            if let Some(payload) = event_payload(data.as_ref()) {
-                yield Ok(
-                    Event::default()
-                        .id(seq.to_string())
-                        .event("snapshot")
-                        .data(payload),
-                );
-            } else {
-                warn!("snapshot payload is not valid utf-8; skipping SSE snapshot event");
+        match initial {
+            Some(events) => {
+                let replayed = events.len();
+                for event in events {
+                    if let Some(sse_event) = to_sse_event(&event) {
+                        last_sent_seq = last_sent_seq.max(event.seq());
+                        yield Ok(sse_event);
+                    }
+                }
+                let resumed_seq = last_sent_seq.max(resume_from.unwrap_or_default());
+                last_sent_seq = resumed_seq;
+                replayed_through = resumed_seq;
+                debug!(resumed_seq, replayed, "resumed SSE stream from Last-Event-ID");
+                yield Ok(resumed_event(resumed_seq, replayed));
+            }
+            None => {
+                // Send pre-serialized snapshot (Arc clone = O(1) refcount bump, not 200KB String copy)
+                let snapshot = live_snapshot_event(&state).await;
+                if let Some(sse_event) = snapshot.as_ref().and_then(to_sse_event) {
+                    last_sent_seq = snapshot.map(|event| event.seq()).unwrap_or_default();
+                    replayed_through = last_sent_seq;
+                    yield Ok(sse_event);
+                }
             }
         }
 
-        // Subscribe to updates
-        let rx = state.event_tx.subscribe();
-        let mut stream = BroadcastStream::new(rx);
-
         while let Some(result) = stream.next().await {
             match result {
                 Ok(event) => {
-                    let (event_type, seq, data) = match event {
-                        PreSerializedEvent::Snapshot { seq, json } => ("snapshot", seq, json),
-                        PreSerializedEvent::Update { seq, json } => ("update", seq, json),
-                        PreSerializedEvent::RuntimeUpdate { seq, json } => {
-                            ("runtime_update", seq, json)
+                    if !event.is_sequenced() {
+                        if let Some(sse_event) = to_sse_event(&event) {
+                            yield Ok(sse_event);
                         }
-                    };
-                    let Some(payload) = event_payload(data.as_ref()) else {
-                        warn!(
-                            seq,
-                            event = event_type,
-                            "event payload is not valid utf-8; dropping SSE event"
-                        );
+                        continue;
+                    }
+                    if event.seq() <= replayed_through {
+                        continue;
+                    }
+                    let Some(sse_event) = to_sse_event(&event) else {
                         continue;
                     };
-                    yield Ok(
-                        Event::default()
-                            .id(seq.to_string())
-                            .event(event_type)
-                            .data(payload),
-                    );
+                    last_sent_seq = last_sent_seq.max(event.seq());
+                    yield Ok(sse_event);
                 }
                 Err(tokio_stream::wrappers::errors::BroadcastStreamRecvError::Lagged(skipped)) => {
-                    warn!(
-                        skipped_events = skipped,
-                        "SSE client lagged behind broadcast buffer; replaying snapshot"
-                    );
-                    // Client fell behind — resend pre-serialized snapshot (Arc clone = O(1))
-                    let (seq, data) = {
-                        let snapshot = state.live_snapshot.read().await;
-                        (snapshot.seq, snapshot.snapshot_json.clone())
+                    // Client fell behind — fill the gap from the replay buffer when possible,
+                    // otherwise resend the pre-serialized snapshot (Arc clone = O(1)).
+                    let events = match resume_events(&state, last_sent_seq).await {
+                        Some(events) => {
+                            warn!(
+                                skipped_events = skipped,
+                                replayed_events = events.len(),
+                                "SSE client lagged behind broadcast buffer; replaying missed events"
+                            );
+                            events
+                        }
+                        None => {
+                            warn!(
+                                skipped_events = skipped,
+                                "SSE client lagged behind broadcast buffer; replaying snapshot"
+                            );
+                            live_snapshot_event(&state).await.into_iter().collect()
+                        }
                     };
-                    if !data.is_empty() {
-                        let Some(payload) = event_payload(data.as_ref()) else {
-                            warn!("snapshot payload is not valid utf-8; skipping SSE snapshot replay");
+                    for event in events {
+                        if event.seq() <= last_sent_seq {
                             continue;
-                        };
-                        yield Ok(
-                            Event::default()
-                                .id(seq.to_string())
-                                .event("snapshot")
-                                .data(payload),
-                        );
+                        }
+                        if let Some(sse_event) = to_sse_event(&event) {
+                            last_sent_seq = last_sent_seq.max(event.seq());
+                            yield Ok(sse_event);
+                        }
                     }
+                    replayed_through = last_sent_seq;
                 }
             }
         }
@@ -98,6 +145,390 @@ pub async fn territory_events(
     )
 }
 
+fn parse_last_event_id(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(LAST_EVENT_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|seq| *seq > 0)
+}
+
+/// Events needed to bring a client from `last_seq` up to date without a full snapshot.
+///
+/// Returns `None` when the gap cannot be filled from the in-memory replay buffer and
+/// persisted `territory_events`; callers fall back to the live snapshot in that case.
+async fn resume_events(state: &AppState, last_seq: u64) -> Option<Vec<PreSerializedEvent>> {
+    let issued_seq = state
+        .next_seq
+        .load(Ordering::Relaxed)
+        .max(state.next_seq_reserved.load(Ordering::Relaxed));
+    if last_seq > issued_seq {
+        // Client saw a sequence this process never issued (e.g. server state was reset).
+        return None;
+    }
+
+    let slice = state.event_replay.lock().await.since(last_seq);
+
+    if slice.snapshot_seq.is_some() {
+        let snapshot = live_snapshot_event(state).await?;
+        let snapshot_seq = snapshot.seq();
+        let mut events = vec![snapshot];
+        events.extend(
+            slice
+                .events
+                .into_iter()
+                .filter(|event| event.seq() > snapshot_seq),
+        );
+        return Some(events);
+    }
+
+    if last_seq >= slice.floor_seq {
+        return Some(slice.events);
+    }
+
+    let pool = state.db.as_ref()?;
+    let mut events = match load_persisted_updates(state, pool, last_seq, slice.floor_seq).await {
+        Ok(events) => events?,
+        Err(e) => {
+            warn!(error = %e, last_seq, "failed to load persisted updates for SSE resume");
+            return None;
+        }
+    };
+    events.extend(slice.events);
+    Some(events)
+}
+
+/// Rebuild `update` events for `(after_seq, through_seq]` from `territory_events`.
+///
+/// Only ownership changes are persisted, so the range must be fully covered by rows;
+/// any missing sequence may have been a runtime update or snapshot and yields `None`.
+async fn load_persisted_updates(
+    state: &AppState,
+    pool: &sqlx::PgPool,
+    after_seq: u64,
+    through_seq: u64,
+) -> Result<Option<Vec<PreSerializedEvent>>, String> {
+    let expected = through_seq - after_seq;
+    let max_events = sse_replay_max_persisted_events();
+    if i64::try_from(expected).map_or(true, |expected| expected > max_events) {
+        return Ok(None);
+    }
+    let after = i64::try_from(after_seq).map_err(|_| "resume seq out of range".to_string())?;
+    let through =
+        i64::try_from(through_seq).map_err(|_| "replay floor seq out of range".to_string())?;
+
+    let rows: Vec<PersistedUpdateRow> = sqlx::query_as(
+        "SELECT stream_seq, recorded_at, acquired_at, territory, guild_uuid, guild_name, \
+                guild_prefix, guild_color_r, guild_color_g, guild_color_b, \
+                prev_guild_uuid, prev_guild_name, prev_guild_prefix, \
+                prev_guild_color_r, prev_guild_color_g, prev_guild_color_b \
+         FROM territory_events \
+         WHERE stream_seq > $1 AND stream_seq <= $2 \
+         ORDER BY stream_seq ASC",
+    )
+    .bind(after)
+    .bind(through)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("query territory_events for resume: {e}"))?;
+
+    if rows.len() as u64 != expected {
+        return Ok(None);
+    }
+
+    let territories = state.live_snapshot.read().await.territories.clone();
+    let mut events = Vec::with_capacity(rows.len());
+    for row in rows {
+        let seq = u64::try_from(row.0).map_err(|_| "negative stream_seq".to_string())?;
+        let timestamp = row.1.to_rfc3339();
+        let Some(change) = persisted_row_to_change(row, &territories) else {
+            return Ok(None);
+        };
+        let json = serde_json::to_vec(&TerritoryEvent::Update {
+            seq,
+            changes: vec![change],
+            timestamp,
+        })
+        .map_err(|e| format!("serialize replayed update: {e}"))?;
+        events.push(PreSerializedEvent::Update {
+            seq,
+            json: Arc::new(Bytes::from(json)),
+        });
+    }
+    Ok(Some(events))
+}
+
+fn persisted_row_to_change(
+    row: PersistedUpdateRow,
+    territories: &TerritoryMap,
+) -> Option<TerritoryChange> {
+    let (
+        _stream_seq,
+        _recorded_at,
+        acquired_at,
+        territory,
+        guild_uuid,
+        guild_name,
+        guild_prefix,
+        guild_color_r,
+        guild_color_g,
+        guild_color_b,
+        prev_guild_uuid,
+        prev_guild_name,
+        prev_guild_prefix,
+        prev_guild_color_r,
+        prev_guild_color_g,
+        prev_guild_color_b,
+    ) = row;
+
+    let previous_guild = match (prev_guild_uuid, prev_guild_name, prev_guild_prefix) {
+        (Some(uuid), Some(name), Some(prefix)) => Some(GuildRef {
+            uuid,
+            name,
+            prefix,
+            color: rgb_triplet(prev_guild_color_r, prev_guild_color_g, prev_guild_color_b),
+        }),
+        _ => None,
+    };
+    // Location, resources and connections are static map data, so the live map supplies them.
+    // Runtime state is not persisted per event and the current values would misdate history,
+    // so replayed changes leave it unset; the next runtime update fills it back in.
+    let live = territories.get(&territory)?;
+
+    Some(TerritoryChange {
+        guild: GuildRef {
+            uuid: guild_uuid,
+            name: guild_name,
+            prefix: guild_prefix,
+            color: rgb_triplet(guild_color_r, guild_color_g, guild_color_b),
+        },
+        previous_guild,
+        acquired: acquired_at.to_rfc3339(),
+        location: live.location.clone(),
+        resources: live.resources.clone(),
+        connections: live.connections.clone(),
+        runtime: None,
+        territory,
+    })
+}
+
+fn rgb_triplet(r: Option<i16>, g: Option<i16>, b: Option<i16>) -> Option<(u8, u8, u8)> {
+    match (r, g, b) {
+        (Some(r), Some(g), Some(b)) => Some((
+            u8::try_from(r).ok()?,
+            u8::try_from(g).ok()?,
+            u8::try_from(b).ok()?,
+        )),
+        _ => None,
+    }
+}
+
+async fn live_snapshot_event(state: &AppState) -> Option<PreSerializedEvent> {
+    let snapshot = state.live_snapshot.read().await;
+    if snapshot.snapshot_json.is_empty() {
+        return None;
+    }
+    Some(PreSerializedEvent::Snapshot {
+        seq: snapshot.seq,
+        json: snapshot.snapshot_json.clone(),
+    })
+}
+
+fn to_sse_event(event: &PreSerializedEvent) -> Option<Event> {
+    let (event_type, seq, data) = match event {
+        PreSerializedEvent::Snapshot { seq, json } => ("snapshot", *seq, json),
+        PreSerializedEvent::Update { seq, json } => ("update", *seq, json),
+        PreSerializedEvent::RuntimeUpdate { seq, json } => ("runtime_update", *seq, json),
+        PreSerializedEvent::War { json } => ("war", 0, json),
+    };
+    let Some(payload) = event_payload(data.as_ref()) else {
+        warn!(
+            seq,
+            event = event_type,
+            "event payload is not valid utf-8; dropping SSE event"
+        );
+        return None;
+    };
+    let sse_event = Event::default().event(event_type).data(payload);
+    // Unsequenced events carry no id so they never move the client's Last-Event-ID.
+    if event.is_sequenced() {
+        Some(sse_event.id(seq.to_string()))
+    } else {
+        Some(sse_event)
+    }
+}
+
+/// Marker sent after a successful resume so clients know no snapshot is coming.
+fn resumed_event(seq: u64, replayed: usize) -> Event {
+    Event::default()
+        .id(seq.to_string())
+        .event("resumed")
+        .data(serde_json::json!({ "seq": seq, "replayed": replayed }).to_string())
+}
+
 fn event_payload(bytes: &Bytes) -> Option<&str> {
     std::str::from_utf8(bytes.as_ref()).ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use axum::http::{HeaderMap, HeaderValue};
+    use bytes::Bytes;
+    use chrono::{DateTime, Utc};
+    use sequoia_shared::{GuildRef, Region, Resources, Territory, TerritoryRuntimeData};
+
+    use super::{parse_last_event_id, persisted_row_to_change, resume_events};
+    use crate::state::{AppState, PreSerializedEvent};
+
+    fn update_event(seq: u64) -> PreSerializedEvent {
+        PreSerializedEvent::Update {
+            seq,
+            json: Arc::new(Bytes::from_static(b"{}")),
+        }
+    }
+
+    #[test]
+    fn last_event_id_parses_positive_sequence_only() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_last_event_id(&headers), None);
+
+        headers.insert("last-event-id", HeaderValue::from_static(" 42 "));
+        assert_eq!(parse_last_event_id(&headers), Some(42));
+
+        headers.insert("last-event-id", HeaderValue::from_static("0"));
+        assert_eq!(parse_last_event_id(&headers), None);
+
+        headers.insert("last-event-id", HeaderValue::from_static("nope"));
+        assert_eq!(parse_last_event_id(&headers), None);
+    }
+
+    #[test]
+    fn persisted_rows_replay_without_current_runtime() {
+        let at: DateTime<Utc> = "2026-01-01T00:00:00Z".parse().unwrap();
+        let live = Territory {
+            guild: GuildRef {
+                uuid: "g2".to_string(),
+                name: "Current".to_string(),
+                prefix: "CUR".to_string(),
+                color: None,
+            },
+            acquired: at,
+            location: Region {
+                start: [0, 0],
+                end: [10, 10],
+            },
+            resources: Resources::default(),
+            connections: vec!["Beta".to_string()],
+            runtime: Some(TerritoryRuntimeData {
+                headquarters: Some(true),
+                ..TerritoryRuntimeData::default()
+            }),
+        };
+        let territories = HashMap::from([("Alpha".to_string(), live)]);
+        let row = (
+            7,
+            at,
+            at,
+            "Alpha".to_string(),
+            "g1".to_string(),
+            "Former".to_string(),
+            "FOR".to_string(),
+            Some(1),
+            Some(2),
+            Some(3),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let change = persisted_row_to_change(row, &territories).expect("territory is on the map");
+        assert_eq!(change.guild.name, "Former");
+        assert_eq!(change.guild.color, Some((1, 2, 3)));
+        assert_eq!(change.connections, vec!["Beta".to_string()]);
+        assert_eq!(change.runtime, None);
+    }
+
+    #[tokio::test]
+    async fn resume_replays_missing_events_from_memory() {
+        let state = AppState::new(None);
+        for seq in 1..=3 {
+            state.publish_event(update_event(seq)).await;
+        }
+        state
+            .next_seq
+            .store(3, std::sync::atomic::Ordering::Relaxed);
+
+        let events = resume_events(&state, 1)
+            .await
+            .expect("gap should be covered by replay buffer");
+        assert_eq!(
+            events
+                .iter()
+                .map(PreSerializedEvent::seq)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+
+        let caught_up = resume_events(&state, 3)
+            .await
+            .expect("caught-up client should resume without events");
+        assert!(caught_up.is_empty());
+    }
+
+    #[tokio::test]
+    async fn resume_falls_back_to_snapshot_when_gap_is_not_retained() {
+        let state = AppState::new(None);
+        state.event_replay.lock().await.reset(10);
+        state.publish_event(update_event(11)).await;
+        state
+            .next_seq
+            .store(11, std::sync::atomic::Ordering::Relaxed);
+
+        // No database configured, so seqs 6..=10 cannot be recovered.
+        assert!(resume_events(&state, 5).await.is_none());
+        // Sequences never issued by this process also require a snapshot.
+        assert!(resume_events(&state, 99).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn resume_across_snapshot_boundary_sends_live_snapshot_first() {
+        let state = AppState::new(None);
+        {
+            let mut snapshot = state.live_snapshot.write().await;
+            snapshot.seq = 2;
+            snapshot.snapshot_json = Arc::new(Bytes::from_static(br#"{"type":"Snapshot"}"#));
+        }
+        state.publish_event(update_event(1)).await;
+        state
+            .publish_event(PreSerializedEvent::Snapshot {
+                seq: 2,
+                json: Arc::new(Bytes::from_static(br#"{"type":"Snapshot"}"#)),
+            })
+            .await;
+        state.publish_event(update_event(3)).await;
+        state
+            .next_seq
+            .store(3, std::sync::atomic::Ordering::Relaxed);
+
+        let events = resume_events(&state, 0)
+            .await
+            .expect("snapshot boundary should still resume");
+        assert!(matches!(
+            events.first(),
+            Some(PreSerializedEvent::Snapshot { seq: 2, .. })
+        ));
+        assert_eq!(
+            events
+                .iter()
+                .map(PreSerializedEvent::seq)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+    }
+}
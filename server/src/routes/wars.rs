@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::IntoResponse;
+use chrono::{DateTime, Duration, Utc};
+use sequoia_shared::{WarEvent, WarEventKind, WarHistory};
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::config::LIVE_WAR_STALE_SECS;
+use crate::state::AppState;
+
+const MAX_WAR_HISTORY_WINDOW_DAYS: i64 = 31;
+
+#[derive(Debug, Deserialize)]
+pub struct WarHistoryQuery {
+    #[serde(default)]
+    pub territory: Option<String>,
+    pub from: String,
+    pub to: String,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+fn default_limit() -> i64 {
+    500
+}
+
+/// `GET /api/wars/live` — wars currently queued or in progress, oldest first.
+pub async fn get_live_wars(State(state): State<AppState>) -> impl IntoResponse {
+    let now = Utc::now();
+    let mut wars = {
+        let mut live = state.live_wars.write().await;
+        prune_stale_live_wars(&mut live, now);
+        live.values().cloned().collect::<Vec<_>>()
+    };
+    wars.sort_by(|a, b| {
+        a.observed_at
+            .cmp(&b.observed_at)
+            .then_with(|| a.territory.cmp(&b.territory))
+    });
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("public, max-age=5"),
+    );
+    (headers, Json(wars))
+}
+
+/// `GET /api/wars/history?territory=&from=&to=` — persisted war events in a time window.
+pub async fn get_war_history(
+    State(state): State<AppState>,
+    Query(params): Query<WarHistoryQuery>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let pool = state.db.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let (from, to) = parse_war_window(&params.from, &params.to)?;
+    let territory = params
+        .territory
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let limit = params.limit.clamp(1, 1000);
+
+    let rows: Vec<(serde_json::Value,)> = sqlx::query_as(
+        "SELECT payload FROM canonical_war_events \
+         WHERE observed_at >= $1 AND observed_at <= $2 \
+           AND ($3::text IS NULL OR territory = $3) \
+         ORDER BY observed_at ASC, id ASC \
+         LIMIT $4",
+    )
+    .bind(from)
+    .bind(to)
+    .bind(territory)
+    .bind(limit + 1)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        warn!("failed to load war history: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let has_more = rows.len() as i64 > limit;
+    let events = rows
+        .into_iter()
+        .take(limit as usize)
+        .filter_map(|(payload,)| serde_json::from_value::<WarEvent>(payload).ok())
+        .collect();
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("public, max-age=30"),
+    );
+    Ok((headers, Json(WarHistory { events, has_more })))
+}
+
+/// Reload wars that were still active when the server last stopped.
+pub async fn warm_live_wars(state: &AppState) -> Result<usize, String> {
+    let Some(pool) = state.db.as_ref() else {
+        return Ok(0);
+    };
+    let cutoff = Utc::now() - Duration::seconds(LIVE_WAR_STALE_SECS);
+    let rows: Vec<(serde_json::Value,)> = sqlx::query_as(
+        "SELECT DISTINCT ON (territory) payload FROM canonical_war_events \
+         WHERE observed_at >= $1 AND kind <> 'tower_state' \
+         ORDER BY territory, observed_at DESC, id DESC",
+    )
+    .bind(cutoff)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("load live wars: {e}"))?;
+
+    let mut live = state.live_wars.write().await;
+    for (payload,) in rows {
+        if let Ok(event) = serde_json::from_value::<WarEvent>(payload) {
+            apply_live_war_event(&mut live, &event);
+        }
+    }
+    Ok(live.len())
+}
+
+/// Fold one war event into the live board; returns `true` for a broadcastable transition.
+pub(crate) fn apply_live_war_event(live: &mut HashMap<String, WarEvent>, event: &WarEvent) -> bool {
+    if let Some(existing) = live.get(&event.territory)
+        && war_observed_at(event) < war_observed_at(existing)
+    {
+        return false;
+    }
+
+    match event.kind {
+        WarEventKind::Queued | WarEventKind::Started => {
+            let previous = live.insert(event.territory.clone(), event.clone());
+            previous.is_none_or(|previous| previous.kind != event.kind)
+        }
+        // Only an end that closes a war on the board is news; duplicates and strays are not.
+        WarEventKind::Ended | WarEventKind::Captured => live.remove(&event.territory).is_some(),
+        WarEventKind::TowerState => {
+            if let Some(existing) = live.get_mut(&event.territory) {
+                existing.tower_state = event.tower_state.clone();
+            }
+            false
+        }
+    }
+}
+
+/// Drop wars that have not been refreshed within `LIVE_WAR_STALE_SECS`.
+pub(crate) fn prune_stale_live_wars(live: &mut HashMap<String, WarEvent>, now: DateTime<Utc>) {
+    let cutoff = now - Duration::seconds(LIVE_WAR_STALE_SECS);
+    live.retain(|_, event| war_observed_at(event).is_some_and(|observed| observed >= cutoff));
+}
+
+pub(crate) fn war_event_kind_label(kind: WarEventKind) -> &'static str {
+    match kind {
+        WarEventKind::Queued => "queued",
+        WarEventKind::Started => "started",
+        WarEventKind::Ended => "ended",
+        WarEventKind::Captured => "captured",
+        WarEventKind::TowerState => "tower_state",
+    }
+}
+
+fn war_observed_at(event: &WarEvent) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&event.observed_at)
+        .ok()
+        .map(|value| value.with_timezone(&Utc))
+}
+
+fn parse_war_window(from: &str, to: &str) -> Result<(DateTime<Utc>, DateTime<Utc>), StatusCode> {
+    let from = DateTime::parse_from_rfc3339(from)
+        .map_err(|_| StatusCode::BAD_REQUEST)?
+        .with_timezone(&Utc);
+    let to = DateTime::parse_from_rfc3339(to)
+        .map_err(|_| StatusCode::BAD_REQUEST)?
+        .with_timezone(&Utc);
+    if from > to || to - from > Duration::days(MAX_WAR_HISTORY_WINDOW_DAYS) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok((from, to))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::{Duration, Utc};
+    use sequoia_shared::{DataProvenance, WarEvent, WarEventKind, WarTowerState};
+
+    use super::{apply_live_war_event, parse_war_window, prune_stale_live_wars};
+
+    fn war(kind: WarEventKind, territory: &str, observed_at: &str) -> WarEvent {
+        WarEvent {
+            id: format!("{territory}:{observed_at}"),
+            kind,
+            territory: territory.to_string(),
+            guild: None,
+            tower_state: None,
+            observed_at: observed_at.to_string(),
+            provenance: DataProvenance::default(),
+        }
+    }
+
+    #[test]
+    fn live_board_tracks_queue_start_and_capture() {
+        let mut live = HashMap::new();
+        assert!(apply_live_war_event(
+            &mut live,
+            &war(WarEventKind::Queued, "Detlas", "2026-01-01T00:00:00Z")
+        ));
+        assert!(!apply_live_war_event(
+            &mut live,
+            &war(WarEventKind::Queued, "Detlas", "2026-01-01T00:00:05Z")
+        ));
+        assert!(apply_live_war_event(
+            &mut live,
+            &war(WarEventKind::Started, "Detlas", "2026-01-01T00:01:00Z")
+        ));
+        assert_eq!(live["Detlas"].kind, WarEventKind::Started);
+
+        assert!(apply_live_war_event(
+            &mut live,
+            &war(WarEventKind::Captured, "Detlas", "2026-01-01T00:03:00Z")
+        ));
+        assert!(live.is_empty());
+        assert!(!apply_live_war_event(
+            &mut live,
+            &war(WarEventKind::Captured, "Detlas", "2026-01-01T00:03:01Z")
+        ));
+        assert!(!apply_live_war_event(
+            &mut live,
+            &war(WarEventKind::Ended, "Almuj", "2026-01-01T00:04:00Z")
+        ));
+    }
+
+    #[test]
+    fn live_board_ignores_out_of_order_events_and_merges_tower_state() {
+        let mut live = HashMap::new();
+        apply_live_war_event(
+            &mut live,
+            &war(WarEventKind::Started, "Ragni", "2026-01-01T00:05:00Z"),
+        );
+        assert!(!apply_live_war_event(
+            &mut live,
+            &war(WarEventKind::Ended, "Ragni", "2026-01-01T00:04:00Z")
+        ));
+        assert!(live.contains_key("Ragni"));
+
+        let mut tower = war(WarEventKind::TowerState, "Ragni", "2026-01-01T00:06:00Z");
+        tower.tower_state = Some(WarTowerState {
+            health: 120_000,
+            defense: 0.6,
+            damage_low: 1_000,
+            damage_high: 1_500,
+            attack_speed: 1.5,
+        });
+        assert!(!apply_live_war_event(&mut live, &tower));
+        assert_eq!(live["Ragni"].kind, WarEventKind::Started);
+        assert_eq!(
+            live["Ragni"].tower_state.as_ref().map(|state| state.health),
+            Some(120_000)
+        );
+    }
+
+    #[test]
+    fn prune_drops_stale_and_unparseable_wars() {
+        let now = Utc::now();
+        let mut live = HashMap::new();
+        apply_live_war_event(
+            &mut live,
+            &war(WarEventKind::Queued, "Fresh", &now.to_rfc3339()),
+        );
+        apply_live_war_event(
+            &mut live,
+            &war(
+                WarEventKind::Queued,
+                "Stale",
+                &(now - Duration::hours(2)).to_rfc3339(),
+            ),
+        );
+        live.insert(
+            "Broken".to_string(),
+            war(WarEventKind::Queued, "Broken", "not-a-time"),
+        );
+
+        prune_stale_live_wars(&mut live, now);
+        assert_eq!(live.len(), 1);
+        assert!(live.contains_key("Fresh"));
+    }
+
+    #[test]
+    fn war_window_requires_ordered_bounded_range() {
+        assert!(parse_war_window("2026-01-01T00:00:00Z", "2026-01-02T00:00:00Z").is_ok());
+        assert!(parse_war_window("2026-01-02T00:00:00Z", "2026-01-01T00:00:00Z").is_err());
+        assert!(parse_war_window("2026-01-01T00:00:00Z", "2026-03-01T00:00:00Z").is_err());
+        assert!(parse_war_window("yesterday", "2026-01-01T00:00:00Z").is_err());
+    }
+}
@@ -0,0 +1,211 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use super::ingest::ensure_internal_ingest_auth;
+
+use crate::services::notifier::WebhookTrigger;
+use crate::state::AppState;
+
+const MAX_WEBHOOK_NAME_LEN: usize = 100;
+const MAX_WEBHOOK_URL_LEN: usize = 2048;
+const MAX_WEBHOOK_SECRET_LEN: usize = 256;
+
+type WebhookRuleRow = (
+    i64,
+    DateTime<Utc>,
+    String,
+    String,
+    bool,
+    serde_json::Value,
+    bool,
+    i64,
+    i64,
+);
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub secret: Option<String>,
+    pub trigger: WebhookTrigger,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookRuleSummary {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub name: String,
+    pub url: String,
+    pub signed: bool,
+    pub trigger: serde_json::Value,
+    pub enabled: bool,
+    pub pending: i64,
+    pub failed: i64,
+}
+
+/// `GET /api/internal/webhooks` — List webhook rules with their outbox backlog. Secrets are
+/// never returned.
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<WebhookRuleSummary>>, StatusCode> {
+    ensure_internal_ingest_auth(&state, &headers)?;
+    let pool = state.db.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let rows: Vec<WebhookRuleRow> = sqlx::query_as(
+        "SELECT r.id, r.created_at, r.name, r.url, r.secret IS NOT NULL, r.trigger, r.enabled, \
+                COUNT(o.id) FILTER (WHERE o.status = 'pending'), \
+                COUNT(o.id) FILTER (WHERE o.status = 'failed') \
+         FROM webhook_rules r \
+         LEFT JOIN webhook_outbox o ON o.rule_id = r.id \
+         GROUP BY r.id \
+         ORDER BY r.id",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        warn!("failed to list webhook rules: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(
+        rows.into_iter()
+            .map(
+                |(id, created_at, name, url, signed, trigger, enabled, pending, failed)| {
+                    WebhookRuleSummary {
+                        id,
+                        created_at,
+                        name,
+                        url,
+                        signed,
+                        trigger,
+                        enabled,
+                        pending,
+                        failed,
+                    }
+                },
+            )
+            .collect(),
+    ))
+}
+
+/// `POST /api/internal/webhooks` — Register a webhook rule.
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<CreateWebhookRequest>,
+) -> Result<impl IntoResponse, StatusCode> {
+    ensure_internal_ingest_auth(&state, &headers)?;
+    let pool = state.db.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    validate_create_request(&request)?;
+
+    let trigger =
+        serde_json::to_value(&request.trigger).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let secret = request
+        .secret
+        .as_deref()
+        .map(str::trim)
+        .filter(|secret| !secret.is_empty());
+    let id: i64 = sqlx::query_scalar(
+        "INSERT INTO webhook_rules (name, url, secret, trigger, enabled) \
+         VALUES ($1, $2, $3, $4, $5) RETURNING id",
+    )
+    .bind(request.name.trim())
+    .bind(request.url.trim())
+    .bind(secret)
+    .bind(trigger)
+    .bind(request.enabled)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        warn!("failed to create webhook rule: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    info!("Created webhook rule {id} ({})", request.name.trim());
+
+    Ok((StatusCode::CREATED, Json(serde_json::json!({ "id": id }))))
+}
+
+/// `DELETE /api/internal/webhooks/{id}` — Remove a rule and its queued deliveries.
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, StatusCode> {
+    ensure_internal_ingest_auth(&state, &headers)?;
+    let pool = state.db.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let result = sqlx::query("DELETE FROM webhook_rules WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| {
+            warn!("failed to delete webhook rule {id}: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn validate_create_request(request: &CreateWebhookRequest) -> Result<(), StatusCode> {
+    let name = request.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_WEBHOOK_NAME_LEN {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if !is_valid_webhook_url(request.url.trim()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if request
+        .secret
+        .as_deref()
+        .is_some_and(|secret| secret.len() > MAX_WEBHOOK_SECRET_LEN)
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    request
+        .trigger
+        .validate()
+        .map_err(|_| StatusCode::BAD_REQUEST)
+}
+
+fn is_valid_webhook_url(url: &str) -> bool {
+    if url.len() > MAX_WEBHOOK_URL_LEN {
+        return false;
+    }
+    let Some(rest) = url.strip_prefix("https://") else {
+        return false;
+    };
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    !host.is_empty() && !host.contains(char::is_whitespace) && !host.contains('@')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_valid_webhook_url;
+
+    #[test]
+    fn webhook_urls_must_be_https_with_a_host() {
+        assert!(is_valid_webhook_url(
+            "https://discord.com/api/webhooks/123/abc"
+        ));
+        assert!(is_valid_webhook_url("https://hooks.example.com"));
+        assert!(!is_valid_webhook_url("http://discord.com/api/webhooks/1"));
+        assert!(!is_valid_webhook_url("https:///path"));
+        assert!(!is_valid_webhook_url("https://user@internal/hook"));
+        assert!(!is_valid_webhook_url("ftp://example.com"));
+    }
+}
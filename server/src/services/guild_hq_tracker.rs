@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sequoia_shared::guild_hq::{GuildHqEstimate, GuildHqMove, GuildHqSource, infer_guild_hqs};
+use tracing::{info, warn};
+
+use crate::config::{GUILD_HQ_INFERENCE_SECS, GUILD_HQ_MOVE_MIN_CONFIDENCE, GUILD_HQ_MOVES_LIMIT};
+use crate::state::AppState;
+
+type GuildHqMoveRow = (DateTime<Utc>, Option<String>, String, f64, String);
+
+/// Periodically re-estimate every guild's HQ and record confident moves.
+pub async fn run(state: AppState) {
+    let pool = state.db.as_ref().cloned();
+    // Last recorded HQ per lowercased guild name; also the stickiness hint for inference.
+    let mut recorded = match pool.as_ref() {
+        Some(pool) => load_latest_hqs(pool).await.unwrap_or_else(|e| {
+            warn!("failed to restore guild HQ history: {e}");
+            HashMap::new()
+        }),
+        None => HashMap::new(),
+    };
+    info!(
+        "Guild HQ tracker started ({} known HQs, interval: {}s)",
+        recorded.len(),
+        GUILD_HQ_INFERENCE_SECS
+    );
+
+    let mut interval = tokio::time::interval(Duration::from_secs(GUILD_HQ_INFERENCE_SECS));
+    loop {
+        interval.tick().await;
+        let estimates = {
+            let snapshot = state.live_snapshot.read().await;
+            if snapshot.territories.is_empty() {
+                continue;
+            }
+            infer_guild_hqs(&snapshot.territories, &recorded)
+        };
+
+        let (moves, seeds) = confident_moves(&recorded, &estimates);
+        let persisted = match pool.as_ref() {
+            Some(pool) if !moves.is_empty() || !seeds.is_empty() => {
+                match record_hq_changes(pool, &recorded, &moves, &seeds).await {
+                    Ok(()) => {
+                        if !moves.is_empty() {
+                            info!("Recorded {} guild HQ moves", moves.len());
+                        }
+                        true
+                    }
+                    Err(e) => {
+                        warn!("failed to record guild HQ moves: {e}");
+                        false
+                    }
+                }
+            }
+            _ => true,
+        };
+        // A failed write leaves `recorded` untouched so the same changes are retried next tick.
+        if persisted {
+            for estimate in moves.into_iter().chain(seeds) {
+                recorded.insert(
+                    estimate.guild_name.to_ascii_lowercase(),
+                    estimate.territory.clone(),
+                );
+            }
+        }
+
+        let by_guild = estimates
+            .into_iter()
+            .map(|estimate| (estimate.guild_name.to_ascii_lowercase(), estimate))
+            .collect();
+        *state.guild_hqs.write().await = by_guild;
+    }
+}
+
+/// Trustworthy estimates that differ from the last recorded HQ, split into moves worth recording
+/// and first sightings that only seed the baseline (nothing is known to have moved).
+fn confident_moves<'a>(
+    recorded: &HashMap<String, String>,
+    estimates: &'a [GuildHqEstimate],
+) -> (Vec<&'a GuildHqEstimate>, Vec<&'a GuildHqEstimate>) {
+    let mut moves = Vec::new();
+    let mut seeds = Vec::new();
+    for estimate in estimates.iter().filter(|estimate| {
+        estimate.source == GuildHqSource::Observed
+            || estimate.confidence >= GUILD_HQ_MOVE_MIN_CONFIDENCE
+    }) {
+        match recorded.get(&estimate.guild_name.to_ascii_lowercase()) {
+            Some(territory) if *territory == estimate.territory => {}
+            Some(_) => moves.push(estimate),
+            None => seeds.push(estimate),
+        }
+    }
+    (moves, seeds)
+}
+
+async fn load_latest_hqs(pool: &sqlx::PgPool) -> Result<HashMap<String, String>, String> {
+    let rows: Vec<(String, String)> =
+        sqlx::query_as("SELECT guild_key, territory FROM guild_hq_baselines")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| format!("load guild HQ baselines: {e}"))?;
+    Ok(rows.into_iter().collect())
+}
+
+/// Insert move rows and refresh the per-guild baselines (moves and first sightings) atomically.
+async fn record_hq_changes(
+    pool: &sqlx::PgPool,
+    recorded: &HashMap<String, String>,
+    moves: &[&GuildHqEstimate],
+    seeds: &[&GuildHqEstimate],
+) -> Result<(), String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("begin HQ move tx: {e}"))?;
+    for estimate in moves {
+        let from = recorded.get(&estimate.guild_name.to_ascii_lowercase());
+        sqlx::query(
+            "INSERT INTO guild_hq_moves \
+             (guild_uuid, guild_name, from_territory, to_territory, confidence, source) \
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(&estimate.guild_uuid)
+        .bind(&estimate.guild_name)
+        .bind(from)
+        .bind(&estimate.territory)
+        .bind(estimate.confidence)
+        .bind(estimate.source.as_str())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("insert HQ move for {}: {e}", estimate.guild_name))?;
+    }
+    for estimate in moves.iter().chain(seeds) {
+        sqlx::query(
+            "INSERT INTO guild_hq_baselines (guild_key, territory) VALUES ($1, $2) \
+             ON CONFLICT (guild_key) DO UPDATE \
+             SET territory = EXCLUDED.territory, updated_at = now()",
+        )
+        .bind(estimate.guild_name.to_ascii_lowercase())
+        .bind(&estimate.territory)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("upsert HQ baseline for {}: {e}", estimate.guild_name))?;
+    }
+    tx.commit()
+        .await
+        .map_err(|e| format!("commit HQ moves: {e}"))
+}
+
+/// Most recent recorded HQ moves for a guild, newest first.
+pub async fn load_guild_hq_moves(
+    pool: &sqlx::PgPool,
+    guild_name: &str,
+) -> Result<Vec<GuildHqMove>, String> {
+    let rows: Vec<GuildHqMoveRow> = sqlx::query_as(
+        "SELECT recorded_at, from_territory, to_territory, confidence, source \
+         FROM guild_hq_moves \
+         WHERE LOWER(guild_name) = LOWER($1) \
+         ORDER BY recorded_at DESC, id DESC \
+         LIMIT $2",
+    )
+    .bind(guild_name)
+    .bind(GUILD_HQ_MOVES_LIMIT)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("load HQ moves for {guild_name}: {e}"))?;
+    Ok(rows
+        .into_iter()
+        .filter_map(
+            |(recorded_at, from_territory, to_territory, confidence, source)| {
+                Some(GuildHqMove {
+                    recorded_at: recorded_at.to_rfc3339(),
+                    from_territory,
+                    to_territory,
+                    confidence,
+                    source: GuildHqSource::from_str_opt(&source)?,
+                })
+            },
+        )
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use sequoia_shared::guild_hq::{GuildHqEstimate, GuildHqSource};
+
+    use super::confident_moves;
+
+    fn estimate(guild: &str, territory: &str, confidence: f64) -> GuildHqEstimate {
+        GuildHqEstimate {
+            guild_uuid: format!("{guild}-uuid"),
+            guild_name: guild.to_string(),
+            guild_prefix: guild[..3].to_string(),
+            territory: territory.to_string(),
+            confidence,
+            source: GuildHqSource::Inferred,
+            connections: 0,
+            externals: 0,
+            runner_up: None,
+        }
+    }
+
+    #[test]
+    fn only_confident_changes_are_recorded() {
+        let recorded = HashMap::from([
+            ("alpha".to_string(), "Detlas".to_string()),
+            ("beta".to_string(), "Ragni".to_string()),
+        ]);
+        let mut observed = estimate("Gamma", "Almuj", 0.1);
+        observed.source = GuildHqSource::Observed;
+        let estimates = vec![
+            estimate("Alpha", "Detlas", 0.9),
+            estimate("Beta", "Nemract", 0.4),
+            estimate("Delta", "Maltic", 0.8),
+            observed,
+        ];
+
+        let (moves, seeds) = confident_moves(&recorded, &estimates);
+        let names = |estimates: Vec<&GuildHqEstimate>| -> Vec<String> {
+            estimates
+                .into_iter()
+                .map(|estimate| estimate.guild_name.clone())
+                .collect()
+        };
+        assert!(moves.is_empty());
+        assert_eq!(names(seeds), vec!["Delta", "Gamma"]);
+
+        // Once seeded, a confident relocation is a real move from the known HQ.
+        let recorded = HashMap::from([("delta".to_string(), "Maltic".to_string())]);
+        let estimates = vec![estimate("Delta", "Almuj", 0.8)];
+        let (moves, seeds) = confident_moves(&recorded, &estimates);
+        assert_eq!(names(moves), vec!["Delta"]);
+        assert!(seeds.is_empty());
+    }
+}
@@ -0,0 +1,277 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use flate2::Compression;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+
+const ARCHIVE_FILE_SUFFIX: &str = ".ndjson.gz";
+const IMPORT_BATCH_ROWS: usize = 500;
+
+/// History tables that retention cleanup prunes and that can be archived before deletion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveTable {
+    TerritoryEvents,
+    TerritorySnapshots,
+    SeasonScalarSamples,
+    SeasonGuildObservations,
+}
+
+impl ArchiveTable {
+    pub fn table_name(self) -> &'static str {
+        match self {
+            Self::TerritoryEvents => "territory_events",
+            Self::TerritorySnapshots => "territory_snapshots",
+            Self::SeasonScalarSamples => "season_scalar_samples",
+            Self::SeasonGuildObservations => "season_guild_observations",
+        }
+    }
+
+    pub fn from_table_name(name: &str) -> Option<Self> {
+        [
+            Self::TerritoryEvents,
+            Self::TerritorySnapshots,
+            Self::SeasonScalarSamples,
+            Self::SeasonGuildObservations,
+        ]
+        .into_iter()
+        .find(|table| table.table_name() == name)
+    }
+
+    fn time_column(self) -> &'static str {
+        match self {
+            Self::TerritoryEvents => "recorded_at",
+            Self::TerritorySnapshots => "created_at",
+            Self::SeasonScalarSamples => "sampled_at",
+            Self::SeasonGuildObservations => "observed_at",
+        }
+    }
+
+    fn order_columns(self) -> &'static str {
+        match self {
+            Self::TerritoryEvents | Self::TerritorySnapshots => "id",
+            Self::SeasonScalarSamples => "sampled_at, season_id",
+            Self::SeasonGuildObservations => "observed_at, guild_name",
+        }
+    }
+
+    /// Insert a JSON array of archived rows, skipping rows that are already present.
+    fn import_sql(self) -> &'static str {
+        match self {
+            Self::TerritoryEvents => {
+                "INSERT INTO territory_events \
+                 SELECT * FROM jsonb_populate_recordset(NULL::territory_events, $1) \
+                 ON CONFLICT DO NOTHING"
+            }
+            Self::TerritorySnapshots => {
+                "INSERT INTO territory_snapshots \
+                 SELECT * FROM jsonb_populate_recordset(NULL::territory_snapshots, $1) \
+                 ON CONFLICT DO NOTHING"
+            }
+            // No unique key on scalar samples, so match on the natural key instead.
+            Self::SeasonScalarSamples => {
+                "INSERT INTO season_scalar_samples \
+                 SELECT r.* FROM jsonb_populate_recordset(NULL::season_scalar_samples, $1) r \
+                 WHERE NOT EXISTS ( \
+                     SELECT 1 FROM season_scalar_samples s \
+                     WHERE s.sampled_at = r.sampled_at AND s.season_id = r.season_id \
+                 )"
+            }
+            Self::SeasonGuildObservations => {
+                "INSERT INTO season_guild_observations \
+                 SELECT * FROM jsonb_populate_recordset(NULL::season_guild_observations, $1) \
+                 ON CONFLICT DO NOTHING"
+            }
+        }
+    }
+}
+
+/// `{dir}/{table}/{YYYY-MM-DD}.ndjson.gz`
+pub fn archive_file_path(dir: &Path, table: ArchiveTable, day: NaiveDate) -> PathBuf {
+    dir.join(table.table_name())
+        .join(format!("{day}{ARCHIVE_FILE_SUFFIX}"))
+}
+
+/// Resolve an archive path given relative to `dir`, rejecting anything that could escape it.
+pub fn resolve_archive_path(dir: &Path, relative: &str) -> Option<(PathBuf, ArchiveTable)> {
+    let relative = Path::new(relative.trim());
+    let mut components = relative.components();
+    let (Some(Component::Normal(table)), Some(Component::Normal(file)), None) =
+        (components.next(), components.next(), components.next())
+    else {
+        return None;
+    };
+    let table = ArchiveTable::from_table_name(table.to_str()?)?;
+    if !file.to_str()?.ends_with(ARCHIVE_FILE_SUFFIX) {
+        return None;
+    }
+    Some((dir.join(relative), table))
+}
+
+/// Append every row of `table` older than `cutoff` to its per-day archive file.
+///
+/// Each run appends a fresh gzip member, so a day that straddles the cutoff is completed on a
+/// later run and a crash between archiving and deleting only produces duplicate lines, which
+/// import skips.
+pub async fn archive_expired_rows(
+    pool: &sqlx::PgPool,
+    dir: &Path,
+    table: ArchiveTable,
+    cutoff: DateTime<Utc>,
+) -> Result<u64, String> {
+    let name = table.table_name();
+    let column = table.time_column();
+    let days_sql = format!(
+        "SELECT DISTINCT ({column} AT TIME ZONE 'UTC')::date AS day \
+         FROM {name} WHERE {column} < $1 ORDER BY day"
+    );
+    let days: Vec<(NaiveDate,)> = sqlx::query_as(&days_sql)
+        .bind(cutoff)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("list expired {name} days: {e}"))?;
+
+    let rows_sql = format!(
+        "SELECT to_jsonb(t)::text FROM {name} t \
+         WHERE {column} >= $1 AND {column} < $2 AND {column} < $3 \
+         ORDER BY {}",
+        table.order_columns()
+    );
+    let mut total = 0u64;
+    for (day,) in days {
+        let day_start = day.and_time(chrono::NaiveTime::MIN).and_utc();
+        let rows: Vec<(String,)> = sqlx::query_as(&rows_sql)
+            .bind(day_start)
+            .bind(day_start + Duration::days(1))
+            .bind(cutoff)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| format!("load expired {name} rows for {day}: {e}"))?;
+        if rows.is_empty() {
+            continue;
+        }
+        let row_count = rows.len() as u64;
+        let path = archive_file_path(dir, table, day);
+        tokio::task::spawn_blocking(move || {
+            append_archive_member(&path, rows.iter().map(|(line,)| line.as_str()))
+        })
+        .await
+        .map_err(|e| format!("archive writer task failed: {e}"))??;
+        total += row_count;
+    }
+    Ok(total)
+}
+
+/// Load an archive file back into its table. Returns `(rows_read, rows_inserted)`.
+pub async fn import_archive_file(
+    pool: &sqlx::PgPool,
+    path: PathBuf,
+    table: ArchiveTable,
+) -> Result<(u64, u64), String> {
+    let rows = tokio::task::spawn_blocking(move || read_archive_rows(&path))
+        .await
+        .map_err(|e| format!("archive reader task failed: {e}"))??;
+
+    let mut inserted = 0u64;
+    for chunk in rows.chunks(IMPORT_BATCH_ROWS) {
+        let result = sqlx::query(table.import_sql())
+            .bind(serde_json::Value::Array(chunk.to_vec()))
+            .execute(pool)
+            .await
+            .map_err(|e| format!("import {} rows: {e}", table.table_name()))?;
+        inserted += result.rows_affected();
+    }
+    Ok((rows.len() as u64, inserted))
+}
+
+fn append_archive_member<'a>(
+    path: &Path,
+    lines: impl Iterator<Item = &'a str>,
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("create archive dir {}: {e}", parent.display()))?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("open archive {}: {e}", path.display()))?;
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    for line in lines {
+        encoder
+            .write_all(line.as_bytes())
+            .and_then(|()| encoder.write_all(b"\n"))
+            .map_err(|e| format!("write archive {}: {e}", path.display()))?;
+    }
+    let file = encoder
+        .finish()
+        .map_err(|e| format!("finish archive {}: {e}", path.display()))?;
+    file.sync_all()
+        .map_err(|e| format!("sync archive {}: {e}", path.display()))
+}
+
+fn read_archive_rows(path: &Path) -> Result<Vec<serde_json::Value>, String> {
+    let file = File::open(path).map_err(|e| format!("open archive {}: {e}", path.display()))?;
+    let mut rows = Vec::new();
+    for (idx, line) in BufReader::new(MultiGzDecoder::new(file))
+        .lines()
+        .enumerate()
+    {
+        let line = line.map_err(|e| format!("read archive {}: {e}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row = serde_json::from_str(&line)
+            .map_err(|e| format!("parse archive {} line {}: {e}", path.display(), idx + 1))?;
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use chrono::NaiveDate;
+
+    use super::{
+        ArchiveTable, append_archive_member, archive_file_path, read_archive_rows,
+        resolve_archive_path,
+    };
+
+    #[test]
+    fn appended_gzip_members_read_back_as_one_archive() {
+        let dir = std::env::temp_dir().join(format!("sequoia-archive-test-{}", std::process::id()));
+        let day = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let path = archive_file_path(&dir, ArchiveTable::TerritoryEvents, day);
+        let _ = std::fs::remove_file(&path);
+
+        append_archive_member(&path, [r#"{"id":1}"#, r#"{"id":2}"#].into_iter()).unwrap();
+        append_archive_member(&path, [r#"{"id":3}"#].into_iter()).unwrap();
+
+        let rows = read_archive_rows(&path).unwrap();
+        let ids: Vec<i64> = rows.iter().filter_map(|row| row["id"].as_i64()).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert!(path.ends_with("territory_events/2025-03-01.ndjson.gz"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn resolve_archive_path_rejects_escapes_and_unknown_tables() {
+        let dir = Path::new("/var/lib/sequoia/archive");
+        let (path, table) =
+            resolve_archive_path(dir, "territory_snapshots/2025-01-02.ndjson.gz").unwrap();
+        assert_eq!(table, ArchiveTable::TerritorySnapshots);
+        assert_eq!(path, dir.join("territory_snapshots/2025-01-02.ndjson.gz"));
+
+        assert!(resolve_archive_path(dir, "../territory_events/2025-01-02.ndjson.gz").is_none());
+        assert!(resolve_archive_path(dir, "/etc/territory_events/x.ndjson.gz").is_none());
+        assert!(resolve_archive_path(dir, "claim_layouts/2025-01-02.ndjson.gz").is_none());
+        assert!(resolve_archive_path(dir, "territory_events/2025-01-02.json").is_none());
+        assert!(resolve_archive_path(dir, "territory_events/a/b.ndjson.gz").is_none());
+    }
+}
@@ -0,0 +1,759 @@
+use std::io::Write;
+
+use flate2::Compression;
+use flate2::Crc;
+use flate2::write::ZlibEncoder;
+use sequoia_shared::Region;
+
+/// OpenGraph's recommended 1.91:1 card size.
+pub const PREVIEW_WIDTH: u32 = 1200;
+pub const PREVIEW_HEIGHT: u32 = 630;
+/// Timelapse frames keep the card's aspect ratio at two thirds of the size.
+pub const TIMELAPSE_WIDTH: u32 = 800;
+pub const TIMELAPSE_HEIGHT: u32 = 420;
+
+const BACKGROUND: (u8, u8, u8) = (13, 17, 23);
+const NEUTRAL_FILL: (u8, u8, u8) = (72, 78, 90);
+const CAPTION_BAND: (u8, u8, u8) = (0, 0, 0);
+const TEXT_COLOR: (u8, u8, u8) = (240, 242, 246);
+const TEXT_SHADOW: (u8, u8, u8) = (0, 0, 0);
+const MAP_PADDING: f64 = 24.0;
+const CAPTION_BAND_HEIGHT: u32 = 56;
+const CAPTION_SCALE: u32 = 3;
+const FILL_ALPHA: u8 = 115;
+const NEUTRAL_FILL_ALPHA: u8 = 60;
+const BORDER_ALPHA: u8 = 230;
+const GLYPH_WIDTH: u32 = 5;
+const GLYPH_HEIGHT: u32 = 8;
+const GLYPH_ADVANCE: u32 = GLYPH_WIDTH + 1;
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// One territory rectangle to draw, owned by a guild (`Some((prefix, color))`) or neutral.
+#[derive(Debug, Clone)]
+pub struct PreviewTerritory {
+    pub location: Region,
+    pub owner: Option<(String, (u8, u8, u8))>,
+}
+
+/// Render territory rectangles with guild colors and prefixes plus a caption band into a PNG.
+///
+/// The map is fit to the bounds of `territories`; an empty slice renders only the caption.
+pub fn render_map_preview(territories: &[PreviewTerritory], caption: &str) -> Vec<u8> {
+    render_canvas(territories, caption, PREVIEW_WIDTH, PREVIEW_HEIGHT).encode_png()
+}
+
+fn render_canvas(
+    territories: &[PreviewTerritory],
+    caption: &str,
+    width: u32,
+    height: u32,
+) -> Canvas {
+    let mut canvas = Canvas::new(width, height, BACKGROUND);
+    let map_height = height - CAPTION_BAND_HEIGHT;
+    if let Some(projection) = Projection::fit(territories, width, map_height) {
+        let rects: Vec<_> = territories
+            .iter()
+            .map(|territory| (projection.rect(&territory.location), territory))
+            .collect();
+        for (rect, territory) in &rects {
+            let (color, alpha) = match &territory.owner {
+                Some((_, color)) => (*color, FILL_ALPHA),
+                None => (NEUTRAL_FILL, NEUTRAL_FILL_ALPHA),
+            };
+            canvas.fill_rect(*rect, color, alpha);
+        }
+        for (rect, territory) in &rects {
+            let color = territory
+                .owner
+                .as_ref()
+                .map_or(NEUTRAL_FILL, |(_, color)| *color);
+            canvas.stroke_rect(*rect, color, BORDER_ALPHA);
+        }
+        for (rect, territory) in &rects {
+            if let Some((prefix, _)) = &territory.owner {
+                canvas.draw_label(*rect, prefix);
+            }
+        }
+    }
+
+    canvas.fill_rect(
+        Rect {
+            x: 0,
+            y: map_height as i32,
+            width: width as i32,
+            height: CAPTION_BAND_HEIGHT as i32,
+        },
+        CAPTION_BAND,
+        200,
+    );
+    let max_chars = ((width - 48) / (GLYPH_ADVANCE * CAPTION_SCALE)) as usize;
+    let caption: String = caption.chars().take(max_chars).collect();
+    let text_y = map_height + (CAPTION_BAND_HEIGHT - GLYPH_HEIGHT * CAPTION_SCALE) / 2;
+    canvas.draw_text(24, text_y as i32, &caption, CAPTION_SCALE, TEXT_COLOR);
+    canvas
+}
+
+/// Animated PNG built one frame at a time at `TIMELAPSE_WIDTH` x `TIMELAPSE_HEIGHT`.
+///
+/// Only the region that changed since the previous frame is stored, and unchanged frames
+/// extend the previous frame's delay, so long quiet stretches cost almost nothing.
+pub struct TimelapseEncoder {
+    fps: u16,
+    previous: Option<Canvas>,
+    pending: Option<PendingFrame>,
+    frames: u32,
+    sequence: u32,
+    chunks: Vec<u8>,
+}
+
+struct PendingFrame {
+    region: Rect,
+    data: Vec<u8>,
+    delay: u16,
+}
+
+impl TimelapseEncoder {
+    pub fn new(fps: u16) -> Self {
+        Self {
+            fps: fps.max(1),
+            previous: None,
+            pending: None,
+            frames: 0,
+            sequence: 0,
+            chunks: Vec::new(),
+        }
+    }
+
+    pub fn push_frame(&mut self, territories: &[PreviewTerritory], caption: &str) {
+        let canvas = render_canvas(territories, caption, TIMELAPSE_WIDTH, TIMELAPSE_HEIGHT);
+        let region = match &self.previous {
+            None => Some(canvas.bounds()),
+            Some(previous) => previous.changed_region(&canvas),
+        };
+        match region {
+            Some(region) => {
+                self.flush_pending();
+                self.pending = Some(PendingFrame {
+                    region,
+                    data: canvas.compress_region(region),
+                    delay: 1,
+                });
+            }
+            None => {
+                if let Some(pending) = self.pending.as_mut()
+                    && pending.delay < u16::MAX
+                {
+                    pending.delay += 1;
+                }
+            }
+        }
+        self.previous = Some(canvas);
+    }
+
+    /// Hold the final frame for `frames` extra frame durations.
+    pub fn hold_last_frame(&mut self, frames: u16) {
+        if let Some(pending) = self.pending.as_mut() {
+            pending.delay = pending.delay.saturating_add(frames);
+        }
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        self.flush_pending();
+        let (width, height) = self
+            .previous
+            .as_ref()
+            .map_or((TIMELAPSE_WIDTH, TIMELAPSE_HEIGHT), |canvas| {
+                (canvas.width, canvas.height)
+            });
+
+        let mut animation_control = Vec::with_capacity(8);
+        animation_control.extend_from_slice(&self.frames.to_be_bytes());
+        // Zero plays means loop forever.
+        animation_control.extend_from_slice(&0u32.to_be_bytes());
+
+        let mut png = Vec::with_capacity(self.chunks.len() + 64);
+        png.extend_from_slice(&PNG_SIGNATURE);
+        write_png_chunk(&mut png, b"IHDR", &png_header(width, height));
+        write_png_chunk(&mut png, b"acTL", &animation_control);
+        png.extend_from_slice(&self.chunks);
+        write_png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    fn flush_pending(&mut self) {
+        let Some(frame) = self.pending.take() else {
+            return;
+        };
+        let mut control = Vec::with_capacity(26);
+        control.extend_from_slice(&self.next_sequence().to_be_bytes());
+        for value in [
+            frame.region.width,
+            frame.region.height,
+            frame.region.x,
+            frame.region.y,
+        ] {
+            control.extend_from_slice(&(value as u32).to_be_bytes());
+        }
+        control.extend_from_slice(&frame.delay.to_be_bytes());
+        control.extend_from_slice(&self.fps.to_be_bytes());
+        // Dispose: none; blend: source, so each delta fully replaces its region.
+        control.extend_from_slice(&[0, 0]);
+        write_png_chunk(&mut self.chunks, b"fcTL", &control);
+
+        if self.frames == 0 {
+            write_png_chunk(&mut self.chunks, b"IDAT", &frame.data);
+        } else {
+            let mut frame_data = Vec::with_capacity(frame.data.len() + 4);
+            frame_data.extend_from_slice(&self.next_sequence().to_be_bytes());
+            frame_data.extend_from_slice(&frame.data);
+            write_png_chunk(&mut self.chunks, b"fdAT", &frame_data);
+        }
+        self.frames += 1;
+    }
+
+    fn next_sequence(&mut self) -> u32 {
+        let sequence = self.sequence;
+        self.sequence += 1;
+        sequence
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rect {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+/// World-to-pixel transform that keeps the aspect ratio and centers the map.
+struct Projection {
+    min_x: f64,
+    min_z: f64,
+    scale: f64,
+    offset_x: f64,
+    offset_y: f64,
+}
+
+impl Projection {
+    fn fit(territories: &[PreviewTerritory], width: u32, height: u32) -> Option<Self> {
+        let mut bounds: Option<(f64, f64, f64, f64)> = None;
+        for territory in territories {
+            let (x0, z0, x1, z1) = world_bounds(&territory.location);
+            bounds = Some(match bounds {
+                Some((min_x, min_z, max_x, max_z)) => {
+                    (min_x.min(x0), min_z.min(z0), max_x.max(x1), max_z.max(z1))
+                }
+                None => (x0, z0, x1, z1),
+            });
+        }
+        let (min_x, min_z, max_x, max_z) = bounds?;
+        let world_width = (max_x - min_x).max(1.0);
+        let world_height = (max_z - min_z).max(1.0);
+        let avail_width = f64::from(width) - 2.0 * MAP_PADDING;
+        let avail_height = f64::from(height) - 2.0 * MAP_PADDING;
+        let scale = (avail_width / world_width).min(avail_height / world_height);
+        Some(Self {
+            min_x,
+            min_z,
+            scale,
+            offset_x: (f64::from(width) - world_width * scale) / 2.0,
+            offset_y: (f64::from(height) - world_height * scale) / 2.0,
+        })
+    }
+
+    fn rect(&self, region: &Region) -> Rect {
+        let (x0, z0, x1, z1) = world_bounds(region);
+        let left = ((x0 - self.min_x) * self.scale + self.offset_x).round() as i32;
+        let top = ((z0 - self.min_z) * self.scale + self.offset_y).round() as i32;
+        let right = ((x1 - self.min_x) * self.scale + self.offset_x).round() as i32;
+        let bottom = ((z1 - self.min_z) * self.scale + self.offset_y).round() as i32;
+        Rect {
+            x: left,
+            y: top,
+            width: (right - left).max(1),
+            height: (bottom - top).max(1),
+        }
+    }
+}
+
+fn world_bounds(region: &Region) -> (f64, f64, f64, f64) {
+    let [sx, sz] = region.start;
+    let [ex, ez] = region.end;
+    (
+        f64::from(sx.min(ex)),
+        f64::from(sz.min(ez)),
+        f64::from(sx.max(ex)),
+        f64::from(sz.max(ez)),
+    )
+}
+
+/// Opaque RGB pixel buffer.
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, background: (u8, u8, u8)) -> Self {
+        let mut pixels = Vec::with_capacity((width * height * 3) as usize);
+        for _ in 0..width * height {
+            pixels.extend_from_slice(&[background.0, background.1, background.2]);
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, color: (u8, u8, u8), alpha: u8) {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return;
+        }
+        let idx = ((y as u32 * self.width + x as u32) * 3) as usize;
+        let alpha = u16::from(alpha);
+        for (channel, value) in [color.0, color.1, color.2].into_iter().enumerate() {
+            let dst = u16::from(self.pixels[idx + channel]);
+            self.pixels[idx + channel] =
+                ((u16::from(value) * alpha + dst * (255 - alpha) + 127) / 255) as u8;
+        }
+    }
+
+    fn fill_rect(&mut self, rect: Rect, color: (u8, u8, u8), alpha: u8) {
+        let x0 = rect.x.max(0);
+        let y0 = rect.y.max(0);
+        let x1 = (rect.x + rect.width).min(self.width as i32);
+        let y1 = (rect.y + rect.height).min(self.height as i32);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                self.blend_pixel(x, y, color, alpha);
+            }
+        }
+    }
+
+    fn stroke_rect(&mut self, rect: Rect, color: (u8, u8, u8), alpha: u8) {
+        let right = rect.x + rect.width - 1;
+        let bottom = rect.y + rect.height - 1;
+        for x in rect.x..=right {
+            self.blend_pixel(x, rect.y, color, alpha);
+            if bottom != rect.y {
+                self.blend_pixel(x, bottom, color, alpha);
+            }
+        }
+        for y in rect.y + 1..bottom {
+            self.blend_pixel(rect.x, y, color, alpha);
+            if right != rect.x {
+                self.blend_pixel(right, y, color, alpha);
+            }
+        }
+    }
+
+    /// Center `text` in `rect` at the largest scale (up to 2x) that fits; skip it otherwise.
+    fn draw_label(&mut self, rect: Rect, text: &str) {
+        let Some(scale) = [2, 1].into_iter().find(|scale| {
+            text_width(text, *scale) as i32 + 4 <= rect.width
+                && (GLYPH_HEIGHT * scale) as i32 + 4 <= rect.height
+        }) else {
+            return;
+        };
+        let x = rect.x + (rect.width - text_width(text, scale) as i32) / 2;
+        let y = rect.y + (rect.height - (GLYPH_HEIGHT * scale) as i32) / 2;
+        self.draw_text(x + 1, y + 1, text, scale, TEXT_SHADOW);
+        self.draw_text(x, y, text, scale, TEXT_COLOR);
+    }
+
+    fn draw_text(&mut self, x: i32, y: i32, text: &str, scale: u32, color: (u8, u8, u8)) {
+        let scale = scale as i32;
+        let mut pen_x = x;
+        for ch in text.chars() {
+            for (column, bits) in glyph(ch).iter().enumerate() {
+                for row in 0..GLYPH_HEIGHT as i32 {
+                    if bits & (1 << row) == 0 {
+                        continue;
+                    }
+                    self.fill_rect(
+                        Rect {
+                            x: pen_x + column as i32 * scale,
+                            y: y + row * scale,
+                            width: scale,
+                            height: scale,
+                        },
+                        color,
+                        255,
+                    );
+                }
+            }
+            pen_x += GLYPH_ADVANCE as i32 * scale;
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        Rect {
+            x: 0,
+            y: 0,
+            width: self.width as i32,
+            height: self.height as i32,
+        }
+    }
+
+    /// Smallest rectangle containing every pixel that differs from `other`.
+    fn changed_region(&self, other: &Canvas) -> Option<Rect> {
+        let stride = (self.width * 3) as usize;
+        let mut bounds: Option<(usize, usize, usize, usize)> = None;
+        for (y, (a, b)) in self
+            .pixels
+            .chunks_exact(stride)
+            .zip(other.pixels.chunks_exact(stride))
+            .enumerate()
+        {
+            if a == b {
+                continue;
+            }
+            let first = a
+                .chunks_exact(3)
+                .zip(b.chunks_exact(3))
+                .position(|(a, b)| a != b)
+                .unwrap_or(0);
+            let last = a
+                .chunks_exact(3)
+                .zip(b.chunks_exact(3))
+                .rposition(|(a, b)| a != b)
+                .unwrap_or(first);
+            bounds = Some(match bounds {
+                Some((x0, y0, x1, _)) => (x0.min(first), y0, x1.max(last), y),
+                None => (first, y, last, y),
+            });
+        }
+        bounds.map(|(x0, y0, x1, y1)| Rect {
+            x: x0 as i32,
+            y: y0 as i32,
+            width: (x1 - x0 + 1) as i32,
+            height: (y1 - y0 + 1) as i32,
+        })
+    }
+
+    /// Zlib stream of unfiltered RGB scanlines for `region`, as stored in IDAT/fdAT.
+    fn compress_region(&self, region: Rect) -> Vec<u8> {
+        let stride = (self.width * 3) as usize;
+        let x0 = region.x as usize * 3;
+        let x1 = x0 + region.width as usize * 3;
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::fast());
+        for row in self
+            .pixels
+            .chunks_exact(stride)
+            .skip(region.y as usize)
+            .take(region.height as usize)
+        {
+            encoder
+                .write_all(&[0])
+                .and_then(|()| encoder.write_all(&row[x0..x1]))
+                .expect("writing to a Vec cannot fail");
+        }
+        encoder.finish().expect("writing to a Vec cannot fail")
+    }
+
+    /// Encode as an 8-bit truecolor PNG with unfiltered scanlines.
+    fn encode_png(&self) -> Vec<u8> {
+        let image_data = self.compress_region(self.bounds());
+        let mut png = Vec::with_capacity(image_data.len() + 64);
+        png.extend_from_slice(&PNG_SIGNATURE);
+        write_png_chunk(&mut png, b"IHDR", &png_header(self.width, self.height));
+        write_png_chunk(&mut png, b"IDAT", &image_data);
+        write_png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+fn png_header(width: u32, height: u32) -> Vec<u8> {
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // Bit depth 8, color type 2 (RGB), deflate, adaptive filtering, no interlace.
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+    header
+}
+
+fn write_png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let mut crc = Crc::new();
+    crc.update(kind);
+    crc.update(data);
+    out.extend_from_slice(&crc.sum().to_be_bytes());
+}
+
+fn text_width(text: &str, scale: u32) -> u32 {
+    let chars = text.chars().count() as u32;
+    (chars * GLYPH_ADVANCE).saturating_sub(1) * scale
+}
+
+/// Column bitmaps (least significant bit on top) for printable ASCII; anything else draws `?`.
+fn glyph(ch: char) -> &'static [u8; 5] {
+    let idx = match ch {
+        ' '..='~' => ch as usize - ' ' as usize,
+        _ => '?' as usize - ' ' as usize,
+    };
+    &FONT_5X8[idx]
+}
+
+const FONT_5X8: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // !
+    [0x00, 0x07, 0x00, 0x07, 0x00], // "
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // #
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // $
+    [0x23, 0x13, 0x08, 0x64, 0x62], // %
+    [0x36, 0x49, 0x56, 0x20, 0x50], // &
+    [0x00, 0x08, 0x07, 0x03, 0x00], // '
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // (
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // )
+    [0x2A, 0x1C, 0x7F, 0x1C, 0x2A], // *
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // +
+    [0x00, 0x80, 0x70, 0x30, 0x00], // ,
+    [0x08, 0x08, 0x08, 0x08, 0x08], // -
+    [0x00, 0x00, 0x60, 0x60, 0x00], // .
+    [0x20, 0x10, 0x08, 0x04, 0x02], // /
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // 0
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // 1
+    [0x72, 0x49, 0x49, 0x49, 0x46], // 2
+    [0x21, 0x41, 0x49, 0x4D, 0x33], // 3
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // 4
+    [0x27, 0x45, 0x45, 0x45, 0x39], // 5
+    [0x3C, 0x4A, 0x49, 0x49, 0x31], // 6
+    [0x41, 0x21, 0x11, 0x09, 0x07], // 7
+    [0x36, 0x49, 0x49, 0x49, 0x36], // 8
+    [0x46, 0x49, 0x49, 0x29, 0x1E], // 9
+    [0x00, 0x00, 0x14, 0x00, 0x00], // :
+    [0x00, 0x40, 0x34, 0x00, 0x00], // ;
+    [0x00, 0x08, 0x14, 0x22, 0x41], // <
+    [0x14, 0x14, 0x14, 0x14, 0x14], // =
+    [0x00, 0x41, 0x22, 0x14, 0x08], // >
+    [0x02, 0x01, 0x59, 0x09, 0x06], // ?
+    [0x3E, 0x41, 0x5D, 0x59, 0x4E], // @
+    [0x7C, 0x12, 0x11, 0x12, 0x7C], // A
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // B
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // C
+    [0x7F, 0x41, 0x41, 0x41, 0x3E], // D
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // E
+    [0x7F, 0x09, 0x09, 0x09, 0x01], // F
+    [0x3E, 0x41, 0x41, 0x51, 0x73], // G
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // H
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // I
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // J
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // K
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // L
+    [0x7F, 0x02, 0x1C, 0x02, 0x7F], // M
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // N
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // O
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // P
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // Q
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // R
+    [0x26, 0x49, 0x49, 0x49, 0x32], // S
+    [0x03, 0x01, 0x7F, 0x01, 0x03], // T
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // U
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // V
+    [0x3F, 0x40, 0x38, 0x40, 0x3F], // W
+    [0x63, 0x14, 0x08, 0x14, 0x63], // X
+    [0x03, 0x04, 0x78, 0x04, 0x03], // Y
+    [0x61, 0x59, 0x49, 0x4D, 0x43], // Z
+    [0x00, 0x7F, 0x41, 0x41, 0x41], // [
+    [0x02, 0x04, 0x08, 0x10, 0x20], // \
+    [0x00, 0x41, 0x41, 0x41, 0x7F], // ]
+    [0x04, 0x02, 0x01, 0x02, 0x04], // ^
+    [0x40, 0x40, 0x40, 0x40, 0x40], // _
+    [0x00, 0x03, 0x07, 0x08, 0x00], // `
+    [0x20, 0x54, 0x54, 0x78, 0x40], // a
+    [0x7F, 0x28, 0x44, 0x44, 0x38], // b
+    [0x38, 0x44, 0x44, 0x44, 0x28], // c
+    [0x38, 0x44, 0x44, 0x28, 0x7F], // d
+    [0x38, 0x54, 0x54, 0x54, 0x18], // e
+    [0x00, 0x08, 0x7E, 0x09, 0x02], // f
+    [0x18, 0xA4, 0xA4, 0x9C, 0x78], // g
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // h
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // i
+    [0x20, 0x40, 0x40, 0x3D, 0x00], // j
+    [0x7F, 0x10, 0x28, 0x44, 0x00], // k
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // l
+    [0x7C, 0x04, 0x78, 0x04, 0x78], // m
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // n
+    [0x38, 0x44, 0x44, 0x44, 0x38], // o
+    [0xFC, 0x18, 0x24, 0x24, 0x18], // p
+    [0x18, 0x24, 0x24, 0x18, 0xFC], // q
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // r
+    [0x48, 0x54, 0x54, 0x54, 0x24], // s
+    [0x04, 0x04, 0x3F, 0x44, 0x24], // t
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // u
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // v
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // w
+    [0x44, 0x28, 0x10, 0x28, 0x44], // x
+    [0x4C, 0x90, 0x90, 0x90, 0x7C], // y
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // z
+    [0x00, 0x08, 0x36, 0x41, 0x00], // {
+    [0x00, 0x00, 0x77, 0x00, 0x00], // |
+    [0x00, 0x41, 0x36, 0x08, 0x00], // }
+    [0x02, 0x01, 0x02, 0x04, 0x02], // ~
+];
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::ZlibDecoder;
+    use sequoia_shared::Region;
+
+    use super::{
+        BACKGROUND, Canvas, PNG_SIGNATURE, PREVIEW_HEIGHT, PREVIEW_WIDTH, PreviewTerritory, Rect,
+        TIMELAPSE_WIDTH, TimelapseEncoder, render_map_preview, text_width,
+    };
+
+    fn territory(start: [i32; 2], end: [i32; 2], owner: Option<&str>) -> PreviewTerritory {
+        PreviewTerritory {
+            location: Region { start, end },
+            owner: owner.map(|prefix| (prefix.to_string(), (200, 40, 40))),
+        }
+    }
+
+    /// Decode the unfiltered RGB scanlines written by `encode_png`.
+    fn decode_pixels(png: &[u8]) -> (u32, u32, Vec<u8>) {
+        assert_eq!(png[..8], PNG_SIGNATURE);
+        let mut offset = 8;
+        let (mut width, mut height, mut image_data) = (0, 0, Vec::new());
+        while offset < png.len() {
+            let len = u32::from_be_bytes(png[offset..offset + 4].try_into().unwrap()) as usize;
+            let kind = &png[offset + 4..offset + 8];
+            let data = &png[offset + 8..offset + 8 + len];
+            match kind {
+                b"IHDR" => {
+                    width = u32::from_be_bytes(data[0..4].try_into().unwrap());
+                    height = u32::from_be_bytes(data[4..8].try_into().unwrap());
+                    assert_eq!(&data[8..10], &[8, 2]);
+                }
+                b"IDAT" => image_data.extend_from_slice(data),
+                _ => {}
+            }
+            offset += 12 + len;
+        }
+        let mut raw = Vec::new();
+        ZlibDecoder::new(image_data.as_slice())
+            .read_to_end(&mut raw)
+            .unwrap();
+        let pixels = raw
+            .chunks_exact(width as usize * 3 + 1)
+            .flat_map(|row| {
+                assert_eq!(row[0], 0);
+                row[1..].to_vec()
+            })
+            .collect();
+        (width, height, pixels)
+    }
+
+    #[test]
+    fn png_chunks_carry_valid_crcs() {
+        let png = Canvas::new(2, 1, (1, 2, 3)).encode_png();
+        // IEND always ends with the same well-known CRC.
+        assert_eq!(png[png.len() - 4..], [0xAE, 0x42, 0x60, 0x82]);
+        let (width, height, pixels) = decode_pixels(&png);
+        assert_eq!((width, height), (2, 1));
+        assert_eq!(pixels, vec![1, 2, 3, 1, 2, 3]);
+    }
+
+    #[test]
+    fn preview_paints_guild_fills_and_fits_map_to_canvas() {
+        let png = render_map_preview(
+            &[
+                territory([0, 0], [100, 100], Some("ABC")),
+                territory([200, 100], [100, 0], None),
+            ],
+            "Test plan",
+        );
+        let (width, height, pixels) = decode_pixels(&png);
+        assert_eq!((width, height), (PREVIEW_WIDTH, PREVIEW_HEIGHT));
+        let pixel = |x: u32, y: u32| {
+            let idx = ((y * width + x) * 3) as usize;
+            (pixels[idx], pixels[idx + 1], pixels[idx + 2])
+        };
+
+        assert_eq!(pixel(2, 2), BACKGROUND);
+        // Near the guild rectangle's top-left corner, clear of border and label.
+        let guild = pixel(PREVIEW_WIDTH / 2 - 200, 140);
+        assert!(guild.0 > guild.1 && guild.0 > BACKGROUND.0);
+        let neutral = pixel(PREVIEW_WIDTH / 2 + 200, 140);
+        assert!(neutral.0 > BACKGROUND.0 && neutral.2 > BACKGROUND.2);
+        assert_ne!(guild, neutral);
+    }
+
+    fn chunks(png: &[u8]) -> Vec<(String, Vec<u8>)> {
+        let mut offset = PNG_SIGNATURE.len();
+        let mut chunks = Vec::new();
+        while offset < png.len() {
+            let len = u32::from_be_bytes(png[offset..offset + 4].try_into().unwrap()) as usize;
+            let kind = String::from_utf8(png[offset + 4..offset + 8].to_vec()).unwrap();
+            chunks.push((kind, png[offset + 8..offset + 8 + len].to_vec()));
+            offset += 12 + len;
+        }
+        chunks
+    }
+
+    fn be_u32(bytes: &[u8]) -> u32 {
+        u32::from_be_bytes(bytes[..4].try_into().unwrap())
+    }
+
+    #[test]
+    fn timelapse_stores_changed_regions_and_merges_unchanged_frames() {
+        let before = vec![
+            territory([0, 0], [100, 100], Some("ABC")),
+            territory([200, 0], [300, 100], Some("ABC")),
+        ];
+        let mut after = before.clone();
+        after[1].owner = Some(("XYZ".to_string(), (40, 40, 200)));
+
+        let mut encoder = TimelapseEncoder::new(4);
+        encoder.push_frame(&before, "Frame");
+        encoder.push_frame(&before, "Frame");
+        encoder.push_frame(&after, "Frame");
+        encoder.hold_last_frame(3);
+        let png = encoder.finish();
+
+        let chunks = chunks(&png);
+        let kinds: Vec<&str> = chunks.iter().map(|(kind, _)| kind.as_str()).collect();
+        assert_eq!(
+            kinds,
+            vec!["IHDR", "acTL", "fcTL", "IDAT", "fcTL", "fdAT", "IEND"]
+        );
+        assert_eq!(be_u32(&chunks[1].1), 2);
+
+        let first = &chunks[2].1;
+        assert_eq!(be_u32(&first[4..]), TIMELAPSE_WIDTH);
+        assert_eq!(u16::from_be_bytes([first[20], first[21]]), 2);
+        assert_eq!(u16::from_be_bytes([first[22], first[23]]), 4);
+
+        // Only the repainted territory on the right half is re-encoded.
+        let second = &chunks[4].1;
+        assert_eq!(be_u32(second), 1);
+        assert!(be_u32(&second[4..]) < TIMELAPSE_WIDTH / 2);
+        assert!(be_u32(&second[12..]) > TIMELAPSE_WIDTH / 2);
+        assert_eq!(u16::from_be_bytes([second[20], second[21]]), 4);
+        assert_eq!(be_u32(&chunks[5].1), 2);
+    }
+
+    #[test]
+    fn labels_only_draw_when_they_fit() {
+        let mut canvas = Canvas::new(40, 20, (0, 0, 0));
+        let rect = Rect {
+            x: 0,
+            y: 0,
+            width: 40,
+            height: 20,
+        };
+        canvas.draw_label(rect, "ABC");
+        assert!(canvas.pixels.iter().any(|value| *value > 200));
+        assert_eq!(text_width("ABC", 2), 34);
+
+        let mut canvas = Canvas::new(40, 20, (0, 0, 0));
+        canvas.draw_label(rect, "TOOLONG");
+        assert!(canvas.pixels.iter().all(|value| *value == 0));
+    }
+}
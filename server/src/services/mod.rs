@@ -1,10 +1,19 @@
 pub mod extra_data_loader;
 pub mod guild_color_loader;
 pub mod guild_evictor;
+pub mod guild_hq_tracker;
+pub mod history_archive;
+pub mod map_preview;
+pub mod notifier;
 pub mod retention_cleaner;
+pub mod season_backtest;
 pub mod season_components;
 pub mod season_data;
 pub mod season_race;
+#[cfg(test)]
+mod season_race_fixtures;
+pub mod season_race_scenario;
+pub mod season_race_simulation;
 pub mod season_scalar_estimator;
 pub mod season_scalar_forecast;
 pub mod snapshot_service;
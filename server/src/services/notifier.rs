@@ -0,0 +1,837 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+use sequoia_shared::guild_hq::{GuildHqSource, infer_guild_hqs};
+use sequoia_shared::{TerritoryChange, TerritoryEvent, TerritoryMap};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, info, warn};
+
+use crate::config::{
+    GUILD_HQ_MOVE_MIN_CONFIDENCE, WEBHOOK_DISPATCH_BATCH, WEBHOOK_DISPATCH_INTERVAL_SECS,
+    WEBHOOK_MAX_ATTEMPTS, WEBHOOK_OUTBOX_RETENTION_DAYS, WEBHOOK_RULE_REFRESH_SECS,
+    map_public_base_url,
+};
+use crate::state::{AppState, PreSerializedEvent};
+
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "x-sequoia-signature";
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "x-sequoia-timestamp";
+const WEBHOOK_USERNAME: &str = "Sequoia Map";
+const WEBHOOK_RETRY_BASE_SECS: i64 = 30;
+const WEBHOOK_RETRY_MAX_SECS: i64 = 3600;
+const OUTBOX_PRUNE_EVERY_TICKS: u64 = 720; // hourly at the default dispatch interval
+const EMBED_COLOR_DEFAULT: u32 = 0xF5C542;
+
+type OutboxRow = (i64, i32, serde_json::Value, String, Option<String>);
+
+/// Condition a webhook rule fires on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WebhookTrigger {
+    /// A territory changes hands. Narrow by territory, by a guild on either side, or both.
+    OwnerChange {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        guild: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        territory: Option<String>,
+    },
+    /// A guild's territory count drops from at least `threshold` to below it.
+    GuildBelow { guild: String, threshold: u32 },
+    /// A guild's headquarters moves to a different territory.
+    HqMoved { guild: String },
+}
+
+impl WebhookTrigger {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        match self {
+            Self::OwnerChange { guild, territory } => {
+                if guild.as_deref().is_none_or(|value| value.trim().is_empty())
+                    && territory
+                        .as_deref()
+                        .is_none_or(|value| value.trim().is_empty())
+                {
+                    return Err("owner_change needs a guild or territory");
+                }
+            }
+            Self::GuildBelow { guild, threshold } => {
+                if guild.trim().is_empty() {
+                    return Err("guild_below needs a guild");
+                }
+                if *threshold == 0 {
+                    return Err("guild_below threshold must be positive");
+                }
+            }
+            Self::HqMoved { guild } => {
+                if guild.trim().is_empty() {
+                    return Err("hq_moved needs a guild");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn guild(&self) -> Option<&str> {
+        match self {
+            Self::OwnerChange { guild, .. } => guild.as_deref(),
+            Self::GuildBelow { guild, .. } | Self::HqMoved { guild } => Some(guild),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookRule {
+    pub id: i64,
+    pub name: String,
+    pub trigger: WebhookTrigger,
+}
+
+/// One rule match, ready to be rendered into an outbox payload.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub rule_id: i64,
+    pub title: String,
+    pub description: String,
+    pub color: Option<(u8, u8, u8)>,
+    pub territory: Option<String>,
+    /// Whether an HQ move was reported by ingest or inferred from the map.
+    pub hq_source: Option<GuildHqSource>,
+}
+
+/// Per-rule evaluation state carried between broadcast events.
+#[derive(Debug, Default)]
+pub struct NotifierEngine {
+    rules: Vec<WebhookRule>,
+    /// Last observed territory count per `guild_below` rule.
+    guild_counts: HashMap<i64, u32>,
+    /// Last known headquarters per lowercased guild name.
+    guild_hq: HashMap<String, String>,
+}
+
+impl NotifierEngine {
+    /// Swap in a fresh rule set, seeding threshold and HQ state from the live map so that
+    /// reloading never fires on state that was already true. HQs are only tracked while an
+    /// `hq_moved` rule exists.
+    pub fn set_rules(&mut self, rules: Vec<WebhookRule>, territories: &TerritoryMap) {
+        self.guild_counts = rules
+            .iter()
+            .filter_map(|rule| match &rule.trigger {
+                WebhookTrigger::GuildBelow { guild, .. } => Some((
+                    rule.id,
+                    self.guild_counts
+                        .get(&rule.id)
+                        .copied()
+                        .unwrap_or_else(|| count_guild_territories(territories, guild)),
+                )),
+                _ => None,
+            })
+            .collect();
+        self.rules = rules;
+        if !self.watches_hqs() {
+            self.guild_hq.clear();
+            return;
+        }
+        for estimate in infer_guild_hqs(territories, &self.guild_hq) {
+            if is_confident_hq(estimate.source, estimate.confidence) {
+                self.guild_hq
+                    .entry(estimate.guild_name.to_ascii_lowercase())
+                    .or_insert(estimate.territory);
+            }
+        }
+    }
+
+    fn watches_hqs(&self) -> bool {
+        self.rules
+            .iter()
+            .any(|rule| matches!(rule.trigger, WebhookTrigger::HqMoved { .. }))
+    }
+
+    pub fn has_rules(&self) -> bool {
+        !self.rules.is_empty()
+    }
+
+    /// Evaluate an ownership update against the live map as it stands after the update.
+    pub fn on_changes(
+        &mut self,
+        changes: &[TerritoryChange],
+        territories: &TerritoryMap,
+    ) -> Vec<Notification> {
+        let mut notifications = Vec::new();
+        for rule in &self.rules {
+            match &rule.trigger {
+                WebhookTrigger::OwnerChange { guild, territory } => {
+                    for change in changes {
+                        let Some(previous) = change.previous_guild.as_ref() else {
+                            continue;
+                        };
+                        if previous.name == change.guild.name {
+                            continue;
+                        }
+                        if territory
+                            .as_deref()
+                            .is_some_and(|wanted| !wanted.eq_ignore_ascii_case(&change.territory))
+                        {
+                            continue;
+                        }
+                        if guild.as_deref().is_some_and(|wanted| {
+                            !wanted.eq_ignore_ascii_case(&change.guild.name)
+                                && !wanted.eq_ignore_ascii_case(&previous.name)
+                        }) {
+                            continue;
+                        }
+                        notifications.push(Notification {
+                            rule_id: rule.id,
+                            title: format!("{} changed hands", change.territory),
+                            description: format!(
+                                "[{}] {} took **{}** from [{}] {}",
+                                change.guild.prefix,
+                                change.guild.name,
+                                change.territory,
+                                previous.prefix,
+                                previous.name
+                            ),
+                            color: change.guild.color,
+                            territory: Some(change.territory.clone()),
+                            hq_source: None,
+                        });
+                    }
+                }
+                WebhookTrigger::GuildBelow { guild, threshold } => {
+                    let current = count_guild_territories(territories, guild);
+                    let previous = self
+                        .guild_counts
+                        .insert(rule.id, current)
+                        .unwrap_or(current);
+                    if previous >= *threshold && current < *threshold {
+                        notifications.push(Notification {
+                            rule_id: rule.id,
+                            title: format!("{guild} dropped below {threshold} territories"),
+                            description: format!(
+                                "**{guild}** now holds {current} territories (was {previous})."
+                            ),
+                            color: None,
+                            territory: None,
+                            hq_source: None,
+                        });
+                    }
+                }
+                WebhookTrigger::HqMoved { .. } => {}
+            }
+        }
+
+        // Ownership changes shift inferred HQs as much as runtime flags do.
+        notifications.extend(self.on_hq_changes(territories));
+        notifications
+    }
+
+    /// Re-estimate HQs with the shared inference and report moves for watched guilds.
+    ///
+    /// Uses the same confidence floor as the HQ tracker; each notification carries whether the
+    /// new HQ was observed by ingest or inferred, so inferred moves are not posted as confirmed.
+    pub fn on_hq_changes(&mut self, territories: &TerritoryMap) -> Vec<Notification> {
+        let mut notifications = Vec::new();
+        if !self.watches_hqs() {
+            return notifications;
+        }
+        for estimate in infer_guild_hqs(territories, &self.guild_hq) {
+            if !is_confident_hq(estimate.source, estimate.confidence) {
+                continue;
+            }
+            let key = estimate.guild_name.to_ascii_lowercase();
+            let previous = self.guild_hq.insert(key, estimate.territory.clone());
+            let Some(previous) = previous.filter(|previous| *previous != estimate.territory) else {
+                continue;
+            };
+            let Some(owner) = territories.get(&estimate.territory).map(|t| &t.guild) else {
+                continue;
+            };
+            let (title, qualifier) = match estimate.source {
+                GuildHqSource::Observed => {
+                    (format!("{} moved their HQ", owner.name), String::new())
+                }
+                GuildHqSource::Inferred => (
+                    format!("{} likely moved their HQ", owner.name),
+                    format!(
+                        " (inferred, {:.0}% confidence)",
+                        estimate.confidence * 100.0
+                    ),
+                ),
+            };
+            for rule in &self.rules {
+                if !matches!(rule.trigger, WebhookTrigger::HqMoved { .. })
+                    || !rule
+                        .trigger
+                        .guild()
+                        .is_some_and(|guild| guild.eq_ignore_ascii_case(&owner.name))
+                {
+                    continue;
+                }
+                notifications.push(Notification {
+                    rule_id: rule.id,
+                    title: title.clone(),
+                    description: format!(
+                        "[{}] {} moved headquarters from {} to **{}**{qualifier}",
+                        owner.prefix, owner.name, previous, estimate.territory
+                    ),
+                    color: owner.color,
+                    territory: Some(estimate.territory.clone()),
+                    hq_source: Some(estimate.source),
+                });
+            }
+        }
+        notifications
+    }
+}
+
+fn is_confident_hq(source: GuildHqSource, confidence: f64) -> bool {
+    source == GuildHqSource::Observed || confidence >= GUILD_HQ_MOVE_MIN_CONFIDENCE
+}
+
+fn count_guild_territories(territories: &TerritoryMap, guild: &str) -> u32 {
+    let count = territories
+        .values()
+        .filter(|territory| territory.guild.name.eq_ignore_ascii_case(guild))
+        .count();
+    u32::try_from(count).unwrap_or(u32::MAX)
+}
+
+/// Discord-compatible webhook body; generic receivers can read the same embed fields.
+pub fn build_discord_payload(
+    rule_name: &str,
+    notification: &Notification,
+    timestamp: DateTime<Utc>,
+    map_base_url: &str,
+) -> serde_json::Value {
+    let color = notification
+        .color
+        .map(|(r, g, b)| (u32::from(r) << 16) | (u32::from(g) << 8) | u32::from(b))
+        .unwrap_or(EMBED_COLOR_DEFAULT);
+    let url = match notification.territory.as_deref() {
+        Some(territory) => format!(
+            "{map_base_url}/?territory={}",
+            utf8_percent_encode(territory, NON_ALPHANUMERIC)
+        ),
+        None => map_base_url.to_string(),
+    };
+    let mut embed = serde_json::json!({
+        "title": notification.title,
+        "description": notification.description,
+        "color": color,
+        "url": url,
+        "timestamp": timestamp.to_rfc3339(),
+        "footer": { "text": format!("Rule: {rule_name}") },
+    });
+    if let Some(source) = notification.hq_source {
+        embed["fields"] = serde_json::json!([{
+            "name": "HQ source",
+            "value": source.as_str(),
+            "inline": true,
+        }]);
+    }
+    serde_json::json!({
+        "username": WEBHOOK_USERNAME,
+        "embeds": [embed],
+    })
+}
+
+/// Hex HMAC-SHA256 over `{timestamp}.{body}`, sent as `sha256=<hex>`.
+pub fn sign_webhook_body(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    let digest = mac.finalize().into_bytes();
+    let hex: String = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("sha256={hex}")
+}
+
+/// Exponential backoff after `attempts` failed deliveries.
+pub fn webhook_retry_delay_secs(attempts: i32) -> i64 {
+    let exponent = u32::try_from(attempts.clamp(0, 16)).unwrap_or(0);
+    WEBHOOK_RETRY_BASE_SECS
+        .saturating_mul(1_i64 << exponent)
+        .min(WEBHOOK_RETRY_MAX_SECS)
+}
+
+/// Subscribe to the broadcast channel and enqueue matching webhook notifications.
+pub async fn run(state: AppState) {
+    let Some(pool) = state.db.as_ref().cloned() else {
+        warn!("webhook notifier disabled: no database configured");
+        return;
+    };
+    let mut rx = state.event_tx.subscribe();
+    let mut engine = NotifierEngine::default();
+    let mut refresh = tokio::time::interval(Duration::from_secs(WEBHOOK_RULE_REFRESH_SECS));
+
+    loop {
+        tokio::select! {
+            _ = refresh.tick() => {
+                match load_enabled_rules(&pool).await {
+                    Ok(rules) => {
+                        let snapshot = state.live_snapshot.read().await;
+                        engine.set_rules(rules, &snapshot.territories);
+                    }
+                    Err(e) => warn!("failed to load webhook rules: {e}"),
+                }
+            }
+            received = rx.recv() => {
+                let event = match received {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("webhook notifier lagged; skipped {skipped} events");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return,
+                };
+                if !engine.has_rules() {
+                    continue;
+                }
+                let notifications = match event {
+                    PreSerializedEvent::Update { json, .. }
+                    | PreSerializedEvent::RuntimeUpdate { json, .. } => {
+                        let Ok(parsed) = serde_json::from_slice::<TerritoryEvent>(&json) else {
+                            continue;
+                        };
+                        let snapshot = state.live_snapshot.read().await;
+                        match parsed {
+                            TerritoryEvent::Update { changes, .. } => {
+                                engine.on_changes(&changes, &snapshot.territories)
+                            }
+                            TerritoryEvent::RuntimeUpdate { .. } => {
+                                engine.on_hq_changes(&snapshot.territories)
+                            }
+                            _ => Vec::new(),
+                        }
+                    }
+                    PreSerializedEvent::Snapshot { .. } | PreSerializedEvent::War { .. } => {
+                        continue;
+                    }
+                };
+                if let Err(e) = enqueue_notifications(&pool, &engine, &notifications).await {
+                    warn!("failed to enqueue webhook notifications: {e}");
+                }
+            }
+        }
+    }
+}
+
+async fn load_enabled_rules(pool: &sqlx::PgPool) -> Result<Vec<WebhookRule>, String> {
+    let rows: Vec<(i64, String, serde_json::Value)> =
+        sqlx::query_as("SELECT id, name, trigger FROM webhook_rules WHERE enabled ORDER BY id")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| format!("load rules: {e}"))?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(id, name, trigger)| {
+            let trigger = serde_json::from_value(trigger)
+                .inspect_err(|e| warn!("skipping webhook rule {id}: invalid trigger: {e}"))
+                .ok()?;
+            Some(WebhookRule { id, name, trigger })
+        })
+        .collect())
+}
+
+async fn enqueue_notifications(
+    pool: &sqlx::PgPool,
+    engine: &NotifierEngine,
+    notifications: &[Notification],
+) -> Result<(), String> {
+    if notifications.is_empty() {
+        return Ok(());
+    }
+    let now = Utc::now();
+    let base_url = map_public_base_url();
+    for notification in notifications {
+        let rule_name = engine
+            .rules
+            .iter()
+            .find(|rule| rule.id == notification.rule_id)
+            .map(|rule| rule.name.as_str())
+            .unwrap_or_default();
+        let payload = build_discord_payload(rule_name, notification, now, &base_url);
+        sqlx::query("INSERT INTO webhook_outbox (rule_id, payload) VALUES ($1, $2)")
+            .bind(notification.rule_id)
+            .bind(payload)
+            .execute(pool)
+            .await
+            .map_err(|e| format!("insert outbox row: {e}"))?;
+    }
+    debug!("queued {} webhook notifications", notifications.len());
+    Ok(())
+}
+
+/// Deliver due outbox rows with retries, and prune old finished rows.
+pub async fn run_dispatcher(state: AppState) {
+    let Some(pool) = state.db.as_ref().cloned() else {
+        return;
+    };
+    let mut interval = tokio::time::interval(Duration::from_secs(WEBHOOK_DISPATCH_INTERVAL_SECS));
+    let mut ticks = 0u64;
+    loop {
+        interval.tick().await;
+        ticks = ticks.wrapping_add(1);
+        if let Err(e) = dispatch_due(&state.http_client, &pool).await {
+            warn!("webhook dispatch failed: {e}");
+        }
+        if ticks.is_multiple_of(OUTBOX_PRUNE_EVERY_TICKS) {
+            prune_outbox(&pool).await;
+        }
+    }
+}
+
+async fn dispatch_due(client: &reqwest::Client, pool: &sqlx::PgPool) -> Result<(), String> {
+    let rows: Vec<OutboxRow> = sqlx::query_as(
+        "SELECT o.id, o.attempts, o.payload, r.url, r.secret \
+         FROM webhook_outbox o JOIN webhook_rules r ON r.id = o.rule_id \
+         WHERE o.status = 'pending' AND o.next_attempt_at <= now() \
+         ORDER BY o.next_attempt_at ASC \
+         LIMIT $1",
+    )
+    .bind(WEBHOOK_DISPATCH_BATCH)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("load due outbox rows: {e}"))?;
+
+    for (id, attempts, payload, url, secret) in rows {
+        let outcome = deliver(client, &url, secret.as_deref(), &payload).await;
+        let attempts = attempts.saturating_add(1);
+        let result = match outcome {
+            Delivery::Delivered => {
+                sqlx::query(
+                    "UPDATE webhook_outbox \
+                     SET status = 'delivered', attempts = $2, delivered_at = now(), last_error = NULL \
+                     WHERE id = $1",
+                )
+                .bind(id)
+                .bind(attempts)
+                .execute(pool)
+                .await
+            }
+            Delivery::Retry { error, retry_after } if attempts < WEBHOOK_MAX_ATTEMPTS => {
+                let delay = retry_after.unwrap_or_else(|| webhook_retry_delay_secs(attempts));
+                sqlx::query(
+                    "UPDATE webhook_outbox \
+                     SET attempts = $2, last_error = $3, \
+                         next_attempt_at = now() + make_interval(secs => $4) \
+                     WHERE id = $1",
+                )
+                .bind(id)
+                .bind(attempts)
+                .bind(error)
+                .bind(delay as f64)
+                .execute(pool)
+                .await
+            }
+            Delivery::Retry { error, .. } | Delivery::Rejected(error) => {
+                info!("webhook outbox row {id} failed permanently: {error}");
+                sqlx::query(
+                    "UPDATE webhook_outbox SET status = 'failed', attempts = $2, last_error = $3 \
+                     WHERE id = $1",
+                )
+                .bind(id)
+                .bind(attempts)
+                .bind(error)
+                .execute(pool)
+                .await
+            }
+        };
+        result.map_err(|e| format!("update outbox row {id}: {e}"))?;
+    }
+    Ok(())
+}
+
+enum Delivery {
+    Delivered,
+    Retry {
+        error: String,
+        retry_after: Option<i64>,
+    },
+    Rejected(String),
+}
+
+async fn deliver(
+    client: &reqwest::Client,
+    url: &str,
+    secret: Option<&str>,
+    payload: &serde_json::Value,
+) -> Delivery {
+    let body = match serde_json::to_vec(payload) {
+        Ok(body) => body,
+        Err(e) => return Delivery::Rejected(format!("serialize payload: {e}")),
+    };
+    let timestamp = Utc::now().timestamp();
+    let mut request = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(WEBHOOK_TIMESTAMP_HEADER, timestamp.to_string());
+    if let Some(secret) = secret.filter(|secret| !secret.is_empty()) {
+        request = request.header(
+            WEBHOOK_SIGNATURE_HEADER,
+            sign_webhook_body(secret, timestamp, &body),
+        );
+    }
+
+    match request.body(body).send().await {
+        Ok(resp) if resp.status().is_success() => Delivery::Delivered,
+        Ok(resp) => {
+            let status = resp.status();
+            let retry_after = resp
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<f64>().ok())
+                .map(|secs| secs.ceil().clamp(1.0, WEBHOOK_RETRY_MAX_SECS as f64) as i64);
+            let error = format!("HTTP {status}");
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                Delivery::Retry { error, retry_after }
+            } else {
+                Delivery::Rejected(error)
+            }
+        }
+        Err(e) => Delivery::Retry {
+            error: e.to_string(),
+            retry_after: None,
+        },
+    }
+}
+
+async fn prune_outbox(pool: &sqlx::PgPool) {
+    let cutoff = Utc::now() - chrono::Duration::days(WEBHOOK_OUTBOX_RETENTION_DAYS);
+    match sqlx::query("DELETE FROM webhook_outbox WHERE status <> 'pending' AND created_at < $1")
+        .bind(cutoff)
+        .execute(pool)
+        .await
+    {
+        Ok(result) if result.rows_affected() > 0 => {
+            info!(
+                "pruned {} finished webhook outbox rows",
+                result.rows_affected()
+            );
+        }
+        Ok(_) => {}
+        Err(e) => warn!("failed to prune webhook outbox: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::Utc;
+    use sequoia_shared::guild_hq::GuildHqSource;
+    use sequoia_shared::{
+        GuildRef, Region, Territory, TerritoryChange, TerritoryMap, TerritoryRuntimeData,
+    };
+
+    use super::{
+        NotifierEngine, WebhookRule, WebhookTrigger, build_discord_payload, sign_webhook_body,
+        webhook_retry_delay_secs,
+    };
+
+    fn guild(name: &str) -> GuildRef {
+        GuildRef {
+            uuid: format!("{name}-uuid"),
+            name: name.to_string(),
+            prefix: name[..3].to_string(),
+            color: Some((10, 20, 30)),
+        }
+    }
+
+    fn territory(owner: &str) -> Territory {
+        Territory {
+            guild: guild(owner),
+            acquired: Utc::now(),
+            location: Region {
+                start: [0, 0],
+                end: [10, 10],
+            },
+            resources: Default::default(),
+            connections: Vec::new(),
+            runtime: None,
+        }
+    }
+
+    fn change(name: &str, owner: &str, previous: &str) -> TerritoryChange {
+        TerritoryChange {
+            territory: name.to_string(),
+            guild: guild(owner),
+            previous_guild: Some(guild(previous)),
+            acquired: Utc::now().to_rfc3339(),
+            location: Region {
+                start: [0, 0],
+                end: [10, 10],
+            },
+            resources: Default::default(),
+            connections: Vec::new(),
+            runtime: None,
+        }
+    }
+
+    fn map(entries: &[(&str, &str)]) -> TerritoryMap {
+        entries
+            .iter()
+            .map(|(name, owner)| (name.to_string(), territory(owner)))
+            .collect::<HashMap<_, _>>()
+    }
+
+    #[test]
+    fn owner_change_and_threshold_rules_fire_once_on_crossing() {
+        let mut engine = NotifierEngine::default();
+        engine.set_rules(
+            vec![
+                WebhookRule {
+                    id: 1,
+                    name: "watch detlas".to_string(),
+                    trigger: WebhookTrigger::OwnerChange {
+                        guild: None,
+                        territory: Some("detlas".to_string()),
+                    },
+                },
+                WebhookRule {
+                    id: 2,
+                    name: "sequoia floor".to_string(),
+                    trigger: WebhookTrigger::GuildBelow {
+                        guild: "Sequoia".to_string(),
+                        threshold: 2,
+                    },
+                },
+            ],
+            &map(&[("Detlas", "Sequoia"), ("Ragni", "Sequoia")]),
+        );
+
+        let after = map(&[("Detlas", "Paladins"), ("Ragni", "Sequoia")]);
+        let notifications = engine.on_changes(&[change("Detlas", "Paladins", "Sequoia")], &after);
+        let rule_ids: Vec<i64> = notifications.iter().map(|n| n.rule_id).collect();
+        assert_eq!(rule_ids, vec![1, 2]);
+        assert!(notifications[0].description.contains("took **Detlas**"));
+
+        // Still below the threshold: no repeat alert.
+        let after = map(&[("Detlas", "Paladins"), ("Ragni", "Paladins")]);
+        let notifications = engine.on_changes(&[change("Ragni", "Paladins", "Sequoia")], &after);
+        assert!(notifications.is_empty());
+    }
+
+    fn hq_rule(guild: &str) -> Vec<WebhookRule> {
+        vec![WebhookRule {
+            id: 7,
+            name: "hq".to_string(),
+            trigger: WebhookTrigger::HqMoved {
+                guild: guild.to_string(),
+            },
+        }]
+    }
+
+    fn set_hq(territories: &mut TerritoryMap, name: &str, headquarters: bool) {
+        territories.get_mut(name).unwrap().runtime = Some(TerritoryRuntimeData {
+            headquarters: Some(headquarters),
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn hq_moved_rule_reports_previous_and_new_headquarters() {
+        let mut engine = NotifierEngine::default();
+        let mut territories = map(&[("Detlas", "Sequoia"), ("Ragni", "Sequoia")]);
+        set_hq(&mut territories, "Detlas", true);
+        engine.set_rules(hq_rule("sequoia"), &territories);
+        assert!(engine.on_hq_changes(&territories).is_empty());
+
+        set_hq(&mut territories, "Detlas", false);
+        set_hq(&mut territories, "Ragni", true);
+        let moved = engine.on_hq_changes(&territories);
+        assert_eq!(moved.len(), 1);
+        assert_eq!(moved[0].title, "Sequoia moved their HQ");
+        assert!(moved[0].description.contains("from Detlas to **Ragni**"));
+        assert_eq!(moved[0].hq_source, Some(GuildHqSource::Observed));
+    }
+
+    #[test]
+    fn inferred_hq_moves_are_flagged_as_inferred() {
+        let mut engine = NotifierEngine::default();
+        engine.set_rules(
+            hq_rule("Sequoia"),
+            &map(&[("Detlas", "Sequoia"), ("Ragni", "Paladins")]),
+        );
+
+        let after = map(&[("Detlas", "Paladins"), ("Ragni", "Sequoia")]);
+        let moved = engine.on_changes(&[change("Detlas", "Paladins", "Sequoia")], &after);
+        assert_eq!(moved.len(), 1);
+        assert_eq!(moved[0].title, "Sequoia likely moved their HQ");
+        assert!(
+            moved[0]
+                .description
+                .ends_with("**Ragni** (inferred, 100% confidence)")
+        );
+        assert_eq!(moved[0].hq_source, Some(GuildHqSource::Inferred));
+
+        let payload = build_discord_payload("hq", &moved[0], Utc::now(), "https://map.example.com");
+        assert_eq!(payload["embeds"][0]["fields"][0]["value"], "inferred");
+    }
+
+    #[test]
+    fn trigger_validation_requires_a_target() {
+        assert!(
+            WebhookTrigger::OwnerChange {
+                guild: None,
+                territory: Some(" ".to_string())
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            WebhookTrigger::GuildBelow {
+                guild: "Sequoia".to_string(),
+                threshold: 0
+            }
+            .validate()
+            .is_err()
+        );
+        let parsed: WebhookTrigger =
+            serde_json::from_str(r#"{"kind":"hq_moved","guild":"Sequoia"}"#).unwrap();
+        assert!(parsed.validate().is_ok());
+    }
+
+    #[test]
+    fn payload_signature_and_backoff_are_stable() {
+        let notification = super::Notification {
+            rule_id: 1,
+            title: "Detlas changed hands".to_string(),
+            description: "desc".to_string(),
+            color: Some((255, 0, 16)),
+            territory: Some("Detlas Suburbs".to_string()),
+            hq_source: None,
+        };
+        let payload =
+            build_discord_payload("rule", &notification, Utc::now(), "https://map.example.com");
+        assert_eq!(payload["embeds"][0]["color"], 0xFF0010);
+        assert_eq!(
+            payload["embeds"][0]["url"],
+            "https://map.example.com/?territory=Detlas%20Suburbs"
+        );
+        assert!(payload["embeds"][0].get("fields").is_none());
+        let notification = super::Notification {
+            territory: Some("Fort & Gate #2 Über".to_string()),
+            ..notification
+        };
+        let payload =
+            build_discord_payload("rule", &notification, Utc::now(), "https://map.example.com");
+        assert_eq!(
+            payload["embeds"][0]["url"],
+            "https://map.example.com/?territory=Fort%20%26%20Gate%20%232%20%C3%9Cber"
+        );
+
+        let signature = sign_webhook_body("secret", 1_700_000_000, b"{}");
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+        assert_ne!(signature, sign_webhook_body("secret", 1_700_000_001, b"{}"));
+
+        assert_eq!(webhook_retry_delay_secs(1), 60);
+        assert_eq!(webhook_retry_delay_secs(3), 240);
+        assert_eq!(webhook_retry_delay_secs(20), 3600);
+    }
+}
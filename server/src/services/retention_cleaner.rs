@@ -1,10 +1,14 @@
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use tracing::{info, warn};
 
 use crate::config::{
-    RETENTION_CHECK_SECS, season_history_retention_days, territory_history_retention_days,
+    RETENTION_CHECK_SECS, history_archive_dir, season_history_retention_days,
+    territory_history_retention_days,
 };
+use crate::services::history_archive::{ArchiveTable, archive_expired_rows};
 use crate::state::AppState;
 
 const BATCH_SIZE: i64 = 10_000;
@@ -17,13 +21,26 @@ pub async fn run(state: AppState) {
     };
     let territory_retention_days = territory_history_retention_days();
     let season_retention_days = season_history_retention_days();
+    let archive_dir: Option<PathBuf> = history_archive_dir();
 
     info!(
-        "Retention cleaner started (territory retention: {}d, season retention: {}d, check interval: {}s)",
-        territory_retention_days, season_retention_days, RETENTION_CHECK_SECS
+        "Retention cleaner started (territory retention: {}d, season retention: {}d, check interval: {}s, archive: {})",
+        territory_retention_days,
+        season_retention_days,
+        RETENTION_CHECK_SECS,
+        archive_dir
+            .as_deref()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_else(|| "disabled".to_string())
     );
 
-    run_cleanup_once(&pool, territory_retention_days, season_retention_days).await;
+    run_cleanup_once(
+        &pool,
+        territory_retention_days,
+        season_retention_days,
+        archive_dir.as_deref(),
+    )
+    .await;
 
     let mut interval = tokio::time::interval(Duration::from_secs(RETENTION_CHECK_SECS));
     // Consume immediate tick so subsequent cleanup runs after the configured interval.
@@ -31,7 +48,44 @@ pub async fn run(state: AppState) {
 
     loop {
         interval.tick().await;
-        run_cleanup_once(&pool, territory_retention_days, season_retention_days).await;
+        run_cleanup_once(
+            &pool,
+            territory_retention_days,
+            season_retention_days,
+            archive_dir.as_deref(),
+        )
+        .await;
+    }
+}
+
+/// Archive expired rows when an archive directory is configured. Returns `false` when
+/// archiving failed, in which case the table must not be pruned this run.
+async fn archive_before_delete(
+    pool: &sqlx::PgPool,
+    archive_dir: Option<&Path>,
+    table: ArchiveTable,
+    cutoff: DateTime<Utc>,
+) -> bool {
+    let Some(dir) = archive_dir else {
+        return true;
+    };
+    match archive_expired_rows(pool, dir, table, cutoff).await {
+        Ok(archived) => {
+            if archived > 0 {
+                info!(
+                    "Archived {archived} {} rows older than {cutoff}",
+                    table.table_name()
+                );
+            }
+            true
+        }
+        Err(e) => {
+            warn!(
+                "Skipping {} retention cleanup: archival failed: {e}",
+                table.table_name()
+            );
+            false
+        }
     }
 }
 
@@ -39,63 +93,89 @@ async fn run_cleanup_once(
     pool: &sqlx::PgPool,
     territory_retention_days: i64,
     season_retention_days: i64,
+    archive_dir: Option<&Path>,
 ) {
     let territory_cutoff = chrono::Utc::now() - chrono::Duration::days(territory_retention_days);
     let season_cutoff = chrono::Utc::now() - chrono::Duration::days(season_retention_days);
 
     // Delete old events in batches to avoid long locks
     let mut total_events = 0i64;
-    loop {
-        match sqlx::query(
-            "DELETE FROM territory_events WHERE id IN \
-             (SELECT id FROM territory_events WHERE recorded_at < $1 LIMIT $2)",
-        )
-        .bind(territory_cutoff)
-        .bind(BATCH_SIZE)
-        .execute(pool)
-        .await
-        {
-            Ok(result) => {
-                let deleted = result.rows_affected() as i64;
-                total_events += deleted;
-                if deleted < BATCH_SIZE {
+    let events_archived = archive_before_delete(
+        pool,
+        archive_dir,
+        ArchiveTable::TerritoryEvents,
+        territory_cutoff,
+    )
+    .await;
+    if events_archived {
+        loop {
+            match sqlx::query(
+                "DELETE FROM territory_events WHERE id IN \
+                 (SELECT id FROM territory_events WHERE recorded_at < $1 LIMIT $2)",
+            )
+            .bind(territory_cutoff)
+            .bind(BATCH_SIZE)
+            .execute(pool)
+            .await
+            {
+                Ok(result) => {
+                    let deleted = result.rows_affected() as i64;
+                    total_events += deleted;
+                    if deleted < BATCH_SIZE {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to delete old events: {e}");
                     break;
                 }
             }
-            Err(e) => {
-                warn!("Failed to delete old events: {e}");
-                break;
-            }
         }
     }
 
     // Delete old snapshots in batches
     let mut total_snapshots = 0i64;
-    loop {
-        match sqlx::query(
-            "DELETE FROM territory_snapshots WHERE id IN \
-             (SELECT id FROM territory_snapshots WHERE created_at < $1 LIMIT $2)",
-        )
-        .bind(territory_cutoff)
-        .bind(BATCH_SIZE)
-        .execute(pool)
-        .await
-        {
-            Ok(result) => {
-                let deleted = result.rows_affected() as i64;
-                total_snapshots += deleted;
-                if deleted < BATCH_SIZE {
+    let snapshots_archived = archive_before_delete(
+        pool,
+        archive_dir,
+        ArchiveTable::TerritorySnapshots,
+        territory_cutoff,
+    )
+    .await;
+    if snapshots_archived {
+        loop {
+            match sqlx::query(
+                "DELETE FROM territory_snapshots WHERE id IN \
+                 (SELECT id FROM territory_snapshots WHERE created_at < $1 LIMIT $2)",
+            )
+            .bind(territory_cutoff)
+            .bind(BATCH_SIZE)
+            .execute(pool)
+            .await
+            {
+                Ok(result) => {
+                    let deleted = result.rows_affected() as i64;
+                    total_snapshots += deleted;
+                    if deleted < BATCH_SIZE {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to delete old snapshots: {e}");
                     break;
                 }
             }
-            Err(e) => {
-                warn!("Failed to delete old snapshots: {e}");
-                break;
-            }
         }
     }
 
-    let total_scalar_samples =
+    let scalar_samples_archived = archive_before_delete(
+        pool,
+        archive_dir,
+        ArchiveTable::SeasonScalarSamples,
+        season_cutoff,
+    )
+    .await;
+    let total_scalar_samples = if scalar_samples_archived {
         match sqlx::query("DELETE FROM season_scalar_samples WHERE sampled_at < $1")
             .bind(season_cutoff)
             .execute(pool)
@@ -106,9 +186,19 @@ async fn run_cleanup_once(
                 warn!("Failed to delete old season scalar samples: {e}");
                 0
             }
-        };
+        }
+    } else {
+        0
+    };
 
-    let total_season_observations =
+    let season_observations_archived = archive_before_delete(
+        pool,
+        archive_dir,
+        ArchiveTable::SeasonGuildObservations,
+        season_cutoff,
+    )
+    .await;
+    let total_season_observations = if season_observations_archived {
         match sqlx::query("DELETE FROM season_guild_observations WHERE observed_at < $1")
             .bind(season_cutoff)
             .execute(pool)
@@ -119,7 +209,10 @@ async fn run_cleanup_once(
                 warn!("Failed to delete old season guild observations: {e}");
                 0
             }
-        };
+        }
+    } else {
+        0
+    };
 
     if total_events > 0
         || total_snapshots > 0
@@ -231,7 +324,7 @@ mod tests {
         .await
         .expect("insert current season observation");
 
-        run_cleanup_once(&pool, 365, 365).await;
+        run_cleanup_once(&pool, 365, 365, None).await;
 
         let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM season_scalar_samples")
             .fetch_one(&pool)
@@ -0,0 +1,447 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{SEASON_BACKTEST_DEFAULT_PROGRESS, SEASON_BACKTEST_MAX_CHECKPOINTS};
+use crate::services::season_data::{self, ResolvedSeasonWindow};
+use crate::services::season_race::{self, SeasonRaceError, SeasonRaceResponse};
+use crate::services::season_scalar_forecast;
+use crate::state::AppState;
+
+type FinalGuildRow = (String, i32, i16);
+type ScalarSampleRow = (DateTime<Utc>, f64);
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SeasonBacktestRequest {
+    /// Completed seasons to replay; every completed season when empty.
+    #[serde(default)]
+    pub season_ids: Vec<i32>,
+    /// Season progress ratios, strictly between 0 and 1, to treat as "now".
+    #[serde(default)]
+    pub progress: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SeasonBacktestResponse {
+    pub generated_at: String,
+    pub momentum_half_life_hours: f64,
+    pub checkpoints: Vec<SeasonBacktestCheckpoint>,
+    pub summary: Vec<SeasonBacktestSummary>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SeasonBacktestCheckpoint {
+    pub season_id: i32,
+    pub progress: f64,
+    pub as_of: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scalar: Option<ScalarBacktestMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub race: Option<RaceBacktestMetrics>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ScalarBacktestMetrics {
+    /// Observed samples after `as_of` the forecast was scored against.
+    pub samples: u32,
+    pub mean_abs_error: f64,
+    pub predicted_final_scalar: f64,
+    pub actual_final_scalar: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RaceBacktestMetrics {
+    pub guilds: u32,
+    pub mean_abs_error_sr: f64,
+    /// Positive when projections overshoot the final season rating.
+    pub mean_error_sr: f64,
+    pub mean_abs_pct_error: f64,
+    /// Share of guilds whose projected rank matched their final rank.
+    pub exact_rank_share: f64,
+    pub winner_correct: bool,
+}
+
+/// Checkpoint metrics averaged across seasons at one progress ratio.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeasonBacktestSummary {
+    pub progress: f64,
+    pub seasons: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scalar_mean_abs_error: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub race_mean_abs_pct_error: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub race_exact_rank_share: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winner_accuracy: Option<f64>,
+}
+
+/// Replay completed seasons as if "now" were each requested progress point and score the
+/// scalar forecast and race projection against what actually happened.
+///
+/// Manual scalar overrides and live in-memory state are ignored so every checkpoint only
+/// sees rows stored up to its `as_of` time.
+pub async fn run_backtest(
+    state: &AppState,
+    request: SeasonBacktestRequest,
+) -> Result<SeasonBacktestResponse, SeasonRaceError> {
+    let Some(pool) = state.db.as_ref() else {
+        return Err(SeasonRaceError::Unavailable);
+    };
+    let generated_at = Utc::now();
+    let progress = normalize_progress(&request.progress)?;
+    let windows = season_data::list_resolved_windows(state)
+        .await
+        .map_err(season_race::map_season_data_error)?;
+    let completed: Vec<&ResolvedSeasonWindow> = windows
+        .iter()
+        .filter(|window| window.end_at <= generated_at)
+        .filter(|window| {
+            request.season_ids.is_empty() || request.season_ids.contains(&window.season_id)
+        })
+        .collect();
+    if request.season_ids.iter().any(|season_id| {
+        !completed
+            .iter()
+            .any(|window| window.season_id == *season_id)
+    }) {
+        return Err(SeasonRaceError::BadRequest);
+    }
+    if completed.len().saturating_mul(progress.len()) > SEASON_BACKTEST_MAX_CHECKPOINTS {
+        return Err(SeasonRaceError::BadRequest);
+    }
+
+    let mut checkpoints = Vec::with_capacity(completed.len() * progress.len());
+    for window in completed {
+        let final_rows: Vec<FinalGuildRow> = sqlx::query_as(
+            "SELECT DISTINCT ON (guild_name) guild_name, season_rating, territory_count \
+             FROM season_guild_observations \
+             WHERE season_id = $1 \
+               AND observed_at <= $2 \
+             ORDER BY guild_name, observed_at DESC",
+        )
+        .bind(window.season_id)
+        .bind(window.end_at)
+        .fetch_all(pool)
+        .await
+        .map_err(|_| SeasonRaceError::Internal)?;
+        let standings = final_standings(final_rows);
+
+        let scalar_samples: Vec<ScalarSampleRow> = sqlx::query_as(
+            "SELECT sampled_at, scalar_weighted \
+             FROM season_scalar_samples \
+             WHERE season_id = $1 \
+               AND sampled_at >= $2 \
+               AND sampled_at <= $3 \
+             ORDER BY sampled_at ASC",
+        )
+        .bind(window.season_id)
+        .bind(window.start_at)
+        .bind(window.end_at)
+        .fetch_all(pool)
+        .await
+        .map_err(|_| SeasonRaceError::Internal)?;
+
+        for ratio in &progress {
+            let window_secs = (window.end_at - window.start_at).num_seconds() as f64;
+            let as_of = window.start_at + Duration::seconds((window_secs * ratio).round() as i64);
+
+            let projection =
+                season_scalar_forecast::build_scalar_projection(pool, &windows, window, as_of, &[])
+                    .await
+                    .map_err(|_| SeasonRaceError::Internal)?;
+            let future_samples: Vec<ScalarSampleRow> = scalar_samples
+                .iter()
+                .copied()
+                .filter(|(sampled_at, _)| *sampled_at > as_of)
+                .collect();
+            let scalar = projection.as_ref().and_then(|projection| {
+                scalar_metrics(&future_samples, |at| projection.scalar_at(at))
+            });
+
+            let race = season_race::build_race_response_at(
+                state,
+                &windows,
+                window.clone(),
+                as_of,
+                &[],
+                false,
+            )
+            .await?;
+
+            checkpoints.push(SeasonBacktestCheckpoint {
+                season_id: window.season_id,
+                progress: *ratio,
+                as_of: as_of.to_rfc3339(),
+                scalar,
+                race: race_metrics(&race, &standings),
+            });
+        }
+    }
+
+    let summary = summarize(&progress, &checkpoints);
+    Ok(SeasonBacktestResponse {
+        generated_at: generated_at.to_rfc3339(),
+        momentum_half_life_hours: season_scalar_forecast::momentum_half_life_hours(),
+        checkpoints,
+        summary,
+    })
+}
+
+fn normalize_progress(requested: &[f64]) -> Result<Vec<f64>, SeasonRaceError> {
+    let mut progress = if requested.is_empty() {
+        SEASON_BACKTEST_DEFAULT_PROGRESS.to_vec()
+    } else {
+        requested.to_vec()
+    };
+    if progress
+        .iter()
+        .any(|ratio| !ratio.is_finite() || *ratio <= 0.0 || *ratio >= 1.0)
+    {
+        return Err(SeasonRaceError::BadRequest);
+    }
+    progress.sort_by(f64::total_cmp);
+    progress.dedup();
+    Ok(progress)
+}
+
+/// Final season rating and rank per guild, ranked like the live leaderboard.
+fn final_standings(mut rows: Vec<FinalGuildRow>) -> HashMap<String, (i64, u32)> {
+    rows.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then_with(|| b.2.cmp(&a.2))
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    rows.into_iter()
+        .enumerate()
+        .map(|(idx, (guild_name, season_rating, _))| {
+            (
+                guild_name,
+                (
+                    i64::from(season_rating),
+                    u32::try_from(idx + 1).unwrap_or(u32::MAX),
+                ),
+            )
+        })
+        .collect()
+}
+
+fn scalar_metrics(
+    future_samples: &[ScalarSampleRow],
+    predict: impl Fn(DateTime<Utc>) -> f64,
+) -> Option<ScalarBacktestMetrics> {
+    let (last_at, last_scalar) = *future_samples.last()?;
+    let total_error: f64 = future_samples
+        .iter()
+        .map(|(sampled_at, scalar)| (predict(*sampled_at) - scalar).abs())
+        .sum();
+    Some(ScalarBacktestMetrics {
+        samples: u32::try_from(future_samples.len()).unwrap_or(u32::MAX),
+        mean_abs_error: total_error / future_samples.len() as f64,
+        predicted_final_scalar: predict(last_at),
+        actual_final_scalar: last_scalar,
+    })
+}
+
+fn race_metrics(
+    race: &SeasonRaceResponse,
+    standings: &HashMap<String, (i64, u32)>,
+) -> Option<RaceBacktestMetrics> {
+    let scored: Vec<(i64, u32, i64, u32)> = race
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            standings.get(&entry.guild_name).map(|(final_sr, rank)| {
+                (
+                    entry.projected_final_sr,
+                    entry.projected_rank,
+                    *final_sr,
+                    *rank,
+                )
+            })
+        })
+        .collect();
+    if scored.is_empty() {
+        return None;
+    }
+
+    let count = scored.len() as f64;
+    let errors = scored
+        .iter()
+        .map(|(projected, _, actual, _)| (projected - actual) as f64);
+    let mean_error_sr = errors.clone().sum::<f64>() / count;
+    let mean_abs_error_sr = errors.map(f64::abs).sum::<f64>() / count;
+    let mean_abs_pct_error = scored
+        .iter()
+        .map(|(projected, _, actual, _)| {
+            (projected - actual).abs() as f64 / (*actual).max(1) as f64
+        })
+        .sum::<f64>()
+        / count;
+    let exact_ranks = scored
+        .iter()
+        .filter(|(_, projected_rank, _, rank)| projected_rank == rank)
+        .count();
+    Some(RaceBacktestMetrics {
+        guilds: u32::try_from(scored.len()).unwrap_or(u32::MAX),
+        mean_abs_error_sr,
+        mean_error_sr,
+        mean_abs_pct_error,
+        exact_rank_share: exact_ranks as f64 / count,
+        winner_correct: scored
+            .iter()
+            .any(|(_, projected_rank, _, rank)| *projected_rank == 1 && *rank == 1),
+    })
+}
+
+fn summarize(
+    progress: &[f64],
+    checkpoints: &[SeasonBacktestCheckpoint],
+) -> Vec<SeasonBacktestSummary> {
+    let mean = |values: Vec<f64>| {
+        (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+    };
+    progress
+        .iter()
+        .map(|ratio| {
+            let at_ratio: Vec<&SeasonBacktestCheckpoint> = checkpoints
+                .iter()
+                .filter(|checkpoint| checkpoint.progress == *ratio)
+                .collect();
+            let scalars: Vec<&ScalarBacktestMetrics> = at_ratio
+                .iter()
+                .filter_map(|checkpoint| checkpoint.scalar.as_ref())
+                .collect();
+            let races: Vec<&RaceBacktestMetrics> = at_ratio
+                .iter()
+                .filter_map(|checkpoint| checkpoint.race.as_ref())
+                .collect();
+            SeasonBacktestSummary {
+                progress: *ratio,
+                seasons: u32::try_from(at_ratio.len()).unwrap_or(u32::MAX),
+                scalar_mean_abs_error: mean(scalars.iter().map(|m| m.mean_abs_error).collect()),
+                race_mean_abs_pct_error: mean(races.iter().map(|m| m.mean_abs_pct_error).collect()),
+                race_exact_rank_share: mean(races.iter().map(|m| m.exact_rank_share).collect()),
+                winner_accuracy: mean(
+                    races
+                        .iter()
+                        .map(|m| if m.winner_correct { 1.0 } else { 0.0 })
+                        .collect(),
+                ),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::{DateTime, Utc};
+
+    use super::{
+        SeasonBacktestCheckpoint, final_standings, normalize_progress, race_metrics,
+        scalar_metrics, summarize,
+    };
+    use crate::services::season_race::{SeasonRaceEntry, SeasonRaceError};
+    use crate::services::season_race_fixtures::{self as fixtures, race};
+
+    fn ts(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    fn entry(name: &str, projected_final_sr: i64, projected_rank: u32) -> SeasonRaceEntry {
+        SeasonRaceEntry {
+            projected_final_sr,
+            current_rank: projected_rank,
+            projected_rank,
+            ..fixtures::entry(name)
+        }
+    }
+
+    #[test]
+    fn final_standings_rank_by_rating_then_territories() {
+        let standings = final_standings(vec![
+            ("Bravo".to_string(), 500, 10),
+            ("Alpha".to_string(), 900, 5),
+            ("Charlie".to_string(), 500, 12),
+        ]);
+        assert_eq!(standings["Alpha"], (900, 1));
+        assert_eq!(standings["Charlie"], (500, 2));
+        assert_eq!(standings["Bravo"], (500, 3));
+    }
+
+    #[test]
+    fn race_metrics_score_projection_against_outcome() {
+        let race = race(vec![
+            entry("Alpha", 1_100, 1),
+            entry("Bravo", 900, 2),
+            entry("Gone", 50, 3),
+        ]);
+        let standings = HashMap::from([
+            ("Alpha".to_string(), (1_000, 2)),
+            ("Bravo".to_string(), (1_200, 1)),
+        ]);
+        let metrics = race_metrics(&race, &standings).unwrap();
+        assert_eq!(metrics.guilds, 2);
+        assert_eq!(metrics.mean_error_sr, -100.0);
+        assert_eq!(metrics.mean_abs_error_sr, 200.0);
+        assert!((metrics.mean_abs_pct_error - 0.175).abs() < 1e-9);
+        assert_eq!(metrics.exact_rank_share, 0.0);
+        assert!(!metrics.winner_correct);
+
+        assert!(race_metrics(&race, &HashMap::new()).is_none());
+    }
+
+    #[test]
+    fn scalar_metrics_compare_forecast_with_later_samples() {
+        let samples = [
+            (ts("2026-01-07T00:00:00Z"), 2.0),
+            (ts("2026-01-09T00:00:00Z"), 3.0),
+        ];
+        let step_at = ts("2026-01-08T00:00:00Z");
+        let metrics = scalar_metrics(&samples, |at| if at < step_at { 2.0 } else { 5.0 }).unwrap();
+        assert_eq!(metrics.samples, 2);
+        assert_eq!(metrics.mean_abs_error, 1.0);
+        assert_eq!(metrics.predicted_final_scalar, 5.0);
+        assert_eq!(metrics.actual_final_scalar, 3.0);
+
+        assert!(scalar_metrics(&[], |_| 1.0).is_none());
+    }
+
+    #[test]
+    fn progress_defaults_and_rejects_out_of_range_points() {
+        assert_eq!(normalize_progress(&[]).unwrap(), vec![0.25, 0.5, 0.75, 0.9]);
+        assert_eq!(
+            normalize_progress(&[0.5, 0.2, 0.5]).unwrap(),
+            vec![0.2, 0.5]
+        );
+        assert_eq!(normalize_progress(&[1.0]), Err(SeasonRaceError::BadRequest));
+        assert_eq!(
+            normalize_progress(&[f64::NAN]),
+            Err(SeasonRaceError::BadRequest)
+        );
+    }
+
+    #[test]
+    fn summary_averages_checkpoints_per_progress() {
+        let race = race(vec![entry("Alpha", 1_000, 1)]);
+        let checkpoint = |season_id, final_rank| SeasonBacktestCheckpoint {
+            season_id,
+            progress: 0.5,
+            as_of: String::new(),
+            scalar: None,
+            race: race_metrics(
+                &race,
+                &HashMap::from([("Alpha".to_string(), (1_000, final_rank))]),
+            ),
+        };
+        let summary = summarize(&[0.5, 0.9], &[checkpoint(28, 1), checkpoint(29, 2)]);
+        assert_eq!(summary[0].seasons, 2);
+        assert_eq!(summary[0].winner_accuracy, Some(0.5));
+        assert_eq!(summary[0].scalar_mean_abs_error, None);
+        assert_eq!(summary[1].seasons, 0);
+        assert_eq!(summary[1].race_exact_rank_share, None);
+    }
+}
@@ -5,9 +5,10 @@ use serde::Serialize;
 
 use sequoia_shared::{SeasonScalarSample, passive_sr_per_hour};
 
-use crate::config;
+use crate::config::{self, SeasonScalarOverridePoint};
 use crate::services::season_components::{self, ProjectedSeasonComponents};
-use crate::services::season_data::{self, SeasonDataError};
+use crate::services::season_data::{self, ResolvedSeasonWindow, SeasonDataError};
+use crate::services::season_race_simulation::{SeasonRaceInterval, SeasonRaceSimulation};
 use crate::services::season_scalar_forecast::{self, ScalarProjection};
 use crate::state::AppState;
 
@@ -65,6 +66,8 @@ pub struct SeasonRaceEntry {
     pub forecast_rate_per_hour: f64,
     pub forecast_source: ForecastSource,
     pub series: Vec<SeasonRacePoint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<SeasonRaceInterval>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -99,6 +102,8 @@ pub struct SeasonRaceResponse {
     pub scalar_points: Vec<SeasonRaceScalarPoint>,
     pub entries: Vec<SeasonRaceEntry>,
     pub assumptions: SeasonRaceAssumptions,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub simulation: Option<SeasonRaceSimulation>,
 }
 
 #[derive(Debug, Clone)]
@@ -111,9 +116,9 @@ pub async fn build_race_response(
     state: &AppState,
     requested_season_id: Option<i32>,
 ) -> Result<SeasonRaceResponse, SeasonRaceError> {
-    let Some(pool) = state.db.as_ref() else {
+    if state.db.is_none() {
         return Err(SeasonRaceError::Unavailable);
-    };
+    }
     let season_windows = season_data::list_resolved_windows(state)
         .await
         .map_err(map_season_data_error)?;
@@ -123,10 +128,35 @@ pub async fn build_race_response(
         .ok_or(SeasonRaceError::Unavailable)?;
     let scalar_override_points =
         config::season_scalar_override_points().map_err(|_| SeasonRaceError::Internal)?;
+    build_race_response_at(
+        state,
+        &season_windows,
+        window,
+        Utc::now(),
+        &scalar_override_points,
+        true,
+    )
+    .await
+}
+
+/// Project the race as it looked at `generated_at`.
+///
+/// With `use_live_state` the in-memory live snapshot and latest scalar sample feed the
+/// projection; backtests turn it off so only rows stored up to `generated_at` are used.
+pub(crate) async fn build_race_response_at(
+    state: &AppState,
+    season_windows: &[ResolvedSeasonWindow],
+    window: ResolvedSeasonWindow,
+    generated_at: DateTime<Utc>,
+    scalar_override_points: &[SeasonScalarOverridePoint],
+    use_live_state: bool,
+) -> Result<SeasonRaceResponse, SeasonRaceError> {
+    let Some(pool) = state.db.as_ref() else {
+        return Err(SeasonRaceError::Unavailable);
+    };
     let lookback_hours = config::season_race_lookback_hours();
     let top_guilds = config::season_race_top_guilds();
 
-    let generated_at = Utc::now();
     let range_end = generated_at.min(window.end_at);
     let remaining_hours = ((window.end_at - generated_at).num_seconds().max(0) as f64) / 3600.0;
     let recent_query_start =
@@ -134,14 +164,18 @@ pub async fn build_race_response(
     let season_complete = generated_at >= window.end_at;
     let scalar_projection = season_scalar_forecast::build_scalar_projection(
         pool,
-        &season_windows,
+        season_windows,
         &window,
         generated_at,
-        &scalar_override_points,
+        scalar_override_points,
     )
     .await
     .map_err(|_| SeasonRaceError::Internal)?;
-    let fallback_scalar = latest_scalar_weighted_for_season(state, window.season_id).await;
+    let fallback_scalar = if use_live_state {
+        latest_scalar_weighted_for_season(state, window.season_id).await
+    } else {
+        None
+    };
     let current_scalar_assumption = scalar_projection
         .as_ref()
         .map(ScalarProjection::current_scalar_weighted)
@@ -189,6 +223,7 @@ pub async fn build_race_response(
                     .map(|_| season_scalar_forecast::momentum_half_life_hours()),
                 note: assumptions_note(season_complete, scalar_projection.as_ref()).to_string(),
             },
+            simulation: None,
         });
     }
 
@@ -259,14 +294,15 @@ pub async fn build_race_response(
             });
     }
 
-    let live_territory_counts = live_territory_counts(state).await;
-    let passive_scalar = latest_scalar_sample_for_season(state, window.season_id)
-        .await
-        .map(|sample| sample.scalar_weighted);
+    let live_territory_counts = if use_live_state {
+        live_territory_counts(state).await
+    } else {
+        HashMap::new()
+    };
     let current_scalar = scalar_projection
         .as_ref()
         .map(ScalarProjection::current_scalar_weighted)
-        .or(passive_scalar);
+        .or(fallback_scalar);
     let actual_guild_names = latest_rows
         .iter()
         .map(|row| row.0.clone())
@@ -435,6 +471,7 @@ pub async fn build_race_response(
             forecast_rate_per_hour: forecast_rate,
             forecast_source,
             series: downsample_series_hourly(chart_observations),
+            interval: None,
         });
     }
 
@@ -460,10 +497,11 @@ pub async fn build_race_response(
                 .map(|_| season_scalar_forecast::momentum_half_life_hours()),
             note: assumptions_note(season_complete, scalar_projection.as_ref()).to_string(),
         },
+        simulation: None,
     })
 }
 
-fn map_season_data_error(error: SeasonDataError) -> SeasonRaceError {
+pub(crate) fn map_season_data_error(error: SeasonDataError) -> SeasonRaceError {
     match error {
         SeasonDataError::Unavailable => SeasonRaceError::Unavailable,
         SeasonDataError::BadRequest => SeasonRaceError::BadRequest,
@@ -627,7 +665,7 @@ fn downsample_series_hourly(observations: Vec<SeriesObservation>) -> Vec<SeasonR
     downsampled
 }
 
-fn apply_projected_ranks(entries: &mut [SeasonRaceEntry]) {
+pub fn apply_projected_ranks(entries: &mut [SeasonRaceEntry]) {
     let mut ranking: Vec<(usize, i64, usize, String)> = entries
         .iter()
         .enumerate()
@@ -785,6 +823,7 @@ mod tests {
                 forecast_rate_per_hour: 0.0,
                 forecast_source: ForecastSource::FlatFallback,
                 series: Vec::new(),
+                interval: None,
             },
             super::SeasonRaceEntry {
                 guild_name: "Alpha".to_string(),
@@ -809,6 +848,7 @@ mod tests {
                 forecast_rate_per_hour: 0.0,
                 forecast_source: ForecastSource::FlatFallback,
                 series: Vec::new(),
+                interval: None,
             },
         ];
 
@@ -0,0 +1,56 @@
+//! Season race builders shared by the race scenario, simulation and backtest tests.
+
+use crate::services::season_race::{
+    ForecastSource, SeasonRaceAssumptions, SeasonRaceEntry, SeasonRaceResponse,
+};
+
+/// An entry with zero ratings and no territories; tests override the fields they exercise.
+pub fn entry(name: &str) -> SeasonRaceEntry {
+    SeasonRaceEntry {
+        guild_name: name.to_string(),
+        guild_prefix: name[..3].to_string(),
+        current_sr: 0,
+        projected_final_sr: 0,
+        current_rank: 1,
+        projected_rank: 1,
+        territory_count: 0,
+        sample_count: 0,
+        last_sampled_at: "2026-01-01T00:00:00Z".to_string(),
+        observed_rate_per_hour: None,
+        passive_rate_per_hour: None,
+        projected_passive_sr_gain: None,
+        projected_excess_sr_gain: None,
+        current_raid_sr: 0,
+        current_passive_hold_sr: 0,
+        current_conquest_sr: 0,
+        projected_raid_sr: 0,
+        projected_passive_hold_sr: 0,
+        projected_conquest_sr: 0,
+        forecast_rate_per_hour: 0.0,
+        forecast_source: ForecastSource::FlatFallback,
+        series: Vec::new(),
+        interval: None,
+    }
+}
+
+/// Season 30 running 2026-01-01 to 2026-01-11, generated halfway with 120 hours left.
+pub fn race(entries: Vec<SeasonRaceEntry>) -> SeasonRaceResponse {
+    SeasonRaceResponse {
+        season_id: 30,
+        label: None,
+        start_at: "2026-01-01T00:00:00Z".to_string(),
+        end_at: "2026-01-11T00:00:00Z".to_string(),
+        generated_at: "2026-01-06T00:00:00Z".to_string(),
+        remaining_hours: 120.0,
+        scalar_points: Vec::new(),
+        entries,
+        assumptions: SeasonRaceAssumptions {
+            lookback_hours: 24,
+            passive_scalar_weighted: None,
+            current_scalar_weighted: None,
+            momentum_half_life_hours: None,
+            note: String::new(),
+        },
+        simulation: None,
+    }
+}
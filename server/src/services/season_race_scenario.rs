@@ -0,0 +1,506 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use sequoia_shared::passive_sr_per_hour;
+
+use crate::config::{SEASON_RACE_SCENARIO_MAX_OVERRIDES, SEASON_RACE_SCENARIO_MAX_SCALAR};
+use crate::services::season_race::{
+    self, SeasonRaceError, SeasonRaceResponse, SeasonRaceScalarPoint,
+};
+use crate::services::season_scalar_forecast::ScalarPointSource;
+use crate::state::AppState;
+
+/// Largest territory count a hold override may claim; well above the whole map.
+const MAX_SCENARIO_TERRITORIES: usize = 2_000;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SeasonRaceScenarioRequest {
+    #[serde(default)]
+    pub season_id: Option<i32>,
+    #[serde(default)]
+    pub overrides: Vec<SeasonRaceOverride>,
+}
+
+/// One "what if" applied on top of the baseline projection.
+///
+/// Each override takes effect at `at` (RFC 3339) or `at_progress` (0.0–1.0 of the season
+/// window), clamped to the remaining window; with neither it applies from now.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SeasonRaceOverride {
+    /// Guild holds exactly `territories` territories from the override time on.
+    HoldTerritories {
+        guild: String,
+        territories: usize,
+        #[serde(default)]
+        at: Option<DateTime<Utc>>,
+        #[serde(default)]
+        at_progress: Option<f64>,
+    },
+    /// Weighted scalar jumps to `scalar` and stays there for the rest of the season.
+    Scalar {
+        scalar: f64,
+        #[serde(default)]
+        at: Option<DateTime<Utc>>,
+        #[serde(default)]
+        at_progress: Option<f64>,
+    },
+    /// Guild earns no further raid SR from the override time on.
+    StopRaiding {
+        guild: String,
+        #[serde(default)]
+        at: Option<DateTime<Utc>>,
+        #[serde(default)]
+        at_progress: Option<f64>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SeasonRaceScenarioChange {
+    pub guild_name: String,
+    pub baseline_final_sr: i64,
+    pub scenario_final_sr: i64,
+    pub baseline_rank: u32,
+    pub scenario_rank: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SeasonRaceScenarioResponse {
+    /// The race re-projected under the overrides.
+    pub race: SeasonRaceResponse,
+    /// Guilds whose projected SR or rank moved, by scenario rank.
+    pub changes: Vec<SeasonRaceScenarioChange>,
+}
+
+pub async fn build_scenario_response(
+    state: &AppState,
+    request: SeasonRaceScenarioRequest,
+) -> Result<SeasonRaceScenarioResponse, SeasonRaceError> {
+    if request.overrides.len() > SEASON_RACE_SCENARIO_MAX_OVERRIDES {
+        return Err(SeasonRaceError::BadRequest);
+    }
+    let baseline = season_race::build_race_response(state, request.season_id).await?;
+    apply_scenario(baseline, &request.overrides)
+}
+
+/// Piecewise-constant value over time; each step holds until the next one.
+type Steps<T> = Vec<(DateTime<Utc>, T)>;
+
+fn apply_scenario(
+    mut race: SeasonRaceResponse,
+    overrides: &[SeasonRaceOverride],
+) -> Result<SeasonRaceScenarioResponse, SeasonRaceError> {
+    let parse = |value: &str| {
+        value
+            .parse::<DateTime<Utc>>()
+            .map_err(|_| SeasonRaceError::Internal)
+    };
+    let start_at = parse(&race.start_at)?;
+    let end_at = parse(&race.end_at)?;
+    let now = parse(&race.generated_at)?.min(end_at);
+    let resolve = |at: Option<DateTime<Utc>>, at_progress: Option<f64>| {
+        resolve_override_time(start_at, end_at, now, at, at_progress)
+    };
+
+    let mut scalar_steps: Steps<f64> = Vec::new();
+    for point in &race.scalar_points {
+        scalar_steps.push((parse(&point.sampled_at)?, point.scalar_weighted));
+    }
+    if scalar_steps.is_empty()
+        && let Some(scalar) = race.assumptions.passive_scalar_weighted
+    {
+        scalar_steps.push((now, scalar));
+    }
+
+    let mut scalar_overrides = Vec::new();
+    let mut holds: HashMap<String, Steps<usize>> = HashMap::new();
+    let mut raid_cutoffs: HashMap<String, DateTime<Utc>> = HashMap::new();
+    for entry in overrides {
+        match entry {
+            SeasonRaceOverride::Scalar {
+                scalar,
+                at,
+                at_progress,
+            } => {
+                if !scalar.is_finite()
+                    || *scalar <= 0.0
+                    || *scalar > SEASON_RACE_SCENARIO_MAX_SCALAR
+                {
+                    return Err(SeasonRaceError::BadRequest);
+                }
+                scalar_overrides.push((resolve(*at, *at_progress)?, *scalar));
+            }
+            SeasonRaceOverride::HoldTerritories {
+                guild,
+                territories,
+                at,
+                at_progress,
+            } => {
+                if *territories > MAX_SCENARIO_TERRITORIES {
+                    return Err(SeasonRaceError::BadRequest);
+                }
+                let key = race_guild_key(&race, guild)?;
+                holds
+                    .entry(key)
+                    .or_default()
+                    .push((resolve(*at, *at_progress)?, *territories));
+            }
+            SeasonRaceOverride::StopRaiding {
+                guild,
+                at,
+                at_progress,
+            } => {
+                let key = race_guild_key(&race, guild)?;
+                let cutoff = resolve(*at, *at_progress)?;
+                raid_cutoffs
+                    .entry(key)
+                    .and_modify(|existing| *existing = (*existing).min(cutoff))
+                    .or_insert(cutoff);
+            }
+        }
+    }
+
+    scalar_overrides.sort_by_key(|(at, _)| *at);
+    for (at, scalar) in &scalar_overrides {
+        scalar_steps.retain(|(step_at, _)| step_at < at);
+        scalar_steps.push((*at, *scalar));
+    }
+    let scalar_changed = !scalar_overrides.is_empty();
+    if (scalar_changed || !holds.is_empty()) && scalar_steps.is_empty() {
+        // No scalar to integrate passive SR against.
+        return Err(SeasonRaceError::Unavailable);
+    }
+
+    let baseline: HashMap<String, (i64, u32)> = race
+        .entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.guild_name.clone(),
+                (entry.projected_final_sr, entry.projected_rank),
+            )
+        })
+        .collect();
+    let remaining_hours = hours_between(now, end_at);
+
+    for entry in &mut race.entries {
+        let key = entry.guild_name.to_ascii_lowercase();
+        let mut delta = 0i64;
+
+        let hold = holds.get_mut(&key);
+        if scalar_changed || hold.is_some() {
+            let mut count_steps: Steps<usize> = vec![(now, entry.territory_count)];
+            if let Some(hold) = hold {
+                hold.sort_by_key(|(at, _)| *at);
+                count_steps.extend(hold.iter().copied());
+            }
+            let scenario_gain = passive_gain(&scalar_steps, &count_steps, now, end_at);
+            let baseline_gain = entry
+                .projected_passive_hold_sr
+                .saturating_sub(entry.current_passive_hold_sr);
+            delta = delta.saturating_add(scenario_gain.saturating_sub(baseline_gain));
+            entry.projected_passive_hold_sr = entry.current_passive_hold_sr + scenario_gain;
+            entry.projected_passive_sr_gain = Some(scenario_gain);
+        }
+
+        if let Some(cutoff) = raid_cutoffs.get(&key) {
+            let baseline_gain = entry
+                .projected_raid_sr
+                .saturating_sub(entry.current_raid_sr);
+            let kept_share = if remaining_hours > 0.0 {
+                hours_between(now, *cutoff) / remaining_hours
+            } else {
+                0.0
+            };
+            let scenario_gain = (baseline_gain as f64 * kept_share).round() as i64;
+            delta = delta.saturating_add(scenario_gain.saturating_sub(baseline_gain));
+            entry.projected_raid_sr = entry.current_raid_sr.saturating_add(scenario_gain);
+        }
+
+        if delta != 0 {
+            entry.projected_final_sr = entry.projected_final_sr.saturating_add(delta).max(0);
+            entry.forecast_rate_per_hour = if remaining_hours > 0.0 {
+                entry.projected_final_sr.saturating_sub(entry.current_sr) as f64 / remaining_hours
+            } else {
+                0.0
+            };
+        }
+    }
+
+    season_race::apply_projected_ranks(&mut race.entries);
+    race.entries.sort_by_key(|entry| entry.projected_rank);
+
+    if scalar_changed {
+        race.scalar_points = scalar_steps
+            .iter()
+            .map(|(at, scalar)| SeasonRaceScalarPoint {
+                sampled_at: at.to_rfc3339(),
+                scalar_weighted: *scalar,
+                source: if scalar_overrides.iter().any(|(step_at, _)| step_at == at) {
+                    ScalarPointSource::ManualOverride
+                } else {
+                    race.scalar_points
+                        .iter()
+                        .find(|point| point.sampled_at == at.to_rfc3339())
+                        .map_or(ScalarPointSource::Observed, |point| point.source)
+                },
+            })
+            .collect();
+    }
+    if !overrides.is_empty() {
+        race.assumptions.note = format!(
+            "{} Scenario applies {} override(s) on top of this projection.",
+            race.assumptions.note,
+            overrides.len()
+        );
+    }
+
+    let changes = race
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            let (baseline_final_sr, baseline_rank) = *baseline.get(&entry.guild_name)?;
+            (baseline_final_sr != entry.projected_final_sr || baseline_rank != entry.projected_rank)
+                .then(|| SeasonRaceScenarioChange {
+                    guild_name: entry.guild_name.clone(),
+                    baseline_final_sr,
+                    scenario_final_sr: entry.projected_final_sr,
+                    baseline_rank,
+                    scenario_rank: entry.projected_rank,
+                })
+        })
+        .collect();
+
+    Ok(SeasonRaceScenarioResponse { race, changes })
+}
+
+/// Lowercased name of the race entry an override targets; unknown guilds are rejected.
+fn race_guild_key(race: &SeasonRaceResponse, guild: &str) -> Result<String, SeasonRaceError> {
+    race.entries
+        .iter()
+        .find(|entry| entry.guild_name.eq_ignore_ascii_case(guild.trim()))
+        .map(|entry| entry.guild_name.to_ascii_lowercase())
+        .ok_or(SeasonRaceError::BadRequest)
+}
+
+fn resolve_override_time(
+    start_at: DateTime<Utc>,
+    end_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+    at: Option<DateTime<Utc>>,
+    at_progress: Option<f64>,
+) -> Result<DateTime<Utc>, SeasonRaceError> {
+    let at = match (at, at_progress) {
+        (Some(_), Some(_)) => return Err(SeasonRaceError::BadRequest),
+        (Some(at), None) => at,
+        (None, Some(progress)) => {
+            if !(0.0..=1.0).contains(&progress) {
+                return Err(SeasonRaceError::BadRequest);
+            }
+            let span_secs = (end_at - start_at).num_seconds() as f64;
+            start_at + chrono::Duration::seconds((span_secs * progress).round() as i64)
+        }
+        (None, None) => now,
+    };
+    Ok(at.clamp(now, end_at.max(now)))
+}
+
+fn hours_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_seconds().max(0) as f64 / 3600.0
+}
+
+fn step_value<T: Copy>(steps: &[(DateTime<Utc>, T)], at: DateTime<Utc>) -> Option<T> {
+    steps
+        .iter()
+        .take_while(|(step_at, _)| *step_at <= at)
+        .last()
+        .or(steps.first())
+        .map(|(_, value)| *value)
+}
+
+/// Passive hold SR between `from` and `to` with both scalar and territory count stepping.
+fn passive_gain(
+    scalar_steps: &[(DateTime<Utc>, f64)],
+    count_steps: &[(DateTime<Utc>, usize)],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> i64 {
+    let mut breakpoints: Vec<DateTime<Utc>> = scalar_steps
+        .iter()
+        .map(|(at, _)| *at)
+        .chain(count_steps.iter().map(|(at, _)| *at))
+        .filter(|at| *at > from && *at < to)
+        .collect();
+    breakpoints.push(from);
+    breakpoints.push(to);
+    breakpoints.sort();
+    breakpoints.dedup();
+
+    let mut total = 0.0;
+    for segment in breakpoints.windows(2) {
+        let (Some(scalar), Some(count)) = (
+            step_value(scalar_steps, segment[0]),
+            step_value(count_steps, segment[0]),
+        ) else {
+            continue;
+        };
+        total += passive_sr_per_hour(count, scalar) * hours_between(segment[0], segment[1]);
+    }
+    total.round().max(0.0) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use sequoia_shared::passive_sr_per_hour;
+
+    use super::{SeasonRaceOverride, apply_scenario, passive_gain, resolve_override_time};
+    use crate::services::season_race::{
+        ForecastSource, SeasonRaceEntry, SeasonRaceError, SeasonRaceResponse,
+    };
+    use crate::services::season_race_fixtures as fixtures;
+
+    fn ts(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    fn entry(name: &str, rank: u32, current_sr: i64, territories: usize) -> SeasonRaceEntry {
+        // 100 hours remain at scalar 2.0 with flat holdings.
+        let passive_gain = (passive_sr_per_hour(territories, 2.0) * 100.0).round() as i64;
+        let raid_gain = 10_000;
+        SeasonRaceEntry {
+            current_sr,
+            projected_final_sr: current_sr + passive_gain + raid_gain,
+            current_rank: rank,
+            projected_rank: rank,
+            territory_count: territories,
+            projected_passive_sr_gain: Some(passive_gain),
+            current_passive_hold_sr: current_sr,
+            projected_raid_sr: raid_gain,
+            projected_passive_hold_sr: current_sr + passive_gain,
+            forecast_source: ForecastSource::RaidAwareProjection,
+            ..fixtures::entry(name)
+        }
+    }
+
+    fn race() -> SeasonRaceResponse {
+        let mut race = fixtures::race(vec![
+            entry("Alpha", 1, 500_000, 40),
+            entry("Bravo", 2, 480_000, 40),
+        ]);
+        race.end_at = "2026-01-05T04:00:00Z".to_string();
+        race.generated_at = "2026-01-01T00:00:00Z".to_string();
+        race.remaining_hours = 100.0;
+        race.assumptions.passive_scalar_weighted = Some(2.0);
+        race.assumptions.note = "Baseline.".to_string();
+        race
+    }
+
+    #[test]
+    fn empty_scenario_keeps_the_baseline_projection() {
+        let baseline = race();
+        let scenario = apply_scenario(race(), &[]).unwrap();
+        assert!(scenario.changes.is_empty());
+        for (before, after) in baseline.entries.iter().zip(&scenario.race.entries) {
+            assert_eq!(before.projected_final_sr, after.projected_final_sr);
+        }
+    }
+
+    #[test]
+    fn holding_and_stopping_raids_reprojects_ranks() {
+        let scenario = apply_scenario(
+            race(),
+            &[
+                SeasonRaceOverride::HoldTerritories {
+                    guild: "bravo".to_string(),
+                    territories: 120,
+                    at: None,
+                    at_progress: Some(0.5),
+                },
+                SeasonRaceOverride::StopRaiding {
+                    guild: "Alpha".to_string(),
+                    at: Some(ts("2026-01-03T02:00:00Z")),
+                    at_progress: None,
+                },
+            ],
+        )
+        .unwrap();
+
+        let bravo = &scenario.race.entries[0];
+        assert_eq!(bravo.guild_name, "Bravo");
+        assert_eq!(bravo.projected_rank, 1);
+        let expected_passive = (passive_sr_per_hour(40, 2.0) * 50.0
+            + passive_sr_per_hour(120, 2.0) * 50.0)
+            .round() as i64;
+        assert_eq!(bravo.projected_passive_sr_gain, Some(expected_passive));
+
+        let alpha = &scenario.race.entries[1];
+        assert_eq!(alpha.projected_raid_sr, 5_000);
+        assert_eq!(alpha.projected_rank, 2);
+        assert_eq!(scenario.changes.len(), 2);
+        assert_eq!(scenario.changes[1].baseline_rank, 1);
+    }
+
+    #[test]
+    fn scalar_jump_replaces_later_steps_for_every_guild() {
+        let scenario = apply_scenario(
+            race(),
+            &[SeasonRaceOverride::Scalar {
+                scalar: 5.0,
+                at: None,
+                at_progress: Some(0.75),
+            }],
+        )
+        .unwrap();
+        let expected = (passive_sr_per_hour(40, 2.0) * 75.0 + passive_sr_per_hour(40, 5.0) * 25.0)
+            .round() as i64;
+        assert!(
+            scenario
+                .race
+                .entries
+                .iter()
+                .all(|entry| entry.projected_passive_sr_gain == Some(expected))
+        );
+        assert_eq!(scenario.race.scalar_points.len(), 2);
+        assert_eq!(scenario.race.scalar_points[1].scalar_weighted, 5.0);
+    }
+
+    #[test]
+    fn invalid_overrides_are_rejected() {
+        let unknown = SeasonRaceOverride::StopRaiding {
+            guild: "Nobody".to_string(),
+            at: None,
+            at_progress: None,
+        };
+        assert_eq!(
+            apply_scenario(race(), &[unknown]).unwrap_err(),
+            SeasonRaceError::BadRequest
+        );
+
+        let start = ts("2026-01-01T00:00:00Z");
+        let end = ts("2026-01-05T04:00:00Z");
+        let now = ts("2026-01-02T00:00:00Z");
+        assert!(resolve_override_time(start, end, now, None, Some(1.5)).is_err());
+        // Past override times apply from now.
+        assert_eq!(
+            resolve_override_time(start, end, now, None, Some(0.0)).unwrap(),
+            now
+        );
+    }
+
+    #[test]
+    fn passive_gain_integrates_both_step_functions() {
+        let from = ts("2026-01-01T00:00:00Z");
+        let to = ts("2026-01-01T10:00:00Z");
+        let gain = passive_gain(
+            &[(from, 1.0), (ts("2026-01-01T05:00:00Z"), 3.0)],
+            &[(from, 10), (ts("2026-01-01T08:00:00Z"), 0)],
+            from,
+            to,
+        );
+        let expected = passive_sr_per_hour(10, 1.0) * 5.0 + passive_sr_per_hour(10, 3.0) * 3.0;
+        assert_eq!(gain, expected.round() as i64);
+    }
+}
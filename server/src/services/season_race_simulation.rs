@@ -0,0 +1,663 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+
+use sequoia_shared::passive_sr_per_hour;
+
+use crate::config::{
+    SEASON_RACE_SIM_DEFAULT_RATE_CV, SEASON_RACE_SIM_DEFAULT_TERRITORY_SD,
+    SEASON_RACE_SIM_DEFAULT_TOP_N, SEASON_RACE_SIM_HISTORY_HOURS, SEASON_RACE_SIM_RUNS,
+    SEASON_RACE_SIM_SCALAR_STEP_JITTER, SEASON_RACE_SIM_STEP_HOURS,
+};
+use crate::services::season_race::{SeasonRaceError, SeasonRaceResponse};
+use crate::services::season_scalar_forecast::ScalarPointSource;
+use crate::state::AppState;
+
+type VolatilityRow = (String, DateTime<Utc>, i32, i16);
+
+/// p10/p50/p90 of a simulated quantity.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SeasonRaceBand<T> {
+    pub p10: T,
+    pub p50: T,
+    pub p90: T,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeasonRaceInterval {
+    pub final_sr: SeasonRaceBand<i64>,
+    pub rank: SeasonRaceBand<u32>,
+    /// Share of runs finishing within the top `SeasonRaceSimulation::top_n`.
+    pub top_n_probability: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SeasonRaceSimulation {
+    pub runs: u32,
+    pub top_n: u32,
+}
+
+/// Observed day-to-day variability of one guild.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GuildVolatility {
+    /// Coefficient of variation of daily non-passive (raid and conquest) SR gain.
+    pub daily_rate_cv: f64,
+    /// Standard deviation of day-to-day territory count changes.
+    pub daily_territory_sd: f64,
+}
+
+impl Default for GuildVolatility {
+    fn default() -> Self {
+        Self {
+            daily_rate_cv: SEASON_RACE_SIM_DEFAULT_RATE_CV,
+            daily_territory_sd: SEASON_RACE_SIM_DEFAULT_TERRITORY_SD,
+        }
+    }
+}
+
+/// Simulated bands for one season and top-N, reused while the race tick is unchanged.
+#[derive(Debug, Clone)]
+pub struct CachedRaceIntervals {
+    tick: String,
+    intervals: HashMap<String, SeasonRaceInterval>,
+    simulation: SeasonRaceSimulation,
+}
+
+/// Simulate the remaining season and attach percentile bands to every race entry.
+///
+/// Does nothing once the season is over. Results are cached per season, top-N and tick (latest
+/// observation plus the hourly seed), and the Monte Carlo runs off the async executor.
+pub async fn attach_intervals(
+    state: &AppState,
+    race: &mut SeasonRaceResponse,
+    top_n: Option<u32>,
+) -> Result<(), SeasonRaceError> {
+    if race.remaining_hours <= 0.0 || race.entries.is_empty() {
+        return Ok(());
+    }
+    let generated_at = race
+        .generated_at
+        .parse::<DateTime<Utc>>()
+        .map_err(|_| SeasonRaceError::Internal)?;
+    let top_n = top_n
+        .unwrap_or(SEASON_RACE_SIM_DEFAULT_TOP_N)
+        .clamp(1, u32::try_from(race.entries.len()).unwrap_or(u32::MAX));
+    let key = (race.season_id, top_n);
+    let hour = generated_at.timestamp() / 3600;
+    let tick = format!(
+        "{}:{hour}",
+        race.entries
+            .iter()
+            .map(|entry| entry.last_sampled_at.as_str())
+            .max()
+            .unwrap_or_default()
+    );
+    if apply_cached_intervals(state, race, key, &tick).await {
+        return Ok(());
+    }
+    let _simulation_guard = state.season_race_sim_lock.lock().await;
+    if apply_cached_intervals(state, race, key, &tick).await {
+        return Ok(());
+    }
+
+    let volatility = load_volatility(state, race, generated_at).await?;
+    // Stable within the hour so cached and repeated requests agree.
+    let seed = (race.season_id as u64) << 32 ^ hour as u64;
+    let snapshot = race.clone();
+    let intervals = tokio::task::spawn_blocking(move || {
+        simulate_race(&snapshot, &volatility, SEASON_RACE_SIM_RUNS, top_n, seed)
+    })
+    .await
+    .map_err(|_| SeasonRaceError::Internal)??;
+
+    let cached = CachedRaceIntervals {
+        tick,
+        intervals: race
+            .entries
+            .iter()
+            .map(|entry| entry.guild_name.clone())
+            .zip(intervals)
+            .collect(),
+        simulation: SeasonRaceSimulation {
+            runs: SEASON_RACE_SIM_RUNS,
+            top_n,
+        },
+    };
+    apply_intervals(race, &cached);
+    let mut cache = state.season_race_intervals.write().await;
+    // Only the live season simulates; drop entries from older ticks as new ones land.
+    cache.retain(|_, entry| entry.tick == cached.tick);
+    cache.insert(key, cached);
+    Ok(())
+}
+
+async fn apply_cached_intervals(
+    state: &AppState,
+    race: &mut SeasonRaceResponse,
+    key: (i32, u32),
+    tick: &str,
+) -> bool {
+    let cache = state.season_race_intervals.read().await;
+    let Some(cached) = cache.get(&key).filter(|cached| {
+        cached.tick == tick
+            && race
+                .entries
+                .iter()
+                .all(|entry| cached.intervals.contains_key(&entry.guild_name))
+    }) else {
+        return false;
+    };
+    apply_intervals(race, cached);
+    true
+}
+
+fn apply_intervals(race: &mut SeasonRaceResponse, cached: &CachedRaceIntervals) {
+    for entry in &mut race.entries {
+        entry.interval = cached.intervals.get(&entry.guild_name).cloned();
+    }
+    race.simulation = Some(cached.simulation.clone());
+}
+
+async fn load_volatility(
+    state: &AppState,
+    race: &SeasonRaceResponse,
+    generated_at: DateTime<Utc>,
+) -> Result<HashMap<String, GuildVolatility>, SeasonRaceError> {
+    let Some(pool) = state.db.as_ref() else {
+        return Err(SeasonRaceError::Unavailable);
+    };
+    let guild_names: Vec<String> = race
+        .entries
+        .iter()
+        .map(|entry| entry.guild_name.clone())
+        .collect();
+    let rows: Vec<VolatilityRow> = sqlx::query_as(
+        "SELECT guild_name, observed_at, season_rating, territory_count \
+         FROM season_guild_observations \
+         WHERE season_id = $1 \
+           AND guild_name = ANY($2) \
+           AND observed_at >= $3 \
+           AND observed_at <= $4 \
+         ORDER BY guild_name ASC, observed_at ASC",
+    )
+    .bind(race.season_id)
+    .bind(&guild_names)
+    .bind(generated_at - Duration::hours(SEASON_RACE_SIM_HISTORY_HOURS))
+    .bind(generated_at)
+    .fetch_all(pool)
+    .await
+    .map_err(|_| SeasonRaceError::Internal)?;
+
+    let scalar_steps = race_scalar_steps(race, generated_at)?;
+    let mut observations: HashMap<String, Vec<(DateTime<Utc>, i64, usize)>> = HashMap::new();
+    for (guild_name, observed_at, season_rating, territory_count) in rows {
+        observations.entry(guild_name).or_default().push((
+            observed_at,
+            i64::from(season_rating),
+            usize::try_from(territory_count.max(0)).unwrap_or(0),
+        ));
+    }
+    Ok(observations
+        .into_iter()
+        .map(|(guild_name, observations)| {
+            let volatility = volatility_from_observations(&observations, &scalar_steps);
+            (guild_name, volatility)
+        })
+        .collect())
+}
+
+/// Variability from the last observation of each UTC day; too little history keeps defaults.
+///
+/// The rate CV covers non-passive gains only: passive SR for the held territories is
+/// subtracted from each day's gain, since the simulation integrates passive separately.
+pub fn volatility_from_observations(
+    observations: &[(DateTime<Utc>, i64, usize)],
+    scalar_steps: &[(DateTime<Utc>, f64, bool)],
+) -> GuildVolatility {
+    let mut daily: BTreeMap<NaiveDate, (DateTime<Utc>, i64, usize)> = BTreeMap::new();
+    for (observed_at, season_rating, territory_count) in observations {
+        daily.insert(
+            observed_at.date_naive(),
+            (*observed_at, *season_rating, *territory_count),
+        );
+    }
+    let days: Vec<(DateTime<Utc>, i64, usize)> = daily.into_values().collect();
+    let mut volatility = GuildVolatility::default();
+    if days.len() < 4 {
+        return volatility;
+    }
+
+    let gains: Vec<f64> = days
+        .windows(2)
+        .map(|pair| {
+            let (from_at, from_sr, from_count) = pair[0];
+            let (to_at, to_sr, _) = pair[1];
+            let hours = (to_at - from_at).num_seconds() as f64 / 3600.0;
+            let passive = passive_sr_per_hour(from_count, scalar_at(scalar_steps, from_at)) * hours;
+            (to_sr - from_sr) as f64 - passive
+        })
+        .collect();
+    let (gain_mean, gain_sd) = mean_and_sd(&gains);
+    if gain_mean > 0.0 {
+        volatility.daily_rate_cv = (gain_sd / gain_mean).clamp(0.05, 1.5);
+    }
+    let territory_changes: Vec<f64> = days
+        .windows(2)
+        .map(|pair| pair[1].2 as f64 - pair[0].2 as f64)
+        .collect();
+    volatility.daily_territory_sd = mean_and_sd(&territory_changes).1.max(0.5);
+    volatility
+}
+
+fn mean_and_sd(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f64>()
+        / (n - 1.0);
+    (mean, variance.sqrt())
+}
+
+/// Monte Carlo over scalar step timing, raid/conquest pace and territory drift.
+///
+/// Each run perturbs the baseline projection: the passive gain is re-integrated over the
+/// perturbed scalar and territory paths and compared with the unperturbed integral, and
+/// non-passive gains vary as independent daily draws, so their spread grows with the square
+/// root of the days left. Intervals follow `race.entries` order.
+pub fn simulate_race(
+    race: &SeasonRaceResponse,
+    volatility: &HashMap<String, GuildVolatility>,
+    runs: u32,
+    top_n: u32,
+    seed: u64,
+) -> Result<Vec<SeasonRaceInterval>, SeasonRaceError> {
+    let end_at = parse_time(&race.end_at)?;
+    let now = parse_time(&race.generated_at)?.min(end_at);
+    let window_hours = (end_at - parse_time(&race.start_at)?).num_seconds().max(0) as f64 / 3600.0;
+    let scalar_steps = race_scalar_steps(race, now)?;
+
+    let mut step_starts = Vec::new();
+    let mut cursor = now;
+    while cursor < end_at {
+        step_starts.push(cursor);
+        cursor += Duration::hours(SEASON_RACE_SIM_STEP_HOURS);
+    }
+    let step_hours: Vec<f64> = step_starts
+        .iter()
+        .map(|start| {
+            let step_end = (*start + Duration::hours(SEASON_RACE_SIM_STEP_HOURS)).min(end_at);
+            (step_end - *start).num_seconds() as f64 / 3600.0
+        })
+        .collect();
+    let remaining_days = step_hours.iter().sum::<f64>() / 24.0;
+    let base_scalars: Vec<f64> = step_starts
+        .iter()
+        .map(|start| scalar_at(&scalar_steps, *start))
+        .collect();
+
+    let guilds: Vec<(usize, i64, i64, GuildVolatility)> = race
+        .entries
+        .iter()
+        .map(|entry| {
+            let non_passive_gain = entry
+                .projected_raid_sr
+                .saturating_sub(entry.current_raid_sr)
+                .saturating_add(
+                    entry
+                        .projected_conquest_sr
+                        .saturating_sub(entry.current_conquest_sr),
+                )
+                .max(0);
+            (
+                entry.territory_count,
+                entry.projected_final_sr,
+                non_passive_gain,
+                volatility
+                    .get(&entry.guild_name)
+                    .copied()
+                    .unwrap_or_default(),
+            )
+        })
+        .collect();
+    let base_passive: Vec<f64> = guilds
+        .iter()
+        .map(|(count, ..)| {
+            base_scalars
+                .iter()
+                .zip(&step_hours)
+                .map(|(scalar, hours)| passive_sr_per_hour(*count, *scalar) * hours)
+                .sum()
+        })
+        .collect();
+
+    let mut rng = SplitMix64::new(seed);
+    let runs = runs.max(1);
+    let mut finals: Vec<Vec<i64>> = vec![Vec::with_capacity(runs as usize); guilds.len()];
+    let mut ranks: Vec<Vec<u32>> = vec![Vec::with_capacity(runs as usize); guilds.len()];
+    let mut run_scalars = Vec::with_capacity(step_starts.len());
+    let mut run_finals = vec![0i64; guilds.len()];
+    let mut order: Vec<usize> = (0..guilds.len()).collect();
+    for _ in 0..runs {
+        let jittered = jitter_scalar_steps(&scalar_steps, window_hours, now, end_at, &mut rng);
+        run_scalars.clear();
+        run_scalars.extend(step_starts.iter().map(|start| scalar_at(&jittered, *start)));
+
+        for (idx, (count, projected_final, non_passive_gain, volatility)) in
+            guilds.iter().enumerate()
+        {
+            let step_sd =
+                volatility.daily_territory_sd * (SEASON_RACE_SIM_STEP_HOURS as f64 / 24.0).sqrt();
+            let mut simulated_count = *count as f64;
+            let mut passive = 0.0;
+            for (scalar, hours) in run_scalars.iter().zip(&step_hours) {
+                passive += passive_sr_per_hour(simulated_count.round() as usize, *scalar) * hours;
+                simulated_count = (simulated_count + rng.normal() * step_sd).max(0.0);
+            }
+            // Summed daily draws: sd = cv * daily gain * sqrt(days) = cv * gain / sqrt(days).
+            let non_passive_gain = *non_passive_gain as f64;
+            let non_passive_delta = if remaining_days > 0.0 {
+                (rng.normal() * volatility.daily_rate_cv * non_passive_gain / remaining_days.sqrt())
+                    .max(-non_passive_gain)
+            } else {
+                0.0
+            };
+            run_finals[idx] = (*projected_final as f64 + passive - base_passive[idx]
+                + non_passive_delta)
+                .round()
+                .max(0.0) as i64;
+        }
+
+        order.sort_by(|a, b| {
+            run_finals[*b]
+                .cmp(&run_finals[*a])
+                .then_with(|| guilds[*b].0.cmp(&guilds[*a].0))
+                .then_with(|| a.cmp(b))
+        });
+        for (rank_idx, guild_idx) in order.iter().enumerate() {
+            finals[*guild_idx].push(run_finals[*guild_idx]);
+            ranks[*guild_idx].push(u32::try_from(rank_idx + 1).unwrap_or(u32::MAX));
+        }
+    }
+
+    Ok(finals
+        .into_iter()
+        .zip(ranks)
+        .map(|(mut finals, mut ranks)| {
+            let top_n_runs = ranks.iter().filter(|rank| **rank <= top_n).count();
+            finals.sort_unstable();
+            ranks.sort_unstable();
+            SeasonRaceInterval {
+                final_sr: band(&finals),
+                rank: band(&ranks),
+                top_n_probability: top_n_runs as f64 / runs as f64,
+            }
+        })
+        .collect())
+}
+
+fn parse_time(value: &str) -> Result<DateTime<Utc>, SeasonRaceError> {
+    value
+        .parse::<DateTime<Utc>>()
+        .map_err(|_| SeasonRaceError::Internal)
+}
+
+/// Scalar steps from the race's scalar points, or its flat passive scalar from `now`.
+fn race_scalar_steps(
+    race: &SeasonRaceResponse,
+    now: DateTime<Utc>,
+) -> Result<Vec<(DateTime<Utc>, f64, bool)>, SeasonRaceError> {
+    let mut steps = Vec::new();
+    for point in &race.scalar_points {
+        let estimated = point.source == ScalarPointSource::Estimated;
+        steps.push((
+            parse_time(&point.sampled_at)?,
+            point.scalar_weighted,
+            estimated,
+        ));
+    }
+    if steps.is_empty()
+        && let Some(scalar) = race.assumptions.passive_scalar_weighted
+    {
+        steps.push((now, scalar, false));
+    }
+    Ok(steps)
+}
+
+fn band<T: Copy>(sorted: &[T]) -> SeasonRaceBand<T> {
+    let at = |quantile: f64| sorted[((sorted.len() - 1) as f64 * quantile).round() as usize];
+    SeasonRaceBand {
+        p10: at(0.1),
+        p50: at(0.5),
+        p90: at(0.9),
+    }
+}
+
+fn scalar_at(steps: &[(DateTime<Utc>, f64, bool)], at: DateTime<Utc>) -> f64 {
+    steps
+        .iter()
+        .take_while(|(step_at, ..)| *step_at <= at)
+        .last()
+        .or(steps.first())
+        .map_or(0.0, |(_, scalar, _)| *scalar)
+}
+
+/// Shift estimated scalar steps by a normal offset, keeping their order and the window.
+fn jitter_scalar_steps(
+    steps: &[(DateTime<Utc>, f64, bool)],
+    window_hours: f64,
+    now: DateTime<Utc>,
+    end_at: DateTime<Utc>,
+    rng: &mut SplitMix64,
+) -> Vec<(DateTime<Utc>, f64, bool)> {
+    let sd_secs = window_hours * 3600.0 * SEASON_RACE_SIM_SCALAR_STEP_JITTER;
+    let mut previous = now;
+    steps
+        .iter()
+        .map(|(at, scalar, estimated)| {
+            let mut at = *at;
+            if *estimated {
+                at += Duration::seconds((rng.normal() * sd_secs).round() as i64);
+                at = at.clamp(previous.min(end_at), end_at);
+            }
+            previous = previous.max(at);
+            (at, *scalar, *estimated)
+        })
+        .collect()
+}
+
+/// Small seedable generator so simulations are reproducible without extra dependencies.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in (0, 1].
+    fn unit(&mut self) -> f64 {
+        ((self.next_u64() >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal via Box-Muller.
+    fn normal(&mut self) -> f64 {
+        let radius = (-2.0 * self.unit().ln()).sqrt();
+        radius * (std::f64::consts::TAU * self.unit()).cos()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::{DateTime, Duration, Utc};
+
+    use sequoia_shared::passive_sr_per_hour;
+
+    use super::{GuildVolatility, SplitMix64, simulate_race, volatility_from_observations};
+    use crate::services::season_race::{ForecastSource, SeasonRaceEntry, SeasonRaceResponse};
+    use crate::services::season_race_fixtures as fixtures;
+
+    fn entry(name: &str, projected_final_sr: i64, territories: usize) -> SeasonRaceEntry {
+        SeasonRaceEntry {
+            current_sr: 400_000,
+            projected_final_sr,
+            territory_count: territories,
+            current_raid_sr: 100_000,
+            current_passive_hold_sr: 200_000,
+            current_conquest_sr: 100_000,
+            projected_raid_sr: 150_000,
+            projected_passive_hold_sr: 200_000,
+            projected_conquest_sr: 120_000,
+            forecast_source: ForecastSource::RaidAwareProjection,
+            ..fixtures::entry(name)
+        }
+    }
+
+    fn race(entries: Vec<SeasonRaceEntry>) -> SeasonRaceResponse {
+        let mut race = fixtures::race(entries);
+        race.assumptions.passive_scalar_weighted = Some(2.0);
+        race
+    }
+
+    #[test]
+    fn bands_bracket_the_projection_and_separate_clear_leaders() {
+        let race = race(vec![
+            entry("Alpha", 900_000, 40),
+            entry("Bravo", 600_000, 40),
+            entry("Charlie", 601_000, 40),
+        ]);
+        let intervals = simulate_race(&race, &HashMap::new(), 2_000, 2, 7).unwrap();
+
+        let alpha = &intervals[0];
+        assert!(alpha.final_sr.p10 < 900_000 && alpha.final_sr.p90 > 900_000);
+        assert!((alpha.final_sr.p50 - 900_000).abs() < 10_000);
+        assert_eq!(alpha.rank.p50, 1);
+        assert_eq!(alpha.top_n_probability, 1.0);
+
+        // Near-tied guilds split the remaining top-2 slot.
+        let (bravo, charlie) = (&intervals[1], &intervals[2]);
+        assert!(bravo.top_n_probability > 0.25 && bravo.top_n_probability < 0.75);
+        assert!((bravo.top_n_probability + charlie.top_n_probability - 1.0).abs() < 1e-9);
+        assert!(bravo.rank.p10 == 2 && bravo.rank.p90 == 3);
+
+        assert_eq!(
+            simulate_race(&race, &HashMap::new(), 2_000, 2, 7).unwrap(),
+            intervals
+        );
+    }
+
+    #[test]
+    fn zero_volatility_collapses_bands_to_the_projection() {
+        let race = race(vec![entry("Alpha", 900_000, 40)]);
+        let still = HashMap::from([(
+            "Alpha".to_string(),
+            GuildVolatility {
+                daily_rate_cv: 0.0,
+                daily_territory_sd: 0.0,
+            },
+        )]);
+        let interval = &simulate_race(&race, &still, 200, 1, 1).unwrap()[0];
+        assert_eq!(interval.final_sr.p10, 900_000);
+        assert_eq!(interval.final_sr.p90, 900_000);
+    }
+
+    #[test]
+    fn volatility_uses_daily_closing_observations() {
+        let start: DateTime<Utc> = "2026-01-01T23:00:00Z".parse().unwrap();
+        let days = [
+            (1_000, 40),
+            (2_000, 42),
+            (3_200, 41),
+            (4_000, 45),
+            (5_100, 44),
+        ];
+        let observations: Vec<_> = days
+            .iter()
+            .enumerate()
+            .flat_map(|(day, (sr, count))| {
+                let at = start + Duration::days(day as i64);
+                // An earlier same-day sample is superseded by the closing one.
+                [(at - Duration::hours(5), 0, 0), (at, *sr, *count)]
+            })
+            .collect();
+        let volatility = volatility_from_observations(&observations, &[]);
+        // Gains 1000, 1200, 800, 1100 and changes +2, -1, +4, -1.
+        assert!((volatility.daily_rate_cv - 0.1666).abs() < 1e-3);
+        assert!((volatility.daily_territory_sd - 2.4495).abs() < 1e-3);
+
+        assert_eq!(
+            volatility_from_observations(&observations[..4], &[]),
+            GuildVolatility::default()
+        );
+    }
+
+    #[test]
+    fn volatility_excludes_passive_gain() {
+        let start: DateTime<Utc> = "2026-01-01T23:00:00Z".parse().unwrap();
+        let scalar = [(start, 1.0, false)];
+        let passive_per_day = passive_sr_per_hour(40, 1.0) * 24.0;
+        let observations: Vec<_> = [0.0, 1_000.0, 2_200.0, 3_000.0, 4_100.0]
+            .iter()
+            .enumerate()
+            .map(|(day, non_passive)| {
+                let sr = non_passive + passive_per_day * day as f64;
+                (start + Duration::days(day as i64), sr.round() as i64, 40)
+            })
+            .collect();
+        let volatility = volatility_from_observations(&observations, &scalar);
+        // Same non-passive gains as above once the steady passive income is removed.
+        assert!((volatility.daily_rate_cv - 0.1666).abs() < 1e-3);
+    }
+
+    #[test]
+    fn bands_widen_with_the_remaining_horizon() {
+        let still_territory = HashMap::from([(
+            "Alpha".to_string(),
+            GuildVolatility {
+                daily_rate_cv: 0.3,
+                daily_territory_sd: 0.0,
+            },
+        )]);
+        let width = |race: &SeasonRaceResponse| {
+            let band = simulate_race(race, &still_territory, 4_000, 1, 3).unwrap()[0].final_sr;
+            band.p90 - band.p10
+        };
+
+        // Five days left at 14k non-passive SR per day.
+        let long = race(vec![entry("Alpha", 900_000, 40)]);
+        // One day left at the same daily pace.
+        let mut short = race(vec![entry("Alpha", 900_000, 40)]);
+        short.generated_at = "2026-01-10T00:00:00Z".to_string();
+        short.remaining_hours = 24.0;
+        short.entries[0].projected_raid_sr = 110_000;
+        short.entries[0].projected_conquest_sr = 104_000;
+
+        let (long_width, short_width) = (width(&long), width(&short));
+        assert!(short_width > 0);
+        // Spread grows with sqrt(5) rather than staying flat or growing linearly.
+        let ratio = long_width as f64 / short_width as f64;
+        assert!(ratio > 2.0 && ratio < 2.5, "ratio {ratio}");
+    }
+
+    #[test]
+    fn normal_samples_are_standardized() {
+        let mut rng = SplitMix64::new(42);
+        let samples: Vec<f64> = (0..20_000).map(|_| rng.normal()).collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let variance =
+            samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+        assert!(mean.abs() < 0.03);
+        assert!((variance - 1.0).abs() < 0.05);
+    }
+}
@@ -67,9 +67,11 @@ pub async fn build_scalar_projection(
         "SELECT season_id, sampled_at, scalar_weighted, confidence, sample_count \
          FROM season_scalar_samples \
          WHERE season_id = ANY($1) \
+           AND sampled_at <= $2 \
          ORDER BY season_id ASC, sampled_at ASC",
     )
     .bind(&season_ids)
+    .bind(generated_at)
     .fetch_all(pool)
     .await
     .map_err(|e| format!("load season scalar samples: {e}"))?;
@@ -143,6 +145,15 @@ impl ScalarProjection {
             .any(|point| point.source == ScalarPointSource::ManualOverride)
     }
 
+    /// Projected weighted scalar in effect at `at`.
+    pub fn scalar_at(&self, at: DateTime<Utc>) -> f64 {
+        self.points
+            .iter()
+            .take_while(|point| point.sampled_at <= at)
+            .last()
+            .map_or(self.current_scalar_weighted, |point| point.scalar_weighted)
+    }
+
     pub fn api_points(&self) -> Vec<SeasonScalarPoint> {
         self.points
             .iter()
@@ -332,7 +332,7 @@ async fn process_polled_map_with<F>(
     }
 
     for event in outgoing {
-        let _ = state.event_tx.send(event);
+        state.publish_event(event).await;
     }
 }
 
@@ -883,6 +883,7 @@ fn build_api_runtime(parts: ApiRuntimeParts) -> Option<TerritoryRuntimeData> {
             menu_captured_territories: None,
             menu_sr_per_hour: None,
             menu_observed_at: None,
+            corroborated_fields: None,
         }),
     })
 }
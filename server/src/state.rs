@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -6,24 +6,29 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
+use sequoia_shared::guild_hq::GuildHqEstimate;
 use sequoia_shared::{
-    ClaimDocumentV1, GuildRef, LiveState, MapIntelOverlay, MapIntelSummary, Resources,
-    SeasonScalarSample, TerritoryMap, TerritoryRuntimeData,
+    ClaimDocumentV2, GuildRef, LiveState, MapIntelOverlay, MapIntelSummary, Resources,
+    SeasonScalarSample, TerritoryMap, TerritoryRuntimeData, WarEvent,
 };
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
-use tokio::sync::{Mutex, RwLock, broadcast};
+use tokio::sync::{Mutex, RwLock, Semaphore, broadcast};
 use tracing::warn;
 
 use crate::config::{
-    WYNNCRAFT_GUILD_LIST_URL, internal_ingest_token, max_history_replay_events,
-    max_history_sr_sample_rows, max_ingest_updates_per_request, seq_live_handoff_v1_enabled,
-    sse_broadcast_buffer, upstream_connect_timeout, upstream_http_timeout,
+    TIMELAPSE_MAX_CONCURRENT_RENDERS, WYNNCRAFT_GUILD_LIST_URL, internal_ingest_token,
+    max_history_export_rows, max_history_replay_events, max_history_sr_sample_rows,
+    max_ingest_updates_per_request, seq_live_handoff_v1_enabled, sse_broadcast_buffer,
+    sse_replay_buffer, upstream_connect_timeout, upstream_http_timeout,
 };
+use crate::services::season_race_simulation::CachedRaceIntervals;
 
 pub type GuildColor = (u8, u8, u8);
 pub type GuildColorMap = HashMap<String, GuildColor>;
 pub type CachedScalarSample = (SeasonScalarSample, Arc<Bytes>);
+/// Timelapse request after clamping: (from, to, frames, fps).
+pub type TimelapseKey = (DateTime<Utc>, DateTime<Utc>, u32, u16);
 
 fn initial_claim_id_seed() -> u64 {
     let now = SystemTime::now()
@@ -94,9 +99,120 @@ pub fn lookup_guild_color(
 /// Pre-serialized SSE event — serialized once by the poller, shared by all clients via Arc.
 #[derive(Debug, Clone)]
 pub enum PreSerializedEvent {
-    Snapshot { seq: u64, json: Arc<Bytes> },
-    Update { seq: u64, json: Arc<Bytes> },
-    RuntimeUpdate { seq: u64, json: Arc<Bytes> },
+    Snapshot {
+        seq: u64,
+        json: Arc<Bytes>,
+    },
+    Update {
+        seq: u64,
+        json: Arc<Bytes>,
+    },
+    RuntimeUpdate {
+        seq: u64,
+        json: Arc<Bytes>,
+    },
+    /// War transitions are advisory and unsequenced; they never advance or gap `seq`.
+    War {
+        json: Arc<Bytes>,
+    },
+}
+
+impl PreSerializedEvent {
+    /// Stream sequence of this event; `0` for unsequenced events.
+    pub fn seq(&self) -> u64 {
+        match self {
+            Self::Snapshot { seq, .. }
+            | Self::Update { seq, .. }
+            | Self::RuntimeUpdate { seq, .. } => *seq,
+            Self::War { .. } => 0,
+        }
+    }
+
+    pub fn is_sequenced(&self) -> bool {
+        !matches!(self, Self::War { .. })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ReplayEntry {
+    Event(PreSerializedEvent),
+    /// Snapshot payloads are not retained; resuming across one replays the live snapshot instead.
+    SnapshotBoundary(u64),
+}
+
+impl ReplayEntry {
+    fn seq(&self) -> u64 {
+        match self {
+            Self::Event(event) => event.seq(),
+            Self::SnapshotBoundary(seq) => *seq,
+        }
+    }
+}
+
+/// Events retained for SSE `Last-Event-ID` resume, newest last.
+#[derive(Debug, Clone, Default)]
+pub struct ReplaySlice {
+    /// Every broadcast event with `seq > floor_seq` is still retained in memory.
+    pub floor_seq: u64,
+    /// Latest snapshot boundary after the requested seq, if any.
+    pub snapshot_seq: Option<u64>,
+    /// Update/runtime events after the requested seq (and after `snapshot_seq` when present).
+    pub events: Vec<PreSerializedEvent>,
+}
+
+/// Bounded ring of recently broadcast `update`/`runtime_update` events.
+#[derive(Debug)]
+pub struct EventReplayBuffer {
+    capacity: usize,
+    floor_seq: u64,
+    entries: VecDeque<ReplayEntry>,
+}
+
+impl EventReplayBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            floor_seq: 0,
+            entries: VecDeque::new(),
+        }
+    }
+
+    /// Drop retained events and mark everything up to `floor_seq` as unavailable in memory.
+    pub fn reset(&mut self, floor_seq: u64) {
+        self.entries.clear();
+        self.floor_seq = floor_seq;
+    }
+
+    pub fn record(&mut self, event: &PreSerializedEvent) {
+        let entry = match event {
+            PreSerializedEvent::Snapshot { seq, .. } => ReplayEntry::SnapshotBoundary(*seq),
+            PreSerializedEvent::War { .. } => return,
+            other => ReplayEntry::Event(other.clone()),
+        };
+        self.entries.push_back(entry);
+        while self.entries.len() > self.capacity {
+            if let Some(evicted) = self.entries.pop_front() {
+                self.floor_seq = self.floor_seq.max(evicted.seq());
+            }
+        }
+    }
+
+    pub fn since(&self, last_seq: u64) -> ReplaySlice {
+        let mut slice = ReplaySlice {
+            floor_seq: self.floor_seq,
+            ..ReplaySlice::default()
+        };
+        for entry in self.entries.iter().filter(|entry| entry.seq() > last_seq) {
+            match entry {
+                ReplayEntry::Event(event) => slice.events.push(event.clone()),
+                ReplayEntry::SnapshotBoundary(seq) => {
+                    slice.snapshot_seq = Some(*seq);
+                    slice.events.clear();
+                }
+            }
+        }
+        slice
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -158,6 +274,8 @@ pub struct AppState {
     pub next_seq: Arc<AtomicU64>,
     pub next_seq_reserved: Arc<AtomicU64>,
     pub event_tx: broadcast::Sender<PreSerializedEvent>,
+    /// Recent broadcast events kept for SSE `Last-Event-ID` resume.
+    pub event_replay: Arc<Mutex<EventReplayBuffer>>,
     pub guild_cache: Arc<DashMap<String, CachedGuild>>,
     pub guild_catalog_cache: Arc<RwLock<Option<CachedGuildCatalog>>>,
     pub season_leaderboard_cache: Arc<RwLock<Option<CachedSeasonLeaderboard>>>,
@@ -172,8 +290,19 @@ pub struct AppState {
     pub guild_colors_dirty: Arc<AtomicBool>,
     /// Canonical territory overrides from ingest service (ownership/runtime).
     pub ingest_overrides: Arc<RwLock<HashMap<String, IngestTerritoryOverride>>>,
+    /// Latest queued/started war per territory, fed by canonical war ingest.
+    pub live_wars: Arc<RwLock<HashMap<String, WarEvent>>>,
     /// Latest computed season scalar sample and pre-serialized API payload.
     pub latest_scalar_sample: Arc<RwLock<Option<CachedScalarSample>>>,
+    /// Latest HQ estimate per lowercased guild name.
+    pub guild_hqs: Arc<RwLock<HashMap<String, GuildHqEstimate>>>,
+    /// Season race Monte Carlo bands per (season id, top-N).
+    pub season_race_intervals: Arc<RwLock<HashMap<(i32, u32), CachedRaceIntervals>>>,
+    pub season_race_sim_lock: Arc<Mutex<()>>,
+    /// Encoded timelapse PNGs, reused until they expire like their `Cache-Control`.
+    pub timelapse_cache: Arc<RwLock<HashMap<TimelapseKey, CachedTimelapse>>>,
+    /// Bounds how many timelapses render at once.
+    pub timelapse_permits: Arc<Semaphore>,
     pub http_client: reqwest::Client,
     /// PostgreSQL pool for history persistence. None if DATABASE_URL is not set.
     pub db: Option<PgPool>,
@@ -181,15 +310,26 @@ pub struct AppState {
     pub internal_ingest_token: Option<String>,
     pub max_ingest_updates_per_request: usize,
     pub max_history_replay_events: i64,
+    pub max_history_export_rows: i64,
     pub max_history_sr_sample_rows: i64,
     pub next_claim_id: Arc<AtomicU64>,
     pub guild_catalog_url: Arc<String>,
     pub observability: Arc<ObservabilityCounters>,
 }
 
+#[derive(Debug, Clone)]
+pub struct CachedTimelapse {
+    pub png: Bytes,
+    pub expires_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedGuild {
     pub data: String,
+    /// `data` with the HQ estimate and moves attached, as `/api/guild/{name}` serves it; filled
+    /// on first serve when another route cached the raw payload.
+    #[serde(skip)]
+    pub body: Option<Bytes>,
     pub fetched_at: DateTime<Utc>,
 }
 
@@ -233,8 +373,16 @@ pub struct CachedMapIntel {
 pub struct StoredClaimLayout {
     pub id: String,
     pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
     pub title: Option<String>,
-    pub document: ClaimDocumentV1,
+    #[serde(default = "default_claim_revision")]
+    pub revision: i32,
+    pub document: ClaimDocumentV2,
+}
+
+fn default_claim_revision() -> i32 {
+    1
 }
 
 #[derive(Debug, Default)]
@@ -397,6 +545,7 @@ impl AppState {
             next_seq: Arc::new(AtomicU64::new(0)),
             next_seq_reserved: Arc::new(AtomicU64::new(0)),
             event_tx,
+            event_replay: Arc::new(Mutex::new(EventReplayBuffer::new(sse_replay_buffer()))),
             guild_cache: Arc::new(DashMap::new()),
             guild_catalog_cache: Arc::new(RwLock::new(None)),
             season_leaderboard_cache: Arc::new(RwLock::new(None)),
@@ -408,26 +557,52 @@ impl AppState {
             guild_colors: Arc::new(RwLock::new(HashMap::new())),
             guild_colors_dirty: Arc::new(AtomicBool::new(true)),
             ingest_overrides: Arc::new(RwLock::new(HashMap::new())),
+            live_wars: Arc::new(RwLock::new(HashMap::new())),
             latest_scalar_sample: Arc::new(RwLock::new(None)),
+            guild_hqs: Arc::new(RwLock::new(HashMap::new())),
+            season_race_intervals: Arc::new(RwLock::new(HashMap::new())),
+            season_race_sim_lock: Arc::new(Mutex::new(())),
+            timelapse_cache: Arc::new(RwLock::new(HashMap::new())),
+            timelapse_permits: Arc::new(Semaphore::new(TIMELAPSE_MAX_CONCURRENT_RENDERS)),
             http_client,
             db,
             seq_live_handoff_v1: seq_live_handoff_v1_enabled(),
             internal_ingest_token,
             max_ingest_updates_per_request: max_ingest_updates_per_request(),
             max_history_replay_events: max_history_replay_events(),
+            max_history_export_rows: max_history_export_rows(),
             max_history_sr_sample_rows: max_history_sr_sample_rows(),
             next_claim_id: Arc::new(AtomicU64::new(initial_claim_id_seed())),
             guild_catalog_url: Arc::new(WYNNCRAFT_GUILD_LIST_URL.to_string()),
             observability: Arc::new(ObservabilityCounters::default()),
         }
     }
+
+    /// Record an event for SSE resume, then broadcast it to connected clients.
+    pub async fn publish_event(&self, event: PreSerializedEvent) {
+        self.event_replay.lock().await.record(&event);
+        let _ = self.event_tx.send(event);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
+    use std::sync::Arc;
 
-    use super::{build_guild_color_lookup, lookup_guild_color, normalize_guild_color_key};
+    use bytes::Bytes;
+
+    use super::{
+        EventReplayBuffer, PreSerializedEvent, build_guild_color_lookup, lookup_guild_color,
+        normalize_guild_color_key,
+    };
+
+    fn update_event(seq: u64) -> PreSerializedEvent {
+        PreSerializedEvent::Update {
+            seq,
+            json: Arc::new(Bytes::from_static(b"{}")),
+        }
+    }
 
     #[test]
     fn normalize_guild_color_key_collapses_spaces_and_lowercases() {
@@ -452,4 +627,67 @@ mod tests {
             Some((16, 16, 254))
         );
     }
+
+    #[test]
+    fn replay_buffer_returns_events_after_requested_seq() {
+        let mut buffer = EventReplayBuffer::new(8);
+        for seq in 1..=4 {
+            buffer.record(&update_event(seq));
+        }
+
+        let slice = buffer.since(2);
+        assert_eq!(slice.floor_seq, 0);
+        assert_eq!(slice.snapshot_seq, None);
+        assert_eq!(
+            slice
+                .events
+                .iter()
+                .map(PreSerializedEvent::seq)
+                .collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+    }
+
+    #[test]
+    fn replay_buffer_raises_floor_when_evicting() {
+        let mut buffer = EventReplayBuffer::new(2);
+        for seq in 1..=5 {
+            buffer.record(&update_event(seq));
+        }
+
+        let slice = buffer.since(1);
+        assert_eq!(slice.floor_seq, 3);
+        assert_eq!(
+            slice
+                .events
+                .iter()
+                .map(PreSerializedEvent::seq)
+                .collect::<Vec<_>>(),
+            vec![4, 5]
+        );
+    }
+
+    #[test]
+    fn replay_buffer_collapses_events_before_snapshot_boundary() {
+        let mut buffer = EventReplayBuffer::new(8);
+        buffer.reset(10);
+        buffer.record(&update_event(11));
+        buffer.record(&PreSerializedEvent::Snapshot {
+            seq: 12,
+            json: Arc::new(Bytes::new()),
+        });
+        buffer.record(&update_event(13));
+
+        let slice = buffer.since(10);
+        assert_eq!(slice.floor_seq, 10);
+        assert_eq!(slice.snapshot_seq, Some(12));
+        assert_eq!(
+            slice
+                .events
+                .iter()
+                .map(PreSerializedEvent::seq)
+                .collect::<Vec<_>>(),
+            vec![13]
+        );
+    }
 }
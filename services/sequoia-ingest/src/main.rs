@@ -7,7 +7,7 @@ use std::time::{Duration, Instant};
 use axum::Json;
 use axum::Router;
 use axum::body::Bytes;
-use axum::extract::{ConnectInfo, DefaultBodyLimit, State};
+use axum::extract::{ConnectInfo, DefaultBodyLimit, Path, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::routing::{get, post};
 use base64::Engine;
@@ -17,8 +17,8 @@ use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use ipnet::IpNet;
 use reqwest::Client;
 use sequoia_shared::{
-    CanonicalTerritoryBatch, CanonicalTerritoryUpdate, DataProvenance, TerritoryRuntimeData,
-    VisibilityClass,
+    CanonicalTerritoryBatch, CanonicalTerritoryUpdate, CanonicalWarBatch, CanonicalWarReport,
+    DataProvenance, Resources, TerritoryRuntimeData, VisibilityClass, WarEvent,
 };
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -34,8 +34,10 @@ use tokio::sync::RwLock;
 use tracing::{info, warn};
 use uuid::Uuid;
 
+mod replay;
+
 const RATE_WINDOW: Duration = Duration::from_secs(60);
-const QUORUM_WINDOW: Duration = Duration::from_secs(120);
+const DEFAULT_QUORUM_WINDOW_SECS: u64 = 120;
 const ACTIVE_REPORTER_WINDOW: Duration = Duration::from_secs(180);
 const TOKEN_TTL_HOURS: i64 = 24;
 const TOKEN_ROTATE_MARGIN_MINS: i64 = 15;
@@ -51,6 +53,14 @@ const DEFAULT_SESSION_REFRESH_INTERVAL_SECS: u64 = 600;
 const DEFAULT_SESSION_FAIL_OPEN_GRACE_SECS: u64 = 1800;
 const DEFAULT_OWNER_CORROBORATION_WINDOW_SECS: u64 = 90;
 const DEFAULT_ACTIVE_REPORTER_STALE_SECS: u64 = 1800;
+const DEFAULT_REPUTATION_HALF_LIFE_HOURS: f64 = 72.0;
+const DEFAULT_REPUTATION_MAX_WEIGHT: f64 = 1.5;
+const REPUTATION_NEUTRAL: f64 = 1.0;
+const REPUTATION_MIN_WEIGHT: f64 = 0.25;
+const REPUTATION_CONFIRM_STEP: f64 = 0.1;
+const REPUTATION_CONFIRM_COOLDOWN: Duration = Duration::from_secs(6 * 3600);
+const ADMIN_MAX_QUARANTINE_SECS: u64 = 7 * 24 * 3600;
+const REPUTATION_CONTRADICT_STEP: f64 = 0.4;
 const CHALLENGE_TTL_SECS: u64 = 120;
 const HDR_IRIS_KEY_ID: &str = "x-iris-key-id";
 const HDR_IRIS_TS: &str = "x-iris-ts";
@@ -63,6 +73,7 @@ struct Config {
     db_url: String,
     sequoia_server_base_url: String,
     internal_ingest_token: String,
+    admin_token: Option<String>,
     api_body_limit_bytes: usize,
     max_reporters: usize,
     rate_limit_ip_per_min: usize,
@@ -70,6 +81,7 @@ struct Config {
     max_rate_limit_keys: usize,
     quorum_min_reporters: usize,
     quorum_min_origins: usize,
+    quorum_window_secs: u64,
     degraded_single_reporter_enabled: bool,
     raw_retention_days: i64,
     reporter_retention_days: i64,
@@ -99,10 +111,21 @@ struct Config {
     owner_corroboration_window_secs: u64,
     owner_revert_on_mismatch: bool,
     active_reporter_stale_secs: u64,
+    reputation_half_life_hours: f64,
+    reputation_max_weight: f64,
 }
 
 impl Config {
     fn from_env() -> anyhow::Result<Self> {
+        Self::from_env_with_internal_token(load_internal_ingest_token()?)
+    }
+
+    /// Reads everything but the internal ingest token, which offline tools do not need.
+    fn from_env_with_internal_token(internal_ingest_token: String) -> anyhow::Result<Self> {
+        let quorum_min_reporters = std::env::var("INGEST_QUORUM_MIN_REPORTERS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(2);
         Ok(Self {
             bind_addr: std::env::var("SEQUOIA_INGEST_BIND")
                 .unwrap_or_else(|_| "0.0.0.0:3010".to_string()),
@@ -110,7 +133,8 @@ impl Config {
                 .unwrap_or_else(|_| "sqlite://./sequoia-ingest.db".to_string()),
             sequoia_server_base_url: std::env::var("SEQUOIA_SERVER_URL")
                 .unwrap_or_else(|_| "http://127.0.0.1:3000".to_string()),
-            internal_ingest_token: load_internal_ingest_token()?,
+            internal_ingest_token,
+            admin_token: load_admin_token()?,
             api_body_limit_bytes: std::env::var("INGEST_API_BODY_LIMIT_BYTES")
                 .ok()
                 .and_then(|v| v.parse().ok())
@@ -132,15 +156,17 @@ impl Config {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(20_000),
-            quorum_min_reporters: std::env::var("INGEST_QUORUM_MIN_REPORTERS")
-                .ok()
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(2),
+            quorum_min_reporters,
             quorum_min_origins: std::env::var("INGEST_QUORUM_MIN_DISTINCT_ORIGINS")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .filter(|v| *v > 0)
                 .unwrap_or(DEFAULT_QUORUM_MIN_DISTINCT_ORIGINS),
+            quorum_window_secs: std::env::var("INGEST_QUORUM_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(DEFAULT_QUORUM_WINDOW_SECS),
             degraded_single_reporter_enabled: std::env::var(
                 "INGEST_DEGRADED_SINGLE_REPORTER_ENABLED",
             )
@@ -256,8 +282,46 @@ impl Config {
                 .and_then(|v| v.parse().ok())
                 .filter(|v| *v > 0)
                 .unwrap_or(DEFAULT_ACTIVE_REPORTER_STALE_SECS),
+            reputation_half_life_hours: std::env::var("INGEST_REPUTATION_HALF_LIFE_HOURS")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| v.is_finite() && *v > 0.0)
+                .unwrap_or(DEFAULT_REPUTATION_HALF_LIFE_HOURS),
+            reputation_max_weight: std::env::var("INGEST_REPUTATION_MAX_WEIGHT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| v.is_finite() && *v >= REPUTATION_NEUTRAL)
+                .unwrap_or_else(|| default_reputation_max_weight(quorum_min_reporters)),
         })
     }
+
+    fn quorum_window(&self) -> Duration {
+        Duration::from_secs(self.quorum_window_secs.max(1))
+    }
+}
+
+/// Keeps an unconfigured reputation cap below the reporter quorum, so no single device can
+/// carry quorum alone unless an operator raises `INGEST_REPUTATION_MAX_WEIGHT`.
+fn default_reputation_max_weight(quorum_min_reporters: usize) -> f64 {
+    (quorum_min_reporters.max(1) as f64 - 0.5)
+        .clamp(REPUTATION_NEUTRAL, DEFAULT_REPUTATION_MAX_WEIGHT)
+}
+
+/// Optional bearer token for `/admin/*`; the admin surface is disabled when unset.
+fn load_admin_token() -> anyhow::Result<Option<String>> {
+    use anyhow::bail;
+
+    let Some(token) = std::env::var("INGEST_ADMIN_TOKEN")
+        .ok()
+        .map(|raw| raw.trim().to_string())
+        .filter(|token| !token.is_empty())
+    else {
+        return Ok(None);
+    };
+    if token.len() < 24 {
+        bail!("ingest admin token is too short; use at least 24 characters");
+    }
+    Ok(Some(token))
 }
 
 fn load_internal_ingest_token() -> anyhow::Result<String> {
@@ -339,6 +403,10 @@ struct Metrics {
     reports_degraded_total: AtomicU64,
     reports_quorum_total: AtomicU64,
     forward_failures_total: AtomicU64,
+    forward_dead_lettered_total: AtomicU64,
+    reputation_confirmed_total: AtomicU64,
+    reputation_contradicted_total: AtomicU64,
+    runtime_fields_uncorroborated_total: AtomicU64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -392,12 +460,54 @@ struct ReporterRecord {
     last_attested_at: DateTime<Utc>,
 }
 
-#[derive(Clone)]
+/// Corroboration track record of one device identity; `score` is its quorum vote weight. Keyed by
+/// device rather than reporter id so re-enrolling does not reset it.
+#[derive(Clone, Debug, PartialEq)]
+struct ReporterReputation {
+    score: f64,
+    confirmed: u64,
+    contradicted: u64,
+    updated_at: DateTime<Utc>,
+}
+
+impl ReporterReputation {
+    fn neutral(now: DateTime<Utc>) -> Self {
+        Self {
+            score: REPUTATION_NEUTRAL,
+            confirmed: 0,
+            contradicted: 0,
+            updated_at: now,
+        }
+    }
+
+    /// Score at `now`, decayed toward neutral with the configured half-life.
+    fn decayed_score(&self, now: DateTime<Utc>, half_life_hours: f64) -> f64 {
+        let elapsed_hours = (now - self.updated_at).num_seconds().max(0) as f64 / 3600.0;
+        REPUTATION_NEUTRAL
+            + (self.score - REPUTATION_NEUTRAL) * 0.5_f64.powf(elapsed_hours / half_life_hours)
+    }
+
+    fn record(&mut self, confirmed: bool, now: DateTime<Utc>, cfg: &Config) {
+        let decayed = self.decayed_score(now, cfg.reputation_half_life_hours);
+        let score = if confirmed {
+            self.confirmed += 1;
+            decayed + REPUTATION_CONFIRM_STEP
+        } else {
+            self.contradicted += 1;
+            decayed - REPUTATION_CONTRADICT_STEP
+        };
+        self.score = score.clamp(REPUTATION_MIN_WEIGHT, cfg.reputation_max_weight);
+        self.updated_at = now;
+    }
+}
+
+/// Queued forward to the Sequoia server; the row in `forward_queue` is the source of truth.
+#[derive(Clone, Debug)]
 struct ForwardJob {
-    route: &'static str,
+    id: i64,
+    route: String,
     payload: serde_json::Value,
     attempts: u32,
-    next_attempt_at: Instant,
 }
 
 #[derive(Clone)]
@@ -410,6 +520,15 @@ struct PendingTerritoryClaim {
     received_at: Instant,
 }
 
+#[derive(Clone)]
+struct PendingWarClaim {
+    reporter_id: String,
+    device_identity: String,
+    origin_ip: IpAddr,
+    event: WarEvent,
+    received_at: Instant,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 struct WorldAttestation {
     #[serde(default)]
@@ -453,10 +572,25 @@ struct ProvisionalOwnershipClaim {
     claimed_guild_uuid: Option<String>,
     claimed_guild_name: Option<String>,
     claimed_acquired: Option<String>,
+    /// Devices whose votes carried a claimed owner change; scored once the API confirms or
+    /// contradicts it. Empty when the claim only repeated the owner already known.
+    scored_devices: Vec<String>,
+    /// Whether a mismatch is reverted, i.e. the claim was accepted without headcount quorum.
+    revert_on_mismatch: bool,
     first_seen: Instant,
     expires_at: Instant,
 }
 
+/// Accepted territory claim and how it got there.
+#[derive(Clone, Debug)]
+struct TerritoryClaimDecision {
+    update: CanonicalTerritoryUpdate,
+    degraded: bool,
+    quorum: bool,
+    reporter_ids: Vec<String>,
+    device_identities: Vec<String>,
+}
+
 #[derive(Clone)]
 struct AppState {
     cfg: Config,
@@ -470,12 +604,17 @@ struct AppState {
     quarantined_until: Arc<RwLock<HashMap<String, Instant>>>,
     seen_idempotency: Arc<RwLock<HashMap<String, Instant>>>,
     pending_territory: Arc<RwLock<HashMap<String, Vec<PendingTerritoryClaim>>>>,
+    pending_war: Arc<RwLock<HashMap<String, Vec<PendingWarClaim>>>>,
     identities: Arc<RwLock<HashMap<String, IdentityRecord>>>,
     challenges: Arc<RwLock<HashMap<String, AttestationChallengeRecord>>>,
     seen_signed_nonces: Arc<RwLock<HashMap<String, Instant>>>,
     provisional_ownership: Arc<RwLock<HashMap<String, ProvisionalOwnershipClaim>>>,
+    reputation: Arc<RwLock<HashMap<String, ReporterReputation>>>,
+    /// Last confirmation per (device identity, territory), to rate-limit reputation gains.
+    reputation_confirmed_at: Arc<RwLock<HashMap<(String, String), Instant>>>,
+    /// Owner guild uuid last known per territory, from the authoritative map and accepted claims.
+    known_owners: Arc<RwLock<HashMap<String, String>>>,
     session_verifier_fail_open_until: Arc<RwLock<Option<Instant>>>,
-    forward_queue: Arc<RwLock<VecDeque<ForwardJob>>>,
     metrics: Arc<Metrics>,
 }
 
@@ -562,6 +701,18 @@ struct ReporterTerritoryBatch {
     updates: Vec<CanonicalTerritoryUpdate>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ReporterWarBatch {
+    #[serde(default)]
+    generated_at: String,
+    #[serde(default)]
+    world_attestation: Option<WorldAttestation>,
+    #[serde(default)]
+    session_refresh_token: Option<String>,
+    #[serde(default)]
+    events: Vec<CanonicalWarReport>,
+}
+
 #[derive(Debug, Serialize)]
 struct HeartbeatResponse {
     ok: bool,
@@ -582,6 +733,101 @@ struct ReportAck {
     quorum: u64,
 }
 
+#[derive(Debug, Serialize)]
+struct AdminReporterView {
+    reporter_id: String,
+    revoked: bool,
+    token_expired: bool,
+    token_expires_at: String,
+    last_seen: String,
+    last_attested_at: String,
+    device_key_id: String,
+    mojang_uuid: String,
+    mojang_username: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    identity_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quarantine_remaining_secs: Option<u64>,
+    malformed_penalty: u32,
+    reputation_weight: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct AdminReportersResponse {
+    reporters: Vec<AdminReporterView>,
+}
+
+#[derive(Debug, Serialize)]
+struct AdminReporterActionResponse {
+    ok: bool,
+    reporter_id: String,
+    revoked: bool,
+    token_expires_at: String,
+    dropped_pending_claims: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct AdminQuarantineEntry {
+    key: String,
+    remaining_secs: u64,
+    malformed_penalty: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct AdminQuarantineResponse {
+    entries: Vec<AdminQuarantineEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdminQuarantineRequest {
+    key: String,
+    #[serde(default)]
+    secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct AdminPendingGroup {
+    claim_hash: String,
+    reporters: Vec<String>,
+    distinct_devices: usize,
+    distinct_origins: usize,
+    vote_weight: f64,
+    oldest_age_secs: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct AdminPendingTerritory {
+    territory: String,
+    claims: usize,
+    groups: Vec<AdminPendingGroup>,
+}
+
+#[derive(Debug, Serialize)]
+struct AdminPendingResponse {
+    quorum_min_reporters: usize,
+    quorum_min_origins: usize,
+    territories: Vec<AdminPendingTerritory>,
+}
+
+#[derive(Debug, Serialize)]
+struct AdminDeadLetter {
+    id: i64,
+    route: String,
+    attempts: u32,
+    enqueued_at: String,
+    failed_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_error: Option<String>,
+    payload_bytes: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct AdminForwardResponse {
+    queue_depth: usize,
+    dead_letter_count: usize,
+    dead_letters: Vec<AdminDeadLetter>,
+}
+
 #[derive(Debug, Clone)]
 struct AuthedReporter {
     reporter_id: String,
@@ -593,14 +839,48 @@ struct AuthedReporter {
     last_attested_at: DateTime<Utc>,
 }
 
+impl AppState {
+    fn new(cfg: Config, db: SqlitePool) -> anyhow::Result<Self> {
+        Ok(Self {
+            cfg,
+            db,
+            http: Client::builder().timeout(Duration::from_secs(10)).build()?,
+            reporters: Arc::new(RwLock::new(HashMap::new())),
+            token_index: Arc::new(RwLock::new(HashMap::new())),
+            ip_windows: Arc::new(RwLock::new(HashMap::new())),
+            reporter_windows: Arc::new(RwLock::new(HashMap::new())),
+            malformed_penalties: Arc::new(RwLock::new(HashMap::new())),
+            quarantined_until: Arc::new(RwLock::new(HashMap::new())),
+            seen_idempotency: Arc::new(RwLock::new(HashMap::new())),
+            pending_territory: Arc::new(RwLock::new(HashMap::new())),
+            pending_war: Arc::new(RwLock::new(HashMap::new())),
+            identities: Arc::new(RwLock::new(HashMap::new())),
+            challenges: Arc::new(RwLock::new(HashMap::new())),
+            seen_signed_nonces: Arc::new(RwLock::new(HashMap::new())),
+            provisional_ownership: Arc::new(RwLock::new(HashMap::new())),
+            reputation: Arc::new(RwLock::new(HashMap::new())),
+            reputation_confirmed_at: Arc::new(RwLock::new(HashMap::new())),
+            known_owners: Arc::new(RwLock::new(HashMap::new())),
+            session_verifier_fail_open_until: Arc::new(RwLock::new(None)),
+            metrics: Arc::new(Metrics::default()),
+        })
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("sequoia_ingest=info")),
-        )
-        .init();
+    let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("sequoia_ingest=info"));
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() == Some("replay") {
+        // Replay writes JSON lines to stdout, so logs go to stderr.
+        tracing_subscriber::fmt()
+            .with_env_filter(env_filter)
+            .with_writer(std::io::stderr)
+            .init();
+        return replay::run(args.collect()).await;
+    }
+    tracing_subscriber::fmt().with_env_filter(env_filter).init();
 
     let cfg = Config::from_env()?;
     let db = SqlitePoolOptions::new()
@@ -609,28 +889,10 @@ async fn main() -> anyhow::Result<()> {
         .await?;
     initialize_db(&db).await?;
 
-    let state = AppState {
-        cfg: cfg.clone(),
-        db,
-        http: Client::builder().timeout(Duration::from_secs(10)).build()?,
-        reporters: Arc::new(RwLock::new(HashMap::new())),
-        token_index: Arc::new(RwLock::new(HashMap::new())),
-        ip_windows: Arc::new(RwLock::new(HashMap::new())),
-        reporter_windows: Arc::new(RwLock::new(HashMap::new())),
-        malformed_penalties: Arc::new(RwLock::new(HashMap::new())),
-        quarantined_until: Arc::new(RwLock::new(HashMap::new())),
-        seen_idempotency: Arc::new(RwLock::new(HashMap::new())),
-        pending_territory: Arc::new(RwLock::new(HashMap::new())),
-        identities: Arc::new(RwLock::new(HashMap::new())),
-        challenges: Arc::new(RwLock::new(HashMap::new())),
-        seen_signed_nonces: Arc::new(RwLock::new(HashMap::new())),
-        provisional_ownership: Arc::new(RwLock::new(HashMap::new())),
-        session_verifier_fail_open_until: Arc::new(RwLock::new(None)),
-        forward_queue: Arc::new(RwLock::new(VecDeque::new())),
-        metrics: Arc::new(Metrics::default()),
-    };
+    let state = AppState::new(cfg.clone(), db)?;
 
     bootstrap_reporters(&state).await?;
+    bootstrap_reputation(&state).await?;
 
     spawn_retention_task(state.clone());
     spawn_forwarder_task(state.clone());
@@ -642,7 +904,26 @@ async fn main() -> anyhow::Result<()> {
         .route("/v1/attest/challenge", post(attest_challenge))
         .route("/v1/enroll", post(enroll))
         .route("/v1/report/territory", post(report_territory))
+        .route("/v1/report/war", post(report_war))
         .route("/v1/heartbeat", post(heartbeat))
+        .route("/admin/reporters", get(admin_list_reporters))
+        .route(
+            "/admin/reporters/{reporter_id}/revoke",
+            post(admin_revoke_reporter),
+        )
+        .route(
+            "/admin/reporters/{reporter_id}/expire-token",
+            post(admin_expire_reporter_token),
+        )
+        .route("/admin/quarantine", get(admin_list_quarantine))
+        .route("/admin/quarantine/lift", post(admin_lift_quarantine))
+        .route("/admin/quarantine/extend", post(admin_extend_quarantine))
+        .route("/admin/pending", get(admin_list_pending))
+        .route("/admin/forward", get(admin_forward_status))
+        .route(
+            "/admin/forward/dead-letters/{id}/requeue",
+            post(admin_requeue_dead_letter),
+        )
         .layer(DefaultBodyLimit::max(cfg.api_body_limit_bytes))
         .with_state(Arc::new(state));
 
@@ -664,6 +945,32 @@ async fn health() -> Json<serde_json::Value> {
 }
 
 async fn metrics(State(state): State<Arc<AppState>>) -> String {
+    let (tracked, mean_score, low, trusted) = {
+        let now = Utc::now();
+        let reputation = state.reputation.read().await;
+        let weights: Vec<f64> = reputation
+            .values()
+            .map(|entry| reporter_vote_weight(&state.cfg, Some(entry), now))
+            .collect();
+        let tracked = weights.len();
+        let mean_score = if tracked == 0 {
+            REPUTATION_NEUTRAL
+        } else {
+            weights.iter().sum::<f64>() / tracked as f64
+        };
+        let solo_quorum = state.cfg.quorum_min_reporters.max(1) as f64;
+        let low = weights
+            .iter()
+            .filter(|weight| **weight < REPUTATION_NEUTRAL)
+            .count();
+        let trusted = weights
+            .iter()
+            .filter(|weight| **weight + 1e-9 >= solo_quorum)
+            .count();
+        (tracked, mean_score, low, trusted)
+    };
+    let forward_queue_depth = forward_queue_depth(&state).await.unwrap_or_default();
+    let forward_dead_letters = forward_dead_letter_count(&state).await.unwrap_or_default();
     format!(
         "# TYPE sequoia_ingest_enrolled_total counter\nsequoia_ingest_enrolled_total {}\n\
 # TYPE sequoia_ingest_attest_ok_total counter\nsequoia_ingest_attest_ok_total {}\n\
@@ -676,7 +983,17 @@ async fn metrics(State(state): State<Arc<AppState>>) -> String {
 # TYPE sequoia_ingest_reports_rejected_total counter\nsequoia_ingest_reports_rejected_total {}\n\
 # TYPE sequoia_ingest_reports_degraded_total counter\nsequoia_ingest_reports_degraded_total {}\n\
 # TYPE sequoia_ingest_reports_quorum_total counter\nsequoia_ingest_reports_quorum_total {}\n\
-# TYPE sequoia_ingest_forward_failures_total counter\nsequoia_ingest_forward_failures_total {}\n",
+# TYPE sequoia_ingest_forward_failures_total counter\nsequoia_ingest_forward_failures_total {}\n\
+# TYPE sequoia_ingest_reputation_confirmed_total counter\nsequoia_ingest_reputation_confirmed_total {}\n\
+# TYPE sequoia_ingest_reputation_contradicted_total counter\nsequoia_ingest_reputation_contradicted_total {}\n\
+# TYPE sequoia_ingest_reputation_tracked gauge\nsequoia_ingest_reputation_tracked {}\n\
+# TYPE sequoia_ingest_reputation_mean_weight gauge\nsequoia_ingest_reputation_mean_weight {:.4}\n\
+# TYPE sequoia_ingest_reputation_low gauge\nsequoia_ingest_reputation_low {}\n\
+# TYPE sequoia_ingest_reputation_trusted gauge\nsequoia_ingest_reputation_trusted {}\n\
+# TYPE sequoia_ingest_forward_dead_lettered_total counter\nsequoia_ingest_forward_dead_lettered_total {}\n\
+# TYPE sequoia_ingest_forward_queue_depth gauge\nsequoia_ingest_forward_queue_depth {}\n\
+# TYPE sequoia_ingest_forward_dead_letters gauge\nsequoia_ingest_forward_dead_letters {}\n\
+# TYPE sequoia_ingest_runtime_fields_uncorroborated_total counter\nsequoia_ingest_runtime_fields_uncorroborated_total {}\n",
         state.metrics.enrolled_total.load(Ordering::Relaxed),
         state.metrics.attest_ok_total.load(Ordering::Relaxed),
         state.metrics.attest_fail_total.load(Ordering::Relaxed),
@@ -698,91 +1015,554 @@ async fn metrics(State(state): State<Arc<AppState>>) -> String {
         state.metrics.reports_degraded_total.load(Ordering::Relaxed),
         state.metrics.reports_quorum_total.load(Ordering::Relaxed),
         state.metrics.forward_failures_total.load(Ordering::Relaxed),
+        state
+            .metrics
+            .reputation_confirmed_total
+            .load(Ordering::Relaxed),
+        state
+            .metrics
+            .reputation_contradicted_total
+            .load(Ordering::Relaxed),
+        tracked,
+        mean_score,
+        low,
+        trusted,
+        state
+            .metrics
+            .forward_dead_lettered_total
+            .load(Ordering::Relaxed),
+        forward_queue_depth,
+        forward_dead_letters,
+        state
+            .metrics
+            .runtime_fields_uncorroborated_total
+            .load(Ordering::Relaxed),
     )
 }
 
-async fn attest_challenge(
-    State(state): State<Arc<AppState>>,
-    ConnectInfo(addr): ConnectInfo<SocketAddr>,
-    headers: HeaderMap,
-    body: Bytes,
-) -> Result<Json<AttestChallengeResponse>, StatusCode> {
-    let ip = resolve_client_ip(&headers, addr, &state.cfg.trusted_proxy_cidrs).to_string();
-    if !check_rate_limit_ip(&state, &ip).await {
-        return Err(StatusCode::TOO_MANY_REQUESTS);
-    }
-    let req: AttestChallengeRequest = parse_json_body(&body)?;
-    if decode_ed25519_public_key(&req.device_pubkey).is_none() {
-        state
-            .metrics
-            .attest_fail_total
-            .fetch_add(1, Ordering::Relaxed);
-        return Err(StatusCode::BAD_REQUEST);
+/// Checks the `/admin/*` bearer token; the surface reads as absent when no token is configured.
+fn ensure_admin(cfg: &Config, headers: &HeaderMap) -> Result<(), StatusCode> {
+    let Some(expected) = cfg.admin_token.as_deref() else {
+        return Err(StatusCode::NOT_FOUND);
+    };
+    let provided = bearer_token(headers).ok_or(StatusCode::UNAUTHORIZED)?;
+    // Compare digests so the check does not leak a matching prefix through timing.
+    if token_hash(&provided) != token_hash(expected) {
+        return Err(StatusCode::UNAUTHORIZED);
     }
+    Ok(())
+}
 
+fn remaining_secs(until: Instant, now: Instant) -> Option<u64> {
+    (until > now).then(|| until.duration_since(now).as_secs().max(1))
+}
+
+async fn admin_list_reporters(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<AdminReportersResponse>, StatusCode> {
+    ensure_admin(&state.cfg, &headers)?;
     let now = Utc::now();
-    let challenge_id = Uuid::new_v4().to_string();
-    let nonce = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
-    let server_id = format!("iris:{}", Uuid::new_v4().simple());
-    let expires_at =
-        now + chrono::Duration::seconds(i64::try_from(CHALLENGE_TTL_SECS).unwrap_or(120));
-    let device_pubkey_hash = token_hash(req.device_pubkey.trim());
+    let now_instant = Instant::now();
+    let reporters = state.reporters.read().await;
+    let identities = state.identities.read().await;
+    let quarantine = state.quarantined_until.read().await;
+    let penalties = state.malformed_penalties.read().await;
+    let reputation = state.reputation.read().await;
 
-    let challenge = AttestationChallengeRecord {
-        challenge_id: challenge_id.clone(),
-        nonce: nonce.clone(),
-        server_id: server_id.clone(),
-        device_pubkey_hash: device_pubkey_hash.clone(),
-        issued_at: now,
-        expires_at,
-        used_at: None,
-    };
+    let mut views = reporters
+        .iter()
+        .map(|(reporter_id, record)| AdminReporterView {
+            reporter_id: reporter_id.clone(),
+            revoked: record.revoked,
+            token_expired: record.token_expires_at <= now,
+            token_expires_at: record.token_expires_at.to_rfc3339(),
+            last_seen: record.last_seen.to_rfc3339(),
+            last_attested_at: record.last_attested_at.to_rfc3339(),
+            device_key_id: record.device_key_id.clone(),
+            mojang_uuid: record.mojang_uuid.clone(),
+            mojang_username: record.mojang_username.clone(),
+            identity_status: identities
+                .get(reporter_id)
+                .map(|identity| identity.status.clone()),
+            quarantine_remaining_secs: quarantine
+                .get(reporter_id)
+                .and_then(|until| remaining_secs(*until, now_instant)),
+            malformed_penalty: penalties
+                .get(&format!("reporter:{reporter_id}"))
+                .copied()
+                .unwrap_or_default(),
+            reputation_weight: reporter_vote_weight(
+                &state.cfg,
+                reputation.get(&canonical_device_identity_hash(&record.device_pubkey_b64)),
+                now,
+            ),
+        })
+        .collect::<Vec<_>>();
+    views.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+    Ok(Json(AdminReportersResponse { reporters: views }))
+}
+
+async fn admin_revoke_reporter(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(reporter_id): Path<String>,
+) -> Result<Json<AdminReporterActionResponse>, StatusCode> {
+    ensure_admin(&state.cfg, &headers)?;
+    let mut response = admin_invalidate_reporter(&state, &reporter_id, true).await?;
+    response.dropped_pending_claims = drop_pending_claims_for(&state, &reporter_id).await;
 
+    if let Err(err) =
+        sqlx::query("UPDATE reporter_identities SET status = 'revoked' WHERE reporter_id = ?")
+            .bind(&reporter_id)
+            .execute(&state.db)
+            .await
     {
-        let mut challenges = state.challenges.write().await;
-        challenges.insert(challenge_id.clone(), challenge.clone());
+        warn!(reporter_id = %reporter_id, error = %err, "failed to persist revoked identity status");
+    }
+    if let Some(identity) = state.identities.write().await.get_mut(&reporter_id) {
+        identity.status = "revoked".to_string();
     }
-    persist_attestation_challenge(&state, &challenge).await;
 
-    Ok(Json(AttestChallengeResponse {
-        ok: true,
-        challenge_id,
-        nonce,
-        server_id,
-        expires_at: expires_at.to_rfc3339(),
-    }))
+    warn!(
+        reporter_id = %reporter_id,
+        dropped_pending_claims = response.dropped_pending_claims,
+        "admin revoked reporter"
+    );
+    Ok(Json(response))
 }
 
-async fn enroll(
+async fn admin_expire_reporter_token(
     State(state): State<Arc<AppState>>,
-    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     headers: HeaderMap,
-    body: Bytes,
-) -> Result<Json<EnrollResponse>, StatusCode> {
-    let ip = resolve_client_ip(&headers, addr, &state.cfg.trusted_proxy_cidrs).to_string();
-    if !check_rate_limit_ip(&state, &ip).await {
-        return Err(StatusCode::TOO_MANY_REQUESTS);
-    }
-    let req: EnrollRequest = parse_json_body(&body)?;
+    Path(reporter_id): Path<String>,
+) -> Result<Json<AdminReporterActionResponse>, StatusCode> {
+    ensure_admin(&state.cfg, &headers)?;
+    let response = admin_invalidate_reporter(&state, &reporter_id, false).await?;
+    info!(reporter_id = %reporter_id, "admin force-expired reporter token");
+    Ok(Json(response))
+}
 
+/// Expires the reporter's token now (and optionally revokes it), forcing a re-enrollment.
+async fn admin_invalidate_reporter(
+    state: &Arc<AppState>,
+    reporter_id: &str,
+    revoke: bool,
+) -> Result<AdminReporterActionResponse, StatusCode> {
     let now = Utc::now();
-    let guild_opt_in = req.guild_opt_in.unwrap_or(false);
-    let field_toggles = req.field_toggles.clone().unwrap_or_default();
+    let record = {
+        let mut reporters = state.reporters.write().await;
+        let mut token_index = state.token_index.write().await;
+        let record = reporters
+            .get_mut(reporter_id)
+            .ok_or(StatusCode::NOT_FOUND)?;
+        token_index.remove(&record.token_hash);
+        record.token_expires_at = record.token_expires_at.min(now);
+        record.revoked |= revoke;
+        record.clone()
+    };
 
-    let device_pubkey = req
-        .device_pubkey
-        .clone()
-        .unwrap_or_default()
-        .trim()
-        .to_string();
-    let device_pubkey_hash = token_hash(&device_pubkey);
-    let mojang_uuid = req
-        .mojang_uuid
-        .clone()
-        .unwrap_or_default()
-        .trim()
-        .to_string();
-    let mojang_username = req
+    persist_reporter(
+        state,
+        reporter_id,
+        &record.token_hash,
+        record.token_expires_at,
+        record.guild_opt_in,
+        &record.field_toggles,
+        record.last_seen,
+        record.revoked,
+        &record.device_pubkey_b64,
+        &record.mojang_uuid,
+        &record.mojang_username,
+        record.last_attested_at,
+    )
+    .await;
+
+    Ok(AdminReporterActionResponse {
+        ok: true,
+        reporter_id: reporter_id.to_string(),
+        revoked: record.revoked,
+        token_expires_at: record.token_expires_at.to_rfc3339(),
+        dropped_pending_claims: 0,
+    })
+}
+
+/// Removes a reporter's not-yet-accepted votes so they cannot complete a quorum later.
+async fn drop_pending_claims_for(state: &Arc<AppState>, reporter_id: &str) -> usize {
+    let mut dropped = 0;
+    {
+        let mut pending = state.pending_territory.write().await;
+        for bucket in pending.values_mut() {
+            let before = bucket.len();
+            bucket.retain(|claim| claim.reporter_id != reporter_id);
+            dropped += before - bucket.len();
+        }
+        pending.retain(|_, bucket| !bucket.is_empty());
+    }
+    {
+        let mut pending = state.pending_war.write().await;
+        for bucket in pending.values_mut() {
+            let before = bucket.len();
+            bucket.retain(|claim| claim.reporter_id != reporter_id);
+            dropped += before - bucket.len();
+        }
+        pending.retain(|_, bucket| !bucket.is_empty());
+    }
+    dropped
+}
+
+async fn device_revoked(state: &Arc<AppState>, device_pubkey_hash: &str) -> bool {
+    let identities = state.identities.read().await;
+    identities.values().any(|identity| {
+        identity.status == "revoked" && identity.device_pubkey_hash == device_pubkey_hash
+    })
+}
+
+async fn admin_list_quarantine(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<AdminQuarantineResponse>, StatusCode> {
+    ensure_admin(&state.cfg, &headers)?;
+    Ok(Json(AdminQuarantineResponse {
+        entries: quarantine_snapshot(&state).await,
+    }))
+}
+
+async fn quarantine_snapshot(state: &Arc<AppState>) -> Vec<AdminQuarantineEntry> {
+    let now = Instant::now();
+    let quarantine = state.quarantined_until.read().await;
+    let penalties = state.malformed_penalties.read().await;
+    let mut entries = quarantine
+        .iter()
+        .filter_map(|(key, until)| {
+            Some(AdminQuarantineEntry {
+                key: key.clone(),
+                remaining_secs: remaining_secs(*until, now)?,
+                malformed_penalty: malformed_penalty_for(&penalties, key),
+            })
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.remaining_secs));
+    entries
+}
+
+/// Quarantine keys are bare reporter ids or IPs; penalty keys carry a `reporter:`/`ip:` prefix.
+fn malformed_penalty_for(penalties: &HashMap<String, u32>, key: &str) -> u32 {
+    penalties
+        .get(&format!("reporter:{key}"))
+        .or_else(|| penalties.get(&format!("ip:{key}")))
+        .copied()
+        .unwrap_or_default()
+}
+
+async fn admin_lift_quarantine(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<AdminQuarantineResponse>, StatusCode> {
+    ensure_admin(&state.cfg, &headers)?;
+    let req: AdminQuarantineRequest = parse_json_body(&body)?;
+    let key = req.key.trim();
+    if key.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let lifted = lift_quarantine(&state, key).await;
+    if !lifted {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    info!(key = %key, "admin lifted quarantine");
+    Ok(Json(AdminQuarantineResponse {
+        entries: quarantine_snapshot(&state).await,
+    }))
+}
+
+/// Clears a quarantine and its malformed-payload strike counts so it does not re-trigger at once.
+async fn lift_quarantine(state: &Arc<AppState>, key: &str) -> bool {
+    let lifted = state.quarantined_until.write().await.remove(key).is_some();
+    let mut penalties = state.malformed_penalties.write().await;
+    penalties.remove(&format!("reporter:{key}"));
+    penalties.remove(&format!("ip:{key}"));
+    lifted
+}
+
+async fn admin_extend_quarantine(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<AdminQuarantineResponse>, StatusCode> {
+    ensure_admin(&state.cfg, &headers)?;
+    let req: AdminQuarantineRequest = parse_json_body(&body)?;
+    let key = req.key.trim();
+    if key.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let secs = req
+        .secs
+        .unwrap_or(state.cfg.quarantine_secs)
+        .clamp(1, ADMIN_MAX_QUARANTINE_SECS);
+    let entry = extend_quarantine(&state, key, Duration::from_secs(secs)).await;
+    warn!(key = %key, remaining_secs = entry.remaining_secs, "admin extended quarantine");
+    Ok(Json(AdminQuarantineResponse {
+        entries: vec![entry],
+    }))
+}
+
+/// Pushes a quarantine out by `extra` from whichever is later, now or its current expiry.
+async fn extend_quarantine(
+    state: &Arc<AppState>,
+    key: &str,
+    extra: Duration,
+) -> AdminQuarantineEntry {
+    let now = Instant::now();
+    let until = {
+        let mut quarantine = state.quarantined_until.write().await;
+        let until = quarantine.entry(key.to_string()).or_insert(now);
+        *until = (*until).max(now) + extra;
+        *until
+    };
+    let penalties = state.malformed_penalties.read().await;
+    AdminQuarantineEntry {
+        key: key.to_string(),
+        remaining_secs: remaining_secs(until, now).unwrap_or_default(),
+        malformed_penalty: malformed_penalty_for(&penalties, key),
+    }
+}
+
+async fn admin_list_pending(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<AdminPendingResponse>, StatusCode> {
+    ensure_admin(&state.cfg, &headers)?;
+    Ok(Json(AdminPendingResponse {
+        quorum_min_reporters: state.cfg.quorum_min_reporters.max(1),
+        quorum_min_origins: state.cfg.quorum_min_origins.max(1),
+        territories: pending_territory_snapshot(&state).await,
+    }))
+}
+
+async fn admin_forward_status(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<AdminForwardResponse>, StatusCode> {
+    ensure_admin(&state.cfg, &headers)?;
+    let internal_error = |err: sqlx::Error| {
+        warn!(error = %err, "failed to read forward queue state");
+        StatusCode::INTERNAL_SERVER_ERROR
+    };
+    let rows = sqlx::query_as::<_, (i64, String, i64, i64, i64, Option<String>, i64)>(
+        "SELECT id, route, attempts, enqueued_at, failed_at, last_error, LENGTH(payload) \
+         FROM forward_dead_letters ORDER BY failed_at DESC LIMIT 100",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(internal_error)?;
+    let millis_to_rfc3339 = |ms: i64| {
+        DateTime::from_timestamp_millis(ms)
+            .map(|value| value.to_rfc3339())
+            .unwrap_or_default()
+    };
+
+    Ok(Json(AdminForwardResponse {
+        queue_depth: forward_queue_depth(&state).await.map_err(internal_error)?,
+        dead_letter_count: forward_dead_letter_count(&state)
+            .await
+            .map_err(internal_error)?,
+        dead_letters: rows
+            .into_iter()
+            .map(
+                |(id, route, attempts, enqueued_at, failed_at, last_error, payload_bytes)| {
+                    AdminDeadLetter {
+                        id,
+                        route,
+                        attempts: u32::try_from(attempts).unwrap_or_default(),
+                        enqueued_at: millis_to_rfc3339(enqueued_at),
+                        failed_at: millis_to_rfc3339(failed_at),
+                        last_error,
+                        payload_bytes: usize::try_from(payload_bytes).unwrap_or_default(),
+                    }
+                },
+            )
+            .collect(),
+    }))
+}
+
+async fn admin_requeue_dead_letter(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, StatusCode> {
+    ensure_admin(&state.cfg, &headers)?;
+    match requeue_dead_letter(&state, id).await {
+        Ok(true) => {
+            info!(dead_letter_id = id, "admin requeued forward dead letter");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Ok(false) => Err(StatusCode::NOT_FOUND),
+        Err(err) => {
+            warn!(dead_letter_id = id, error = %err, "failed to requeue forward dead letter");
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Pending territory buckets grouped by claim hash, i.e. by which reporters agree with each other.
+async fn pending_territory_snapshot(state: &Arc<AppState>) -> Vec<AdminPendingTerritory> {
+    let now = Instant::now();
+    let buckets = {
+        let pending = state.pending_territory.read().await;
+        pending
+            .iter()
+            .map(|(territory, bucket)| (territory.clone(), bucket.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    let mut territories = Vec::with_capacity(buckets.len());
+    for (territory, bucket) in buckets {
+        let mut by_hash: HashMap<&str, Vec<&PendingTerritoryClaim>> = HashMap::new();
+        for claim in bucket
+            .iter()
+            .filter(|claim| now.duration_since(claim.received_at) <= state.cfg.quorum_window())
+        {
+            by_hash
+                .entry(claim.claim_hash.as_str())
+                .or_default()
+                .push(claim);
+        }
+
+        let mut groups = Vec::with_capacity(by_hash.len());
+        for (claim_hash, claims) in by_hash {
+            let mut reporters = claims
+                .iter()
+                .map(|claim| claim.reporter_id.clone())
+                .collect::<Vec<_>>();
+            reporters.sort();
+            reporters.dedup();
+            groups.push(AdminPendingGroup {
+                claim_hash: claim_hash.to_string(),
+                reporters,
+                distinct_devices: claims
+                    .iter()
+                    .map(|claim| claim.device_identity.as_str())
+                    .collect::<HashSet<_>>()
+                    .len(),
+                distinct_origins: claims
+                    .iter()
+                    .map(|claim| claim.origin_ip)
+                    .collect::<HashSet<_>>()
+                    .len(),
+                vote_weight: device_vote_weight(
+                    state,
+                    claims.iter().map(|claim| claim.device_identity.as_str()),
+                    Utc::now(),
+                )
+                .await,
+                oldest_age_secs: claims
+                    .iter()
+                    .map(|claim| now.duration_since(claim.received_at).as_secs())
+                    .max()
+                    .unwrap_or_default(),
+            });
+        }
+        if groups.is_empty() {
+            continue;
+        }
+        groups.sort_by(|a, b| b.vote_weight.total_cmp(&a.vote_weight));
+        territories.push(AdminPendingTerritory {
+            territory,
+            claims: bucket.len(),
+            groups,
+        });
+    }
+    territories.sort_by(|a, b| a.territory.cmp(&b.territory));
+    territories
+}
+
+async fn attest_challenge(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<AttestChallengeResponse>, StatusCode> {
+    let ip = resolve_client_ip(&headers, addr, &state.cfg.trusted_proxy_cidrs).to_string();
+    if !check_rate_limit_ip(&state, &ip).await {
+        return Err(StatusCode::TOO_MANY_REQUESTS);
+    }
+    let req: AttestChallengeRequest = parse_json_body(&body)?;
+    if decode_ed25519_public_key(&req.device_pubkey).is_none() {
+        state
+            .metrics
+            .attest_fail_total
+            .fetch_add(1, Ordering::Relaxed);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let now = Utc::now();
+    let challenge_id = Uuid::new_v4().to_string();
+    let nonce = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    let server_id = format!("iris:{}", Uuid::new_v4().simple());
+    let expires_at =
+        now + chrono::Duration::seconds(i64::try_from(CHALLENGE_TTL_SECS).unwrap_or(120));
+    let device_pubkey_hash = token_hash(req.device_pubkey.trim());
+
+    let challenge = AttestationChallengeRecord {
+        challenge_id: challenge_id.clone(),
+        nonce: nonce.clone(),
+        server_id: server_id.clone(),
+        device_pubkey_hash: device_pubkey_hash.clone(),
+        issued_at: now,
+        expires_at,
+        used_at: None,
+    };
+
+    {
+        let mut challenges = state.challenges.write().await;
+        challenges.insert(challenge_id.clone(), challenge.clone());
+    }
+    persist_attestation_challenge(&state, &challenge).await;
+
+    Ok(Json(AttestChallengeResponse {
+        ok: true,
+        challenge_id,
+        nonce,
+        server_id,
+        expires_at: expires_at.to_rfc3339(),
+    }))
+}
+
+async fn enroll(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<EnrollResponse>, StatusCode> {
+    let ip = resolve_client_ip(&headers, addr, &state.cfg.trusted_proxy_cidrs).to_string();
+    if !check_rate_limit_ip(&state, &ip).await {
+        return Err(StatusCode::TOO_MANY_REQUESTS);
+    }
+    let req: EnrollRequest = parse_json_body(&body)?;
+
+    let now = Utc::now();
+    let guild_opt_in = req.guild_opt_in.unwrap_or(false);
+    let field_toggles = req.field_toggles.clone().unwrap_or_default();
+
+    let device_pubkey = req
+        .device_pubkey
+        .clone()
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    let device_pubkey_hash = token_hash(&device_pubkey);
+    if device_revoked(&state, &device_pubkey_hash).await {
+        warn!(device_key_id = %hash_prefix(&device_pubkey_hash, 16), "rejecting enrollment from revoked device");
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let mojang_uuid = req
+        .mojang_uuid
+        .clone()
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    let mojang_username = req
         .mojang_username
         .clone()
         .unwrap_or_default()
@@ -1155,15 +1935,7 @@ async fn report_territory(
         }
         update.idempotency_key = Some(idempotency_key.clone());
 
-        persist_raw_report(
-            &state,
-            "territory",
-            &authed.reporter_id,
-            &ip,
-            &serde_json::to_value(&update).unwrap_or_default(),
-        )
-        .await;
-
+        let raw_payload = serde_json::to_value(&update).unwrap_or_default();
         let ownership_claim = update.guild.clone().map(|guild| (guild.uuid, guild.name));
         let acquired_claim = update.acquired.clone();
 
@@ -1175,7 +1947,29 @@ async fn report_territory(
             update.clone(),
         )
         .await;
-        if let Some((mut accepted_update, was_degraded, was_quorum)) = decision {
+        persist_raw_report(
+            &state,
+            "territory",
+            &authed.reporter_id,
+            &ip,
+            &raw_payload,
+            raw_report_outcome(
+                decision.is_some(),
+                decision.as_ref().is_some_and(|decision| decision.quorum),
+            ),
+        )
+        .await;
+        if let Some(TerritoryClaimDecision {
+            update: mut accepted_update,
+            degraded: was_degraded,
+            quorum: was_quorum,
+            reporter_ids,
+            device_identities,
+        }) = decision
+        {
+            // A claim backed by fewer distinct reporters than the quorum floor (degraded mode, or
+            // a trusted reporter carrying quorum alone) stays provisional until corroborated.
+            let provisional = reporter_ids.len() < state.cfg.quorum_min_reporters.max(1);
             if let Some(runtime) = accepted_update.runtime.as_mut() {
                 let mut provenance = runtime
                     .provenance
@@ -1195,20 +1989,31 @@ async fn report_territory(
                 runtime.provenance = Some(provenance);
             }
 
-            if state.cfg.owner_soft_corroboration
-                && ownership_claim.is_some()
-                && !was_quorum
-                && state.cfg.owner_revert_on_mismatch
-            {
-                if let Some(runtime) = accepted_update.runtime.as_mut() {
-                    let mut provenance = runtime
-                        .provenance
-                        .clone()
-                        .unwrap_or_else(default_provenance);
-                    provenance.confidence = provenance.confidence.min(0.55).max(0.35);
-                    runtime.provenance = Some(provenance);
+            if state.cfg.owner_soft_corroboration && ownership_claim.is_some() {
+                let revert_on_mismatch = provisional && state.cfg.owner_revert_on_mismatch;
+                if revert_on_mismatch {
+                    if let Some(runtime) = accepted_update.runtime.as_mut() {
+                        let mut provenance = runtime
+                            .provenance
+                            .clone()
+                            .unwrap_or_else(default_provenance);
+                        provenance.confidence = provenance.confidence.min(0.55).max(0.35);
+                        runtime.provenance = Some(provenance);
+                    }
+                    state
+                        .metrics
+                        .owner_provisional_total
+                        .fetch_add(1, Ordering::Relaxed);
                 }
                 let (guild_uuid, guild_name) = ownership_claim.clone().unwrap_or_default();
+                // Repeating an owner anyone can read from the public API proves nothing, so only
+                // claimed owner changes feed reputation.
+                let scored_devices =
+                    if note_owner_claim(&state, &accepted_update.territory, &guild_uuid).await {
+                        device_identities
+                    } else {
+                        Vec::new()
+                    };
                 register_provisional_ownership(
                     &state,
                     &accepted_update.territory,
@@ -1223,12 +2028,10 @@ async fn report_territory(
                         Some(guild_name)
                     },
                     acquired_claim.clone(),
+                    scored_devices,
+                    revert_on_mismatch,
                 )
                 .await;
-                state
-                    .metrics
-                    .owner_provisional_total
-                    .fetch_add(1, Ordering::Relaxed);
             }
 
             accepted += 1;
@@ -1285,31 +2088,218 @@ async fn report_territory(
     }))
 }
 
-async fn authenticate(
-    state: &Arc<AppState>,
-    headers: &HeaderMap,
-) -> Result<AuthedReporter, StatusCode> {
-    let token = bearer_token(headers).ok_or(StatusCode::UNAUTHORIZED)?;
-    let token_hash = token_hash(&token);
+async fn report_war(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<ReportAck>, StatusCode> {
+    let mut batch: ReporterWarBatch = parse_json_body(&body)?;
+    let client_ip = resolve_client_ip(&headers, addr, &state.cfg.trusted_proxy_cidrs);
+    let ip = client_ip.to_string();
+    let authed = authenticate(&state, &headers).await?;
 
-    let reporter_id = {
-        let token_index = state.token_index.read().await;
-        token_index.get(&token_hash).cloned()
+    if !check_rate_limit_ip(&state, &ip).await
+        || !check_rate_limit_reporter(&state, &authed.reporter_id).await
+    {
+        return Err(StatusCode::TOO_MANY_REQUESTS);
     }
-    .ok_or(StatusCode::UNAUTHORIZED)?;
 
-    let now = Utc::now();
-    let (
-        revoked,
-        expired,
-        field_toggles,
-        device_key_id,
-        device_pubkey_b64,
-        mojang_uuid,
-        mojang_username,
-        last_attested_at,
-    ) = {
-        let reporters = state.reporters.read().await;
+    if is_quarantined(&state, &ip).await || is_quarantined(&state, &authed.reporter_id).await {
+        return Err(StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    if state.cfg.auth_required {
+        verify_signed_request(
+            &state,
+            &headers,
+            "POST",
+            "/v1/report/war",
+            &body,
+            &authed.reporter_id,
+            &authed.device_key_id,
+            &authed.device_pubkey_b64,
+        )
+        .await?;
+        validate_world_attestation(&state, batch.world_attestation.as_ref(), Utc::now())?;
+        maybe_refresh_session_attestation(
+            &state,
+            &authed,
+            batch.session_refresh_token.as_deref(),
+            Utc::now(),
+        )
+        .await?;
+    }
+
+    if state.cfg.max_reports_per_batch > 0 && batch.events.len() > state.cfg.max_reports_per_batch {
+        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+    {
+        let mut reporters = state.reporters.write().await;
+        if let Some(record) = reporters.get_mut(&authed.reporter_id) {
+            record.last_seen = Utc::now();
+        }
+    }
+
+    let device_identity = canonical_device_identity_hash(&authed.device_pubkey_b64);
+
+    let mut accepted = 0_u64;
+    let mut rejected = 0_u64;
+    let mut degraded = 0_u64;
+    let mut quorum = 0_u64;
+    let mut canonical_events = Vec::new();
+
+    for report in batch.events.drain(..) {
+        let mut event = report.event;
+        let Some(normalized_territory) =
+            normalize_territory_name(&event.territory, state.cfg.max_territory_name_len)
+        else {
+            rejected += 1;
+            register_malformed(&state, &authed.reporter_id, &ip).await;
+            continue;
+        };
+        event.territory = normalized_territory.to_string();
+        if event.observed_at.trim().is_empty() {
+            event.observed_at = Utc::now().to_rfc3339();
+        }
+
+        let idempotency_key = match normalize_idempotency_key(
+            report.idempotency_key.as_deref(),
+            state.cfg.max_idempotency_key_len,
+        ) {
+            Some(Some(normalized)) => normalized,
+            Some(None) => war_idempotency_hash(&authed.reporter_id, &event),
+            None => {
+                rejected += 1;
+                register_malformed(&state, &authed.reporter_id, &ip).await;
+                continue;
+            }
+        };
+
+        if !claim_idempotency_key(&state, &idempotency_key).await {
+            rejected += 1;
+            continue;
+        }
+
+        let raw_payload = serde_json::to_value(&event).unwrap_or_default();
+        let decision = evaluate_war_claim(
+            &state,
+            &authed.reporter_id,
+            &device_identity,
+            client_ip,
+            event,
+        )
+        .await;
+        persist_raw_report(
+            &state,
+            "war",
+            &authed.reporter_id,
+            &ip,
+            &raw_payload,
+            raw_report_outcome(
+                decision.is_some(),
+                decision
+                    .as_ref()
+                    .is_some_and(|(_, _, was_quorum)| *was_quorum),
+            ),
+        )
+        .await;
+        if let Some((mut accepted_event, was_degraded, was_quorum)) = decision {
+            let provenance = &mut accepted_event.provenance;
+            if provenance.source.trim().is_empty() {
+                provenance.source = "fabric_reporter".to_string();
+            }
+            if provenance.observed_at.trim().is_empty() {
+                provenance.observed_at = accepted_event.observed_at.clone();
+            }
+            if was_degraded {
+                provenance.confidence = provenance.confidence.clamp(0.35, 0.55);
+            } else if was_quorum {
+                provenance.confidence = provenance.confidence.max(0.75);
+            }
+
+            accepted += 1;
+            if was_degraded {
+                degraded += 1;
+            }
+            if was_quorum {
+                quorum += 1;
+            }
+            canonical_events.push(CanonicalWarReport {
+                event: accepted_event,
+                idempotency_key: Some(idempotency_key),
+            });
+        }
+    }
+
+    if !canonical_events.is_empty() {
+        enqueue_forward(
+            &state,
+            "/api/internal/ingest/war",
+            serde_json::to_value(CanonicalWarBatch {
+                generated_at: batch.generated_at,
+                events: canonical_events,
+            })
+            .unwrap_or_else(
+                |_| serde_json::json!({"generated_at": Utc::now().to_rfc3339(), "events": []}),
+            ),
+        )
+        .await;
+    }
+
+    state
+        .metrics
+        .reports_accepted_total
+        .fetch_add(accepted, Ordering::Relaxed);
+    state
+        .metrics
+        .reports_rejected_total
+        .fetch_add(rejected, Ordering::Relaxed);
+    state
+        .metrics
+        .reports_degraded_total
+        .fetch_add(degraded, Ordering::Relaxed);
+    state
+        .metrics
+        .reports_quorum_total
+        .fetch_add(quorum, Ordering::Relaxed);
+
+    touch_identity_last_seen(&state, &authed.reporter_id, Utc::now()).await;
+
+    Ok(Json(ReportAck {
+        ok: true,
+        accepted,
+        rejected,
+        degraded,
+        quorum,
+    }))
+}
+
+async fn authenticate(
+    state: &Arc<AppState>,
+    headers: &HeaderMap,
+) -> Result<AuthedReporter, StatusCode> {
+    let token = bearer_token(headers).ok_or(StatusCode::UNAUTHORIZED)?;
+    let token_hash = token_hash(&token);
+
+    let reporter_id = {
+        let token_index = state.token_index.read().await;
+        token_index.get(&token_hash).cloned()
+    }
+    .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let now = Utc::now();
+    let (
+        revoked,
+        expired,
+        field_toggles,
+        device_key_id,
+        device_pubkey_b64,
+        mojang_uuid,
+        mojang_username,
+        last_attested_at,
+    ) = {
+        let reporters = state.reporters.read().await;
         let Some(record) = reporters.get(&reporter_id) else {
             return Err(StatusCode::UNAUTHORIZED);
         };
@@ -2061,8 +3051,29 @@ async fn evaluate_territory_claim(
     device_identity: &str,
     origin_ip: IpAddr,
     update: CanonicalTerritoryUpdate,
-) -> Option<(CanonicalTerritoryUpdate, bool, bool)> {
-    let now = Instant::now();
+) -> Option<TerritoryClaimDecision> {
+    evaluate_territory_claim_at(
+        state,
+        reporter_id,
+        device_identity,
+        origin_ip,
+        update,
+        Instant::now(),
+        Utc::now(),
+    )
+    .await
+}
+
+/// [`evaluate_territory_claim`] against an explicit clock, so stored reports can be replayed.
+async fn evaluate_territory_claim_at(
+    state: &Arc<AppState>,
+    reporter_id: &str,
+    device_identity: &str,
+    origin_ip: IpAddr,
+    update: CanonicalTerritoryUpdate,
+    now: Instant,
+    now_utc: DateTime<Utc>,
+) -> Option<TerritoryClaimDecision> {
     let claim_hash = territory_claim_hash(&update);
     let territory_name = update.territory.clone();
 
@@ -2091,7 +3102,8 @@ async fn evaluate_territory_claim(
     }
 
     let bucket = pending.entry(territory_name.clone()).or_default();
-    bucket.retain(|claim| now.duration_since(claim.received_at) <= QUORUM_WINDOW);
+    let quorum_window = state.cfg.quorum_window();
+    bucket.retain(|claim| now.duration_since(claim.received_at) <= quorum_window);
     if state.cfg.max_claims_per_territory > 0 && bucket.len() >= state.cfg.max_claims_per_territory
     {
         let drop_count = bucket.len() - state.cfg.max_claims_per_territory + 1;
@@ -2104,10 +3116,17 @@ async fn evaluate_territory_claim(
         );
     }
 
-    if bucket
+    if let Some(idx) = bucket
         .iter()
-        .any(|claim| claim.reporter_id == reporter_id && claim.claim_hash == claim_hash)
+        .position(|claim| claim.reporter_id == reporter_id && claim.claim_hash == claim_hash)
     {
+        // A repeat sighting of the same owner claim refreshes this reporter's runtime sample and
+        // keeps its vote inside the quorum window without counting as another vote. Moving it to
+        // the back keeps the bucket oldest-first for the capacity trim above.
+        let mut existing = bucket.remove(idx);
+        existing.update = update;
+        existing.received_at = now;
+        bucket.push(existing);
         return None;
     }
 
@@ -2129,93 +3148,263 @@ async fn evaluate_territory_claim(
         origins.insert(claim.origin_ip);
     }
 
-    let distinct_reporters = reporters.len();
     let distinct_devices = devices.len();
-    let distinct_origins = origins.len();
-    let quorum_reporter_threshold = state.cfg.quorum_min_reporters.max(1);
-    let quorum_origin_threshold = state
-        .cfg
-        .quorum_min_origins
-        .max(1)
-        .min(quorum_reporter_threshold);
-    let quorum_ok = quorum_satisfied(
-        distinct_reporters,
+    let vote_weight = device_vote_weight(state, devices.iter().map(String::as_str), now_utc).await;
+    let (quorum_ok, degraded_ok) = quorum_decision(
+        state,
+        vote_weight,
+        reporters.len(),
         distinct_devices,
-        distinct_origins,
-        quorum_reporter_threshold,
-        quorum_origin_threshold,
-    );
-    let active_reporters = active_reporter_count(state).await;
-    let degraded_ok = !quorum_ok
-        && state.cfg.degraded_single_reporter_enabled
-        && active_reporters <= 1
-        && distinct_reporters == 1
-        && distinct_devices == 1;
+        origins.len(),
+        now_utc,
+    )
+    .await;
 
     if quorum_ok || degraded_ok {
-        let mut accepted = update.clone();
-        if let Some(runtime) = bucket
+        let agreeing: Vec<&PendingTerritoryClaim> = bucket
             .iter()
-            .rev()
-            .find(|claim| claim.claim_hash == claim_hash)
-            .map(|claim| claim.update.runtime.clone())
-            .flatten()
-        {
-            accepted.runtime = Some(runtime);
+            .filter(|claim| claim.claim_hash == claim_hash)
+            .collect();
+        // Runtime fields need as much backing as the owner vote did, up to the devices present,
+        // so degraded and trusted single-device acceptances keep their own runtime data.
+        let mut consensus =
+            RuntimeConsensus::new(state.cfg.quorum_min_reporters.max(1).min(distinct_devices));
+        let mut runtime = merge_runtime_consensus(&agreeing, &mut consensus);
+        if consensus.uncorroborated > 0 {
+            state
+                .metrics
+                .runtime_fields_uncorroborated_total
+                .fetch_add(consensus.uncorroborated, Ordering::Relaxed);
         }
 
-        let mut runtime = accepted.runtime.take().unwrap_or_default();
+        let mut accepted = update;
         let mut provenance = runtime.provenance.take().unwrap_or_else(default_provenance);
         provenance.reporter_count = u16::try_from(distinct_devices).unwrap_or(u16::MAX);
+        if accepted.guild.is_some() {
+            consensus.corroborated.insert(
+                "owner".to_string(),
+                u16::try_from(distinct_devices).unwrap_or(u16::MAX),
+            );
+        }
+        provenance.corroborated_fields = Some(consensus.corroborated);
         runtime.provenance = Some(provenance);
         accepted.runtime = Some(runtime);
 
         bucket.retain(|claim| claim.claim_hash != claim_hash);
-        return Some((accepted, degraded_ok, quorum_ok));
+        let mut reporter_ids: Vec<String> = reporters.into_iter().collect();
+        reporter_ids.sort();
+        let mut device_identities: Vec<String> = devices.into_iter().collect();
+        device_identities.sort();
+        return Some(TerritoryClaimDecision {
+            update: accepted,
+            degraded: degraded_ok,
+            quorum: quorum_ok,
+            reporter_ids,
+            device_identities,
+        });
     }
 
     None
 }
 
-async fn active_reporter_count(state: &Arc<AppState>) -> usize {
-    let now = Utc::now();
-    let active_since = now
-        - chrono::TimeDelta::seconds(
-            i64::try_from(ACTIVE_REPORTER_WINDOW.as_secs()).unwrap_or(180),
+async fn evaluate_war_claim(
+    state: &Arc<AppState>,
+    reporter_id: &str,
+    device_identity: &str,
+    origin_ip: IpAddr,
+    event: WarEvent,
+) -> Option<(WarEvent, bool, bool)> {
+    let now = Instant::now();
+    let quorum_key = war_quorum_key(&event);
+
+    let mut pending = state.pending_war.write().await;
+    if !pending.contains_key(&quorum_key)
+        && state.cfg.max_pending_territories > 0
+        && pending.len() >= state.cfg.max_pending_territories
+        && let Some(oldest_key) = pending
+            .iter()
+            .filter_map(|(key, claims)| {
+                claims
+                    .iter()
+                    .map(|claim| claim.received_at)
+                    .min()
+                    .map(|received_at| (key.clone(), received_at))
+            })
+            .min_by_key(|(_, received_at)| *received_at)
+            .map(|(key, _)| key)
+    {
+        pending.remove(&oldest_key);
+        warn!(
+            dropped_war_key = %oldest_key,
+            max_pending_territories = state.cfg.max_pending_territories,
+            "pending war claim map reached capacity; dropped oldest war bucket"
         );
-    let reporters = state.reporters.read().await;
-    reporters
-        .values()
-        .filter(|record| !record.revoked)
-        .filter(|record| record.last_seen >= active_since)
-        .count()
-}
+    }
 
-fn apply_toggle_policy(
-    mut update: CanonicalTerritoryUpdate,
-    toggles: &ReporterFieldToggles,
-) -> Option<CanonicalTerritoryUpdate> {
-    if !toggles.share_owner {
-        update.guild = None;
-        update.acquired = None;
+    let bucket = pending.entry(quorum_key.clone()).or_default();
+    let quorum_window = state.cfg.quorum_window();
+    bucket.retain(|claim| now.duration_since(claim.received_at) <= quorum_window);
+    if state.cfg.max_claims_per_territory > 0 && bucket.len() >= state.cfg.max_claims_per_territory
+    {
+        let drop_count = bucket.len() - state.cfg.max_claims_per_territory + 1;
+        bucket.drain(0..drop_count);
     }
 
-    if !toggles.share_trading_routes {
-        update.connections = None;
-        if let Some(runtime) = update.runtime.as_mut()
-            && let Some(extra_scrapes) = runtime.extra_scrapes.as_mut()
-        {
-            extra_scrapes.remove("trading_routes");
-            if extra_scrapes.is_empty() {
-                runtime.extra_scrapes = None;
-            }
-        }
+    if bucket.iter().any(|claim| claim.reporter_id == reporter_id) {
+        return None;
     }
 
-    if let Some(runtime) = update.runtime.as_mut() {
-        if !toggles.share_headquarters {
-            runtime.headquarters = None;
-        }
+    bucket.push(PendingWarClaim {
+        reporter_id: reporter_id.to_string(),
+        device_identity: device_identity.to_string(),
+        origin_ip,
+        event: event.clone(),
+        received_at: now,
+    });
+
+    let mut reporters = HashSet::new();
+    let mut devices = HashSet::new();
+    let mut origins = HashSet::new();
+    for claim in bucket.iter() {
+        reporters.insert(claim.reporter_id.clone());
+        devices.insert(claim.device_identity.clone());
+        origins.insert(claim.origin_ip);
+    }
+
+    let distinct_devices = devices.len();
+    let now_utc = Utc::now();
+    let vote_weight = device_vote_weight(state, devices.iter().map(String::as_str), now_utc).await;
+    let (quorum_ok, degraded_ok) = quorum_decision(
+        state,
+        vote_weight,
+        reporters.len(),
+        distinct_devices,
+        origins.len(),
+        now_utc,
+    )
+    .await;
+
+    if quorum_ok || degraded_ok {
+        // The bucket already agrees on territory/kind/guild; keep the earliest sighting so
+        // `observed_at` reflects when the transition happened, with the freshest tower state.
+        let mut accepted = bucket
+            .iter()
+            .min_by_key(|claim| claim.received_at)
+            .map(|claim| claim.event.clone())
+            .unwrap_or(event);
+        if let Some(tower_state) = bucket
+            .iter()
+            .rev()
+            .find_map(|claim| claim.event.tower_state.clone())
+        {
+            accepted.tower_state = Some(tower_state);
+        }
+        accepted.provenance.reporter_count = u16::try_from(distinct_devices).unwrap_or(u16::MAX);
+
+        pending.remove(&quorum_key);
+        return Some((accepted, degraded_ok, quorum_ok));
+    }
+
+    None
+}
+
+/// Returns `(quorum_ok, degraded_ok)` for a bucket of agreeing claims.
+async fn quorum_decision(
+    state: &Arc<AppState>,
+    vote_weight: f64,
+    distinct_reporters: usize,
+    distinct_devices: usize,
+    distinct_origins: usize,
+    now: DateTime<Utc>,
+) -> (bool, bool) {
+    let quorum_reporter_threshold = state.cfg.quorum_min_reporters.max(1);
+    let quorum_origin_threshold = state
+        .cfg
+        .quorum_min_origins
+        .max(1)
+        .min(quorum_reporter_threshold);
+    let quorum_ok = quorum_satisfied(
+        vote_weight,
+        distinct_origins,
+        quorum_reporter_threshold,
+        quorum_origin_threshold,
+    );
+    let active_reporters = active_reporter_count(state, now).await;
+    let degraded_ok = !quorum_ok
+        && state.cfg.degraded_single_reporter_enabled
+        && active_reporters <= 1
+        && distinct_reporters == 1
+        && distinct_devices == 1;
+    (quorum_ok, degraded_ok)
+}
+
+/// Sum of reputation-weighted votes, counting each device once.
+async fn device_vote_weight<'a>(
+    state: &Arc<AppState>,
+    devices: impl IntoIterator<Item = &'a str>,
+    now: DateTime<Utc>,
+) -> f64 {
+    let reputation = state.reputation.read().await;
+    devices
+        .into_iter()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .map(|device_identity| {
+            reporter_vote_weight(&state.cfg, reputation.get(device_identity), now)
+        })
+        .sum()
+}
+
+fn reporter_vote_weight(
+    cfg: &Config,
+    reputation: Option<&ReporterReputation>,
+    now: DateTime<Utc>,
+) -> f64 {
+    reputation
+        .map_or(REPUTATION_NEUTRAL, |reputation| {
+            reputation.decayed_score(now, cfg.reputation_half_life_hours)
+        })
+        .clamp(REPUTATION_MIN_WEIGHT, cfg.reputation_max_weight)
+}
+
+async fn active_reporter_count(state: &Arc<AppState>, now: DateTime<Utc>) -> usize {
+    let active_since = now
+        - chrono::TimeDelta::seconds(
+            i64::try_from(ACTIVE_REPORTER_WINDOW.as_secs()).unwrap_or(180),
+        );
+    let reporters = state.reporters.read().await;
+    reporters
+        .values()
+        .filter(|record| !record.revoked)
+        .filter(|record| record.last_seen >= active_since)
+        .count()
+}
+
+fn apply_toggle_policy(
+    mut update: CanonicalTerritoryUpdate,
+    toggles: &ReporterFieldToggles,
+) -> Option<CanonicalTerritoryUpdate> {
+    if !toggles.share_owner {
+        update.guild = None;
+        update.acquired = None;
+    }
+
+    if !toggles.share_trading_routes {
+        update.connections = None;
+        if let Some(runtime) = update.runtime.as_mut()
+            && let Some(extra_scrapes) = runtime.extra_scrapes.as_mut()
+        {
+            extra_scrapes.remove("trading_routes");
+            if extra_scrapes.is_empty() {
+                runtime.extra_scrapes = None;
+            }
+        }
+    }
+
+    if let Some(runtime) = update.runtime.as_mut() {
+        if !toggles.share_headquarters {
+            runtime.headquarters = None;
+        }
         if !toggles.share_held_resources {
             runtime.held_resources = None;
         }
@@ -2275,6 +3464,7 @@ fn default_provenance() -> DataProvenance {
         menu_captured_territories: None,
         menu_sr_per_hour: None,
         menu_observed_at: None,
+        corroborated_fields: None,
     }
 }
 
@@ -2284,21 +3474,167 @@ fn has_scalar_menu_provenance(provenance: &DataProvenance) -> bool {
         && provenance.menu_sr_per_hour.is_some()
 }
 
+/// Per-field tally for [`merge_runtime_consensus`].
+struct RuntimeConsensus {
+    min_support: usize,
+    corroborated: HashMap<String, u16>,
+    uncorroborated: u64,
+}
+
+impl RuntimeConsensus {
+    fn new(min_support: usize) -> Self {
+        Self {
+            min_support: min_support.max(1),
+            corroborated: HashMap::new(),
+            uncorroborated: 0,
+        }
+    }
+
+    /// Uses the merged value when at least `min_support` reporters sent the field and a strict
+    /// majority of them back it. Otherwise the most recent sample is kept, recorded with `0`
+    /// backing reporters, so a split or a share toggle never erases a value the server holds.
+    fn settle<T>(
+        &mut self,
+        field: &str,
+        merged: T,
+        latest: T,
+        reported: usize,
+        support: usize,
+    ) -> T {
+        let corroborated = reported >= self.min_support && support * 2 > reported;
+        let (value, support) = if corroborated {
+            (merged, support)
+        } else {
+            self.uncorroborated += 1;
+            (latest, 0)
+        };
+        self.corroborated.insert(
+            field.to_string(),
+            u16::try_from(support).unwrap_or(u16::MAX),
+        );
+        value
+    }
+
+    /// Most common value, backed by the reporters that saw exactly it. `values` are ordered
+    /// latest first, so ties go to the most recent sighting.
+    fn vote<T: Clone + PartialEq>(&mut self, field: &str, values: &[&T]) -> Option<T> {
+        let mut best: Option<(&T, usize)> = None;
+        for value in values {
+            let count = values.iter().filter(|other| *other == value).count();
+            if best.is_none_or(|(_, best_count)| count > best_count) {
+                best = Some((value, count));
+            }
+        }
+        let (value, count) = best?;
+        Some(self.settle(field, value.clone(), values[0].clone(), values.len(), count))
+    }
+
+    /// Lower median per resource, so every amount is one a reporter actually observed; backed
+    /// by every reporter that sent the field since readings drift between sightings.
+    fn median(&mut self, field: &str, values: &[&Resources]) -> Option<Resources> {
+        let latest = (*values.first()?).clone();
+        let median_of = |amount: fn(&Resources) -> i32| {
+            let mut amounts: Vec<i32> = values.iter().map(|resources| amount(resources)).collect();
+            amounts.sort_unstable();
+            amounts[(amounts.len() - 1) / 2]
+        };
+        let merged = Resources {
+            emeralds: median_of(|resources| resources.emeralds),
+            ore: median_of(|resources| resources.ore),
+            crops: median_of(|resources| resources.crops),
+            fish: median_of(|resources| resources.fish),
+            wood: median_of(|resources| resources.wood),
+        };
+        Some(self.settle(field, merged, latest, values.len(), values.len()))
+    }
+}
+
+/// Merges the runtime data of claims that agree on the owner one field at a time: resources by
+/// median, booleans and labels by majority, falling back to the most recent sample. Provenance
+/// comes from the latest sample.
+fn merge_runtime_consensus(
+    claims: &[&PendingTerritoryClaim],
+    consensus: &mut RuntimeConsensus,
+) -> TerritoryRuntimeData {
+    // One sample per device, latest first, mirroring how owner votes are counted.
+    let mut devices = HashSet::new();
+    let runtimes: Vec<&TerritoryRuntimeData> = claims
+        .iter()
+        .rev()
+        .filter(|claim| devices.insert(claim.device_identity.as_str()))
+        .filter_map(|claim| claim.update.runtime.as_ref())
+        .collect();
+
+    TerritoryRuntimeData {
+        headquarters: consensus.vote(
+            "headquarters",
+            &collect_runtime_field(&runtimes, |runtime| runtime.headquarters.as_ref()),
+        ),
+        headquarters_territory: consensus.vote(
+            "headquarters_territory",
+            &collect_runtime_field(&runtimes, |runtime| runtime.headquarters_territory.as_ref()),
+        ),
+        held_resources: consensus.median(
+            "held_resources",
+            &collect_runtime_field(&runtimes, |runtime| runtime.held_resources.as_ref()),
+        ),
+        production_rates: consensus.median(
+            "production_rates",
+            &collect_runtime_field(&runtimes, |runtime| runtime.production_rates.as_ref()),
+        ),
+        storage_capacity: consensus.median(
+            "storage_capacity",
+            &collect_runtime_field(&runtimes, |runtime| runtime.storage_capacity.as_ref()),
+        ),
+        treasury: consensus.vote(
+            "treasury",
+            &collect_runtime_field(&runtimes, |runtime| runtime.treasury.as_ref()),
+        ),
+        defense_tier: consensus.vote(
+            "defense_tier",
+            &collect_runtime_field(&runtimes, |runtime| runtime.defense_tier.as_ref()),
+        ),
+        contested: consensus.vote(
+            "contested",
+            &collect_runtime_field(&runtimes, |runtime| runtime.contested.as_ref()),
+        ),
+        active_war: consensus.vote(
+            "active_war",
+            &collect_runtime_field(&runtimes, |runtime| runtime.active_war.as_ref()),
+        ),
+        extra_scrapes: consensus.vote(
+            "extra_scrapes",
+            &collect_runtime_field(&runtimes, |runtime| runtime.extra_scrapes.as_ref()),
+        ),
+        provenance: runtimes
+            .iter()
+            .find_map(|runtime| runtime.provenance.clone()),
+    }
+}
+
+fn collect_runtime_field<'a, T>(
+    runtimes: &[&'a TerritoryRuntimeData],
+    field: impl Fn(&'a TerritoryRuntimeData) -> Option<&'a T>,
+) -> Vec<&'a T> {
+    runtimes
+        .iter()
+        .filter_map(|runtime| field(runtime))
+        .collect()
+}
+
 fn quorum_satisfied(
-    distinct_reporters: usize,
-    distinct_devices: usize,
+    vote_weight: f64,
     distinct_origins: usize,
     reporter_threshold: usize,
     origin_threshold: usize,
 ) -> bool {
     let required_reporters = reporter_threshold.max(1);
     let required_origins = origin_threshold.max(1).min(required_reporters);
-    // Distinct device identities are the hard corroboration boundary. Shared public IPs are
-    // common for legitimate observers behind the same NAT, so origin diversity remains a
-    // configurable soft floor rather than a mandatory one-to-one quorum gate.
-    distinct_reporters >= required_reporters
-        && distinct_devices >= required_reporters
-        && distinct_origins >= required_origins
+    // Distinct device identities are the hard corroboration boundary: `vote_weight` counts each
+    // device once, so a neutral reporter is one vote and a trusted one can carry quorum alone.
+    // Shared public IPs are common for legitimate observers behind the same NAT, so origin
+    // diversity remains a configurable soft floor rather than a mandatory one-to-one quorum gate.
+    vote_weight + 1e-9 >= required_reporters as f64 && distinct_origins >= required_origins
 }
 
 fn canonicalize_json_value(value: serde_json::Value) -> serde_json::Value {
@@ -2334,10 +3670,8 @@ fn canonical_json_bytes<T: Serialize>(value: &T) -> Vec<u8> {
 fn territory_claim_hash(update: &CanonicalTerritoryUpdate) -> String {
     let mut canonical = update.clone();
     canonical.idempotency_key = None;
-    if let Some(runtime) = canonical.runtime.as_mut() {
-        // Quorum should compare semantic claim data, not reporter-local metadata.
-        runtime.provenance = None;
-    }
+    // Quorum votes on the owner claim; runtime data is merged per field once it is reached.
+    canonical.runtime = None;
     let payload = canonical_json_bytes(&canonical);
     let mut hasher = Sha256::new();
     hasher.update(&payload);
@@ -2352,6 +3686,34 @@ fn territory_idempotency_hash(reporter_id: &str, update: &CanonicalTerritoryUpda
     hex::encode(hasher.finalize())
 }
 
+fn war_quorum_key(event: &WarEvent) -> String {
+    let guild = event
+        .guild
+        .as_ref()
+        .map(|guild| {
+            if guild.uuid.trim().is_empty() {
+                guild.name.trim().to_ascii_lowercase()
+            } else {
+                guild.uuid.trim().to_ascii_lowercase()
+            }
+        })
+        .unwrap_or_default();
+    let kind = serde_json::to_value(event.kind)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default();
+    format!("{}|{kind}|{guild}", event.territory)
+}
+
+fn war_idempotency_hash(reporter_id: &str, event: &WarEvent) -> String {
+    let payload = canonical_json_bytes(event);
+    let mut hasher = Sha256::new();
+    hasher.update(b"war:");
+    hasher.update(reporter_id.as_bytes());
+    hasher.update(&payload);
+    hex::encode(hasher.finalize())
+}
+
 fn token_hash(token: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(token.as_bytes());
@@ -2387,20 +3749,41 @@ fn new_token() -> String {
 }
 
 async fn enqueue_forward(state: &Arc<AppState>, route: &'static str, payload: serde_json::Value) {
-    let mut queue = state.forward_queue.write().await;
-    if state.cfg.max_forward_queue > 0 && queue.len() >= state.cfg.max_forward_queue {
-        queue.pop_front();
-        warn!(
-            max_forward_queue = state.cfg.max_forward_queue,
-            "forward queue reached capacity; dropped oldest queued job"
-        );
+    if state.cfg.max_forward_queue > 0 {
+        match forward_queue_depth(state).await {
+            Ok(depth) if depth >= state.cfg.max_forward_queue => {
+                // Evicted jobs are kept as dead letters rather than dropped outright.
+                if let Err(err) = dead_letter_oldest_forward_job(state, "queue_capacity").await {
+                    warn!(error = %err, "failed to evict oldest forward job at capacity");
+                } else {
+                    warn!(
+                        max_forward_queue = state.cfg.max_forward_queue,
+                        "forward queue reached capacity; moved oldest queued job to dead letters"
+                    );
+                }
+            }
+            Ok(_) => {}
+            Err(err) => warn!(error = %err, "failed to read forward queue depth"),
+        }
+    }
+
+    let now_ms = Utc::now().timestamp_millis();
+    if let Err(err) = sqlx::query(
+        "INSERT INTO forward_queue (route, payload, attempts, next_attempt_at, enqueued_at) VALUES (?, ?, 0, ?, ?)",
+    )
+    .bind(route)
+    .bind(payload.to_string())
+    .bind(now_ms)
+    .bind(now_ms)
+    .execute(&state.db)
+    .await
+    {
+        state
+            .metrics
+            .forward_failures_total
+            .fetch_add(1, Ordering::Relaxed);
+        warn!(route = route, error = %err, "failed to persist forward job");
     }
-    queue.push_back(ForwardJob {
-        route,
-        payload,
-        attempts: 0,
-        next_attempt_at: Instant::now(),
-    });
 }
 
 fn spawn_forwarder_task(state: AppState) {
@@ -2408,80 +3791,224 @@ fn spawn_forwarder_task(state: AppState) {
         loop {
             tokio::time::sleep(Duration::from_secs(1)).await;
 
-            let mut maybe_job = None;
-            {
-                let mut queue = state.forward_queue.write().await;
-                if let Some(index) = queue
-                    .iter()
-                    .position(|job| job.next_attempt_at <= Instant::now())
-                {
-                    maybe_job = queue.remove(index);
-                }
-            }
-
-            let Some(mut job) = maybe_job else {
-                continue;
-            };
+            // Drain everything that is due so a backlog replays quickly once the server is back.
+            loop {
+                let job = match next_due_forward_job(&state, Utc::now()).await {
+                    Ok(Some(job)) => job,
+                    Ok(None) => break,
+                    Err(err) => {
+                        warn!(error = %err, "failed to load next forward job");
+                        break;
+                    }
+                };
 
-            let url = format!("{}{}", state.cfg.sequoia_server_base_url, job.route);
-            let send = state
-                .http
-                .post(url)
-                .header("x-internal-ingest-token", &state.cfg.internal_ingest_token)
-                .json(&job.payload)
-                .send()
-                .await;
+                let url = format!("{}{}", state.cfg.sequoia_server_base_url, job.route);
+                let send = state
+                    .http
+                    .post(url)
+                    .header("x-internal-ingest-token", &state.cfg.internal_ingest_token)
+                    .json(&job.payload)
+                    .send()
+                    .await;
+
+                let failure = match send {
+                    Ok(response) if response.status().is_success() => None,
+                    Ok(response) => {
+                        let status = response.status();
+                        warn!(status = %status, attempts = job.attempts, "forward request rejected");
+                        Some(format!("status {status}"))
+                    }
+                    Err(err) => {
+                        warn!(error = %err, attempts = job.attempts, "forward request failed");
+                        Some(err.to_string())
+                    }
+                };
 
-            match send {
-                Ok(response) if response.status().is_success() => {
-                    // delivered
-                }
-                Ok(response) => {
-                    state
-                        .metrics
-                        .forward_failures_total
-                        .fetch_add(1, Ordering::Relaxed);
-                    let status = response.status();
-                    warn!(status = %status, attempts = job.attempts, "forward request rejected");
-                    schedule_retry(&state, &mut job).await;
-                }
-                Err(err) => {
-                    state
-                        .metrics
-                        .forward_failures_total
-                        .fetch_add(1, Ordering::Relaxed);
-                    warn!(error = %err, attempts = job.attempts, "forward request failed");
-                    schedule_retry(&state, &mut job).await;
+                let result = match failure {
+                    None => sqlx::query("DELETE FROM forward_queue WHERE id = ?")
+                        .bind(job.id)
+                        .execute(&state.db)
+                        .await
+                        .map(|_| ()),
+                    Some(error) => {
+                        state
+                            .metrics
+                            .forward_failures_total
+                            .fetch_add(1, Ordering::Relaxed);
+                        schedule_retry(&state, &job, &error, Utc::now()).await
+                    }
+                };
+                if let Err(err) = result {
+                    warn!(job_id = job.id, error = %err, "failed to update forward queue");
+                    break;
                 }
             }
         }
     });
 }
 
-async fn schedule_retry(state: &AppState, job: &mut ForwardJob) {
-    job.attempts = job.attempts.saturating_add(1);
-    if job.attempts >= state.cfg.forward_max_attempts {
-        warn!(
-            route = job.route,
-            attempts = job.attempts,
-            max_attempts = state.cfg.forward_max_attempts,
-            "dropping forward job after exhausting retry attempts"
-        );
-        return;
+/// Oldest job of each route, if due. Later jobs on a route wait behind a backing-off head so
+/// canonical updates are never replayed out of order; a head whose stored payload no longer
+/// parses is dead-lettered right away instead of being forwarded as `null`.
+async fn next_due_forward_job(
+    state: &AppState,
+    now: DateTime<Utc>,
+) -> Result<Option<ForwardJob>, sqlx::Error> {
+    loop {
+        let Some((id, route, payload, attempts)) = sqlx::query_as::<_, (i64, String, String, i64)>(
+            "SELECT id, route, payload, attempts FROM forward_queue \
+             WHERE id IN (SELECT MIN(id) FROM forward_queue GROUP BY route) \
+               AND next_attempt_at <= ? \
+             ORDER BY id LIMIT 1",
+        )
+        .bind(now.timestamp_millis())
+        .fetch_optional(&state.db)
+        .await?
+        else {
+            return Ok(None);
+        };
+        let attempts = u32::try_from(attempts).unwrap_or_default();
+
+        match serde_json::from_str(&payload) {
+            Ok(payload) => {
+                return Ok(Some(ForwardJob {
+                    id,
+                    route,
+                    payload,
+                    attempts,
+                }));
+            }
+            Err(err) => {
+                warn!(route = %route, id, error = %err, "dead-lettering forward job with unparsable payload");
+                let last_error = format!("unparsable payload: {err}");
+                dead_letter_forward_job(state, id, attempts, &last_error, now).await?;
+            }
+        }
     }
+}
 
-    let backoff_secs = (1_u64 << job.attempts.min(6)).min(60);
-    job.next_attempt_at = Instant::now() + Duration::from_secs(backoff_secs);
+fn forward_backoff(attempts: u32) -> Duration {
+    Duration::from_secs((1_u64 << attempts.min(6)).min(60))
+}
 
-    let mut queue = state.forward_queue.write().await;
-    if state.cfg.max_forward_queue > 0 && queue.len() >= state.cfg.max_forward_queue {
-        queue.pop_front();
+async fn schedule_retry(
+    state: &AppState,
+    job: &ForwardJob,
+    last_error: &str,
+    now: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    let attempts = job.attempts.saturating_add(1);
+    if attempts >= state.cfg.forward_max_attempts {
         warn!(
-            max_forward_queue = state.cfg.max_forward_queue,
-            "forward queue reached capacity during retry; dropped oldest queued job"
+            route = %job.route,
+            attempts,
+            max_attempts = state.cfg.forward_max_attempts,
+            "moving forward job to dead letters after exhausting retry attempts"
         );
+        return dead_letter_forward_job(state, job.id, attempts, last_error, now).await;
     }
-    queue.push_back(job.clone());
+
+    let next_attempt_at = now
+        + chrono::TimeDelta::from_std(forward_backoff(attempts))
+            .unwrap_or_else(|_| chrono::TimeDelta::seconds(60));
+    sqlx::query(
+        "UPDATE forward_queue SET attempts = ?, next_attempt_at = ?, last_error = ? WHERE id = ?",
+    )
+    .bind(i64::from(attempts))
+    .bind(next_attempt_at.timestamp_millis())
+    .bind(last_error)
+    .bind(job.id)
+    .execute(&state.db)
+    .await?;
+    Ok(())
+}
+
+async fn dead_letter_forward_job(
+    state: &AppState,
+    id: i64,
+    attempts: u32,
+    last_error: &str,
+    now: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    let mut tx = state.db.begin().await?;
+    let moved = sqlx::query(
+        "INSERT INTO forward_dead_letters (id, route, payload, attempts, enqueued_at, failed_at, last_error) \
+         SELECT id, route, payload, ?, enqueued_at, ?, ? FROM forward_queue WHERE id = ?",
+    )
+    .bind(i64::from(attempts))
+    .bind(now.timestamp_millis())
+    .bind(last_error)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    sqlx::query("DELETE FROM forward_queue WHERE id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    state
+        .metrics
+        .forward_dead_lettered_total
+        .fetch_add(moved, Ordering::Relaxed);
+    Ok(())
+}
+
+async fn dead_letter_oldest_forward_job(state: &AppState, reason: &str) -> Result<(), sqlx::Error> {
+    let oldest = sqlx::query_as::<_, (i64, i64)>(
+        "SELECT id, attempts FROM forward_queue ORDER BY id LIMIT 1",
+    )
+    .fetch_optional(&state.db)
+    .await?;
+    let Some((id, attempts)) = oldest else {
+        return Ok(());
+    };
+    dead_letter_forward_job(
+        state,
+        id,
+        u32::try_from(attempts).unwrap_or_default(),
+        reason,
+        Utc::now(),
+    )
+    .await
+}
+
+async fn forward_queue_depth(state: &AppState) -> Result<usize, sqlx::Error> {
+    let (depth,) = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM forward_queue")
+        .fetch_one(&state.db)
+        .await?;
+    Ok(usize::try_from(depth).unwrap_or_default())
+}
+
+async fn forward_dead_letter_count(state: &AppState) -> Result<usize, sqlx::Error> {
+    let (count,) = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM forward_dead_letters")
+        .fetch_one(&state.db)
+        .await?;
+    Ok(usize::try_from(count).unwrap_or_default())
+}
+
+/// Moves a dead letter back to the tail of the forward queue with a fresh retry budget.
+/// Put a dead letter back under its original id, so it is forwarded ahead of (and then
+/// superseded by) any newer job still queued for its route rather than after them.
+async fn requeue_dead_letter(state: &AppState, id: i64) -> Result<bool, sqlx::Error> {
+    let now_ms = Utc::now().timestamp_millis();
+    let mut tx = state.db.begin().await?;
+    let requeued = sqlx::query(
+        "INSERT INTO forward_queue (id, route, payload, attempts, next_attempt_at, enqueued_at) \
+         SELECT id, route, payload, 0, ?, enqueued_at FROM forward_dead_letters WHERE id = ?",
+    )
+    .bind(now_ms)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    sqlx::query("DELETE FROM forward_dead_letters WHERE id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(requeued > 0)
 }
 
 async fn register_provisional_ownership(
@@ -2490,6 +4017,8 @@ async fn register_provisional_ownership(
     claimed_guild_uuid: Option<String>,
     claimed_guild_name: Option<String>,
     claimed_acquired: Option<String>,
+    scored_devices: Vec<String>,
+    revert_on_mismatch: bool,
 ) {
     let now = Instant::now();
     let expires_at = now + Duration::from_secs(state.cfg.owner_corroboration_window_secs);
@@ -2501,18 +4030,34 @@ async fn register_provisional_ownership(
             claimed_guild_uuid,
             claimed_guild_name,
             claimed_acquired,
+            scored_devices,
+            revert_on_mismatch,
             first_seen: now,
             expires_at,
         },
     );
 }
 
-fn spawn_ownership_corroborator_task(state: AppState) {
-    tokio::spawn(async move {
+fn normalize_owner_uuid(guild_uuid: &str) -> String {
+    guild_uuid.trim().to_ascii_lowercase()
+}
+
+/// Records `guild_uuid` as the known owner of `territory`, returning whether it changes a
+/// previously known owner. Territories without a known owner yet are not counted as changes.
+async fn note_owner_claim(state: &Arc<AppState>, territory: &str, guild_uuid: &str) -> bool {
+    let claimed = normalize_owner_uuid(guild_uuid);
+    let mut known = state.known_owners.write().await;
+    known
+        .insert(territory.to_string(), claimed.clone())
+        .is_some_and(|previous| previous != claimed)
+}
+
+fn spawn_ownership_corroborator_task(state: AppState) {
+    tokio::spawn(async move {
         let state = Arc::new(state);
         loop {
             tokio::time::sleep(Duration::from_secs(5)).await;
-            if !state.cfg.owner_soft_corroboration || !state.cfg.owner_revert_on_mismatch {
+            if !state.cfg.owner_soft_corroboration {
                 continue;
             }
             let now = Instant::now();
@@ -2535,6 +4080,15 @@ fn spawn_ownership_corroborator_task(state: AppState) {
                     continue;
                 }
             };
+            {
+                let mut known = state.known_owners.write().await;
+                for (territory, (guild_uuid, _, _)) in &authoritative {
+                    known.insert(
+                        territory.clone(),
+                        normalize_owner_uuid(guild_uuid.as_deref().unwrap_or_default()),
+                    );
+                }
+            }
 
             let mut remove_keys = Vec::new();
             for claim in due_claims {
@@ -2552,7 +4106,14 @@ fn spawn_ownership_corroborator_task(state: AppState) {
                         .as_deref()
                         .map(str::trim)
                         .unwrap_or_default();
-                if !mismatch {
+                record_reputation_outcome(
+                    &state,
+                    &claim.territory,
+                    &claim.scored_devices,
+                    !mismatch,
+                )
+                .await;
+                if !mismatch || !claim.revert_on_mismatch {
                     remove_keys.push(claim.territory.clone());
                     continue;
                 }
@@ -2575,6 +4136,7 @@ fn spawn_ownership_corroborator_task(state: AppState) {
                             menu_captured_territories: None,
                             menu_sr_per_hour: None,
                             menu_observed_at: None,
+                            corroborated_fields: None,
                         }),
                         ..TerritoryRuntimeData::default()
                     }),
@@ -2613,6 +4175,63 @@ fn spawn_ownership_corroborator_task(state: AppState) {
     });
 }
 
+/// Folds a corroboration outcome into each device's reputation and persists the new scores.
+/// Confirmations count at most once per device and territory per [`REPUTATION_CONFIRM_COOLDOWN`].
+async fn record_reputation_outcome(
+    state: &Arc<AppState>,
+    territory: &str,
+    device_identities: &[String],
+    confirmed: bool,
+) {
+    let device_identities = if confirmed {
+        let now = Instant::now();
+        let mut confirmed_at = state.reputation_confirmed_at.write().await;
+        device_identities
+            .iter()
+            .filter(|device_identity| {
+                let key = ((*device_identity).clone(), territory.to_string());
+                if confirmed_at
+                    .get(&key)
+                    .is_some_and(|at| now.duration_since(*at) < REPUTATION_CONFIRM_COOLDOWN)
+                {
+                    return false;
+                }
+                confirmed_at.insert(key, now);
+                true
+            })
+            .cloned()
+            .collect::<Vec<_>>()
+    } else {
+        device_identities.to_vec()
+    };
+    if device_identities.is_empty() {
+        return;
+    }
+    let now = Utc::now();
+    let updated = {
+        let mut reputation = state.reputation.write().await;
+        device_identities
+            .iter()
+            .map(|device_identity| {
+                let entry = reputation
+                    .entry(device_identity.clone())
+                    .or_insert_with(|| ReporterReputation::neutral(now));
+                entry.record(confirmed, now, &state.cfg);
+                (device_identity.clone(), entry.clone())
+            })
+            .collect::<Vec<_>>()
+    };
+    let counter = if confirmed {
+        &state.metrics.reputation_confirmed_total
+    } else {
+        &state.metrics.reputation_contradicted_total
+    };
+    counter.fetch_add(updated.len() as u64, Ordering::Relaxed);
+    for (device_identity, reputation) in updated {
+        persist_reputation(state, &device_identity, &reputation).await;
+    }
+}
+
 async fn fetch_wynncraft_ownership_map(
     state: &Arc<AppState>,
 ) -> Result<
@@ -2708,6 +4327,18 @@ async fn purge_expired(state: &AppState) -> Result<(), String> {
         .await
         .map_err(|e| format!("delete expired reporters: {e}"))?;
 
+    sqlx::query("DELETE FROM device_reputation WHERE updated_at < ?")
+        .bind(reporter_cutoff.to_rfc3339())
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("delete expired device_reputation: {e}"))?;
+
+    sqlx::query("DELETE FROM forward_dead_letters WHERE failed_at < ?")
+        .bind(cutoff.timestamp_millis())
+        .execute(&state.db)
+        .await
+        .map_err(|e| format!("delete expired forward_dead_letters: {e}"))?;
+
     let now_utc = Utc::now();
     let now_rfc3339 = now_utc.to_rfc3339();
     sqlx::query("DELETE FROM attestation_challenges WHERE expires_at < ? OR used_at IS NOT NULL")
@@ -2731,6 +4362,15 @@ async fn purge_expired(state: &AppState) -> Result<(), String> {
             }
         }
     }
+    {
+        let mut reputation = state.reputation.write().await;
+        reputation.retain(|_, entry| entry.updated_at >= reporter_cutoff);
+    }
+    {
+        let now = Instant::now();
+        let mut confirmed_at = state.reputation_confirmed_at.write().await;
+        confirmed_at.retain(|_, at| now.duration_since(*at) < REPUTATION_CONFIRM_COOLDOWN);
+    }
 
     let now = Instant::now();
     {
@@ -2757,6 +4397,13 @@ async fn purge_expired(state: &AppState) -> Result<(), String> {
         let mut pending = state.pending_territory.write().await;
         pending.retain(|_, claims| !claims.is_empty());
     }
+    {
+        let mut pending = state.pending_war.write().await;
+        for claims in pending.values_mut() {
+            claims.retain(|claim| claim.received_at.elapsed() <= state.cfg.quorum_window());
+        }
+        pending.retain(|_, claims| !claims.is_empty());
+    }
     {
         let mut claims = state.provisional_ownership.write().await;
         claims.retain(|_, claim| claim.expires_at > now);
@@ -2883,6 +4530,11 @@ async fn initialize_db(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     )
     .execute(pool)
     .await?;
+    // Reports stored before outcomes were recorded keep a NULL outcome.
+    sqlx::query("ALTER TABLE raw_reports ADD COLUMN outcome TEXT")
+        .execute(pool)
+        .await
+        .ok();
 
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS reporter_identities (\
@@ -2929,6 +4581,53 @@ async fn initialize_db(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS forward_queue (\
+         id INTEGER PRIMARY KEY AUTOINCREMENT,\
+         route TEXT NOT NULL,\
+         payload TEXT NOT NULL,\
+         attempts INTEGER NOT NULL DEFAULT 0,\
+         next_attempt_at INTEGER NOT NULL,\
+         enqueued_at INTEGER NOT NULL,\
+         last_error TEXT\
+         )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS forward_dead_letters (\
+         id INTEGER PRIMARY KEY,\
+         route TEXT NOT NULL,\
+         payload TEXT NOT NULL,\
+         attempts INTEGER NOT NULL,\
+         enqueued_at INTEGER NOT NULL,\
+         failed_at INTEGER NOT NULL,\
+         last_error TEXT\
+         )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_forward_dead_letters_failed_at \
+         ON forward_dead_letters (failed_at)",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS device_reputation (\
+         device_identity TEXT PRIMARY KEY,\
+         score REAL NOT NULL,\
+         confirmed INTEGER NOT NULL DEFAULT 0,\
+         contradicted INTEGER NOT NULL DEFAULT 0,\
+         updated_at TEXT NOT NULL\
+         )",
+    )
+    .execute(pool)
+    .await?;
+
     Ok(())
 }
 
@@ -3299,6 +4998,71 @@ async fn persist_challenge_used_at(
     }
 }
 
+async fn persist_reputation(
+    state: &AppState,
+    device_identity: &str,
+    reputation: &ReporterReputation,
+) {
+    if let Err(err) = sqlx::query(
+        "INSERT OR REPLACE INTO device_reputation (device_identity, score, confirmed, contradicted, updated_at) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(device_identity)
+    .bind(reputation.score)
+    .bind(i64::try_from(reputation.confirmed).unwrap_or(i64::MAX))
+    .bind(i64::try_from(reputation.contradicted).unwrap_or(i64::MAX))
+    .bind(reputation.updated_at.to_rfc3339())
+    .execute(&state.db)
+    .await
+    {
+        warn!(error = %err, device_key_id = %hash_prefix(device_identity, 16), "failed to persist device reputation");
+    }
+}
+
+fn parse_reputation_row(
+    score: f64,
+    confirmed: i64,
+    contradicted: i64,
+    updated_at: &str,
+) -> ReporterReputation {
+    ReporterReputation {
+        score,
+        confirmed: u64::try_from(confirmed).unwrap_or_default(),
+        contradicted: u64::try_from(contradicted).unwrap_or_default(),
+        updated_at: DateTime::parse_from_rfc3339(updated_at)
+            .map(|value| value.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+    }
+}
+
+/// Stored reputation per device identity. Only reads, so offline replay can use it on a
+/// read-only database.
+async fn load_device_reputation(
+    db: &SqlitePool,
+) -> Result<HashMap<String, ReporterReputation>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String, f64, i64, i64, String)>(
+        "SELECT device_identity, score, confirmed, contradicted, updated_at FROM device_reputation",
+    )
+    .fetch_all(db)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(
+            |(device_identity, score, confirmed, contradicted, updated_at)| {
+                (
+                    device_identity,
+                    parse_reputation_row(score, confirmed, contradicted, &updated_at),
+                )
+            },
+        )
+        .collect())
+}
+
+async fn bootstrap_reputation(state: &AppState) -> Result<(), sqlx::Error> {
+    let loaded = load_device_reputation(&state.db).await?;
+    state.reputation.write().await.extend(loaded);
+    Ok(())
+}
+
 async fn bootstrap_identities(state: &AppState) -> Result<(), sqlx::Error> {
     let rows = sqlx::query_as::<_, (String, String, String, String, String, String, String, String, String, String)>(
         "SELECT reporter_id, device_pubkey_hash, device_pubkey_b64, device_key_id, mojang_uuid, mojang_username, status, registered_at, last_attested_at, last_seen FROM reporter_identities",
@@ -3389,21 +5153,32 @@ async fn bootstrap_challenges(state: &AppState) -> Result<(), sqlx::Error> {
     Ok(())
 }
 
+/// What live decisioning did with a raw report, stored so replays can be diffed against it.
+fn raw_report_outcome(accepted: bool, quorum: bool) -> &'static str {
+    match (accepted, quorum) {
+        (true, true) => "quorum",
+        (true, false) => "degraded",
+        (false, _) => "pending",
+    }
+}
+
 async fn persist_raw_report(
     state: &AppState,
     kind: &str,
     reporter_id: &str,
     ip: &str,
     payload: &serde_json::Value,
+    outcome: &str,
 ) {
     if let Err(err) = sqlx::query(
-        "INSERT INTO raw_reports (kind, reporter_id, ip_address, received_at, payload) VALUES (?, ?, ?, ?, ?)",
+        "INSERT INTO raw_reports (kind, reporter_id, ip_address, received_at, payload, outcome) VALUES (?, ?, ?, ?, ?, ?)",
     )
     .bind(kind)
     .bind(reporter_id)
     .bind(ip)
     .bind(Utc::now().to_rfc3339())
     .bind(payload.to_string())
+    .bind(outcome)
     .execute(&state.db)
     .await
     {
@@ -3414,19 +5189,26 @@ async fn persist_raw_report(
 #[cfg(test)]
 mod tests {
     use super::{
-        AppState, Config, Metrics, ReporterFieldToggles, ReporterRecord, apply_toggle_policy,
-        canonical_device_identity_hash, check_rate_limit, evaluate_territory_claim, initialize_db,
-        normalize_idempotency_key, normalize_persisted_token, normalize_territory_name,
-        parse_trusted_proxy_cidrs, quorum_satisfied, resolve_client_ip,
+        AppState, Config, Metrics, REPUTATION_MIN_WEIGHT, REPUTATION_NEUTRAL, ReporterFieldToggles,
+        ReporterRecord, ReporterReputation, admin_invalidate_reporter, apply_toggle_policy,
+        canonical_device_identity_hash, check_rate_limit, default_reputation_max_weight,
+        drop_pending_claims_for, enqueue_forward, ensure_admin, evaluate_territory_claim,
+        evaluate_territory_claim_at, evaluate_war_claim, extend_quarantine,
+        forward_dead_letter_count, forward_queue_depth, initialize_db, lift_quarantine,
+        next_due_forward_job, normalize_idempotency_key, normalize_persisted_token,
+        normalize_territory_name, note_owner_claim, parse_trusted_proxy_cidrs,
+        pending_territory_snapshot, quorum_satisfied, record_reputation_outcome,
+        reporter_vote_weight, requeue_dead_letter, resolve_client_ip, schedule_retry,
         session_verifier_within_fail_open_grace, territory_claim_hash, territory_idempotency_hash,
-        token_hash,
+        token_hash, war_quorum_key,
     };
-    use axum::http::{HeaderMap, HeaderValue};
+    use axum::http::{HeaderMap, HeaderValue, StatusCode};
     use base64::Engine;
     use chrono::Utc;
     use reqwest::Client;
     use sequoia_shared::{
-        CanonicalTerritoryUpdate, DataProvenance, GuildRef, TerritoryRuntimeData,
+        CanonicalTerritoryUpdate, DataProvenance, GuildRef, Resources, TerritoryRuntimeData,
+        WarEvent, WarEventKind,
     };
     use sqlx_sqlite::SqlitePoolOptions;
     use std::collections::{HashMap, VecDeque};
@@ -3442,9 +5224,11 @@ mod tests {
     ) -> TerritoryRuntimeData {
         TerritoryRuntimeData {
             headquarters: None,
+            headquarters_territory: None,
             held_resources: None,
             production_rates: None,
             storage_capacity: None,
+            treasury: None,
             defense_tier: None,
             contested: None,
             active_war: None,
@@ -3459,6 +5243,7 @@ mod tests {
                 menu_captured_territories: Some(65),
                 menu_sr_per_hour: Some(30301),
                 menu_observed_at: Some(menu_observed_at.to_string()),
+                corroborated_fields: None,
             }),
         }
     }
@@ -3475,7 +5260,7 @@ mod tests {
         }
     }
 
-    async fn test_state_with_active_reporters(
+    pub(crate) async fn test_state_with_active_reporters(
         degraded_single_reporter_enabled: bool,
         active_reporters: usize,
         quorum_min_reporters: usize,
@@ -3493,6 +5278,7 @@ mod tests {
                 db_url: "sqlite::memory:".to_string(),
                 sequoia_server_base_url: "http://127.0.0.1:3000".to_string(),
                 internal_ingest_token: "abcdefghijklmnopqrstuvwxyz".to_string(),
+                admin_token: None,
                 api_body_limit_bytes: 2 * 1024 * 1024,
                 max_reporters: 10_000,
                 rate_limit_ip_per_min: 300,
@@ -3500,6 +5286,7 @@ mod tests {
                 max_rate_limit_keys: 20_000,
                 quorum_min_reporters,
                 quorum_min_origins,
+                quorum_window_secs: 120,
                 degraded_single_reporter_enabled,
                 raw_retention_days: 7,
                 reporter_retention_days: 30,
@@ -3529,6 +5316,8 @@ mod tests {
                 owner_corroboration_window_secs: 90,
                 owner_revert_on_mismatch: true,
                 active_reporter_stale_secs: 1800,
+                reputation_half_life_hours: 72.0,
+                reputation_max_weight: 2.0,
             },
             db,
             http: Client::new(),
@@ -3540,12 +5329,15 @@ mod tests {
             quarantined_until: Arc::new(RwLock::new(HashMap::new())),
             seen_idempotency: Arc::new(RwLock::new(HashMap::new())),
             pending_territory: Arc::new(RwLock::new(HashMap::new())),
+            pending_war: Arc::new(RwLock::new(HashMap::new())),
             identities: Arc::new(RwLock::new(HashMap::new())),
             challenges: Arc::new(RwLock::new(HashMap::new())),
             seen_signed_nonces: Arc::new(RwLock::new(HashMap::new())),
             provisional_ownership: Arc::new(RwLock::new(HashMap::new())),
+            reputation: Arc::new(RwLock::new(HashMap::new())),
+            reputation_confirmed_at: Arc::new(RwLock::new(HashMap::new())),
+            known_owners: Arc::new(RwLock::new(HashMap::new())),
             session_verifier_fail_open_until: Arc::new(RwLock::new(None)),
-            forward_queue: Arc::new(RwLock::new(VecDeque::new())),
             metrics: Arc::new(Metrics::default()),
         });
 
@@ -3592,7 +5384,7 @@ mod tests {
     }
 
     #[test]
-    fn territory_claim_hash_ignores_idempotency_and_provenance_metadata() {
+    fn territory_claim_hash_ignores_idempotency_and_runtime_data() {
         let first = CanonicalTerritoryUpdate {
             territory: "Ragni Plains".to_string(),
             guild: None,
@@ -3621,11 +5413,26 @@ mod tests {
         let second_hash = territory_claim_hash(&second);
         assert_eq!(first_hash, second_hash);
 
-        let mut changed = first.clone();
-        if let Some(runtime) = changed.runtime.as_mut() {
+        let mut runtime_changed = first.clone();
+        if let Some(runtime) = runtime_changed.runtime.as_mut() {
             runtime.defense_tier = Some("Very High".to_string());
         }
-        assert_ne!(territory_claim_hash(&first), territory_claim_hash(&changed));
+        assert_eq!(
+            territory_claim_hash(&first),
+            territory_claim_hash(&runtime_changed)
+        );
+
+        let mut owner_changed = first.clone();
+        owner_changed.guild = Some(GuildRef {
+            uuid: "guild-uuid".to_string(),
+            name: "Guild".to_string(),
+            prefix: "GLD".to_string(),
+            color: None,
+        });
+        assert_ne!(
+            territory_claim_hash(&first),
+            territory_claim_hash(&owner_changed)
+        );
         assert_ne!(
             territory_idempotency_hash("reporter-a", &first),
             territory_idempotency_hash("reporter-b", &first)
@@ -3907,11 +5714,288 @@ mod tests {
 
     #[test]
     fn quorum_requires_distinct_devices_and_supports_origin_floors() {
-        assert!(!quorum_satisfied(1, 1, 1, 2, 1));
-        assert!(!quorum_satisfied(2, 1, 1, 2, 1));
-        assert!(quorum_satisfied(2, 2, 1, 2, 1));
-        assert!(!quorum_satisfied(2, 2, 1, 2, 2));
-        assert!(quorum_satisfied(2, 2, 2, 2, 2));
+        assert!(!quorum_satisfied(1.0, 1, 2, 1));
+        assert!(quorum_satisfied(2.0, 1, 2, 1));
+        assert!(!quorum_satisfied(2.0, 1, 2, 2));
+        assert!(quorum_satisfied(2.0, 2, 2, 2));
+        assert!(quorum_satisfied(1.1 + 0.9, 1, 2, 1));
+        assert!(!quorum_satisfied(1.0 + 0.75, 1, 2, 1));
+    }
+
+    #[tokio::test]
+    async fn reputation_decays_toward_neutral_and_clamps_weight() {
+        let state = test_state_with_active_reporters(false, 1, 2, 1).await;
+        let start = Utc::now();
+        let mut reputation = ReporterReputation::neutral(start);
+        for _ in 0..20 {
+            reputation.record(true, start, &state.cfg);
+        }
+        assert_eq!(reputation.score, state.cfg.reputation_max_weight);
+        assert_eq!(reputation.confirmed, 20);
+
+        let one_half_life = start + chrono::TimeDelta::hours(72);
+        let decayed = reputation.decayed_score(one_half_life, state.cfg.reputation_half_life_hours);
+        assert!((decayed - 1.5).abs() < 1e-9);
+
+        for _ in 0..10 {
+            reputation.record(false, start, &state.cfg);
+        }
+        assert_eq!(reputation.score, REPUTATION_MIN_WEIGHT);
+        assert_eq!(reputation.contradicted, 10);
+        assert_eq!(
+            reporter_vote_weight(&state.cfg, None, start),
+            REPUTATION_NEUTRAL
+        );
+    }
+
+    #[tokio::test]
+    async fn trusted_device_can_reach_quorum_alone_when_cap_allows() {
+        let state = test_state_with_active_reporters(false, 3, 2, 1).await;
+        state.reputation.write().await.insert(
+            "device-a".to_string(),
+            ReporterReputation {
+                score: 2.0,
+                confirmed: 10,
+                contradicted: 0,
+                updated_at: Utc::now(),
+            },
+        );
+
+        let decision = evaluate_territory_claim(
+            &state,
+            "reporter-a",
+            "device-a",
+            IpAddr::from([203, 0, 113, 10]),
+            basic_claim_update(),
+        )
+        .await
+        .expect("a trusted device should carry quorum on its own when the cap is raised");
+        assert!(decision.quorum);
+        assert!(!decision.degraded);
+        assert_eq!(decision.reporter_ids, vec!["reporter-a".to_string()]);
+    }
+
+    #[test]
+    fn default_reputation_cap_stays_below_reporter_quorum() {
+        assert_eq!(default_reputation_max_weight(1), REPUTATION_NEUTRAL);
+        assert_eq!(default_reputation_max_weight(2), 1.5);
+        assert_eq!(default_reputation_max_weight(5), 1.5);
+        assert!(!quorum_satisfied(default_reputation_max_weight(2), 1, 2, 1));
+    }
+
+    #[tokio::test]
+    async fn reputation_scores_only_owner_changes_and_rate_limits_confirmations() {
+        let state = test_state_with_active_reporters(false, 2, 2, 1).await;
+        assert!(
+            !note_owner_claim(&state, "Ragni Plains", "guild-a").await,
+            "no baseline owner yet"
+        );
+        assert!(!note_owner_claim(&state, "Ragni Plains", " GUILD-A ").await);
+        assert!(note_owner_claim(&state, "Ragni Plains", "guild-b").await);
+
+        let devices = vec!["device-a".to_string()];
+        record_reputation_outcome(&state, "Ragni Plains", &devices, true).await;
+        record_reputation_outcome(&state, "Ragni Plains", &devices, true).await;
+        record_reputation_outcome(&state, "Detlas", &devices, true).await;
+        let confirmed = state.reputation.read().await["device-a"].confirmed;
+        assert_eq!(
+            confirmed, 2,
+            "repeat confirmations on one territory are rate limited"
+        );
+
+        record_reputation_outcome(&state, "Ragni Plains", &devices, false).await;
+        record_reputation_outcome(&state, "Ragni Plains", &devices, false).await;
+        assert_eq!(state.reputation.read().await["device-a"].contradicted, 2);
+    }
+
+    #[tokio::test]
+    async fn low_reputation_reporter_needs_extra_corroboration() {
+        let state = test_state_with_active_reporters(false, 3, 2, 1).await;
+        state.reputation.write().await.insert(
+            "device-a".to_string(),
+            ReporterReputation {
+                score: REPUTATION_MIN_WEIGHT,
+                confirmed: 0,
+                contradicted: 4,
+                updated_at: Utc::now(),
+            },
+        );
+        let origin = IpAddr::from([203, 0, 113, 10]);
+
+        // Re-enrolling hands out a new reporter id, but the device keeps its reputation.
+        assert!(
+            evaluate_territory_claim(
+                &state,
+                "reporter-a-reenrolled",
+                "device-a",
+                origin,
+                basic_claim_update()
+            )
+            .await
+            .is_none()
+        );
+        assert!(
+            evaluate_territory_claim(
+                &state,
+                "reporter-b",
+                "device-b",
+                origin,
+                basic_claim_update()
+            )
+            .await
+            .is_none(),
+            "a distrusted reporter should not count as a full corroborating vote"
+        );
+        let decision = evaluate_territory_claim(
+            &state,
+            "reporter-c",
+            "device-c",
+            origin,
+            basic_claim_update(),
+        )
+        .await
+        .expect("a third neutral vote should satisfy quorum");
+        assert!(decision.quorum);
+        assert_eq!(decision.reporter_ids.len(), 3);
+    }
+
+    fn owner_claim_with_runtime(
+        held_emeralds: i32,
+        treasury: &str,
+        contested: bool,
+    ) -> CanonicalTerritoryUpdate {
+        let mut update = basic_claim_update();
+        update.guild = Some(GuildRef {
+            uuid: "guild-uuid".to_string(),
+            name: "Guild".to_string(),
+            prefix: "GLD".to_string(),
+            color: None,
+        });
+        update.acquired = Some("2026-02-28T20:00:00Z".to_string());
+        if let Some(runtime) = update.runtime.as_mut() {
+            runtime.held_resources = Some(Resources {
+                emeralds: held_emeralds,
+                ore: 40,
+                ..Resources::default()
+            });
+            runtime.treasury = Some(treasury.to_string());
+            runtime.contested = Some(contested);
+        }
+        update
+    }
+
+    #[tokio::test]
+    async fn owner_quorum_merges_conflicting_runtime_fields() {
+        let state = test_state_with_active_reporters(false, 3, 3, 1).await;
+        let origin = IpAddr::from([203, 0, 113, 10]);
+
+        assert!(
+            evaluate_territory_claim(
+                &state,
+                "reporter-a",
+                "device-a",
+                origin,
+                owner_claim_with_runtime(1_000, "High", false),
+            )
+            .await
+            .is_none()
+        );
+        assert!(
+            evaluate_territory_claim(
+                &state,
+                "reporter-b",
+                "device-b",
+                origin,
+                owner_claim_with_runtime(1_240, "High", true),
+            )
+            .await
+            .is_none()
+        );
+        let decision = evaluate_territory_claim(
+            &state,
+            "reporter-c",
+            "device-c",
+            origin,
+            owner_claim_with_runtime(1_120, "Low", false),
+        )
+        .await
+        .expect("reporters agreeing on the owner should reach quorum despite runtime drift");
+
+        assert!(decision.quorum);
+        let runtime = decision.update.runtime.expect("merged runtime");
+        assert_eq!(
+            runtime.held_resources,
+            Some(Resources {
+                emeralds: 1_120,
+                ore: 40,
+                ..Resources::default()
+            })
+        );
+        assert_eq!(runtime.treasury.as_deref(), Some("High"));
+        assert_eq!(runtime.contested, Some(false));
+
+        let provenance = runtime.provenance.expect("merged provenance");
+        assert_eq!(provenance.reporter_count, 3);
+        let corroborated = provenance
+            .corroborated_fields
+            .expect("per-field corroboration");
+        assert_eq!(corroborated.get("owner"), Some(&3));
+        assert_eq!(corroborated.get("held_resources"), Some(&3));
+        assert_eq!(corroborated.get("treasury"), Some(&2));
+        assert_eq!(corroborated.get("contested"), Some(&2));
+        assert!(state.pending_territory.read().await["Ragni Plains"].is_empty());
+    }
+
+    #[tokio::test]
+    async fn uncorroborated_runtime_fields_fall_back_to_latest_sample() {
+        let state = test_state_with_active_reporters(false, 2, 2, 1).await;
+        let origin = IpAddr::from([203, 0, 113, 10]);
+
+        let mut first = owner_claim_with_runtime(1_000, "High", false);
+        if let Some(runtime) = first.runtime.as_mut() {
+            runtime.defense_tier = Some("Very High".to_string());
+        }
+        assert!(
+            evaluate_territory_claim(&state, "reporter-a", "device-a", origin, first)
+                .await
+                .is_none()
+        );
+        let decision = evaluate_territory_claim(
+            &state,
+            "reporter-b",
+            "device-b",
+            origin,
+            owner_claim_with_runtime(1_100, "Low", false),
+        )
+        .await
+        .expect("owner quorum");
+
+        let runtime = decision.update.runtime.expect("merged runtime");
+        assert_eq!(
+            runtime.held_resources.map(|resources| resources.emeralds),
+            Some(1_000)
+        );
+        assert_eq!(runtime.contested, Some(false));
+        assert_eq!(runtime.defense_tier.as_deref(), Some("Very High"));
+        assert_eq!(
+            runtime.treasury.as_deref(),
+            Some("Low"),
+            "a 1-1 split should keep the most recent sample"
+        );
+        let corroborated = runtime
+            .provenance
+            .and_then(|provenance| provenance.corroborated_fields)
+            .expect("per-field corroboration");
+        assert_eq!(corroborated.get("contested"), Some(&2));
+        assert_eq!(corroborated.get("defense_tier"), Some(&0));
+        assert_eq!(corroborated.get("treasury"), Some(&0));
+        assert_eq!(
+            state
+                .metrics
+                .runtime_fields_uncorroborated_total
+                .load(std::sync::atomic::Ordering::Relaxed),
+            2
+        );
     }
 
     #[tokio::test]
@@ -3935,15 +6019,16 @@ mod tests {
         )
         .await;
 
-        let (accepted, was_degraded, was_quorum) =
+        let decision =
             decision.expect("single active reporter should be accepted in degraded mode");
-        let provenance = accepted
+        let provenance = decision
+            .update
             .runtime
             .as_ref()
             .and_then(|runtime| runtime.provenance.as_ref())
             .expect("degraded acceptance should include provenance metadata");
-        assert!(was_degraded);
-        assert!(!was_quorum);
+        assert!(decision.degraded);
+        assert!(!decision.quorum);
         assert_eq!(provenance.reporter_count, 1);
         assert_eq!(provenance.source, "fabric_reporter");
         assert!(!provenance.observed_at.is_empty());
@@ -3996,15 +6081,15 @@ mod tests {
         .expect("second distinct reporter should satisfy quorum behind one NAT");
 
         assert!(
-            !second.1,
+            !second.degraded,
             "same-origin corroboration should not use degraded mode"
         );
         assert!(
-            second.2,
+            second.quorum,
             "same-origin corroboration should still count as quorum"
         );
         let provenance = second
-            .0
+            .update
             .runtime
             .as_ref()
             .and_then(|runtime| runtime.provenance.as_ref())
@@ -4012,6 +6097,38 @@ mod tests {
         assert_eq!(provenance.reporter_count, 2);
     }
 
+    #[tokio::test]
+    async fn repeat_sighting_keeps_a_pending_vote_inside_the_quorum_window() {
+        let state = test_state_with_active_reporters(false, 2, 2, 1).await;
+        let origin = IpAddr::from([203, 0, 113, 10]);
+        let window = state.cfg.quorum_window();
+        let start = Instant::now();
+        let claim = |reporter: &'static str, device: &'static str, at: Instant| {
+            evaluate_territory_claim_at(
+                &state,
+                reporter,
+                device,
+                origin,
+                basic_claim_update(),
+                at,
+                Utc::now(),
+            )
+        };
+
+        assert!(claim("reporter-a", "device-a", start).await.is_none());
+        let repeat_at = start + window - Duration::from_secs(1);
+        assert!(claim("reporter-a", "device-a", repeat_at).await.is_none());
+        // Past the first sighting's window, but within the repeat's.
+        let decision = claim(
+            "reporter-b",
+            "device-b",
+            start + window + Duration::from_secs(1),
+        )
+        .await
+        .expect("refreshed vote should still count toward quorum");
+        assert!(decision.quorum);
+    }
+
     #[tokio::test]
     async fn quorum_rejects_same_device_reenrollment_without_origin_diversity() {
         let state = test_state_with_active_reporters(false, 2, 2, 1).await;
@@ -4065,11 +6182,11 @@ mod tests {
                 .await
                 .expect("distinct origin should satisfy the stricter quorum floor");
         assert!(
-            accepted.2,
+            accepted.quorum,
             "distinct-origin corroboration should count as quorum"
         );
         let provenance = accepted
-            .0
+            .update
             .runtime
             .as_ref()
             .and_then(|runtime| runtime.provenance.as_ref())
@@ -4077,6 +6194,344 @@ mod tests {
         assert_eq!(provenance.reporter_count, 3);
     }
 
+    #[tokio::test]
+    async fn admin_surface_requires_configured_bearer_token() {
+        let state = test_state_with_active_reporters(false, 0, 2, 1).await;
+        let mut cfg = state.cfg.clone();
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            ensure_admin(&cfg, &headers),
+            Err(StatusCode::NOT_FOUND),
+            "admin routes should be hidden when no admin token is configured"
+        );
+
+        cfg.admin_token = Some("admin-token-abcdefghijklmnop".to_string());
+        assert_eq!(ensure_admin(&cfg, &headers), Err(StatusCode::UNAUTHORIZED));
+        headers.insert(
+            "authorization",
+            HeaderValue::from_static("Bearer abcdefghijklmnopqrstuvwxyz"),
+        );
+        assert_eq!(ensure_admin(&cfg, &headers), Err(StatusCode::UNAUTHORIZED));
+        headers.insert(
+            "authorization",
+            HeaderValue::from_static("Bearer admin-token-abcdefghijklmnop"),
+        );
+        assert_eq!(ensure_admin(&cfg, &headers), Ok(()));
+    }
+
+    #[tokio::test]
+    async fn admin_revoke_expires_token_and_drops_pending_votes() {
+        let state = test_state_with_active_reporters(false, 2, 2, 1).await;
+        assert!(
+            evaluate_territory_claim(
+                &state,
+                "active-reporter-0",
+                "device-a",
+                IpAddr::from([203, 0, 113, 10]),
+                basic_claim_update(),
+            )
+            .await
+            .is_none()
+        );
+
+        let response = admin_invalidate_reporter(&state, "active-reporter-0", true)
+            .await
+            .expect("known reporter should be revocable");
+        assert!(response.revoked);
+        assert_eq!(
+            drop_pending_claims_for(&state, "active-reporter-0").await,
+            1
+        );
+        assert!(state.pending_territory.read().await.is_empty());
+
+        let reporters = state.reporters.read().await;
+        let record = &reporters["active-reporter-0"];
+        assert!(record.revoked);
+        assert!(record.token_expires_at <= Utc::now());
+        assert!(!reporters["active-reporter-1"].revoked);
+        drop(reporters);
+
+        assert_eq!(
+            admin_invalidate_reporter(&state, "missing-reporter", false)
+                .await
+                .err(),
+            Some(StatusCode::NOT_FOUND)
+        );
+    }
+
+    #[tokio::test]
+    async fn admin_quarantine_extend_accumulates_and_lift_clears_strikes() {
+        let state = test_state_with_active_reporters(false, 0, 2, 1).await;
+        state
+            .malformed_penalties
+            .write()
+            .await
+            .insert("ip:203.0.113.10".to_string(), 8);
+
+        let first = extend_quarantine(&state, "203.0.113.10", Duration::from_secs(60)).await;
+        assert_eq!(first.malformed_penalty, 8);
+        let second = extend_quarantine(&state, "203.0.113.10", Duration::from_secs(60)).await;
+        assert!(second.remaining_secs > 60 && second.remaining_secs <= 120);
+
+        assert!(lift_quarantine(&state, "203.0.113.10").await);
+        assert!(state.quarantined_until.read().await.is_empty());
+        assert!(state.malformed_penalties.read().await.is_empty());
+        assert!(!lift_quarantine(&state, "203.0.113.10").await);
+    }
+
+    #[tokio::test]
+    async fn admin_pending_snapshot_groups_agreeing_claims() {
+        let state = test_state_with_active_reporters(false, 3, 3, 1).await;
+        let origin = IpAddr::from([203, 0, 113, 10]);
+        let mut disagreeing = basic_claim_update();
+        disagreeing.acquired = Some("2026-02-28T20:00:00Z".to_string());
+        for (reporter_id, device, update) in [
+            ("reporter-a", "device-a", basic_claim_update()),
+            ("reporter-b", "device-b", basic_claim_update()),
+            ("reporter-c", "device-c", disagreeing),
+        ] {
+            assert!(
+                evaluate_territory_claim(&state, reporter_id, device, origin, update)
+                    .await
+                    .is_none()
+            );
+        }
+
+        let snapshot = pending_territory_snapshot(&state).await;
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].territory, "Ragni Plains");
+        assert_eq!(snapshot[0].claims, 3);
+        assert_eq!(snapshot[0].groups.len(), 2);
+        assert_eq!(
+            snapshot[0].groups[0].reporters,
+            vec!["reporter-a", "reporter-b"]
+        );
+        assert_eq!(snapshot[0].groups[0].distinct_devices, 2);
+        assert!((snapshot[0].groups[0].vote_weight - 2.0).abs() < 1e-9);
+        assert_eq!(snapshot[0].groups[1].reporters, vec!["reporter-c"]);
+    }
+
+    async fn forward_test_state(max_forward_queue: usize) -> Arc<AppState> {
+        let state = test_state_with_active_reporters(false, 0, 2, 1).await;
+        initialize_db(&state.db)
+            .await
+            .expect("initialize forward queue schema");
+        let mut inner = Arc::try_unwrap(state)
+            .unwrap_or_else(|_| panic!("test state should be uniquely owned"));
+        inner.cfg.max_forward_queue = max_forward_queue;
+        inner.cfg.forward_max_attempts = 3;
+        Arc::new(inner)
+    }
+
+    #[tokio::test]
+    async fn forward_queue_replays_in_order_per_route_and_survives_retries() {
+        let state = forward_test_state(0).await;
+        for (route, seq) in [("/territory", 1), ("/war", 2), ("/territory", 3)] {
+            enqueue_forward(&state, route, serde_json::json!({ "seq": seq })).await;
+        }
+        let now = Utc::now();
+
+        let head = next_due_forward_job(&state, now)
+            .await
+            .expect("load job")
+            .expect("territory head should be due");
+        assert_eq!(head.route, "/territory");
+        assert_eq!(head.payload["seq"], 1);
+
+        schedule_retry(&state, &head, "status 503", now)
+            .await
+            .expect("schedule retry");
+        let next = next_due_forward_job(&state, now)
+            .await
+            .expect("load job")
+            .expect("other route should not wait behind a backing-off head");
+        assert_eq!(next.route, "/war");
+        sqlx::query("DELETE FROM forward_queue WHERE id = ?")
+            .bind(next.id)
+            .execute(&state.db)
+            .await
+            .expect("ack war job");
+        assert!(
+            next_due_forward_job(&state, now)
+                .await
+                .expect("load job")
+                .is_none(),
+            "later territory job must wait for the retrying head"
+        );
+
+        let retried = next_due_forward_job(&state, now + chrono::TimeDelta::seconds(3))
+            .await
+            .expect("load job")
+            .expect("head should be due after its backoff");
+        assert_eq!(retried.id, head.id);
+        assert_eq!(retried.attempts, 1);
+        assert_eq!(forward_queue_depth(&state).await.expect("depth"), 2);
+    }
+
+    #[tokio::test]
+    async fn forward_queue_dead_letters_unparsable_payloads_instead_of_forwarding() {
+        let state = forward_test_state(0).await;
+        enqueue_forward(&state, "/territory", serde_json::json!({ "seq": 1 })).await;
+        enqueue_forward(&state, "/territory", serde_json::json!({ "seq": 2 })).await;
+        sqlx::query("UPDATE forward_queue SET payload = '{not json' WHERE id = (SELECT MIN(id) FROM forward_queue)")
+            .execute(&state.db)
+            .await
+            .expect("corrupt head payload");
+
+        let job = next_due_forward_job(&state, Utc::now())
+            .await
+            .expect("load job")
+            .expect("next job due");
+        assert_eq!(job.payload["seq"], 2);
+        assert_eq!(forward_queue_depth(&state).await.expect("depth"), 1);
+        assert_eq!(forward_dead_letter_count(&state).await.expect("count"), 1);
+        let (last_error,) =
+            sqlx::query_as::<_, (String,)>("SELECT last_error FROM forward_dead_letters")
+                .fetch_one(&state.db)
+                .await
+                .expect("dead letter row");
+        assert!(last_error.starts_with("unparsable payload:"));
+    }
+
+    #[tokio::test]
+    async fn forward_queue_dead_letters_exhausted_and_evicted_jobs() {
+        let state = forward_test_state(2).await;
+        enqueue_forward(&state, "/territory", serde_json::json!({ "seq": 1 })).await;
+        enqueue_forward(&state, "/territory", serde_json::json!({ "seq": 2 })).await;
+        enqueue_forward(&state, "/territory", serde_json::json!({ "seq": 3 })).await;
+        assert_eq!(forward_queue_depth(&state).await.expect("depth"), 2);
+        assert_eq!(forward_dead_letter_count(&state).await.expect("count"), 1);
+
+        let mut now = Utc::now();
+        let mut job = next_due_forward_job(&state, now)
+            .await
+            .expect("load job")
+            .expect("job due");
+        assert_eq!(job.payload["seq"], 2);
+        while job.attempts < 2 {
+            schedule_retry(&state, &job, "status 500", now)
+                .await
+                .expect("schedule retry");
+            now += chrono::TimeDelta::seconds(60);
+            job = next_due_forward_job(&state, now)
+                .await
+                .expect("load job")
+                .expect("job due");
+        }
+        schedule_retry(&state, &job, "status 500", now)
+            .await
+            .expect("dead letter job");
+        assert_eq!(forward_dead_letter_count(&state).await.expect("count"), 2);
+        assert_eq!(
+            state
+                .metrics
+                .forward_dead_lettered_total
+                .load(std::sync::atomic::Ordering::Relaxed),
+            2
+        );
+
+        assert!(requeue_dead_letter(&state, job.id).await.expect("requeue"));
+        assert!(!requeue_dead_letter(&state, job.id).await.expect("requeue"));
+        assert_eq!(forward_queue_depth(&state).await.expect("depth"), 2);
+        // The requeued job keeps its place ahead of the newer seq 3 update on the same route.
+        let head = next_due_forward_job(&state, Utc::now())
+            .await
+            .expect("load job")
+            .expect("requeued job due");
+        assert_eq!(head.id, job.id);
+        assert_eq!(head.attempts, 0);
+        assert_eq!(head.payload["seq"], 2);
+    }
+
+    fn war_event(kind: WarEventKind, guild_uuid: &str, observed_at: &str) -> WarEvent {
+        WarEvent {
+            id: String::new(),
+            kind,
+            territory: "Ragni Plains".to_string(),
+            guild: Some(GuildRef {
+                uuid: guild_uuid.to_string(),
+                name: "Aequitas".to_string(),
+                prefix: "Aeq".to_string(),
+                color: None,
+            }),
+            tower_state: None,
+            observed_at: observed_at.to_string(),
+            provenance: DataProvenance::default(),
+        }
+    }
+
+    #[test]
+    fn war_quorum_key_separates_kind_and_guild_but_ignores_timestamps() {
+        let queued = war_event(WarEventKind::Queued, "guild-a", "2026-02-28T20:00:00Z");
+        let queued_later = war_event(WarEventKind::Queued, "GUILD-A", "2026-02-28T20:00:04Z");
+        let started = war_event(WarEventKind::Started, "guild-a", "2026-02-28T20:00:00Z");
+        let other_guild = war_event(WarEventKind::Queued, "guild-b", "2026-02-28T20:00:00Z");
+
+        assert_eq!(war_quorum_key(&queued), war_quorum_key(&queued_later));
+        assert_ne!(war_quorum_key(&queued), war_quorum_key(&started));
+        assert_ne!(war_quorum_key(&queued), war_quorum_key(&other_guild));
+    }
+
+    #[tokio::test]
+    async fn war_quorum_requires_distinct_reporters_for_same_territory_kind_and_guild() {
+        let state = test_state_with_active_reporters(false, 2, 2, 1).await;
+        let origin = IpAddr::from([203, 0, 113, 10]);
+
+        assert!(
+            evaluate_war_claim(
+                &state,
+                "reporter-a",
+                "device-a",
+                origin,
+                war_event(WarEventKind::Started, "guild-a", "2026-02-28T20:00:00Z"),
+            )
+            .await
+            .is_none()
+        );
+        assert!(
+            evaluate_war_claim(
+                &state,
+                "reporter-b",
+                "device-b",
+                origin,
+                war_event(WarEventKind::Started, "guild-b", "2026-02-28T20:00:01Z"),
+            )
+            .await
+            .is_none(),
+            "a different attacking guild must not corroborate"
+        );
+
+        let (accepted, was_degraded, was_quorum) = evaluate_war_claim(
+            &state,
+            "reporter-b",
+            "device-b",
+            origin,
+            war_event(WarEventKind::Started, "guild-a", "2026-02-28T20:00:02Z"),
+        )
+        .await
+        .expect("second distinct reporter should satisfy war quorum");
+        assert!(!was_degraded);
+        assert!(was_quorum);
+        assert_eq!(accepted.observed_at, "2026-02-28T20:00:00Z");
+        assert_eq!(accepted.provenance.reporter_count, 2);
+    }
+
+    #[tokio::test]
+    async fn war_degraded_mode_accepts_single_active_reporter() {
+        let state = test_state_with_active_reporters(true, 1, 2, 1).await;
+        let (_, was_degraded, was_quorum) = evaluate_war_claim(
+            &state,
+            "reporter-a",
+            "device-a",
+            IpAddr::from([203, 0, 113, 10]),
+            war_event(WarEventKind::Queued, "guild-a", "2026-02-28T20:00:00Z"),
+        )
+        .await
+        .expect("single active reporter should be accepted in degraded mode");
+        assert!(was_degraded);
+        assert!(!was_quorum);
+    }
+
     #[test]
     fn normalize_persisted_token_hashes_plaintext_and_normalizes_digest_case() {
         let legacy_plaintext =
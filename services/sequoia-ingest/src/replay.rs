@@ -0,0 +1,798 @@
+//! Offline replay of stored raw territory reports through quorum decisioning.
+//!
+//! `sequoia-ingest replay [flags]` reads `raw_reports` (read-only) in arrival order, feeds each
+//! report through `apply_toggle_policy` and `evaluate_territory_claim_at` on a clock driven by
+//! the stored `received_at`, and writes JSON lines to stdout: one `accepted` line per canonical
+//! update the alternative config would emit, one `diff` line per report whose outcome differs
+//! from what live ingest recorded, and a final `summary` line.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::{Context, bail};
+use chrono::{DateTime, Utc};
+use sequoia_shared::CanonicalTerritoryUpdate;
+use serde::Serialize;
+use sqlx_sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+
+use super::{
+    AppState, Config, ReporterFieldToggles, ReporterRecord, apply_toggle_policy,
+    canonical_device_identity_hash, evaluate_territory_claim_at, load_device_reputation,
+    raw_report_outcome,
+};
+
+const REPLAY_PAGE_SIZE: i64 = 2_000;
+
+const USAGE: &str = "usage: sequoia-ingest replay [--db URL] [--from RFC3339] [--to RFC3339] [--limit N] \
+[--quorum-min-reporters N] [--quorum-min-origins N] [--quorum-window-secs N] \
+[--degraded-single-reporter true|false] [--owner-corroboration-window-secs N] \
+[--reputation-max-weight X] [--share owner,headquarters,held_resources,production_rates,storage_capacity,defense_tier,trading_routes]";
+
+/// Command-line overrides; anything unset falls back to the usual `INGEST_*` environment.
+#[derive(Debug, Default, PartialEq)]
+struct ReplayOptions {
+    db_url: Option<String>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    limit: Option<usize>,
+    quorum_min_reporters: Option<usize>,
+    quorum_min_origins: Option<usize>,
+    quorum_window_secs: Option<u64>,
+    degraded_single_reporter: Option<bool>,
+    owner_corroboration_window_secs: Option<u64>,
+    reputation_max_weight: Option<f64>,
+    toggles: Option<ReporterFieldToggles>,
+}
+
+impl ReplayOptions {
+    fn parse(args: &[String]) -> anyhow::Result<Self> {
+        let mut options = Self::default();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            if matches!(flag, "-h" | "--help") {
+                bail!("{USAGE}");
+            }
+            let value = match inline_value {
+                Some(value) => value,
+                None => iter
+                    .next()
+                    .cloned()
+                    .with_context(|| format!("missing value for {flag}\n{USAGE}"))?,
+            };
+            match flag {
+                "--db" => options.db_url = Some(value),
+                "--from" => options.from = Some(parse_flag(flag, &value)?),
+                "--to" => options.to = Some(parse_flag(flag, &value)?),
+                "--limit" => options.limit = Some(parse_flag(flag, &value)?),
+                "--quorum-min-reporters" => {
+                    options.quorum_min_reporters = Some(parse_flag(flag, &value)?);
+                }
+                "--quorum-min-origins" => {
+                    options.quorum_min_origins = Some(parse_flag(flag, &value)?);
+                }
+                "--quorum-window-secs" => {
+                    options.quorum_window_secs = Some(parse_flag(flag, &value)?);
+                }
+                "--degraded-single-reporter" => {
+                    options.degraded_single_reporter = Some(parse_flag(flag, &value)?);
+                }
+                "--owner-corroboration-window-secs" => {
+                    options.owner_corroboration_window_secs = Some(parse_flag(flag, &value)?);
+                }
+                "--reputation-max-weight" => {
+                    options.reputation_max_weight = Some(parse_flag(flag, &value)?);
+                }
+                "--share" => options.toggles = Some(parse_shared_fields(&value)?),
+                _ => bail!("unknown replay flag {flag}\n{USAGE}"),
+            }
+        }
+        Ok(options)
+    }
+
+    fn apply(&self, cfg: &mut Config) {
+        if let Some(db_url) = &self.db_url {
+            cfg.db_url = db_url.clone();
+        }
+        if let Some(value) = self.quorum_min_reporters {
+            cfg.quorum_min_reporters = value;
+        }
+        if let Some(value) = self.quorum_min_origins {
+            cfg.quorum_min_origins = value;
+        }
+        if let Some(value) = self.quorum_window_secs {
+            cfg.quorum_window_secs = value;
+        }
+        if let Some(value) = self.degraded_single_reporter {
+            cfg.degraded_single_reporter_enabled = value;
+        }
+        if let Some(value) = self.owner_corroboration_window_secs {
+            cfg.owner_corroboration_window_secs = value;
+        }
+        if let Some(value) = self.reputation_max_weight {
+            cfg.reputation_max_weight = value;
+        }
+    }
+}
+
+fn parse_flag<T: FromStr>(flag: &str, value: &str) -> anyhow::Result<T> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("invalid value {value:?} for {flag}"))
+}
+
+/// `--share` lists the fields reporters share; everything not listed is stripped.
+fn parse_shared_fields(value: &str) -> anyhow::Result<ReporterFieldToggles> {
+    let mut toggles = ReporterFieldToggles {
+        share_owner: false,
+        share_headquarters: false,
+        share_held_resources: false,
+        share_production_rates: false,
+        share_storage_capacity: false,
+        share_defense_tier: false,
+        share_trading_routes: false,
+    };
+    for field in value.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+        match field {
+            "owner" => toggles.share_owner = true,
+            "headquarters" => toggles.share_headquarters = true,
+            "held_resources" => toggles.share_held_resources = true,
+            "production_rates" => toggles.share_production_rates = true,
+            "storage_capacity" => toggles.share_storage_capacity = true,
+            "defense_tier" => toggles.share_defense_tier = true,
+            "trading_routes" => toggles.share_trading_routes = true,
+            _ => bail!("unknown --share field {field:?}"),
+        }
+    }
+    Ok(toggles)
+}
+
+/// One stored territory report as it was received.
+#[derive(Clone, Debug)]
+struct RawTerritoryReport {
+    id: i64,
+    reporter_id: String,
+    origin_ip: IpAddr,
+    device_identity: String,
+    received_at: DateTime<Utc>,
+    update: CanonicalTerritoryUpdate,
+    /// Outcome recorded by live ingest; `None` for reports stored before outcomes were kept.
+    actual_outcome: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ReplayConfigSnapshot {
+    quorum_min_reporters: usize,
+    quorum_min_origins: usize,
+    quorum_window_secs: u64,
+    degraded_single_reporter_enabled: bool,
+    owner_corroboration_window_secs: u64,
+    reputation_max_weight: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    toggles: Option<ReporterFieldToggles>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ReplaySummary {
+    config: ReplayConfigSnapshot,
+    reports: u64,
+    filtered_by_toggles: u64,
+    unparseable: u64,
+    actual_unknown: u64,
+    actual_accepted: u64,
+    replay_accepted: u64,
+    replay_quorum: u64,
+    replay_degraded: u64,
+    /// Accepted by the replay but held pending (or never decided) live.
+    newly_accepted: u64,
+    /// Accepted live but held pending by the replay.
+    newly_pending: u64,
+    /// Accepted by both, but one through quorum and the other through degraded mode.
+    mode_changed: u64,
+    provisional_owner_claims: u64,
+    /// Offline proxy for corroboration: a later accepted update named a different owner
+    /// within the corroboration window.
+    provisional_owner_contradicted: u64,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ReplayLine<'a> {
+    Accepted {
+        raw_report_id: i64,
+        received_at: String,
+        territory: &'a str,
+        outcome: &'static str,
+        reporter_ids: &'a [String],
+        update: &'a CanonicalTerritoryUpdate,
+    },
+    Diff {
+        raw_report_id: i64,
+        received_at: String,
+        territory: &'a str,
+        reporter_id: &'a str,
+        actual: &'a str,
+        replay: &'static str,
+    },
+    Summary(&'a ReplaySummary),
+}
+
+/// Drives a fresh, isolated `AppState` through stored reports on a simulated clock.
+struct Replayer {
+    state: Arc<AppState>,
+    toggles: Option<ReporterFieldToggles>,
+    clock_base: Instant,
+    first_received_at: Option<DateTime<Utc>>,
+    last_received_at: Option<DateTime<Utc>>,
+    provisional_owners: HashMap<String, (String, DateTime<Utc>)>,
+    summary: ReplaySummary,
+}
+
+impl Replayer {
+    fn new(state: Arc<AppState>, toggles: Option<ReporterFieldToggles>) -> Self {
+        let cfg = &state.cfg;
+        let summary = ReplaySummary {
+            config: ReplayConfigSnapshot {
+                quorum_min_reporters: cfg.quorum_min_reporters,
+                quorum_min_origins: cfg.quorum_min_origins,
+                quorum_window_secs: cfg.quorum_window_secs,
+                degraded_single_reporter_enabled: cfg.degraded_single_reporter_enabled,
+                owner_corroboration_window_secs: cfg.owner_corroboration_window_secs,
+                reputation_max_weight: cfg.reputation_max_weight,
+                toggles: toggles.clone(),
+            },
+            ..ReplaySummary::default()
+        };
+        Self {
+            state,
+            toggles,
+            clock_base: Instant::now(),
+            first_received_at: None,
+            last_received_at: None,
+            provisional_owners: HashMap::new(),
+            summary,
+        }
+    }
+
+    /// Maps a stored timestamp onto the monotonic clock quorum windows are measured with.
+    fn instant_for(&mut self, received_at: DateTime<Utc>) -> (Instant, DateTime<Utc>) {
+        let first = *self.first_received_at.get_or_insert(received_at);
+        // Reports arrive in `received_at` order; the clamp only guards the monotonic clock.
+        let at = self
+            .last_received_at
+            .map_or(received_at, |last| last.max(received_at));
+        self.last_received_at = Some(at);
+        let offset = (at - first).to_std().unwrap_or_default();
+        (self.clock_base + offset, at)
+    }
+
+    async fn feed(
+        &mut self,
+        report: RawTerritoryReport,
+        out: &mut impl Write,
+    ) -> std::io::Result<()> {
+        self.summary.reports += 1;
+        let update = match &self.toggles {
+            Some(toggles) => match apply_toggle_policy(report.update, toggles) {
+                Some(update) => update,
+                None => {
+                    self.summary.filtered_by_toggles += 1;
+                    return Ok(());
+                }
+            },
+            None => report.update,
+        };
+
+        let (now, now_utc) = self.instant_for(report.received_at);
+        self.mark_active(&report.reporter_id, now_utc).await;
+        let territory = update.territory.clone();
+
+        let decision = evaluate_territory_claim_at(
+            &self.state,
+            &report.reporter_id,
+            &report.device_identity,
+            report.origin_ip,
+            update,
+            now,
+            now_utc,
+        )
+        .await;
+        let replay_outcome = raw_report_outcome(
+            decision.is_some(),
+            decision.as_ref().is_some_and(|decision| decision.quorum),
+        );
+        let received_at = report.received_at.to_rfc3339();
+
+        if let Some(decision) = &decision {
+            self.summary.replay_accepted += 1;
+            if decision.quorum {
+                self.summary.replay_quorum += 1;
+            } else {
+                self.summary.replay_degraded += 1;
+            }
+            self.track_owner(
+                decision.update.clone(),
+                decision.reporter_ids.len(),
+                now_utc,
+            );
+            write_line(
+                out,
+                &ReplayLine::Accepted {
+                    raw_report_id: report.id,
+                    received_at: received_at.clone(),
+                    territory: &decision.update.territory,
+                    outcome: replay_outcome,
+                    reporter_ids: &decision.reporter_ids,
+                    update: &decision.update,
+                },
+            )?;
+        }
+
+        let Some(actual) = report.actual_outcome.as_deref() else {
+            self.summary.actual_unknown += 1;
+            return Ok(());
+        };
+        let actual_accepted = actual != "pending";
+        if actual_accepted {
+            self.summary.actual_accepted += 1;
+        }
+        if actual == replay_outcome {
+            return Ok(());
+        }
+        match (actual_accepted, decision.is_some()) {
+            (false, true) => self.summary.newly_accepted += 1,
+            (true, false) => self.summary.newly_pending += 1,
+            _ => self.summary.mode_changed += 1,
+        }
+        write_line(
+            out,
+            &ReplayLine::Diff {
+                raw_report_id: report.id,
+                received_at,
+                territory: &territory,
+                reporter_id: &report.reporter_id,
+                actual,
+                replay: replay_outcome,
+            },
+        )
+    }
+
+    /// Reports stand in for heartbeats when counting active reporters for degraded mode.
+    async fn mark_active(&self, reporter_id: &str, now: DateTime<Utc>) {
+        let mut reporters = self.state.reporters.write().await;
+        reporters
+            .entry(reporter_id.to_string())
+            .and_modify(|record| record.last_seen = now)
+            .or_insert_with(|| ReporterRecord {
+                token_hash: String::new(),
+                token_expires_at: now,
+                revoked: false,
+                guild_opt_in: false,
+                field_toggles: ReporterFieldToggles::default(),
+                last_seen: now,
+                device_pubkey_b64: String::new(),
+                device_key_id: String::new(),
+                mojang_uuid: String::new(),
+                mojang_username: String::new(),
+                last_attested_at: now,
+            });
+    }
+
+    fn track_owner(
+        &mut self,
+        update: CanonicalTerritoryUpdate,
+        distinct_reporters: usize,
+        now: DateTime<Utc>,
+    ) {
+        let Some(guild) = update.guild else {
+            return;
+        };
+        let window = chrono::TimeDelta::seconds(
+            i64::try_from(self.state.cfg.owner_corroboration_window_secs).unwrap_or(i64::MAX),
+        );
+        if let Some((claimed_uuid, claimed_at)) = self.provisional_owners.remove(&update.territory)
+            && now - claimed_at <= window
+            && claimed_uuid.trim() != guild.uuid.trim()
+        {
+            self.summary.provisional_owner_contradicted += 1;
+        }
+        if distinct_reporters < self.state.cfg.quorum_min_reporters.max(1) {
+            self.summary.provisional_owner_claims += 1;
+            self.provisional_owners
+                .insert(update.territory, (guild.uuid, now));
+        }
+    }
+
+    fn finish(self, out: &mut impl Write) -> std::io::Result<ReplaySummary> {
+        write_line(out, &ReplayLine::Summary(&self.summary))?;
+        Ok(self.summary)
+    }
+}
+
+fn write_line(out: &mut impl Write, line: &ReplayLine<'_>) -> std::io::Result<()> {
+    serde_json::to_writer(&mut *out, line)?;
+    out.write_all(b"\n")
+}
+
+pub(crate) async fn run(args: Vec<String>) -> anyhow::Result<()> {
+    let options = ReplayOptions::parse(&args)?;
+    // Replay never forwards, so it does not need the internal ingest token.
+    let mut cfg = Config::from_env_with_internal_token(String::new())?;
+    options.apply(&mut cfg);
+
+    let connect_options = SqliteConnectOptions::from_str(&cfg.db_url)
+        .with_context(|| format!("invalid database url {}", cfg.db_url))?
+        .read_only(true);
+    let db = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(connect_options)
+        .await
+        .context("open ingest database read-only")?;
+
+    let has_outcome = sqlx::query_as::<_, (i64,)>(
+        "SELECT COUNT(*) FROM pragma_table_info('raw_reports') WHERE name = 'outcome'",
+    )
+    .fetch_one(&db)
+    .await?
+    .0 > 0;
+    let devices = sqlx::query_as::<_, (String, String)>(
+        "SELECT reporter_id, device_pubkey_b64 FROM reporters",
+    )
+    .fetch_all(&db)
+    .await?
+    .into_iter()
+    .filter(|(_, pubkey)| !pubkey.trim().is_empty())
+    .map(|(reporter_id, pubkey)| (reporter_id, canonical_device_identity_hash(&pubkey)))
+    .collect::<HashMap<_, _>>();
+
+    let state = Arc::new(AppState::new(cfg, db.clone())?);
+    load_replay_reputation(&state).await?;
+    let mut replayer = Replayer::new(state, options.toggles.clone());
+    let stdout = std::io::stdout();
+    let mut out = std::io::BufWriter::new(stdout.lock());
+
+    // Seeding the cursor with `--from` skips everything stored before it.
+    let mut cursor = (
+        options
+            .from
+            .map(|from| from.to_rfc3339())
+            .unwrap_or_default(),
+        0_i64,
+    );
+    let mut fed = 0_usize;
+    'pages: loop {
+        let rows =
+            load_report_page(&db, has_outcome, (&cursor.0, cursor.1), REPLAY_PAGE_SIZE).await?;
+        if rows.is_empty() {
+            break;
+        }
+        for (id, reporter_id, ip_address, received_at, payload, outcome) in rows {
+            cursor = (received_at.clone(), id);
+            let Ok(received_at) =
+                DateTime::parse_from_rfc3339(&received_at).map(|value| value.with_timezone(&Utc))
+            else {
+                replayer.summary.unparseable += 1;
+                continue;
+            };
+            if options.from.is_some_and(|from| received_at < from) {
+                continue;
+            }
+            if options.to.is_some_and(|to| received_at > to) {
+                break 'pages;
+            }
+            let Ok(update) = serde_json::from_str::<CanonicalTerritoryUpdate>(&payload) else {
+                replayer.summary.unparseable += 1;
+                continue;
+            };
+            let device_identity = devices
+                .get(&reporter_id)
+                .cloned()
+                .unwrap_or_else(|| format!("reporter:{reporter_id}"));
+            replayer
+                .feed(
+                    RawTerritoryReport {
+                        id,
+                        origin_ip: ip_address.parse().unwrap_or(IpAddr::from([0, 0, 0, 0])),
+                        reporter_id,
+                        device_identity,
+                        received_at,
+                        update,
+                        actual_outcome: outcome,
+                    },
+                    &mut out,
+                )
+                .await?;
+            fed += 1;
+            if options.limit.is_some_and(|limit| fed >= limit) {
+                break 'pages;
+            }
+        }
+    }
+
+    replayer.finish(&mut out)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Seed the replay with stored device reputation so weighted acceptances replay as they ran
+/// live and `--reputation-max-weight` has scores to cap.
+async fn load_replay_reputation(state: &AppState) -> anyhow::Result<()> {
+    let reputation = load_device_reputation(&state.db)
+        .await
+        .context("load device reputation")?;
+    *state.reputation.write().await = reputation;
+    Ok(())
+}
+
+type RawReportRow = (i64, String, String, String, String, Option<String>);
+
+/// Next page of territory reports after the `(received_at, id)` cursor, in arrival order.
+///
+/// `received_at` is always written with `to_rfc3339` in UTC, so text order is time order (the
+/// retention purge relies on the same); `id` breaks ties between reports stored in one instant.
+async fn load_report_page(
+    db: &SqlitePool,
+    has_outcome: bool,
+    after: (&str, i64),
+    limit: i64,
+) -> anyhow::Result<Vec<RawReportRow>> {
+    let select = if has_outcome {
+        "SELECT id, reporter_id, ip_address, received_at, payload, outcome FROM raw_reports \
+         WHERE kind = 'territory' AND (received_at > ? OR (received_at = ? AND id > ?)) \
+         ORDER BY received_at, id LIMIT ?"
+    } else {
+        "SELECT id, reporter_id, ip_address, received_at, payload, NULL FROM raw_reports \
+         WHERE kind = 'territory' AND (received_at > ? OR (received_at = ? AND id > ?)) \
+         ORDER BY received_at, id LIMIT ?"
+    };
+    let (received_at, id) = after;
+    Ok(sqlx::query_as::<_, RawReportRow>(select)
+        .bind(received_at)
+        .bind(received_at)
+        .bind(id)
+        .bind(limit)
+        .fetch_all(db)
+        .await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        RawTerritoryReport, ReplayOptions, Replayer, load_replay_reputation, load_report_page,
+        parse_shared_fields,
+    };
+    use crate::AppState;
+    use crate::tests::test_state_with_active_reporters;
+    use chrono::{DateTime, Utc};
+    use sequoia_shared::{CanonicalTerritoryUpdate, GuildRef};
+    use std::net::IpAddr;
+    use std::sync::Arc;
+
+    fn owner_report(
+        id: i64,
+        reporter: &str,
+        guild_uuid: &str,
+        received_at: &str,
+        actual: Option<&str>,
+    ) -> RawTerritoryReport {
+        RawTerritoryReport {
+            id,
+            reporter_id: reporter.to_string(),
+            origin_ip: IpAddr::from([203, 0, 113, 10]),
+            device_identity: format!("device-{reporter}"),
+            received_at: DateTime::parse_from_rfc3339(received_at)
+                .expect("valid timestamp")
+                .with_timezone(&Utc),
+            update: CanonicalTerritoryUpdate {
+                territory: "Ragni Plains".to_string(),
+                guild: Some(GuildRef {
+                    uuid: guild_uuid.to_string(),
+                    name: "Guild".to_string(),
+                    prefix: "GLD".to_string(),
+                    color: None,
+                }),
+                acquired: Some("2026-02-28T20:00:00Z".to_string()),
+                location: None,
+                resources: None,
+                connections: None,
+                runtime: None,
+                idempotency_key: None,
+            },
+            actual_outcome: actual.map(str::to_string),
+        }
+    }
+
+    async fn replay_state(
+        degraded: bool,
+        quorum_min_reporters: usize,
+        quorum_window_secs: u64,
+    ) -> Arc<AppState> {
+        let state = test_state_with_active_reporters(degraded, 0, quorum_min_reporters, 1).await;
+        let mut inner = Arc::try_unwrap(state)
+            .unwrap_or_else(|_| panic!("test state should be uniquely owned"));
+        inner.cfg.quorum_window_secs = quorum_window_secs;
+        Arc::new(inner)
+    }
+
+    fn lines(out: &[u8]) -> Vec<serde_json::Value> {
+        String::from_utf8_lossy(out)
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json line"))
+            .collect()
+    }
+
+    #[test]
+    fn replay_options_parse_overrides_and_shared_fields() {
+        let args = [
+            "--quorum-min-reporters=3",
+            "--quorum-window-secs",
+            "300",
+            "--degraded-single-reporter",
+            "true",
+            "--share",
+            "owner,trading_routes",
+            "--from",
+            "2026-02-28T00:00:00Z",
+        ]
+        .map(str::to_string);
+        let options = ReplayOptions::parse(&args).expect("parse replay flags");
+        assert_eq!(options.quorum_min_reporters, Some(3));
+        assert_eq!(options.quorum_window_secs, Some(300));
+        assert_eq!(options.degraded_single_reporter, Some(true));
+        let toggles = options.toggles.expect("shared fields");
+        assert!(toggles.share_owner && toggles.share_trading_routes);
+        assert!(!toggles.share_held_resources);
+        assert!(options.from.is_some());
+
+        assert!(ReplayOptions::parse(&["--quorum-min-reporters".to_string()]).is_err());
+        assert!(ReplayOptions::parse(&["--bogus=1".to_string()]).is_err());
+        assert!(parse_shared_fields("owner,treasury").is_err());
+    }
+
+    #[tokio::test]
+    async fn report_pages_follow_received_at_then_id() {
+        let state = replay_state(false, 2, 120).await;
+        crate::initialize_db(&state.db).await.expect("init db");
+        for (id, received_at) in [
+            (1, "2026-02-28T20:00:05+00:00"),
+            (2, "2026-02-28T20:00:01+00:00"),
+            (3, "2026-02-28T20:00:05+00:00"),
+            (4, "2026-02-28T20:00:03.250+00:00"),
+        ] {
+            sqlx::query(
+                "INSERT INTO raw_reports (id, kind, reporter_id, ip_address, received_at, payload) \
+                 VALUES (?, 'territory', 'a', '203.0.113.10', ?, '{}')",
+            )
+            .bind(id)
+            .bind(received_at)
+            .execute(&state.db)
+            .await
+            .expect("insert report");
+        }
+
+        let first = load_report_page(&state.db, false, ("", 0), 3)
+            .await
+            .expect("first page");
+        let ids: Vec<i64> = first.iter().map(|row| row.0).collect();
+        assert_eq!(ids, vec![2, 4, 1]);
+
+        let last = &first[2];
+        let rest = load_report_page(&state.db, false, (&last.3, last.0), 3)
+            .await
+            .expect("second page");
+        let ids: Vec<i64> = rest.iter().map(|row| row.0).collect();
+        assert_eq!(ids, vec![3]);
+    }
+
+    #[tokio::test]
+    async fn replay_keeps_reputation_weighted_acceptances() {
+        let report = || owner_report(1, "a", "guild-1", "2026-02-28T20:00:00Z", Some("quorum"));
+
+        let state = replay_state(false, 2, 120).await;
+        crate::initialize_db(&state.db).await.expect("init db");
+        sqlx::query(
+            "INSERT INTO device_reputation (device_identity, score, confirmed, contradicted, updated_at) \
+             VALUES ('device-a', 2.0, 10, 0, '2026-02-28T20:00:00Z')",
+        )
+        .execute(&state.db)
+        .await
+        .expect("insert reputation");
+        load_replay_reputation(&state)
+            .await
+            .expect("load reputation");
+
+        // The trusted device carried quorum alone live, and does again in replay.
+        let mut out = Vec::new();
+        let mut replayer = Replayer::new(state, None);
+        replayer.feed(report(), &mut out).await.expect("feed");
+        let summary = replayer.finish(&mut out).expect("finish");
+        assert_eq!(summary.replay_accepted, 1);
+        assert_eq!(summary.newly_pending, 0);
+        assert!(lines(&out).iter().all(|line| line["type"] != "diff"));
+
+        // Without the stored score the same report only reaches pending.
+        let mut out = Vec::new();
+        let mut replayer = Replayer::new(replay_state(false, 2, 120).await, None);
+        replayer.feed(report(), &mut out).await.expect("feed");
+        let summary = replayer.finish(&mut out).expect("finish");
+        assert_eq!(summary.replay_accepted, 0);
+        assert_eq!(summary.newly_pending, 1);
+    }
+
+    #[tokio::test]
+    async fn replay_uses_stored_timestamps_for_quorum_windows() {
+        let reports = [
+            owner_report(1, "a", "guild-1", "2026-02-28T20:00:00Z", Some("pending")),
+            owner_report(2, "b", "guild-1", "2026-02-28T20:03:00Z", Some("quorum")),
+        ];
+
+        // A 120s window expires the first vote before the second arrives three minutes later.
+        let mut out = Vec::new();
+        let mut replayer = Replayer::new(replay_state(false, 2, 120).await, None);
+        for report in reports.clone() {
+            replayer.feed(report, &mut out).await.expect("feed");
+        }
+        let summary = replayer.finish(&mut out).expect("finish");
+        assert_eq!(summary.replay_accepted, 0);
+        assert_eq!(summary.actual_accepted, 1);
+        assert_eq!(summary.newly_pending, 1);
+        let emitted = lines(&out);
+        assert_eq!(emitted[0]["type"], "diff");
+        assert_eq!(emitted[0]["raw_report_id"], 2);
+        assert_eq!(emitted[0]["actual"], "quorum");
+        assert_eq!(emitted[0]["replay"], "pending");
+
+        // Widening the window lets the two reports corroborate each other again.
+        let mut out = Vec::new();
+        let mut replayer = Replayer::new(replay_state(false, 2, 300).await, None);
+        for report in reports {
+            replayer.feed(report, &mut out).await.expect("feed");
+        }
+        let summary = replayer.finish(&mut out).expect("finish");
+        assert_eq!(summary.replay_accepted, 1);
+        assert_eq!(summary.newly_pending, 0);
+        let emitted = lines(&out);
+        assert_eq!(emitted[0]["type"], "accepted");
+        assert_eq!(emitted[0]["reporter_ids"], serde_json::json!(["a", "b"]));
+        assert_eq!(emitted.last().expect("summary")["type"], "summary");
+    }
+
+    #[tokio::test]
+    async fn replay_counts_contradicted_provisional_owners_and_toggle_filtering() {
+        let mut out = Vec::new();
+        let mut replayer = Replayer::new(replay_state(true, 2, 120).await, None);
+        for report in [
+            owner_report(1, "a", "guild-1", "2026-02-28T20:00:00Z", None),
+            owner_report(2, "a", "guild-2", "2026-02-28T20:00:30Z", None),
+        ] {
+            replayer.feed(report, &mut out).await.expect("feed");
+        }
+        let summary = replayer.finish(&mut out).expect("finish");
+        assert_eq!(summary.replay_degraded, 2);
+        assert_eq!(summary.actual_unknown, 2);
+        assert_eq!(summary.provisional_owner_claims, 2);
+        assert_eq!(summary.provisional_owner_contradicted, 1);
+
+        let mut out = Vec::new();
+        let hidden = parse_shared_fields("held_resources").expect("toggles");
+        let mut replayer = Replayer::new(replay_state(true, 2, 120).await, Some(hidden));
+        replayer
+            .feed(
+                owner_report(1, "a", "guild-1", "2026-02-28T20:00:00Z", Some("degraded")),
+                &mut out,
+            )
+            .await
+            .expect("feed");
+        let summary = replayer.finish(&mut out).expect("finish");
+        assert_eq!(summary.filtered_by_toggles, 1);
+        assert_eq!(summary.replay_accepted, 0);
+    }
+}
@@ -4,11 +4,15 @@ use std::collections::{HashMap, HashSet};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
+use crate::economy::TowerUpgrades;
 use crate::territory::{GuildRef, Region, Resources, TerritoryMap};
 use crate::tower::count_guild_connections;
 
 pub const CLAIM_DOCUMENT_VERSION_V1: u8 = 1;
+pub const CLAIM_DOCUMENT_VERSION_V2: u8 = 2;
 pub const MAX_CLAIM_DOCUMENT_BYTES: usize = 256 * 1024;
+pub const MAX_CLAIM_ANNOTATIONS: usize = 200;
+pub const MAX_CLAIM_ANNOTATION_TEXT_LEN: usize = 500;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "kind", rename_all = "snake_case")]
@@ -173,6 +177,210 @@ impl ClaimDocumentV1 {
     }
 }
 
+/// Planned tower upgrade levels for one territory, as indices into the `shared::tower` tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ClaimTowerLevels {
+    pub damage: u8,
+    pub attack: u8,
+    pub health: u8,
+    pub defense: u8,
+    pub aura: u8,
+    pub volley: u8,
+}
+
+impl ClaimTowerLevels {
+    /// Every level is within the tower tables, i.e. at most [`TowerUpgrades::MAX`].
+    pub fn is_valid(&self) -> bool {
+        let (levels, max) = (self.upgrades(), TowerUpgrades::MAX);
+        levels.damage <= max.damage
+            && levels.attack <= max.attack
+            && levels.health <= max.health
+            && levels.defense <= max.defense
+            && levels.aura <= max.aura
+            && levels.volley <= max.volley
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn upgrades(&self) -> TowerUpgrades {
+        TowerUpgrades {
+            damage: u32::from(self.damage),
+            attack: u32::from(self.attack),
+            health: u32::from(self.health),
+            defense: u32::from(self.defense),
+            aura: u32::from(self.aura),
+            volley: u32::from(self.volley),
+        }
+    }
+}
+
+/// Free-form planning markup attached to territories.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ClaimAnnotation {
+    Note {
+        territory: String,
+        text: String,
+    },
+    Arrow {
+        from: String,
+        to: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+    },
+}
+
+impl ClaimAnnotation {
+    pub fn territories(&self) -> Vec<&str> {
+        match self {
+            Self::Note { territory, .. } => vec![territory.as_str()],
+            Self::Arrow { from, to, .. } => vec![from.as_str(), to.as_str()],
+        }
+    }
+}
+
+/// Claim document with per-guild HQs, per-territory tower levels and annotations.
+///
+/// Deserializing accepts V1 documents too and upgrades them in place, so saved layouts, drafts
+/// and share fragments written before V2 keep loading.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ClaimDocumentWire")]
+pub struct ClaimDocumentV2 {
+    pub version: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub base: ClaimDocumentBase,
+    #[serde(default)]
+    pub overrides: HashMap<String, ClaimOwner>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub territory_state_overrides: HashMap<String, ClaimTerritoryStateOverride>,
+    #[serde(default)]
+    pub macros: Vec<ClaimMacro>,
+    #[serde(default)]
+    pub view: ClaimViewState,
+    /// HQ territory keyed by [`ClaimOwner::identity_key`].
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub hqs: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tower_levels: HashMap<String, ClaimTowerLevels>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<ClaimAnnotation>,
+}
+
+/// Any supported document version on the wire; V1 has none of the V2-only fields.
+#[derive(Deserialize)]
+struct ClaimDocumentWire {
+    version: u8,
+    #[serde(default)]
+    title: Option<String>,
+    base: ClaimDocumentBase,
+    #[serde(default)]
+    overrides: HashMap<String, ClaimOwner>,
+    #[serde(default)]
+    territory_state_overrides: HashMap<String, ClaimTerritoryStateOverride>,
+    #[serde(default)]
+    macros: Vec<ClaimMacro>,
+    #[serde(default)]
+    view: ClaimViewState,
+    #[serde(default)]
+    hqs: HashMap<String, String>,
+    #[serde(default)]
+    tower_levels: HashMap<String, ClaimTowerLevels>,
+    #[serde(default)]
+    annotations: Vec<ClaimAnnotation>,
+}
+
+impl From<ClaimDocumentWire> for ClaimDocumentV2 {
+    fn from(wire: ClaimDocumentWire) -> Self {
+        // Unknown versions are kept so validation can reject them.
+        let version = if wire.version == CLAIM_DOCUMENT_VERSION_V1 {
+            CLAIM_DOCUMENT_VERSION_V2
+        } else {
+            wire.version
+        };
+        Self {
+            version,
+            title: wire.title,
+            base: wire.base,
+            overrides: wire.overrides,
+            territory_state_overrides: wire.territory_state_overrides,
+            macros: wire.macros,
+            view: wire.view,
+            hqs: wire.hqs,
+            tower_levels: wire.tower_levels,
+            annotations: wire.annotations,
+        }
+    }
+}
+
+impl From<ClaimDocumentV1> for ClaimDocumentV2 {
+    fn from(document: ClaimDocumentV1) -> Self {
+        Self {
+            version: CLAIM_DOCUMENT_VERSION_V2,
+            title: document.title,
+            base: document.base,
+            overrides: document.overrides,
+            territory_state_overrides: document.territory_state_overrides,
+            macros: document.macros,
+            view: document.view,
+            hqs: HashMap::new(),
+            tower_levels: HashMap::new(),
+            annotations: Vec::new(),
+        }
+    }
+}
+
+impl Default for ClaimDocumentV2 {
+    fn default() -> Self {
+        ClaimDocumentV1::default().into()
+    }
+}
+
+impl ClaimDocumentV2 {
+    pub fn blank() -> Self {
+        Self::default()
+    }
+
+    pub fn frozen_live(
+        title: Option<String>,
+        seq: u64,
+        owners: HashMap<String, ClaimOwner>,
+    ) -> Self {
+        ClaimDocumentV1::frozen_live(title, seq, owners).into()
+    }
+
+    /// Effective owner of `territory` after overrides.
+    pub fn owner_of(&self, territory: &str) -> ClaimOwner {
+        self.overrides
+            .get(territory)
+            .cloned()
+            .unwrap_or_else(|| base_owner_for(self, territory))
+    }
+
+    /// HQ territory of `owner`, if one is set.
+    pub fn hq_for(&self, owner: &ClaimOwner) -> Option<&str> {
+        self.hqs.get(&owner.identity_key()?).map(String::as_str)
+    }
+
+    /// Drop HQs whose territory no longer belongs to their guild, e.g. after repainting.
+    pub fn retain_owned_hqs(&mut self) {
+        let stale: Vec<String> = self
+            .hqs
+            .iter()
+            .filter(|(key, territory)| {
+                self.owner_of(territory).identity_key().as_deref() != Some(key.as_str())
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in stale {
+            self.hqs.remove(&key);
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ClaimValidationError {
     UnsupportedVersion(u8),
@@ -180,6 +388,12 @@ pub enum ClaimValidationError {
     UnknownTerritory(String),
     DuplicateMacroId(String),
     EmptyMacroName(String),
+    /// The HQ territory is not owned by the guild it is set for.
+    HqNotOwned(String),
+    InvalidTowerLevels(String),
+    TooManyAnnotations(usize),
+    /// Index of an annotation with empty or oversized text, or an arrow pointing at itself.
+    InvalidAnnotation(usize),
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
@@ -227,7 +441,7 @@ pub struct ClaimMetrics {
     pub guilds: Vec<ClaimGuildMetrics>,
 }
 
-fn base_owner_for(document: &ClaimDocumentV1, territory: &str) -> ClaimOwner {
+fn base_owner_for(document: &ClaimDocumentV2, territory: &str) -> ClaimOwner {
     match &document.base {
         ClaimDocumentBase::Blank => ClaimOwner::Neutral,
         ClaimDocumentBase::FrozenLiveSnapshot { owners, .. } => owners
@@ -237,15 +451,15 @@ fn base_owner_for(document: &ClaimDocumentV1, territory: &str) -> ClaimOwner {
     }
 }
 
-pub fn claim_document_size(document: &ClaimDocumentV1) -> Result<usize, serde_json::Error> {
+pub fn claim_document_size(document: &ClaimDocumentV2) -> Result<usize, serde_json::Error> {
     serde_json::to_vec(document).map(|bytes| bytes.len())
 }
 
 pub fn validate_claim_document<'a>(
-    document: &ClaimDocumentV1,
+    document: &ClaimDocumentV2,
     territories: impl IntoIterator<Item = &'a str>,
 ) -> Result<(), ClaimValidationError> {
-    if document.version != CLAIM_DOCUMENT_VERSION_V1 {
+    if document.version != CLAIM_DOCUMENT_VERSION_V2 {
         return Err(ClaimValidationError::UnsupportedVersion(document.version));
     }
 
@@ -295,11 +509,50 @@ pub fn validate_claim_document<'a>(
         }
     }
 
+    for (owner_key, territory) in &document.hqs {
+        ensure_known(territory)?;
+        if document.owner_of(territory).identity_key().as_deref() != Some(owner_key.as_str()) {
+            return Err(ClaimValidationError::HqNotOwned(territory.clone()));
+        }
+    }
+
+    for (territory, levels) in &document.tower_levels {
+        ensure_known(territory)?;
+        if !levels.is_valid() {
+            return Err(ClaimValidationError::InvalidTowerLevels(territory.clone()));
+        }
+    }
+
+    if document.annotations.len() > MAX_CLAIM_ANNOTATIONS {
+        return Err(ClaimValidationError::TooManyAnnotations(
+            document.annotations.len(),
+        ));
+    }
+    for (index, annotation) in document.annotations.iter().enumerate() {
+        for territory in annotation.territories() {
+            ensure_known(territory)?;
+        }
+        let valid = match annotation {
+            ClaimAnnotation::Note { text, .. } => {
+                !text.trim().is_empty() && text.chars().count() <= MAX_CLAIM_ANNOTATION_TEXT_LEN
+            }
+            ClaimAnnotation::Arrow { from, to, label } => {
+                from != to
+                    && label
+                        .as_ref()
+                        .is_none_or(|label| label.chars().count() <= MAX_CLAIM_ANNOTATION_TEXT_LEN)
+            }
+        };
+        if !valid {
+            return Err(ClaimValidationError::InvalidAnnotation(index));
+        }
+    }
+
     Ok(())
 }
 
 pub fn materialize_claim_owners(
-    document: &ClaimDocumentV1,
+    document: &ClaimDocumentV2,
     territories: &TerritoryMap,
 ) -> HashMap<String, ClaimOwner> {
     let mut owners = HashMap::with_capacity(territories.len());
@@ -315,7 +568,7 @@ pub fn materialize_claim_owners(
 }
 
 pub fn compact_claim_overrides(
-    document: &ClaimDocumentV1,
+    document: &ClaimDocumentV2,
     territories: &TerritoryMap,
 ) -> HashMap<String, ClaimOwner> {
     let mut overrides = HashMap::new();
@@ -332,7 +585,7 @@ pub fn compact_claim_overrides(
 }
 
 pub fn compute_claim_metrics(
-    document: &ClaimDocumentV1,
+    document: &ClaimDocumentV2,
     territories: &TerritoryMap,
 ) -> ClaimMetrics {
     let effective = materialize_claim_owners(document, territories);
@@ -445,6 +698,176 @@ pub fn compute_claim_metrics(
     }
 }
 
+/// A territory whose effective owner differs between two documents.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClaimOwnerChange {
+    pub territory: String,
+    pub before: ClaimOwner,
+    pub after: ClaimOwner,
+}
+
+/// A territory whose resource override was added, removed or changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClaimResourceOverrideChange {
+    pub territory: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<Resources>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<Resources>,
+}
+
+/// Metrics of one guild on both sides of a diff; a missing side means it held nothing there.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClaimGuildMetricsDelta {
+    pub owner: ClaimOwner,
+    pub territory_delta: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<ClaimGuildMetrics>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<ClaimGuildMetrics>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct ClaimDiff {
+    #[serde(default)]
+    pub owner_changes: Vec<ClaimOwnerChange>,
+    #[serde(default)]
+    pub resource_changes: Vec<ClaimResourceOverrideChange>,
+    #[serde(default)]
+    pub guilds: Vec<ClaimGuildMetricsDelta>,
+}
+
+impl ClaimDiff {
+    pub fn is_empty(&self) -> bool {
+        self.owner_changes.is_empty() && self.resource_changes.is_empty()
+    }
+
+    /// Territories with an owner or resource change.
+    pub fn changed_territories(&self) -> HashSet<String> {
+        self.owner_changes
+            .iter()
+            .map(|change| change.territory.clone())
+            .chain(
+                self.resource_changes
+                    .iter()
+                    .map(|change| change.territory.clone()),
+            )
+            .collect()
+    }
+}
+
+/// Compare two documents over the territories of `territories`.
+///
+/// `before` is the reference (live ownership or another layout) and `after` is the plan being
+/// evaluated, so the diff reads as what adopting `after` would change: territories a guild gains
+/// in `after` count as positive deltas. Every caller keeps this orientation.
+///
+/// Guild deltas only list guilds whose territory count, resource counts or hubs changed;
+/// `changed_territory_count` is relative to each document's own base and is ignored.
+pub fn diff_claim_documents(
+    before: &ClaimDocumentV2,
+    after: &ClaimDocumentV2,
+    territories: &TerritoryMap,
+) -> ClaimDiff {
+    let before_owners = materialize_claim_owners(before, territories);
+    let after_owners = materialize_claim_owners(after, territories);
+    let mut owner_changes: Vec<ClaimOwnerChange> = after_owners
+        .into_iter()
+        .filter_map(|(territory, after_owner)| {
+            let before_owner = before_owners
+                .get(&territory)
+                .cloned()
+                .unwrap_or(ClaimOwner::Neutral);
+            (before_owner.identity_key() != after_owner.identity_key()).then_some(
+                ClaimOwnerChange {
+                    territory,
+                    before: before_owner,
+                    after: after_owner,
+                },
+            )
+        })
+        .collect();
+    owner_changes.sort_by(|a, b| a.territory.cmp(&b.territory));
+
+    let override_resources = |document: &ClaimDocumentV2, territory: &str| {
+        document
+            .territory_state_overrides
+            .get(territory)
+            .and_then(|state| state.resources.clone())
+    };
+    let mut resource_changes: Vec<ClaimResourceOverrideChange> = territories
+        .keys()
+        .filter_map(|territory| {
+            let before_resources = override_resources(before, territory);
+            let after_resources = override_resources(after, territory);
+            (before_resources != after_resources).then(|| ClaimResourceOverrideChange {
+                territory: territory.clone(),
+                before: before_resources,
+                after: after_resources,
+            })
+        })
+        .collect();
+    resource_changes.sort_by(|a, b| a.territory.cmp(&b.territory));
+
+    let keyed = |metrics: ClaimMetrics| -> HashMap<String, ClaimGuildMetrics> {
+        metrics
+            .guilds
+            .into_iter()
+            .filter_map(|guild| Some((guild.owner.identity_key()?, guild)))
+            .collect()
+    };
+    let mut before_guilds = keyed(compute_claim_metrics(before, territories));
+    let after_guilds = keyed(compute_claim_metrics(after, territories));
+    let mut guilds = Vec::new();
+    for (key, after_metrics) in after_guilds {
+        let before_metrics = before_guilds.remove(&key);
+        if before_metrics
+            .as_ref()
+            .is_some_and(|before_metrics| same_guild_metrics(before_metrics, &after_metrics))
+        {
+            continue;
+        }
+        guilds.push(ClaimGuildMetricsDelta {
+            owner: after_metrics.owner.clone(),
+            territory_delta: i64::from(after_metrics.territory_count)
+                - before_metrics
+                    .as_ref()
+                    .map_or(0, |metrics| i64::from(metrics.territory_count)),
+            before: before_metrics,
+            after: Some(after_metrics),
+        });
+    }
+    guilds.extend(
+        before_guilds
+            .into_values()
+            .map(|before_metrics| ClaimGuildMetricsDelta {
+                owner: before_metrics.owner.clone(),
+                territory_delta: -i64::from(before_metrics.territory_count),
+                before: Some(before_metrics),
+                after: None,
+            }),
+    );
+    guilds.sort_by(|a, b| {
+        b.territory_delta
+            .abs()
+            .cmp(&a.territory_delta.abs())
+            .then_with(|| a.owner.display_name().cmp(b.owner.display_name()))
+    });
+
+    ClaimDiff {
+        owner_changes,
+        resource_changes,
+        guilds,
+    }
+}
+
+fn same_guild_metrics(a: &ClaimGuildMetrics, b: &ClaimGuildMetrics) -> bool {
+    a.territory_count == b.territory_count
+        && a.resources == b.resources
+        && a.top_by_connections == b.top_by_connections
+        && a.top_by_externals == b.top_by_externals
+}
+
 fn build_hub_metrics(
     territory_name: &str,
     territory: &crate::territory::Territory,
@@ -610,7 +1033,7 @@ mod tests {
 
     #[test]
     fn validate_claim_document_rejects_unknown_territories_and_duplicate_macro_ids() {
-        let mut document = ClaimDocumentV1::blank();
+        let mut document = ClaimDocumentV2::blank();
         document
             .overrides
             .insert("Missing".to_string(), ClaimOwner::Neutral);
@@ -643,7 +1066,7 @@ mod tests {
     #[test]
     fn materialize_claim_owners_uses_blank_or_frozen_base_and_overrides() {
         let territories = sample_map();
-        let mut document = ClaimDocumentV1::blank();
+        let mut document = ClaimDocumentV2::blank();
         document
             .overrides
             .insert("A".to_string(), guild("g1", "Alpha", "ALP"));
@@ -654,7 +1077,7 @@ mod tests {
 
         let mut frozen_owners = HashMap::new();
         frozen_owners.insert("B".to_string(), guild("g2", "Beta", "BET"));
-        let mut frozen = ClaimDocumentV1::frozen_live(None, 12, frozen_owners);
+        let mut frozen = ClaimDocumentV2::frozen_live(None, 12, frozen_owners);
         frozen
             .overrides
             .insert("A".to_string(), guild("g1", "Alpha", "ALP"));
@@ -668,7 +1091,7 @@ mod tests {
         let territories = sample_map();
         let mut owners = HashMap::new();
         owners.insert("A".to_string(), guild("g1", "Alpha", "ALP"));
-        let mut document = ClaimDocumentV1::frozen_live(None, 1, owners);
+        let mut document = ClaimDocumentV2::frozen_live(None, 1, owners);
         document
             .overrides
             .insert("A".to_string(), guild("g1", "Alpha", "ALP"));
@@ -689,7 +1112,7 @@ mod tests {
         owners.insert("B".to_string(), guild("g1", "Alpha", "ALP"));
         owners.insert("C".to_string(), guild("g1", "Alpha", "ALP"));
         owners.insert("D".to_string(), guild("g2", "Beta", "BET"));
-        let document = ClaimDocumentV1::frozen_live(None, 7, owners);
+        let document = ClaimDocumentV2::frozen_live(None, 7, owners);
 
         let metrics = compute_claim_metrics(&document, &territories);
         assert_eq!(metrics.total_territories, 4);
@@ -718,4 +1141,196 @@ mod tests {
             Some(2)
         );
     }
+
+    #[test]
+    fn v1_documents_upgrade_losslessly_to_v2() {
+        let mut owners = HashMap::new();
+        owners.insert("B".to_string(), guild("g2", "Beta", "BET"));
+        let mut v1 = ClaimDocumentV1::frozen_live(Some("Plan".to_string()), 3, owners);
+        v1.overrides
+            .insert("A".to_string(), guild("g1", "Alpha", "ALP"));
+        v1.territory_state_overrides.insert(
+            "A".to_string(),
+            ClaimTerritoryStateOverride {
+                resources: Some(Resources {
+                    ore: 10,
+                    ..Resources::default()
+                }),
+            },
+        );
+        v1.macros.push(ClaimMacro {
+            id: "m".to_string(),
+            name: "North".to_string(),
+            territories: vec!["A".to_string()],
+        });
+
+        let encoded = serde_json::to_string(&v1).expect("serialize v1");
+        let decoded: ClaimDocumentV2 = serde_json::from_str(&encoded).expect("deserialize as v2");
+        let upgraded = ClaimDocumentV2::from(v1.clone());
+        assert_eq!(decoded, upgraded);
+        assert_eq!(decoded.version, CLAIM_DOCUMENT_VERSION_V2);
+        assert_eq!(decoded.overrides, v1.overrides);
+        assert_eq!(
+            decoded.territory_state_overrides,
+            v1.territory_state_overrides
+        );
+        assert_eq!(decoded.macros, v1.macros);
+        assert!(validate_claim_document(&decoded, ["A", "B", "C", "D"]).is_ok());
+
+        let round_trip: ClaimDocumentV2 =
+            serde_json::from_str(&serde_json::to_string(&decoded).expect("serialize v2"))
+                .expect("deserialize v2");
+        assert_eq!(round_trip, decoded);
+
+        let mut future = serde_json::to_value(&decoded).expect("value");
+        future["version"] = serde_json::json!(9);
+        let future: ClaimDocumentV2 = serde_json::from_value(future).expect("deserialize");
+        assert_eq!(
+            validate_claim_document(&future, ["A", "B"]),
+            Err(ClaimValidationError::UnsupportedVersion(9))
+        );
+    }
+
+    #[test]
+    fn validate_claim_document_checks_hqs_towers_and_annotations() {
+        let names = ["A", "B", "C", "D"];
+        let alpha = guild("g1", "Alpha", "ALP");
+        let alpha_key = alpha.identity_key().unwrap();
+        let mut document = ClaimDocumentV2::blank();
+        document.overrides.insert("A".to_string(), alpha.clone());
+        document.hqs.insert(alpha_key.clone(), "A".to_string());
+        document.tower_levels.insert(
+            "A".to_string(),
+            ClaimTowerLevels {
+                damage: 11,
+                aura: 3,
+                ..ClaimTowerLevels::default()
+            },
+        );
+        document.annotations = vec![
+            ClaimAnnotation::Note {
+                territory: "A".to_string(),
+                text: "Hold at all costs".to_string(),
+            },
+            ClaimAnnotation::Arrow {
+                from: "B".to_string(),
+                to: "A".to_string(),
+                label: Some("push".to_string()),
+            },
+        ];
+        assert_eq!(validate_claim_document(&document, names), Ok(()));
+        assert_eq!(document.hq_for(&alpha), Some("A"));
+
+        let mut unowned = document.clone();
+        unowned.hqs.insert(alpha_key.clone(), "B".to_string());
+        assert_eq!(
+            validate_claim_document(&unowned, names),
+            Err(ClaimValidationError::HqNotOwned("B".to_string()))
+        );
+        unowned.retain_owned_hqs();
+        assert!(unowned.hqs.is_empty());
+
+        let mut towers = document.clone();
+        towers.tower_levels.insert(
+            "B".to_string(),
+            ClaimTowerLevels {
+                volley: 4,
+                ..ClaimTowerLevels::default()
+            },
+        );
+        assert_eq!(
+            validate_claim_document(&towers, names),
+            Err(ClaimValidationError::InvalidTowerLevels("B".to_string()))
+        );
+
+        let mut arrows = document.clone();
+        arrows.annotations.push(ClaimAnnotation::Arrow {
+            from: "C".to_string(),
+            to: "C".to_string(),
+            label: None,
+        });
+        assert_eq!(
+            validate_claim_document(&arrows, names),
+            Err(ClaimValidationError::InvalidAnnotation(2))
+        );
+
+        let mut notes = document;
+        notes.annotations.push(ClaimAnnotation::Note {
+            territory: "Missing".to_string(),
+            text: "?".to_string(),
+        });
+        assert_eq!(
+            validate_claim_document(&notes, names),
+            Err(ClaimValidationError::UnknownTerritory(
+                "Missing".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn diff_claim_documents_reports_owner_resource_and_guild_changes() {
+        let territories = sample_map();
+        let mut owners = HashMap::new();
+        owners.insert("A".to_string(), guild("g1", "Alpha", "ALP"));
+        owners.insert("B".to_string(), guild("g1", "Alpha", "ALP"));
+        owners.insert("D".to_string(), guild("g2", "Beta", "BET"));
+        let live = ClaimDocumentV2::frozen_live(None, 3, owners);
+
+        let mut plan = live.clone();
+        plan.overrides
+            .insert("B".to_string(), guild("g2", "Beta", "BET"));
+        plan.overrides
+            .insert("C".to_string(), guild("g3", "Gamma", "GAM"));
+        plan.territory_state_overrides.insert(
+            "D".to_string(),
+            ClaimTerritoryStateOverride {
+                resources: Some(Resources {
+                    wood: 3_600,
+                    ..Resources::default()
+                }),
+            },
+        );
+
+        assert!(diff_claim_documents(&live, &live, &territories).is_empty());
+
+        let diff = diff_claim_documents(&live, &plan, &territories);
+        let changed: Vec<&str> = diff
+            .owner_changes
+            .iter()
+            .map(|change| change.territory.as_str())
+            .collect();
+        assert_eq!(changed, vec!["B", "C"]);
+        assert_eq!(diff.owner_changes[1].before, ClaimOwner::Neutral);
+        assert_eq!(diff.resource_changes.len(), 1);
+        assert!(diff.resource_changes[0].before.is_none());
+        assert_eq!(
+            diff.changed_territories(),
+            HashSet::from(["B".to_string(), "C".to_string(), "D".to_string()])
+        );
+
+        let delta = |name: &str| {
+            diff.guilds
+                .iter()
+                .find(|entry| entry.owner.display_name() == name)
+                .map(|entry| entry.territory_delta)
+        };
+        assert_eq!(delta("Alpha"), Some(-1));
+        assert_eq!(delta("Beta"), Some(1));
+        assert_eq!(delta("Gamma"), Some(1));
+        let gamma = diff
+            .guilds
+            .iter()
+            .find(|entry| entry.owner.display_name() == "Gamma")
+            .unwrap();
+        assert!(gamma.before.is_none());
+
+        let reverse = diff_claim_documents(&plan, &live, &territories);
+        assert_eq!(reverse.owner_changes.len(), 2);
+        assert!(
+            reverse
+                .guilds
+                .iter()
+                .any(|entry| entry.owner.display_name() == "Gamma" && entry.after.is_none())
+        );
+    }
 }
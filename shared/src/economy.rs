@@ -0,0 +1,270 @@
+use crate::resource_routing::RoutedResource;
+use crate::territory::{Resources, Territory, TerritoryMap};
+use crate::tower::{AURA_LABELS, DAMAGES, VOLLEY_LABELS};
+use crate::treasury::TreasuryLevel;
+
+// Upkeep per level as listed in the in-game Territory Management upgrade menu (mirrored by the
+// Wynncraft wiki's guild territory upgrade table). Table lengths follow the `tower` stat tables so
+// the two cannot drift apart on level count.
+
+/// Hourly cost per damage/attack/health/defense upgrade level, indexed like `tower::DAMAGES`.
+pub const TOWER_STAT_UPGRADE_COSTS: [i32; DAMAGES.len()] = [
+    0, 100, 300, 600, 1200, 2400, 4800, 8400, 12000, 15600, 19200, 22800,
+];
+
+/// Hourly crop cost per Aura level, indexed like `tower::AURA_LABELS` (0 = off).
+pub const AURA_UPGRADE_COSTS: [i32; AURA_LABELS.len()] = [0, 800, 1600, 3200];
+
+/// Hourly ore cost per Volley level, indexed like `tower::VOLLEY_LABELS` (0 = off).
+pub const VOLLEY_UPGRADE_COSTS: [i32; VOLLEY_LABELS.len()] = [0, 200, 400, 800];
+
+const MAX_STAT_LEVEL: u32 = TOWER_STAT_UPGRADE_COSTS.len() as u32 - 1;
+const MAX_AURA_LEVEL: u32 = AURA_UPGRADE_COSTS.len() as u32 - 1;
+const MAX_VOLLEY_LEVEL: u32 = VOLLEY_UPGRADE_COSTS.len() as u32 - 1;
+
+/// Upgrade levels of one territory's tower and bonuses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TowerUpgrades {
+    pub damage: u32,
+    pub attack: u32,
+    pub health: u32,
+    pub defense: u32,
+    pub aura: u32,
+    pub volley: u32,
+}
+
+impl TowerUpgrades {
+    pub const MAX: Self = Self {
+        damage: MAX_STAT_LEVEL,
+        attack: MAX_STAT_LEVEL,
+        health: MAX_STAT_LEVEL,
+        defense: MAX_STAT_LEVEL,
+        aura: MAX_AURA_LEVEL,
+        volley: MAX_VOLLEY_LEVEL,
+    };
+
+    /// Same level on all four tower stats, no bonuses.
+    pub fn uniform(level: u32) -> Self {
+        Self {
+            damage: level,
+            attack: level,
+            health: level,
+            defense: level,
+            aura: 0,
+            volley: 0,
+        }
+    }
+
+    /// Hourly resource upkeep: damage and Volley cost ore, attack and Aura cost crops, health
+    /// costs wood and defense costs fish.
+    pub fn hourly_cost(&self) -> Resources {
+        let stat = |level: u32| TOWER_STAT_UPGRADE_COSTS[level.min(MAX_STAT_LEVEL) as usize];
+        Resources {
+            emeralds: 0,
+            ore: stat(self.damage)
+                + VOLLEY_UPGRADE_COSTS[self.volley.min(MAX_VOLLEY_LEVEL) as usize],
+            crops: stat(self.attack) + AURA_UPGRADE_COSTS[self.aura.min(MAX_AURA_LEVEL) as usize],
+            fish: stat(self.defense),
+            wood: stat(self.health),
+        }
+    }
+}
+
+/// Treasury level of a territory: the ingest-reported tier, else derived from hold time.
+pub fn territory_treasury(territory: &Territory, now_secs: i64) -> TreasuryLevel {
+    territory
+        .runtime
+        .as_ref()
+        .and_then(|rt| rt.treasury.as_deref())
+        .and_then(TreasuryLevel::from_api_tier)
+        .unwrap_or_else(|| {
+            TreasuryLevel::from_held_seconds((now_secs - territory.acquired.timestamp()).max(0))
+        })
+}
+
+/// Hourly production with the treasury buff applied.
+pub fn treasury_production(base: &Resources, level: TreasuryLevel) -> Resources {
+    let buff = |amount: i32| amount * (100 + i32::from(level.buff_percent())) / 100;
+    Resources {
+        emeralds: buff(base.emeralds),
+        ore: buff(base.ore),
+        crops: buff(base.crops),
+        fish: buff(base.fish),
+        wood: buff(base.wood),
+    }
+}
+
+/// Guild-wide hourly production against tower upkeep.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EconomyBalance {
+    pub territories: u32,
+    pub production: Resources,
+    pub upkeep: Resources,
+}
+
+impl EconomyBalance {
+    /// Per-resource surplus (positive) or deficit (negative).
+    pub fn balance(&self) -> Resources {
+        Resources {
+            emeralds: self.production.emeralds - self.upkeep.emeralds,
+            ore: self.production.ore - self.upkeep.ore,
+            crops: self.production.crops - self.upkeep.crops,
+            fish: self.production.fish - self.upkeep.fish,
+            wood: self.production.wood - self.upkeep.wood,
+        }
+    }
+
+    /// Resources the guild cannot cover, with the hourly shortfall.
+    pub fn deficits(&self) -> Vec<(RoutedResource, i32)> {
+        let balance = self.balance();
+        RoutedResource::ALL
+            .into_iter()
+            .filter_map(|resource| {
+                let amount = resource.amount(&balance);
+                (amount < 0).then_some((resource, -amount))
+            })
+            .collect()
+    }
+
+    pub fn is_affordable(&self) -> bool {
+        self.deficits().is_empty()
+    }
+}
+
+/// Sum treasury-buffed production and upgrade upkeep over every territory of `guild_name`.
+///
+/// Ingest `production_rates` are used as-is since they already include the treasury buff.
+/// Returns `None` when the guild owns nothing.
+pub fn guild_economy(
+    territories: &TerritoryMap,
+    guild_name: &str,
+    now_secs: i64,
+    upgrades_for: impl Fn(&str) -> TowerUpgrades,
+) -> Option<EconomyBalance> {
+    let mut economy = EconomyBalance::default();
+    for (name, territory) in territories {
+        if !territory.guild.name.eq_ignore_ascii_case(guild_name) {
+            continue;
+        }
+        economy.territories += 1;
+        let production = territory
+            .runtime
+            .as_ref()
+            .and_then(|rt| rt.production_rates.clone())
+            .unwrap_or_else(|| {
+                treasury_production(
+                    &territory.resources,
+                    territory_treasury(territory, now_secs),
+                )
+            });
+        let upkeep = upgrades_for(name).hourly_cost();
+        for resource in RoutedResource::ALL {
+            *resource.amount_mut(&mut economy.production) += resource.amount(&production);
+            *resource.amount_mut(&mut economy.upkeep) += resource.amount(&upkeep);
+        }
+    }
+    (economy.territories > 0).then_some(economy)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{
+        AURA_UPGRADE_COSTS, MAX_STAT_LEVEL, TOWER_STAT_UPGRADE_COSTS, TowerUpgrades,
+        VOLLEY_UPGRADE_COSTS, guild_economy, treasury_production,
+    };
+    use crate::resource_routing::RoutedResource;
+    use crate::territory::{Resources, Territory, TerritoryMap};
+    use crate::test_support::territory;
+    use crate::treasury::TreasuryLevel;
+
+    const DAY: i64 = 86_400;
+
+    fn holding(guild: &str, acquired_secs: i64, resources: Resources) -> Territory {
+        Territory {
+            resources,
+            ..territory(guild, &[], acquired_secs)
+        }
+    }
+
+    #[test]
+    fn upgrade_costs_map_to_their_resources() {
+        assert_eq!(TowerUpgrades::default().hourly_cost(), Resources::default());
+        let cost = TowerUpgrades::MAX.hourly_cost();
+        assert_eq!(cost.ore, 22800 + 800);
+        assert_eq!(cost.crops, 22800 + 3200);
+        assert_eq!(cost.fish, 22800);
+        assert_eq!(cost.wood, 22800);
+        assert_eq!(cost.emeralds, 0);
+        assert_eq!(TowerUpgrades::uniform(3).hourly_cost().ore, 600);
+    }
+
+    #[test]
+    fn upgrade_cost_tables_start_free_and_rise_per_level() {
+        for table in [
+            &TOWER_STAT_UPGRADE_COSTS[..],
+            &AURA_UPGRADE_COSTS[..],
+            &VOLLEY_UPGRADE_COSTS[..],
+        ] {
+            assert_eq!(table[0], 0);
+            assert!(table.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+        assert_eq!(
+            TowerUpgrades::MAX.damage as usize,
+            crate::tower::DAMAGES.len() - 1
+        );
+        assert_eq!(
+            TowerUpgrades::uniform(99).hourly_cost(),
+            TowerUpgrades::uniform(MAX_STAT_LEVEL).hourly_cost()
+        );
+    }
+
+    #[test]
+    fn treasury_buff_scales_production() {
+        let base = Resources {
+            emeralds: 9000,
+            ore: 3600,
+            ..Default::default()
+        };
+        let buffed = treasury_production(&base, TreasuryLevel::VeryHigh);
+        assert_eq!((buffed.emeralds, buffed.ore), (11700, 4680));
+        assert_eq!(treasury_production(&base, TreasuryLevel::VeryLow), base);
+    }
+
+    #[test]
+    fn guild_economy_reports_deficits_against_upkeep() {
+        let now = 20 * DAY;
+        let ore = Resources {
+            emeralds: 9000,
+            ore: 3600,
+            ..Default::default()
+        };
+        let mut map: TerritoryMap = HashMap::new();
+        // Held 15 days (Very High, +30%) and 2 days (Medium, +20%).
+        map.insert("A".to_string(), holding("Alpha", 5 * DAY, ore.clone()));
+        map.insert("B".to_string(), holding("Alpha", 18 * DAY, ore));
+        map.insert("C".to_string(), holding("Beta", 0, Resources::default()));
+
+        let idle = guild_economy(&map, "alpha", now, |_| TowerUpgrades::default()).unwrap();
+        assert_eq!(idle.territories, 2);
+        assert_eq!(idle.production.ore, 4680 + 4320);
+        assert!(idle.is_affordable());
+
+        let maxed = guild_economy(&map, "Alpha", now, |name| {
+            if name == "A" {
+                TowerUpgrades::MAX
+            } else {
+                TowerUpgrades::uniform(1)
+            }
+        })
+        .unwrap();
+        assert_eq!(maxed.balance().ore, 9000 - 23600 - 100);
+        let deficits = maxed.deficits();
+        assert_eq!(deficits[0], (RoutedResource::Ore, 14700));
+        assert_eq!(deficits.len(), 4);
+        assert!(!maxed.is_affordable());
+
+        assert!(guild_economy(&map, "Gamma", now, |_| TowerUpgrades::default()).is_none());
+    }
+}
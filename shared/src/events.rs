@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::ingest::TerritoryRuntimeChange;
+use crate::ingest::{TerritoryRuntimeChange, WarEvent};
 use crate::territory::{GuildRef, Region, Resources, TerritoryMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +24,11 @@ pub enum TerritoryEvent {
         updates: Vec<TerritoryRuntimeChange>,
         timestamp: String,
     },
+    /// Unsequenced war transitions (queued/started/ended/captured).
+    War {
+        events: Vec<WarEvent>,
+        timestamp: String,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
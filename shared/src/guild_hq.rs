@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::territory::TerritoryMap;
+use crate::tower::{
+    HQ_BASE_INDEX_BONUS, HQ_CONNECTION_INDEX_BONUS, HQ_EXTERNAL_INDEX_BONUS,
+    count_guild_connections,
+};
+
+/// Extra score given to a guild's previously known HQ so estimates do not flap between
+/// near-equal candidates.
+pub const HQ_STICKY_SCORE_BONUS: u32 = 6;
+
+/// Confidence floor for an inferred HQ with a close runner-up.
+const INFERRED_CONFIDENCE_FLOOR: f64 = 0.35;
+
+/// Confidence cap for an inferred HQ with more than one candidate.
+const INFERRED_CONFIDENCE_CAP: f64 = 0.95;
+
+/// Where a guild HQ estimate came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuildHqSource {
+    /// Reported by ingest runtime data.
+    Observed,
+    /// Scored from the public territory map.
+    Inferred,
+}
+
+impl GuildHqSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Observed => "observed",
+            Self::Inferred => "inferred",
+        }
+    }
+
+    pub fn from_str_opt(value: &str) -> Option<Self> {
+        match value {
+            "observed" => Some(Self::Observed),
+            "inferred" => Some(Self::Inferred),
+            _ => None,
+        }
+    }
+}
+
+/// Most likely HQ territory for one guild.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuildHqEstimate {
+    pub guild_uuid: String,
+    pub guild_name: String,
+    pub guild_prefix: String,
+    pub territory: String,
+    /// 0.0–1.0; observed HQs are always 1.0.
+    pub confidence: f64,
+    pub source: GuildHqSource,
+    /// Guild-owned direct connections of the HQ territory.
+    pub connections: u32,
+    /// Guild-owned territories within 3 hops of the HQ territory.
+    pub externals: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runner_up: Option<String>,
+}
+
+/// One recorded change of a guild's HQ.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuildHqMove {
+    pub recorded_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_territory: Option<String>,
+    pub to_territory: String,
+    pub confidence: f64,
+    pub source: GuildHqSource,
+}
+
+/// HQ defense-index bonus a territory would get as the guild HQ.
+///
+/// Guilds put their HQ where this is highest, so it doubles as the inference score.
+pub fn hq_candidate_score(connections: u32, externals: u32) -> u32 {
+    HQ_BASE_INDEX_BONUS
+        + HQ_CONNECTION_INDEX_BONUS * connections
+        + HQ_EXTERNAL_INDEX_BONUS * externals
+}
+
+struct Candidate<'a> {
+    name: &'a str,
+    score: u32,
+    connections: u32,
+    externals: u32,
+    acquired: i64,
+}
+
+/// Estimate every guild's HQ from the territory map.
+///
+/// Ingest runtime data wins when present: a territory flagged `headquarters: true` (or named by
+/// `headquarters_territory`) is reported as observed, and territories flagged `false` are never
+/// candidates. Otherwise each owned territory is scored with [`hq_candidate_score`], with
+/// `previous` (lowercased guild name -> territory) adding [`HQ_STICKY_SCORE_BONUS`]. Ties prefer
+/// the territory held longest.
+pub fn infer_guild_hqs(
+    territories: &TerritoryMap,
+    previous: &HashMap<String, String>,
+) -> Vec<GuildHqEstimate> {
+    let lookup = |name: &str| {
+        territories
+            .get(name)
+            .map(|t| (t.guild.uuid.as_str(), t.connections.as_slice()))
+    };
+
+    let mut by_guild: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (name, territory) in territories {
+        if territory.guild.uuid.is_empty() || territory.guild.name.is_empty() {
+            continue;
+        }
+        by_guild
+            .entry(territory.guild.uuid.as_str())
+            .or_default()
+            .push(name.as_str());
+    }
+
+    let mut estimates = Vec::with_capacity(by_guild.len());
+    for (guild_uuid, owned) in by_guild {
+        let guild = &territories[owned[0]].guild;
+        let previous_hq = previous
+            .get(&guild.name.to_ascii_lowercase())
+            .map(String::as_str);
+
+        let observed = owned.iter().copied().find(|name| {
+            territories[*name]
+                .runtime
+                .as_ref()
+                .and_then(|rt| rt.headquarters)
+                == Some(true)
+        });
+        let observed = observed.or_else(|| {
+            owned.iter().find_map(|name| {
+                let named = territories[*name]
+                    .runtime
+                    .as_ref()?
+                    .headquarters_territory
+                    .as_deref()?;
+                owned.iter().copied().find(|candidate| *candidate == named)
+            })
+        });
+
+        let mut candidates: Vec<Candidate<'_>> = owned
+            .iter()
+            .copied()
+            .filter(|name| {
+                observed.is_some_and(|hq| hq == *name)
+                    || territories[*name]
+                        .runtime
+                        .as_ref()
+                        .and_then(|rt| rt.headquarters)
+                        != Some(false)
+            })
+            .map(|name| {
+                let territory = &territories[name];
+                let (connections, _, externals) =
+                    count_guild_connections(name, &territory.connections, guild_uuid, lookup);
+                let mut score = hq_candidate_score(connections, externals);
+                if previous_hq == Some(name) {
+                    score += HQ_STICKY_SCORE_BONUS;
+                }
+                Candidate {
+                    name,
+                    score,
+                    connections,
+                    externals,
+                    acquired: territory.acquired.timestamp(),
+                }
+            })
+            .collect();
+        if candidates.is_empty() {
+            continue;
+        }
+        candidates.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.acquired.cmp(&b.acquired))
+                .then_with(|| a.name.cmp(b.name))
+        });
+
+        let (best, source, confidence) = match observed {
+            Some(hq) => {
+                let best = candidates
+                    .iter()
+                    .position(|candidate| candidate.name == hq)
+                    .unwrap_or(0);
+                (best, GuildHqSource::Observed, 1.0)
+            }
+            None => (
+                0,
+                GuildHqSource::Inferred,
+                inferred_confidence(candidates[0].score, candidates.get(1).map(|c| c.score)),
+            ),
+        };
+        let runner_up = candidates
+            .iter()
+            .enumerate()
+            .find(|(idx, _)| *idx != best)
+            .map(|(_, candidate)| candidate.name.to_string());
+        let best = &candidates[best];
+        estimates.push(GuildHqEstimate {
+            guild_uuid: guild.uuid.clone(),
+            guild_name: guild.name.clone(),
+            guild_prefix: guild.prefix.clone(),
+            territory: best.name.to_string(),
+            confidence,
+            source,
+            connections: best.connections,
+            externals: best.externals,
+            runner_up,
+        });
+    }
+    estimates.sort_by(|a, b| a.guild_name.cmp(&b.guild_name));
+    estimates
+}
+
+/// Confidence from the score margin over the runner-up; a sole candidate is certain.
+fn inferred_confidence(best: u32, runner_up: Option<u32>) -> f64 {
+    let Some(runner_up) = runner_up else {
+        return 1.0;
+    };
+    let best = f64::from(best.max(1));
+    let margin = (best - f64::from(runner_up)).max(0.0) / best;
+    (INFERRED_CONFIDENCE_FLOOR + margin * 1.5)
+        .clamp(INFERRED_CONFIDENCE_FLOOR, INFERRED_CONFIDENCE_CAP)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{GuildHqSource, infer_guild_hqs};
+    use crate::ingest::TerritoryRuntimeData;
+    use crate::territory::TerritoryMap;
+    use crate::test_support::territory;
+
+    /// A - B - C chain owned by Alpha, plus D owned by Beta hanging off C.
+    fn chain() -> TerritoryMap {
+        let mut map = HashMap::new();
+        map.insert("A".to_string(), territory("Alpha", &["B"], 100));
+        map.insert("B".to_string(), territory("Alpha", &["A", "C"], 200));
+        map.insert("C".to_string(), territory("Alpha", &["B", "D"], 300));
+        map.insert("D".to_string(), territory("Beta", &["C"], 400));
+        map
+    }
+
+    #[test]
+    fn best_connected_territory_is_inferred_with_margin_confidence() {
+        let estimates = infer_guild_hqs(&chain(), &HashMap::new());
+        let alpha = estimates.iter().find(|e| e.guild_name == "Alpha").unwrap();
+        assert_eq!(alpha.territory, "B");
+        assert_eq!(alpha.source, GuildHqSource::Inferred);
+        assert_eq!((alpha.connections, alpha.externals), (2, 2));
+        assert!(alpha.confidence > 0.35 && alpha.confidence < 0.95);
+
+        let beta = estimates.iter().find(|e| e.guild_name == "Beta").unwrap();
+        assert_eq!(beta.territory, "D");
+        assert_eq!(beta.confidence, 1.0);
+        assert!(beta.runner_up.is_none());
+    }
+
+    #[test]
+    fn observed_runtime_flags_override_and_exclude_candidates() {
+        let mut map = chain();
+        map.get_mut("B").unwrap().runtime = Some(TerritoryRuntimeData {
+            headquarters: Some(false),
+            ..Default::default()
+        });
+        let alpha = infer_guild_hqs(&map, &HashMap::new())
+            .into_iter()
+            .find(|e| e.guild_name == "Alpha")
+            .unwrap();
+        // A and C tie on score; A has been held longer.
+        assert_eq!(alpha.territory, "A");
+        assert_eq!(alpha.source, GuildHqSource::Inferred);
+
+        map.get_mut("A").unwrap().runtime = Some(TerritoryRuntimeData {
+            headquarters_territory: Some("C".to_string()),
+            ..Default::default()
+        });
+        let alpha = infer_guild_hqs(&map, &HashMap::new())
+            .into_iter()
+            .find(|e| e.guild_name == "Alpha")
+            .unwrap();
+        assert_eq!(alpha.territory, "C");
+        assert_eq!(alpha.source, GuildHqSource::Observed);
+        assert_eq!(alpha.confidence, 1.0);
+    }
+
+    #[test]
+    fn previous_hq_is_sticky_between_close_candidates() {
+        let mut map = chain();
+        map.get_mut("B").unwrap().runtime = Some(TerritoryRuntimeData {
+            headquarters: Some(false),
+            ..Default::default()
+        });
+        let previous = HashMap::from([("alpha".to_string(), "C".to_string())]);
+        let alpha = infer_guild_hqs(&map, &previous)
+            .into_iter()
+            .find(|e| e.guild_name == "Alpha")
+            .unwrap();
+        assert_eq!(alpha.territory, "C");
+        assert_eq!(alpha.runner_up.as_deref(), Some("A"));
+    }
+}
@@ -137,3 +137,253 @@ pub struct HistoryHeat {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub entries: Vec<HistoryHeatEntry>,
 }
+
+/// One contiguous span during which a single guild held a territory.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TerritoryOwnershipInterval {
+    pub guild_uuid: String,
+    pub guild_name: String,
+    pub guild_prefix: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_color: Option<(u8, u8, u8)>,
+    pub start: String,
+    /// `None` while the guild still held the territory at the end of the window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    pub duration_secs: i64,
+}
+
+/// Ownership timeline for a single territory over a time window, oldest interval first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerritoryOwnershipHistory {
+    pub territory: String,
+    pub from: String,
+    pub to: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub intervals: Vec<TerritoryOwnershipInterval>,
+}
+
+/// Bucket width for guild history time series.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryBucket {
+    Hour,
+    Day,
+}
+
+impl HistoryBucket {
+    pub fn seconds(self) -> i64 {
+        match self {
+            Self::Hour => 3600,
+            Self::Day => 86_400,
+        }
+    }
+}
+
+/// Territory count for one guild over a single bucket.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GuildTerritoryCountPoint {
+    /// Bucket start.
+    pub timestamp: String,
+    /// Count at the end of the bucket (or at the window end for the last bucket).
+    pub territory_count: u32,
+    pub min_count: u32,
+    pub max_count: u32,
+}
+
+/// Whether a ledger entry added or removed a territory from the guild.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GuildLedgerDirection {
+    Gained,
+    Lost,
+}
+
+/// One territory gained or lost by the guild, with the guild on the other side.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GuildLedgerEntry {
+    pub stream_seq: u64,
+    pub timestamp: String,
+    pub territory: String,
+    pub direction: GuildLedgerDirection,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counterpart_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counterpart_prefix: Option<String>,
+}
+
+/// Map footprint of one guild over a time window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildHistory {
+    pub guild_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_prefix: Option<String>,
+    pub from: String,
+    pub to: String,
+    pub bucket: HistoryBucket,
+    pub start_count: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub series: Vec<GuildTerritoryCountPoint>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ledger: Vec<GuildLedgerEntry>,
+}
+
+/// Directed takeover tally: how often `attacker` took territories from `defender`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryRivalryEntry {
+    pub attacker_name: String,
+    pub attacker_prefix: String,
+    pub defender_name: String,
+    pub defender_prefix: String,
+    pub take_count: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub territories: Vec<String>,
+}
+
+/// Sparse guild-vs-guild takeover matrix for a time window, largest tallies first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryRivalries {
+    /// `None` when the window was given explicitly via `from`/`to`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<HistoryHeatSource>,
+    pub season_id: Option<i32>,
+    pub from: String,
+    pub to: String,
+    pub fallback_applied: bool,
+    pub total_takes: u64,
+    pub has_more: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entries: Vec<HistoryRivalryEntry>,
+}
+
+/// Both directions of a rivalry folded together; `guild_a` sorts before `guild_b`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryRivalryPair {
+    pub guild_a_name: String,
+    pub guild_a_prefix: String,
+    pub guild_b_name: String,
+    pub guild_b_prefix: String,
+    pub a_took_from_b: u64,
+    pub b_took_from_a: u64,
+    pub territories: Vec<String>,
+}
+
+impl HistoryRivalryPair {
+    pub fn total(&self) -> u64 {
+        self.a_took_from_b + self.b_took_from_a
+    }
+}
+
+impl HistoryRivalries {
+    /// Fold directed entries into guild pairs, busiest first. Pairs that fought in both
+    /// directions rank ahead of one-sided pairs with the same total.
+    pub fn top_pairs(&self, limit: usize) -> Vec<HistoryRivalryPair> {
+        let mut pairs: HashMap<(String, String), HistoryRivalryPair> = HashMap::new();
+        for entry in &self.entries {
+            let attacker_first = entry.attacker_name <= entry.defender_name;
+            let (a_name, a_prefix, b_name, b_prefix) = if attacker_first {
+                (
+                    &entry.attacker_name,
+                    &entry.attacker_prefix,
+                    &entry.defender_name,
+                    &entry.defender_prefix,
+                )
+            } else {
+                (
+                    &entry.defender_name,
+                    &entry.defender_prefix,
+                    &entry.attacker_name,
+                    &entry.attacker_prefix,
+                )
+            };
+            let pair = pairs
+                .entry((a_name.clone(), b_name.clone()))
+                .or_insert_with(|| HistoryRivalryPair {
+                    guild_a_name: a_name.clone(),
+                    guild_a_prefix: a_prefix.clone(),
+                    guild_b_name: b_name.clone(),
+                    guild_b_prefix: b_prefix.clone(),
+                    a_took_from_b: 0,
+                    b_took_from_a: 0,
+                    territories: Vec::new(),
+                });
+            if attacker_first {
+                pair.a_took_from_b += entry.take_count;
+            } else {
+                pair.b_took_from_a += entry.take_count;
+            }
+            pair.territories.extend(entry.territories.iter().cloned());
+        }
+
+        let mut pairs: Vec<HistoryRivalryPair> = pairs
+            .into_values()
+            .map(|mut pair| {
+                pair.territories.sort();
+                pair.territories.dedup();
+                pair
+            })
+            .collect();
+        pairs.sort_by(|a, b| {
+            b.total()
+                .cmp(&a.total())
+                .then_with(|| {
+                    b.a_took_from_b
+                        .min(b.b_took_from_a)
+                        .cmp(&a.a_took_from_b.min(a.b_took_from_a))
+                })
+                .then_with(|| a.guild_a_name.cmp(&b.guild_a_name))
+                .then_with(|| a.guild_b_name.cmp(&b.guild_b_name))
+        });
+        pairs.truncate(limit);
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HistoryRivalries, HistoryRivalryEntry};
+
+    fn entry(
+        attacker: &str,
+        defender: &str,
+        take_count: u64,
+        territories: &[&str],
+    ) -> HistoryRivalryEntry {
+        HistoryRivalryEntry {
+            attacker_name: attacker.to_string(),
+            attacker_prefix: attacker[..3].to_string(),
+            defender_name: defender.to_string(),
+            defender_prefix: defender[..3].to_string(),
+            take_count,
+            territories: territories.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn top_pairs_folds_both_directions_and_prefers_mutual_fights() {
+        let rivalries = HistoryRivalries {
+            source: None,
+            season_id: None,
+            from: "2026-01-01T00:00:00Z".to_string(),
+            to: "2026-01-08T00:00:00Z".to_string(),
+            fallback_applied: false,
+            total_takes: 18,
+            has_more: false,
+            entries: vec![
+                entry("Sequoia", "Aequitas", 6, &["Detlas", "Ragni"]),
+                entry("Paladins", "Nerfuria", 6, &["Almuj"]),
+                entry("Aequitas", "Sequoia", 3, &["Ragni", "Nemract"]),
+                entry("Titans", "Nerfuria", 3, &["Cinfras"]),
+            ],
+        };
+
+        let pairs = rivalries.top_pairs(2);
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0].guild_a_name, "Aequitas");
+        assert_eq!(pairs[0].guild_b_name, "Sequoia");
+        assert_eq!((pairs[0].a_took_from_b, pairs[0].b_took_from_a), (3, 6));
+        assert_eq!(pairs[0].territories, vec!["Detlas", "Nemract", "Ragni"]);
+        assert_eq!(pairs[1].guild_a_name, "Nerfuria");
+        assert_eq!(pairs[1].b_took_from_a, 6);
+    }
+}
@@ -32,6 +32,10 @@ pub struct DataProvenance {
     pub menu_sr_per_hour: Option<i32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub menu_observed_at: Option<String>,
+    /// Devices backing each merged runtime field; `0` marks a value carried from the most recent
+    /// report without corroboration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub corroborated_fields: Option<HashMap<String, u16>>,
 }
 
 impl Default for DataProvenance {
@@ -46,6 +50,7 @@ impl Default for DataProvenance {
             menu_captured_territories: None,
             menu_sr_per_hour: None,
             menu_observed_at: None,
+            corroborated_fields: None,
         }
     }
 }
@@ -123,6 +128,13 @@ pub struct WarEvent {
     pub provenance: DataProvenance,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct WarHistory {
+    pub events: Vec<WarEvent>,
+    #[serde(default)]
+    pub has_more: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CanonicalTerritoryUpdate {
     pub territory: String,
@@ -1,11 +1,16 @@
 pub mod claims;
 pub mod colors;
+pub mod economy;
 pub mod events;
+pub mod guild_hq;
 pub mod history;
 pub mod ingest;
 pub mod map_intel;
+pub mod resource_routing;
 pub mod season_rating;
 pub mod territory;
+#[cfg(test)]
+mod test_support;
 pub mod tower;
 pub mod treasury;
 
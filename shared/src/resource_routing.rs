@@ -0,0 +1,402 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::guild_hq::infer_guild_hqs;
+use crate::territory::{Resources, TerritoryMap};
+
+/// Default tax taken by each foreign territory a resource passes through.
+pub const DEFAULT_FOREIGN_TAX_RATE: f64 = 0.05;
+
+/// Resources are moved one hop towards the HQ once per transfer interval.
+pub const RESOURCE_TRANSFER_INTERVAL_SECS: u32 = 60;
+
+const TRANSFERS_PER_HOUR: i64 = 3600 / RESOURCE_TRANSFER_INTERVAL_SECS as i64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoutedResource {
+    Emeralds,
+    Ore,
+    Crops,
+    Fish,
+    Wood,
+}
+
+impl RoutedResource {
+    pub const ALL: [Self; 5] = [
+        Self::Emeralds,
+        Self::Ore,
+        Self::Crops,
+        Self::Fish,
+        Self::Wood,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Emeralds => "Emeralds",
+            Self::Ore => "Ore",
+            Self::Crops => "Crops",
+            Self::Fish => "Fish",
+            Self::Wood => "Wood",
+        }
+    }
+
+    pub fn amount(self, resources: &Resources) -> i32 {
+        match self {
+            Self::Emeralds => resources.emeralds,
+            Self::Ore => resources.ore,
+            Self::Crops => resources.crops,
+            Self::Fish => resources.fish,
+            Self::Wood => resources.wood,
+        }
+    }
+
+    pub(crate) fn amount_mut(self, resources: &mut Resources) -> &mut i32 {
+        match self {
+            Self::Emeralds => &mut resources.emeralds,
+            Self::Ore => &mut resources.ore,
+            Self::Crops => &mut resources.crops,
+            Self::Fish => &mut resources.fish,
+            Self::Wood => &mut resources.wood,
+        }
+    }
+}
+
+/// Shortest route from one owned territory to the guild HQ.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceRoute {
+    pub territory: String,
+    /// Territories from `territory` to the HQ, both inclusive.
+    pub path: Vec<String>,
+    pub hops: u32,
+    /// Intermediate territories on the path owned by someone else.
+    pub foreign_hops: u32,
+    /// Share of the production that reaches the HQ after foreign taxes.
+    pub delivered_fraction: f64,
+    /// Hourly production of `territory`.
+    pub production: Resources,
+}
+
+/// Owned territory whose per-transfer transit volume exceeds its storage capacity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageBottleneck {
+    pub territory: String,
+    pub resource: RoutedResource,
+    /// Amount passing through (or arriving at) the territory per transfer.
+    pub transit_per_transfer: i64,
+    pub capacity: i32,
+}
+
+/// Resource flow of one guild's territory network into its HQ.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuildResourceRouting {
+    pub guild_name: String,
+    pub hq: String,
+    /// Routes for every reachable owned territory, longest first. The HQ itself is a zero-hop route.
+    pub routes: Vec<ResourceRoute>,
+    /// Owned territories with no path to the HQ.
+    #[serde(default)]
+    pub unreachable: Vec<String>,
+    /// Hourly production of all owned territories.
+    pub produced: Resources,
+    /// Hourly production that reaches the HQ.
+    pub delivered: Resources,
+    pub longest_route: u32,
+    #[serde(default)]
+    pub bottlenecks: Vec<StorageBottleneck>,
+}
+
+impl GuildResourceRouting {
+    /// Hourly production lost to foreign taxes or unreachable territories.
+    pub fn lost(&self) -> Resources {
+        let mut lost = Resources::default();
+        for resource in RoutedResource::ALL {
+            *resource.amount_mut(&mut lost) =
+                resource.amount(&self.produced) - resource.amount(&self.delivered);
+        }
+        lost
+    }
+}
+
+/// Hourly production of a territory, preferring ingest runtime rates over the static map data.
+fn territory_production(territories: &TerritoryMap, name: &str) -> Resources {
+    let territory = &territories[name];
+    territory
+        .runtime
+        .as_ref()
+        .and_then(|rt| rt.production_rates.clone())
+        .unwrap_or_else(|| territory.resources.clone())
+}
+
+/// Route every territory of `guild_name` to its HQ over the connection graph.
+///
+/// `hq` overrides the HQ; otherwise it comes from [`infer_guild_hqs`]. Routes take the fewest
+/// hops, then the fewest foreign territories, and every foreign hop keeps `1 - foreign_tax_rate`
+/// of what passes through. Bottlenecks compare the per-transfer volume each owned territory
+/// forwards (its own production plus everything routed through it) with the `storage_capacity`
+/// reported by ingest runtime data. Returns `None` when the guild owns nothing or has no HQ.
+pub fn route_guild_resources(
+    territories: &TerritoryMap,
+    guild_name: &str,
+    hq: Option<&str>,
+    foreign_tax_rate: f64,
+) -> Option<GuildResourceRouting> {
+    let owns = |name: &str| {
+        territories
+            .get(name)
+            .is_some_and(|t| t.guild.name.eq_ignore_ascii_case(guild_name))
+    };
+    let mut owned: Vec<&str> = territories
+        .keys()
+        .map(String::as_str)
+        .filter(|name| owns(name))
+        .collect();
+    if owned.is_empty() {
+        return None;
+    }
+    owned.sort_unstable();
+
+    let hq = match hq {
+        Some(hq) if owns(hq) => hq.to_string(),
+        Some(_) => return None,
+        None => {
+            infer_guild_hqs(territories, &HashMap::new())
+                .into_iter()
+                .find(|estimate| estimate.guild_name.eq_ignore_ascii_case(guild_name))?
+                .territory
+        }
+    };
+
+    // Connections are treated as two-way so one-sided map data still routes.
+    let mut neighbors: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for (name, territory) in territories {
+        for connection in &territory.connections {
+            let Some((connection, _)) = territories.get_key_value(connection) else {
+                continue;
+            };
+            neighbors
+                .entry(name.as_str())
+                .or_default()
+                .insert(connection.as_str());
+            neighbors
+                .entry(connection.as_str())
+                .or_default()
+                .insert(name.as_str());
+        }
+    }
+
+    // Layered BFS from the HQ; each territory keeps the next hop towards the HQ with the fewest
+    // foreign territories behind it.
+    let mut next_hop: HashMap<&str, (&str, u32, u32)> = HashMap::new();
+    next_hop.insert(hq.as_str(), (hq.as_str(), 0, 0));
+    let mut frontier = vec![hq.as_str()];
+    let mut depth = 0u32;
+    while !frontier.is_empty() {
+        depth += 1;
+        let mut candidates: HashMap<&str, (&str, u32)> = HashMap::new();
+        for &from in &frontier {
+            let (_, _, foreign_behind) = next_hop[from];
+            let foreign_here = u32::from(from != hq && !owns(from));
+            let foreign = foreign_behind + foreign_here;
+            for &to in neighbors.get(from).into_iter().flatten() {
+                if next_hop.contains_key(to) {
+                    continue;
+                }
+                let better = candidates
+                    .get(to)
+                    .is_none_or(|&(best, best_foreign)| (foreign, from) < (best_foreign, best));
+                if better {
+                    candidates.insert(to, (from, foreign));
+                }
+            }
+        }
+        let mut next_frontier: Vec<&str> = candidates.keys().copied().collect();
+        next_frontier.sort_unstable();
+        for (to, (from, foreign)) in candidates {
+            next_hop.insert(to, (from, depth, foreign));
+        }
+        frontier = next_frontier;
+    }
+
+    let mut routes = Vec::with_capacity(owned.len());
+    let mut unreachable = Vec::new();
+    let mut produced = Resources::default();
+    let mut delivered = Resources::default();
+    // Hourly volume each owned territory forwards, keyed by territory.
+    let mut transit: HashMap<&str, [f64; 5]> = HashMap::new();
+    for &name in &owned {
+        let production = territory_production(territories, name);
+        for resource in RoutedResource::ALL {
+            *resource.amount_mut(&mut produced) += resource.amount(&production);
+        }
+        let Some(&(_, hops, foreign_hops)) = next_hop.get(name) else {
+            unreachable.push(name.to_string());
+            continue;
+        };
+
+        let mut path = vec![name.to_string()];
+        let mut carried = 1.0;
+        let mut at = name;
+        loop {
+            if owns(at) {
+                let entry = transit.entry(at).or_default();
+                for (slot, resource) in entry.iter_mut().zip(RoutedResource::ALL) {
+                    *slot += f64::from(resource.amount(&production)) * carried;
+                }
+            } else {
+                carried *= 1.0 - foreign_tax_rate;
+            }
+            if at == hq {
+                break;
+            }
+            at = next_hop[at].0;
+            path.push(at.to_string());
+        }
+
+        for resource in RoutedResource::ALL {
+            *resource.amount_mut(&mut delivered) +=
+                (f64::from(resource.amount(&production)) * carried).round() as i32;
+        }
+        routes.push(ResourceRoute {
+            territory: name.to_string(),
+            path,
+            hops,
+            foreign_hops,
+            delivered_fraction: carried,
+            production,
+        });
+    }
+    routes.sort_by(|a, b| {
+        b.hops
+            .cmp(&a.hops)
+            .then_with(|| a.territory.cmp(&b.territory))
+    });
+
+    let mut bottlenecks = Vec::new();
+    for &name in &owned {
+        let Some(capacity) = territories[name]
+            .runtime
+            .as_ref()
+            .and_then(|rt| rt.storage_capacity.as_ref())
+        else {
+            continue;
+        };
+        let Some(hourly) = transit.get(name) else {
+            continue;
+        };
+        for (volume, resource) in hourly.iter().zip(RoutedResource::ALL) {
+            let capacity = resource.amount(capacity);
+            let per_transfer = (volume / TRANSFERS_PER_HOUR as f64).ceil() as i64;
+            if capacity > 0 && per_transfer > i64::from(capacity) {
+                bottlenecks.push(StorageBottleneck {
+                    territory: name.to_string(),
+                    resource,
+                    transit_per_transfer: per_transfer,
+                    capacity,
+                });
+            }
+        }
+    }
+    bottlenecks.sort_by(|a, b| {
+        let overflow = |b: &StorageBottleneck| b.transit_per_transfer - i64::from(b.capacity);
+        overflow(b)
+            .cmp(&overflow(a))
+            .then_with(|| a.territory.cmp(&b.territory))
+    });
+
+    Some(GuildResourceRouting {
+        guild_name: territories[owned[0]].guild.name.clone(),
+        longest_route: routes.first().map(|route| route.hops).unwrap_or_default(),
+        hq,
+        routes,
+        unreachable,
+        produced,
+        delivered,
+        bottlenecks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{RoutedResource, route_guild_resources};
+    use crate::ingest::TerritoryRuntimeData;
+    use crate::territory::{Resources, Territory, TerritoryMap};
+    use crate::test_support::territory;
+
+    fn producing(guild: &str, connections: &[&str], ore: i32) -> Territory {
+        Territory {
+            resources: Resources {
+                ore,
+                ..Default::default()
+            },
+            ..territory(guild, connections, 0)
+        }
+    }
+
+    /// HQ - A - X - B, where X belongs to Beta, plus an isolated Alpha territory Z.
+    fn network() -> TerritoryMap {
+        let mut map = HashMap::new();
+        map.insert("HQ".to_string(), producing("Alpha", &["A"], 0));
+        map.insert("A".to_string(), producing("Alpha", &["HQ", "X"], 3600));
+        map.insert("X".to_string(), producing("Beta", &["A", "B"], 0));
+        map.insert("B".to_string(), producing("Alpha", &["X"], 7200));
+        map.insert("Z".to_string(), producing("Alpha", &[], 1000));
+        map
+    }
+
+    #[test]
+    fn routes_take_shortest_path_and_pay_foreign_tax() {
+        let routing = route_guild_resources(&network(), "alpha", Some("HQ"), 0.1).unwrap();
+        assert_eq!(routing.hq, "HQ");
+        assert_eq!(routing.longest_route, 3);
+        assert_eq!(routing.unreachable, vec!["Z".to_string()]);
+
+        let far = &routing.routes[0];
+        assert_eq!(far.territory, "B");
+        assert_eq!(far.path, vec!["B", "X", "A", "HQ"]);
+        assert_eq!(far.foreign_hops, 1);
+        assert!((far.delivered_fraction - 0.9).abs() < 1e-9);
+
+        assert_eq!(routing.produced.ore, 3600 + 7200 + 1000);
+        assert_eq!(routing.delivered.ore, 3600 + 6480);
+        assert_eq!(routing.lost().ore, 720 + 1000);
+    }
+
+    #[test]
+    fn owned_detours_beat_foreign_paths_of_equal_length() {
+        let mut map = network();
+        map.get_mut("A").unwrap().connections.push("Y".to_string());
+        map.insert("Y".to_string(), producing("Alpha", &["A", "B"], 0));
+        let routing = route_guild_resources(&map, "Alpha", Some("HQ"), 0.1).unwrap();
+        let far = routing.routes.iter().find(|r| r.territory == "B").unwrap();
+        assert_eq!(far.path, vec!["B", "Y", "A", "HQ"]);
+        assert_eq!(far.foreign_hops, 0);
+        assert_eq!(far.delivered_fraction, 1.0);
+    }
+
+    #[test]
+    fn storage_capacity_flags_transit_bottlenecks() {
+        let mut map = network();
+        map.get_mut("A").unwrap().runtime = Some(TerritoryRuntimeData {
+            storage_capacity: Some(Resources {
+                ore: 100,
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let routing = route_guild_resources(&map, "Alpha", Some("HQ"), 0.1).unwrap();
+        // A forwards its own 60 ore plus 108 taxed ore from B every transfer.
+        assert_eq!(routing.bottlenecks.len(), 1);
+        let bottleneck = &routing.bottlenecks[0];
+        assert_eq!(bottleneck.territory, "A");
+        assert_eq!(bottleneck.resource, RoutedResource::Ore);
+        assert_eq!(bottleneck.transit_per_transfer, 168);
+        assert_eq!(bottleneck.capacity, 100);
+
+        assert!(route_guild_resources(&map, "Alpha", Some("X"), 0.1).is_none());
+        assert!(route_guild_resources(&map, "Gamma", None, 0.1).is_none());
+    }
+}
@@ -0,0 +1,25 @@
+//! Territory map builders shared by unit tests.
+
+use chrono::{TimeZone, Utc};
+
+use crate::territory::{GuildRef, Region, Territory};
+
+/// A territory owned by `guild` with no resources or runtime data.
+pub fn territory(guild: &str, connections: &[&str], acquired_secs: i64) -> Territory {
+    Territory {
+        guild: GuildRef {
+            uuid: format!("{guild}-uuid"),
+            name: guild.to_string(),
+            prefix: guild[..3].to_string(),
+            color: None,
+        },
+        acquired: Utc.timestamp_opt(acquired_secs, 0).unwrap(),
+        location: Region {
+            start: [0, 0],
+            end: [10, 10],
+        },
+        resources: Default::default(),
+        connections: connections.iter().map(|c| c.to_string()).collect(),
+        runtime: None,
+    }
+}